@@ -0,0 +1,364 @@
+//! Block import for the Parlia consensus, used to relay new blocks to the peers.
+
+use futures_util::StreamExt;
+use lru::LruCache;
+use reth_bsc_consensus::{Parlia, DIFF_INTURN, DIFF_NOTURN};
+use reth_consensus::{Consensus, ConsensusError};
+use reth_evm_bsc::SnapshotReader;
+use reth_network::{
+    import::{BlockImport, BlockImportError, BlockImportOutcome, BlockValidation},
+    message::NewBlockMessage,
+};
+use reth_network_peers::PeerId;
+use reth_primitives::{GotExpected, B256};
+use reth_provider::{CanonStateNotificationStream, ParliaProvider};
+use reth_tasks::TaskSpawner;
+use std::{
+    collections::VecDeque,
+    fmt,
+    num::NonZeroUsize,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tracing::{debug, trace};
+
+/// The number of recently received blocks to remember, to only relay each block once.
+const KNOWN_BLOCKS_CACHE_NUM: usize = 1024;
+
+/// The number of relayed blocks to remember until they are executed.
+const PENDING_BLOCKS_CACHE_NUM: usize = 128;
+
+/// A [`BlockImport`] that relays the `NewBlock` messages of the Parlia consensus.
+///
+/// Each received block is checked against the Parlia seal rules that don't require execution:
+/// the header extra data, the proposer recovered from the seal against the validator snapshot of
+/// the parent and the difficulty. Valid blocks are relayed as
+/// [`BlockValidation::ValidHeader`], so that the full block is sent to a square root of the peers,
+/// and announced as [`BlockValidation::ValidBlock`], so that their hash is sent to the remaining
+/// peers, once they were executed and became canonical.
+///
+/// The execution of the blocks is driven by the Parlia engine task, which receives the same
+/// messages.
+///
+/// The headers are checked on blocking tasks, because reading the parent and its snapshot hits
+/// the database, and the results are reported back to [`BlockImport::poll`] over a channel.
+pub struct ParliaBlockImport<P> {
+    /// Checks the headers of the received blocks
+    validator: Arc<HeaderValidator<P>>,
+    /// The spawner of the header checks
+    task_spawner: Box<dyn TaskSpawner>,
+    /// Sends the results of the header checks
+    validated_tx: UnboundedSender<ValidatedBlock>,
+    /// Receives the results of the header checks
+    validated_rx: UnboundedReceiver<ValidatedBlock>,
+    /// Notifications of the blocks that became canonical
+    canon_state: CanonStateNotificationStream,
+    /// The hashes of the recently received blocks
+    known_blocks: LruCache<B256, ()>,
+    /// The relayed blocks waiting to be executed, with the peer that sent them
+    pending_blocks: LruCache<B256, (PeerId, NewBlockMessage)>,
+    /// The outcomes to return from [`BlockImport::poll`]
+    outcomes: VecDeque<BlockImportOutcome>,
+}
+
+impl<P> ParliaBlockImport<P>
+where
+    P: ParliaProvider,
+{
+    /// Creates a new instance that is notified about executed blocks by the given stream and
+    /// checks the received headers on blocking tasks of the given spawner.
+    pub fn new(
+        parlia: Parlia,
+        provider: P,
+        canon_state: CanonStateNotificationStream,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let parlia = Arc::new(parlia);
+        let provider = Arc::new(provider);
+        let snapshot_reader = SnapshotReader::new(provider.clone(), parlia.clone());
+        let (validated_tx, validated_rx) = mpsc::unbounded_channel();
+        Self {
+            validator: Arc::new(HeaderValidator { parlia, provider, snapshot_reader }),
+            task_spawner,
+            validated_tx,
+            validated_rx,
+            canon_state,
+            known_blocks: LruCache::new(NonZeroUsize::new(KNOWN_BLOCKS_CACHE_NUM).unwrap()),
+            pending_blocks: LruCache::new(NonZeroUsize::new(PENDING_BLOCKS_CACHE_NUM).unwrap()),
+            outcomes: VecDeque::new(),
+        }
+    }
+
+    /// Handles the result of a header check.
+    fn on_validated_block(&mut self, validated: ValidatedBlock) {
+        let ValidatedBlock { peer_id, block, result } = validated;
+        match result {
+            Ok(true) => {
+                self.pending_blocks.put(block.hash, (peer_id, block.clone()));
+                self.outcomes.push_back(BlockImportOutcome {
+                    peer: peer_id,
+                    result: Ok(BlockValidation::ValidHeader { block }),
+                });
+            }
+            Ok(false) => {
+                // retry once the block is received again
+                self.known_blocks.pop(&block.hash);
+            }
+            Err(err) => {
+                debug!(target: "consensus::parlia", %err, ?peer_id, hash = ?block.hash, "Received block with invalid header");
+                self.outcomes.push_back(BlockImportOutcome {
+                    peer: peer_id,
+                    result: Err(BlockImportError::Consensus(err)),
+                });
+            }
+        }
+    }
+}
+
+/// The result of checking the header of a received block.
+#[derive(Debug)]
+struct ValidatedBlock {
+    /// The peer that sent the block
+    peer_id: PeerId,
+    /// The received block
+    block: NewBlockMessage,
+    /// Whether the header is valid, see [`HeaderValidator::validate_header`]
+    result: Result<bool, ConsensusError>,
+}
+
+/// Checks the headers of the received blocks against the Parlia rules.
+struct HeaderValidator<P> {
+    /// The consensus instance
+    parlia: Arc<Parlia>,
+    /// The provider used to read the parent headers
+    provider: Arc<P>,
+    /// The snapshot reader used to read the validator set of the parent
+    snapshot_reader: SnapshotReader<P>,
+}
+
+impl<P> HeaderValidator<P>
+where
+    P: ParliaProvider,
+{
+    /// Checks the header of the block against the Parlia rules that don't require execution.
+    ///
+    /// Returns `Ok(false)` if the block can't be checked, because its parent or the snapshot of
+    /// its parent is not available locally.
+    fn validate_header(&self, block: &NewBlockMessage) -> Result<bool, ConsensusError> {
+        let header = block.block.block.header.clone().seal(block.hash);
+
+        // extra data, mix hash, gas and fork specific fields
+        match Consensus::validate_header(self.parlia.as_ref(), &header) {
+            Ok(()) => {}
+            // not necessarily the fault of the peer if the clocks are skewed
+            Err(ConsensusError::TimestampIsInFuture { .. }) => return Ok(false),
+            Err(err) => return Err(err),
+        }
+
+        let parent = match self.provider.header(&header.parent_hash) {
+            Ok(Some(parent)) => parent,
+            Ok(None) => {
+                trace!(target: "consensus::parlia", hash = ?block.hash, "Parent of new block not found");
+                return Ok(false)
+            }
+            Err(err) => {
+                debug!(target: "consensus::parlia", %err, "Failed to read parent of new block");
+                return Ok(false)
+            }
+        };
+        let snap = match self.snapshot_reader.snapshot(&parent, None) {
+            Ok(snap) => snap,
+            Err(err) => {
+                debug!(target: "consensus::parlia", %err, "Failed to read snapshot of new block");
+                return Ok(false)
+            }
+        };
+
+        let proposer = self
+            .parlia
+            .recover_proposer(&header)
+            .map_err(|_| ConsensusError::InvalidHeaderExtra)?;
+        if proposer != header.beneficiary {
+            return Err(ConsensusError::WrongHeaderSigner(
+                GotExpected { got: proposer, expected: header.beneficiary }.into(),
+            ))
+        }
+        if !snap.validators.contains(&proposer) {
+            return Err(ConsensusError::UnauthorizedSigner { signer: proposer })
+        }
+
+        let expected_difficulty = if snap.is_inturn(proposer) { DIFF_INTURN } else { DIFF_NOTURN };
+        if header.difficulty != expected_difficulty {
+            return Err(ConsensusError::InvalidDifficulty { difficulty: header.difficulty })
+        }
+
+        Ok(true)
+    }
+}
+
+impl<P> BlockImport for ParliaBlockImport<P>
+where
+    P: ParliaProvider + 'static,
+{
+    fn on_new_block(&mut self, peer_id: PeerId, incoming_block: NewBlockMessage) {
+        // the same block is received from many peers, but only needs to be checked and relayed
+        // once
+        if self.known_blocks.put(incoming_block.hash, ()).is_some() {
+            return
+        }
+
+        let validator = self.validator.clone();
+        let validated_tx = self.validated_tx.clone();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let result = validator.validate_header(&incoming_block);
+            let _ = validated_tx.send(ValidatedBlock { peer_id, block: incoming_block, result });
+        }));
+    }
+
+    fn poll(&mut self, cx: &mut Context<'_>) -> Poll<BlockImportOutcome> {
+        // relay the blocks with checked headers
+        while let Poll::Ready(Some(validated)) = self.validated_rx.poll_recv(cx) {
+            self.on_validated_block(validated);
+        }
+
+        // announce the relayed blocks that have been executed
+        while let Poll::Ready(Some(notification)) = Pin::new(&mut self.canon_state).poll_next(cx) {
+            for block in notification.committed().blocks().values() {
+                if let Some((peer, block)) = self.pending_blocks.pop(&block.hash()) {
+                    self.outcomes.push_back(BlockImportOutcome {
+                        peer,
+                        result: Ok(BlockValidation::ValidBlock { block }),
+                    });
+                }
+            }
+        }
+
+        if let Some(outcome) = self.outcomes.pop_front() {
+            return Poll::Ready(outcome)
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<P> fmt::Debug for ParliaBlockImport<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParliaBlockImport")
+            .field("parlia", &self.validator.parlia)
+            .field("known_blocks", &self.known_blocks.len())
+            .field("pending_blocks", &self.pending_blocks.len())
+            .field("outcomes", &self.outcomes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_bsc_consensus::{hash_with_chain_id, EXTRA_SEAL_LEN, EXTRA_VANITY_LEN};
+    use reth_chainspec::ChainSpec;
+    use reth_eth_wire::NewBlock;
+    use reth_primitives::{parlia::ParliaConfig, public_key_to_address, Block, Bytes, Header};
+    use reth_provider::{
+        test_utils::{MockEthProvider, TestCanonStateSubscriptions},
+        CanonStateSubscriptions,
+    };
+    use reth_tasks::TokioTaskExecutor;
+    use secp256k1::{Message, SecretKey, SECP256K1};
+    use std::future::poll_fn;
+
+    /// Returns a block on top of the genesis with a single validator, sealed by the given key.
+    fn sealed_block(genesis: &Header, key: &SecretKey) -> NewBlockMessage {
+        let chain_spec = ChainSpec::default();
+        let mut header = Header {
+            number: 1,
+            parent_hash: genesis.hash_slow(),
+            beneficiary: public_key_to_address(key.public_key(SECP256K1)),
+            difficulty: DIFF_INTURN,
+            timestamp: genesis.timestamp + 3,
+            extra_data: Bytes::from(vec![0; EXTRA_VANITY_LEN + EXTRA_SEAL_LEN]),
+            ..Default::default()
+        };
+
+        let message = Message::from_digest_slice(
+            hash_with_chain_id(&header, chain_spec.chain.id()).as_slice(),
+        )
+        .unwrap();
+        let (recovery_id, signature) =
+            SECP256K1.sign_ecdsa_recoverable(&message, key).serialize_compact();
+        let mut extra_data = header.extra_data.to_vec();
+        extra_data[EXTRA_VANITY_LEN..EXTRA_VANITY_LEN + 64].copy_from_slice(&signature);
+        extra_data[EXTRA_VANITY_LEN + 64] = recovery_id.to_i32() as u8;
+        header.extra_data = extra_data.into();
+
+        let hash = header.hash_slow();
+        NewBlockMessage {
+            hash,
+            block: Arc::new(NewBlock {
+                block: Block { header, ..Default::default() },
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Returns a block import whose provider holds a genesis with the given validator.
+    fn block_import(validator: &SecretKey) -> (ParliaBlockImport<MockEthProvider>, Header) {
+        let mut extra_data = vec![0; EXTRA_VANITY_LEN];
+        extra_data
+            .extend_from_slice(public_key_to_address(validator.public_key(SECP256K1)).as_slice());
+        extra_data.extend_from_slice(&[0; EXTRA_SEAL_LEN]);
+        let genesis = Header { extra_data: extra_data.into(), ..Default::default() };
+
+        let provider = MockEthProvider::default();
+        provider.add_header(genesis.hash_slow(), genesis.clone());
+
+        let chain_spec = Arc::new(ChainSpec::default());
+        let import = ParliaBlockImport::new(
+            Parlia::new(chain_spec, ParliaConfig::default()),
+            provider,
+            TestCanonStateSubscriptions::default().canonical_state_stream(),
+            Box::new(TokioTaskExecutor::default()),
+        );
+        (import, genesis)
+    }
+
+    #[tokio::test]
+    async fn relays_block_with_valid_header() {
+        let validator = SecretKey::from_slice(&[1; 32]).unwrap();
+        let (mut import, genesis) = block_import(&validator);
+
+        let peer_id = PeerId::random();
+        let block = sealed_block(&genesis, &validator);
+        import.on_new_block(peer_id, block.clone());
+
+        let outcome = poll_fn(|cx| import.poll(cx)).await;
+        assert_eq!(outcome.peer, peer_id);
+        assert!(
+            matches!(outcome.result, Ok(BlockValidation::ValidHeader { block: relayed }) if relayed.hash == block.hash)
+        );
+        assert!(import.pending_blocks.contains(&block.hash));
+    }
+
+    #[tokio::test]
+    async fn rejects_block_with_invalid_header() {
+        let validator = SecretKey::from_slice(&[1; 32]).unwrap();
+        let (mut import, genesis) = block_import(&validator);
+
+        // sealed by a key that is not part of the validator set
+        let signer = SecretKey::from_slice(&[2; 32]).unwrap();
+        let peer_id = PeerId::random();
+        let block = sealed_block(&genesis, &signer);
+        import.on_new_block(peer_id, block.clone());
+
+        let outcome = poll_fn(|cx| import.poll(cx)).await;
+        assert_eq!(outcome.peer, peer_id);
+        assert!(matches!(
+            outcome.result,
+            Err(BlockImportError::Consensus(ConsensusError::UnauthorizedSigner { signer: unauthorized }))
+                if unauthorized == public_key_to_address(signer.public_key(SECP256K1))
+        ));
+        assert!(import.pending_blocks.is_empty());
+    }
+}
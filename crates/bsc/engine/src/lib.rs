@@ -34,6 +34,9 @@ use client::*;
 mod task;
 use task::*;
 
+mod import;
+pub use import::ParliaBlockImport;
+
 const STORAGE_CACHE_NUM: usize = 1000;
 
 /// Builder type for configuring the setup
@@ -10,6 +10,7 @@ use reth_bsc_consensus::{
 use reth_chainspec::{BscHardforks, ChainSpec, EthereumHardforks};
 use reth_errors::{BlockExecutionError, BlockValidationError, ProviderError};
 use reth_evm::{
+    call_addresses::CallAddressInspector,
     execute::{
         BatchExecutor, BlockExecutionInput, BlockExecutionOutput, BlockExecutorProvider, Executor,
     },
@@ -25,14 +26,14 @@ use reth_provider::{ExecutionOutcome, ParliaProvider};
 use reth_prune_types::PruneModes;
 use reth_revm::{
     batch::{BlockBatchRecord, BlockExecutorStats},
-    db::states::bundle_state::BundleRetention,
+    db::{states::bundle_state::BundleRetention, BundleState},
     Evm, State,
 };
 use revm_primitives::{
     db::{Database, DatabaseCommit},
     BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, ResultAndState, TransactTo,
 };
-use std::{collections::HashMap, num::NonZeroUsize, sync::Arc, time::Instant};
+use std::{collections::HashMap, fmt, num::NonZeroUsize, sync::Arc, time::Instant};
 use tracing::{debug, warn};
 
 const SNAP_CACHE_NUM: usize = 2048;
@@ -124,6 +125,20 @@ where
             snapshots: Vec::new(),
         }
     }
+
+    fn call_addresses<DB>(
+        &self,
+        db: DB,
+        input: BlockExecutionInput<'_, BlockWithSenders, Header>,
+    ) -> Result<Option<Vec<Vec<Address>>>, BlockExecutionError>
+    where
+        DB: Database<Error: Into<ProviderError> + std::fmt::Display>,
+    {
+        let BlockExecutionInput { block, total_difficulty, ancestor_headers } = input;
+        self.bsc_executor(db)
+            .trace_call_addresses(block, total_difficulty, ancestor_headers)
+            .map(Some)
+    }
 }
 
 /// Helper type for the output of executing a block.
@@ -158,6 +173,7 @@ where
         &self,
         block: &BlockWithSenders,
         mut evm: Evm<'_, Ext, &mut State<DB>>,
+        mut tx_state_hook: Option<&mut TransactionStateHook>,
     ) -> Result<(Vec<TransactionSigned>, Vec<Receipt>, u64), BlockExecutionError>
     where
         DB: Database<Error: Into<ProviderError> + std::fmt::Display>,
@@ -215,6 +231,8 @@ where
             self.patch_mainnet_after_tx(transaction, evm.db_mut());
             self.patch_chapel_after_tx(transaction, evm.db_mut());
 
+            call_tx_state_hook(tx_state_hook.as_deref_mut(), transaction.hash(), evm.db_mut());
+
             // append gas used
             cumulative_gas_used += result.gas_used();
 
@@ -235,12 +253,27 @@ where
     }
 }
 
+/// A hook that is called with the hash of every executed transaction of a block, including the
+/// system transactions, and the state changes of the block up to and including that transaction.
+pub type TransactionStateHook = Box<dyn FnMut(B256, &BundleState) + Send>;
+
+/// Merges the pending transitions into the bundle state and passes it to the hook, if any.
+fn call_tx_state_hook<DB: Database>(
+    hook: Option<&mut TransactionStateHook>,
+    tx_hash: B256,
+    state: &mut State<DB>,
+) {
+    if let Some(hook) = hook {
+        state.merge_transitions(BundleRetention::PlainState);
+        hook(tx_hash, &state.bundle_state);
+    }
+}
+
 /// A basic Bsc block executor.
 ///
 /// Expected usage:
 /// - Create a new instance of the executor.
 /// - Execute the block.
-#[derive(Debug)]
 pub struct BscBlockExecutor<EvmConfig, DB, P> {
     /// Chain specific evm config that's used to execute a block.
     executor: BscEvmExecutor<EvmConfig>,
@@ -250,6 +283,25 @@ pub struct BscBlockExecutor<EvmConfig, DB, P> {
     pub(crate) provider: Arc<P>,
     /// Parlia consensus instance
     pub(crate) parlia: Arc<Parlia>,
+    /// Optional hook called after every executed transaction
+    tx_state_hook: Option<TransactionStateHook>,
+}
+
+impl<EvmConfig, DB, P> fmt::Debug for BscBlockExecutor<EvmConfig, DB, P>
+where
+    EvmConfig: fmt::Debug,
+    DB: fmt::Debug,
+    P: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BscBlockExecutor")
+            .field("executor", &self.executor)
+            .field("state", &self.state)
+            .field("provider", &self.provider)
+            .field("parlia", &self.parlia)
+            .field("tx_state_hook", &self.tx_state_hook.is_some())
+            .finish()
+    }
 }
 
 impl<EvmConfig, DB, P> BscBlockExecutor<EvmConfig, DB, P> {
@@ -268,9 +320,24 @@ impl<EvmConfig, DB, P> BscBlockExecutor<EvmConfig, DB, P> {
             state,
             provider: shared_provider,
             parlia,
+            tx_state_hook: None,
         }
     }
 
+    /// Installs a hook that is called after every transaction of the block, see
+    /// [`TransactionStateHook`].
+    ///
+    /// This is meant for diagnostics such as computing intermediate state roots: the state
+    /// transitions are merged after every transaction, so the reverts of the block are not
+    /// retained in the resulting bundle.
+    pub fn with_tx_state_hook(
+        mut self,
+        hook: impl FnMut(B256, &BundleState) + Send + 'static,
+    ) -> Self {
+        self.tx_state_hook = Some(Box::new(hook));
+        self
+    }
+
     #[inline]
     pub(crate) fn chain_spec(&self) -> &ChainSpec {
         &self.executor.chain_spec
@@ -347,7 +414,7 @@ where
 
         let (mut system_txs, mut receipts, mut gas_used) = {
             let evm = self.executor.evm_config.evm_with_env(&mut self.state, env.clone());
-            self.executor.execute_pre_and_transactions(block, evm)
+            self.executor.execute_pre_and_transactions(block, evm, self.tx_state_hook.as_mut())
         }?;
 
         // 5. apply post execution changes
@@ -370,6 +437,39 @@ where
         }
     }
 
+    /// Executes the normal transactions of the block without verifying it and returns the
+    /// addresses reached by the calls of every transaction, see [`CallAddressInspector`].
+    ///
+    /// The system transactions are executed outside the inspected EVM and reach no addresses.
+    fn trace_call_addresses(
+        &mut self,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+        ancestor: Option<&HashMap<B256, Header>>,
+    ) -> Result<Vec<Vec<Address>>, BlockExecutionError> {
+        let parent = self.get_header_by_hash(block.parent_hash, ancestor)?;
+
+        let state_clear_flag = self.chain_spec().is_spurious_dragon_active_at_block(block.number);
+        self.state.set_state_clear_flag(state_clear_flag);
+
+        let env = self.evm_env_for_block(&block.header, total_difficulty);
+        if !self.chain_spec().is_feynman_active_at_timestamp(block.timestamp) {
+            self.upgrade_system_contracts(block.number, block.timestamp, parent.timestamp)?;
+        }
+
+        let mut inspector = CallAddressInspector::default();
+        {
+            let evm = self.executor.evm_config.evm_with_env_and_inspector(
+                &mut self.state,
+                env,
+                &mut inspector,
+            );
+            self.executor.execute_pre_and_transactions(block, evm, None)
+        }?;
+
+        Ok(inspector.into_transaction_addresses(block))
+    }
+
     pub(crate) fn get_justified_header(
         &self,
         ancestor: Option<&HashMap<B256, Header>>,
@@ -530,7 +630,7 @@ where
             }
             return Err(BscBlockExecutionError::UnexpectedSystemTx.into());
         }
-        system_txs.remove(0);
+        let tx_hash = system_txs.remove(0).hash();
 
         let tx_env = evm.tx_mut();
         tx_env.caller = sender;
@@ -568,6 +668,9 @@ where
         })?;
 
         evm.db_mut().commit(state);
+        drop(evm);
+
+        call_tx_state_hook(self.tx_state_hook.as_mut(), tx_hash, &mut self.state);
 
         // append gas used
         *cumulative_gas_used += result.gas_used();
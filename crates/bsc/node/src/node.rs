@@ -3,8 +3,9 @@
 use crate::EthEngineTypes;
 use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
 use reth_bsc_consensus::Parlia;
+use reth_bsc_engine::ParliaBlockImport;
 use reth_evm_bsc::{BscEvmConfig, BscExecutorProvider};
-use reth_network::NetworkHandle;
+use reth_network::{NetworkHandle, NetworkManager};
 use reth_node_api::{FullNodeComponents, NodeAddOns};
 use reth_node_builder::{
     components::{
@@ -15,6 +16,7 @@ use reth_node_builder::{
     BuilderContext, Node, PayloadBuilderConfig,
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
+use reth_payload_primitives::{MinerHandle, MinerSettings};
 use reth_provider::CanonStateSubscriptions;
 use reth_rpc::EthApi;
 use reth_tracing::tracing::{debug, info};
@@ -210,7 +212,7 @@ where
             .max_payload_tasks(conf.max_payload_tasks())
             .extradata(conf.extradata_bytes());
 
-        let payload_generator = BasicPayloadJobGenerator::with_builder(
+        let mut payload_generator = BasicPayloadJobGenerator::with_builder(
             ctx.provider().clone(),
             pool,
             ctx.task_executor().clone(),
@@ -218,16 +220,39 @@ where
             ctx.chain_spec(),
             payload_builder,
         );
-        let (payload_service, payload_builder) =
+
+        // the miner gates the payload jobs, so it's only installed if the node is configured to
+        // seal blocks. It's shared with the `miner_` rpc namespace
+        let miner = (conf.mine() || conf.coinbase().is_some()).then(|| {
+            MinerHandle::new(
+                conf.coinbase(),
+                MinerSettings { gas_price: conf.gas_price(), ..Default::default() },
+            )
+        });
+        if let Some(miner) = &miner {
+            if conf.mine() {
+                miner.start()?;
+                info!(target: "reth::cli", coinbase = ?miner.coinbase(), "Sealing blocks");
+            }
+            payload_generator = payload_generator.with_miner(miner.clone());
+        }
+
+        let (payload_service, mut payload_builder) =
             PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
 
         ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
 
+        if let Some(miner) = miner {
+            payload_builder = payload_builder.with_miner(miner);
+        }
         Ok(payload_builder)
     }
 }
 
-/// A basic bsc payload service.
+/// A basic bsc network builder.
+///
+/// Relays the blocks received from the peers with the [`ParliaBlockImport`], serves the state
+/// over the `snap` protocol and gossips the votes of the validators over the `bsc` protocol.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct BscNetworkBuilder {
     // TODO bsc
@@ -243,7 +268,20 @@ where
         ctx: &BuilderContext<Node>,
         pool: Pool,
     ) -> eyre::Result<NetworkHandle> {
-        let network = ctx.network_builder().await?;
+        // validate and relay the blocks received from the peers
+        let block_import = ParliaBlockImport::new(
+            Parlia::new(ctx.chain_spec(), ctx.reth_config().parlia.clone()),
+            ctx.provider().clone(),
+            ctx.provider().canonical_state_stream(),
+            Box::new(ctx.task_executor().clone()),
+        );
+        let network_config = ctx.build_network_config(
+            ctx.network_config_builder()?
+                .block_import(Box::new(block_import))
+                .snap(ctx.provider().clone())
+                .bsc(),
+        );
+        let network = NetworkManager::builder(network_config).await?;
         let handle = ctx.start_network(network, pool);
 
         Ok(handle)
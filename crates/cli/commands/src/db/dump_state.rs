@@ -0,0 +1,197 @@
+use clap::{Parser, ValueEnum};
+use reth_db::DatabaseEnv;
+use reth_db_common::DbTool;
+use reth_primitives::{Address, BlockNumber, GenesisAccount, B256};
+use reth_provider::{
+    DumpedAccount, HeaderProvider, ProviderError, StageCheckpointReader, StateDumpReader,
+};
+use reth_rpc_types_compat::state::from_primitive_dump_account;
+use reth_stages::StageId;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::info;
+
+/// The arguments for the `reth db dump-state` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The block to dump the state at, defaults to the latest executed block.
+    ///
+    /// The state of historical blocks is only available if their history is not pruned.
+    #[arg(long)]
+    block: Option<BlockNumber>,
+
+    /// The address to start the dump at.
+    #[arg(long)]
+    start: Option<Address>,
+
+    /// The maximum number of accounts to dump.
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Exclude the code of the accounts.
+    #[arg(long)]
+    nocode: bool,
+
+    /// Exclude the storage of the accounts.
+    #[arg(long)]
+    nostorage: bool,
+
+    /// The format of the dump.
+    #[arg(long, value_enum, default_value_t = DumpFormat::Dump)]
+    format: DumpFormat,
+
+    /// The file to write the dump to, defaults to stdout.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+/// The format of a state dump.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum DumpFormat {
+    /// A single JSON object holding the state root and the accounts keyed by address, as returned
+    /// by `debug_dumpBlock`.
+    #[default]
+    Dump,
+    /// One JSON object per line, the first one holding the state root and every other one an
+    /// account.
+    Iterative,
+    /// A genesis `alloc` object, to start a new network from the state.
+    Alloc,
+}
+
+impl Command {
+    /// Execute `db dump-state` command
+    pub fn execute(self, tool: &DbTool<Arc<DatabaseEnv>>) -> eyre::Result<()> {
+        let provider = tool.provider_factory.provider()?;
+        let block_number = match self.block {
+            Some(block_number) => block_number,
+            None => {
+                provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number
+            }
+        };
+        let root = provider
+            .header_by_number(block_number)?
+            .ok_or(ProviderError::HeaderNotFound(block_number.into()))?
+            .state_root;
+
+        let mut writer: BufWriter<Box<dyn Write>> = BufWriter::new(match &self.output {
+            Some(path) => Box::new(File::create(path)?),
+            None => Box::new(io::stdout().lock()),
+        });
+
+        info!(target: "reth::cli", block_number, %root, format = ?self.format, "Dumping state");
+
+        match self.format {
+            DumpFormat::Dump => write!(writer, "{{\"root\":\"{root:?}\",\"accounts\":{{")?,
+            DumpFormat::Iterative => writeln!(writer, "{{\"root\":\"{root:?}\"}}")?,
+            DumpFormat::Alloc => write!(writer, "{{")?,
+        }
+
+        // the accounts are written as they are walked, so the dump is never held in memory
+        let mut dumped = 0;
+        let mut result = Ok(());
+        let start = self.start.unwrap_or_default();
+        provider.dump_state(block_number, start, !self.nostorage, &mut |account| {
+            if self.limit.is_some_and(|limit| dumped >= limit) {
+                return false
+            }
+            result = self.write_account(&mut writer, dumped, account);
+            dumped += 1;
+            result.is_ok()
+        })?;
+        result?;
+
+        match self.format {
+            DumpFormat::Dump => writeln!(writer, "}}}}")?,
+            DumpFormat::Iterative => {}
+            DumpFormat::Alloc => writeln!(writer, "}}")?,
+        }
+        writer.flush()?;
+
+        info!(target: "reth::cli", accounts = dumped, "Dumped state");
+
+        Ok(())
+    }
+
+    /// Writes the `index`-th account of the dump.
+    fn write_account(
+        &self,
+        writer: &mut impl Write,
+        index: usize,
+        account: DumpedAccount,
+    ) -> eyre::Result<()> {
+        let DumpedAccount { address, account, storage_root, storage, code } = account;
+        let code = code.map(|code| code.original_bytes()).filter(|_| !self.nocode);
+        let storage = Some(storage).filter(|_| !self.nostorage);
+        let separator = if index == 0 { "" } else { "," };
+
+        match self.format {
+            DumpFormat::Dump => {
+                let account =
+                    from_primitive_dump_account(address, account, storage_root, code, storage);
+                write!(writer, "{separator}\"{address:?}\":")?;
+                serde_json::to_writer(&mut *writer, &account)?;
+            }
+            DumpFormat::Iterative => {
+                let account =
+                    from_primitive_dump_account(address, account, storage_root, code, storage);
+                serde_json::to_writer(&mut *writer, &account)?;
+                writeln!(writer)?;
+            }
+            DumpFormat::Alloc => {
+                let account = GenesisAccount {
+                    nonce: Some(account.nonce).filter(|nonce| *nonce != 0),
+                    balance: account.balance,
+                    code: code.filter(|code| !code.is_empty()),
+                    storage: storage
+                        .map(|storage| {
+                            storage
+                                .into_iter()
+                                .map(|entry| (entry.key, B256::from(entry.value)))
+                                .collect()
+                        })
+                        .filter(|storage: &BTreeMap<_, _>| !storage.is_empty()),
+                    ..Default::default()
+                };
+                write!(writer, "{separator}\"{address:?}\":")?;
+                serde_json::to_writer(&mut *writer, &account)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_dump_state_args() {
+        let cmd = Command::try_parse_from([
+            "reth",
+            "--block",
+            "100",
+            "--format",
+            "alloc",
+            "--nocode",
+            "--output",
+            "alloc.json",
+        ])
+        .unwrap();
+        assert_eq!(cmd.block, Some(100));
+        assert_eq!(cmd.format, DumpFormat::Alloc);
+        assert!(cmd.nocode);
+        assert!(!cmd.nostorage);
+        assert_eq!(cmd.output, Some(PathBuf::from("alloc.json")));
+
+        let cmd = Command::try_parse_from(["reth"]).unwrap();
+        assert_eq!(cmd.format, DumpFormat::Dump);
+        assert_eq!(cmd.start, None);
+    }
+}
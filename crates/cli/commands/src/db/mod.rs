@@ -7,6 +7,7 @@ use std::io::{self, Write};
 mod checksum;
 mod clear;
 mod diff;
+mod dump_state;
 mod get;
 mod list;
 mod stats;
@@ -36,6 +37,8 @@ pub enum Subcommands {
     Diff(diff::Command),
     /// Gets the content of a table for the given key
     Get(get::Command),
+    /// Dumps the state at a block as JSON
+    DumpState(dump_state::Command),
     /// Deletes all database entries
     Drop {
         /// Bypasses the interactive confirmation and drops the database directly
@@ -78,10 +81,9 @@ impl Command {
         eyre::ensure!(db_path.is_dir(), "Database does not exist: {:?}", db_path);
 
         match self.command {
-            // TODO: We'll need to add this on the DB trait.
             Subcommands::Stats(command) => {
                 db_ro_exec!(self.env, tool, {
-                    command.execute(data_dir, &tool)?;
+                    command.execute(&tool)?;
                 });
             }
             Subcommands::List(command) => {
@@ -104,6 +106,11 @@ impl Command {
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::DumpState(command) => {
+                db_ro_exec!(self.env, tool, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Drop { force } => {
                 if !force {
                     // Ask for confirmation
@@ -17,6 +17,8 @@ use reth_node_core::{
 use reth_primitives::BlockHashOrNumber;
 use std::{path::PathBuf, sync::Arc};
 
+mod replay;
+
 /// `reth p2p` command
 #[derive(Debug, Parser)]
 pub struct Command {
@@ -68,10 +70,17 @@ pub enum Subcommands {
         #[arg(value_parser = hash_or_num_value_parser)]
         id: BlockHashOrNumber,
     },
+    /// Replay the messages of a captured peer session against a node
+    Replay(replay::Command),
 }
 impl Command {
     /// Execute `p2p` command
     pub async fn execute(&self) -> eyre::Result<()> {
+        // replaying connects to the given node directly, without starting a network
+        if let Subcommands::Replay(command) = &self.command {
+            return command.execute(self.chain.clone()).await
+        }
+
         let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain);
         let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
 
@@ -99,7 +108,11 @@ impl Command {
             .disable_discv4_discovery_if(self.chain.chain.is_optimism())
             .boot_nodes(boot_nodes.clone())
             .apply(|builder| {
-                self.network.discovery.apply_to_builder(builder, rlpx_socket, boot_nodes)
+                self.network
+                    .discovery
+                    .clone()
+                    .with_discv5_discovery_default(self.chain.is_bsc())
+                    .apply_to_builder(builder, rlpx_socket, boot_nodes)
             })
             .build_with_noop_provider()
             .manager()
@@ -151,6 +164,7 @@ impl Command {
                 let body = result.into_iter().next().unwrap();
                 println!("Successfully downloaded body: {body:?}")
             }
+            Subcommands::Replay(_) => unreachable!("replay is executed without network"),
         }
 
         Ok(())
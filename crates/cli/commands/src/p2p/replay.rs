@@ -0,0 +1,258 @@
+//! Command that replays a capture of a peer session against a node.
+
+use clap::Parser;
+use futures::{SinkExt, StreamExt};
+use reth_chainspec::ChainSpec;
+use reth_ecies::stream::ECIESStream;
+use reth_eth_wire::{
+    capture::{CaptureDirection, CaptureFile},
+    protocol::Protocol,
+    BlockRangeUpdate, EthMessage, EthVersion, HelloMessageWithProtocols, ProtocolMessage, Status,
+    UnauthedEthStream, UnauthedP2PStream,
+};
+use reth_network::config::rng_secret_key;
+use reth_network_peers::{pk2id, NodeRecord};
+use reth_primitives::Head;
+use secp256k1::SECP256K1;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tokio::net::TcpStream;
+
+/// The messages the captured peer sent over `eth`.
+#[derive(Debug)]
+struct ReplaySession {
+    /// The negotiated `eth` version.
+    version: EthVersion,
+    /// The status the peer sent in the handshake.
+    status: Status,
+    /// The block range the peer sent in the handshake, for `eth/69`.
+    block_range: Option<BlockRangeUpdate>,
+    /// The messages the peer sent after the handshake, with their capture timestamps.
+    messages: Vec<(u64, EthMessage)>,
+}
+
+impl ReplaySession {
+    /// Decodes the inbound `eth` messages of the capture, skipping the other capabilities.
+    fn decode(capture: &CaptureFile) -> eyre::Result<Self> {
+        let Some(eth) = capture.header.capabilities.iter().find(|cap| cap.name == "eth") else {
+            eyre::bail!("No eth capability was shared in the captured session")
+        };
+        let version = EthVersion::try_from(eth.version)?;
+
+        // the status is sent in the handshake
+        let mut status = None;
+        let mut messages = Vec::new();
+        for captured in
+            capture.messages.iter().filter(|msg| msg.direction == CaptureDirection::Inbound)
+        {
+            let Some(id) = captured.message_id() else { continue };
+            match capture.header.find_capability(id) {
+                Some(cap) if cap.name == eth.name => {}
+                cap => {
+                    println!(
+                        "Skipping message {id:#x} of capability {:?}",
+                        cap.map(|cap| &cap.name)
+                    );
+                    continue
+                }
+            }
+
+            let mut buf = captured.message.to_vec();
+            buf[0] -= eth.offset;
+            let msg = ProtocolMessage::decode_message(version, &mut buf.as_slice())?;
+            match msg.message {
+                EthMessage::Status(msg) if status.is_none() => status = Some((msg, None)),
+                EthMessage::StatusEth69(msg) if status.is_none() => {
+                    status = Some((msg.into(), Some(msg.block_range())))
+                }
+                EthMessage::UpgradeStatus(_) => {}
+                msg => messages.push((captured.timestamp, msg)),
+            }
+        }
+        let Some((status, block_range)) = status else {
+            eyre::bail!("No status was received in the captured session")
+        };
+
+        Ok(Self { version, status, block_range, messages })
+    }
+}
+
+/// `reth p2p replay` command
+///
+/// Connects to the given node as if it was the captured peer, and sends the messages the peer
+/// sent in the captured session.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The capture file, written by a node started with `--p2p.capture-dir`.
+    #[arg(value_name = "FILE")]
+    path: PathBuf,
+
+    /// The enode of the node to replay the session against.
+    #[arg(long, value_name = "ENODE")]
+    peer: NodeRecord,
+
+    /// Wait between messages as long as the captured peer did.
+    #[arg(long)]
+    keep_timing: bool,
+
+    /// How long to wait for responses after the last message was sent, in seconds.
+    #[arg(long, value_name = "SECONDS", default_value = "5")]
+    linger: u64,
+}
+
+impl Command {
+    /// Execute `p2p replay` command
+    pub async fn execute(&self, chain: Arc<ChainSpec>) -> eyre::Result<()> {
+        let capture = CaptureFile::read(&self.path)?;
+        let ReplaySession { version, status, block_range, messages } =
+            ReplaySession::decode(&capture)?;
+        println!(
+            "Replaying {} messages of session with {} ({}) over eth/{}",
+            messages.len(),
+            capture.header.peer_id,
+            capture.header.client_version,
+            version as u8
+        );
+
+        let secret_key = rng_secret_key();
+        let hello = HelloMessageWithProtocols::builder(pk2id(&secret_key.public_key(SECP256K1)))
+            .client_version(capture.header.client_version.clone())
+            .protocol(Protocol::eth(version))
+            .build();
+
+        let stream = TcpStream::connect(self.peer.tcp_addr()).await?;
+        let ecies = ECIESStream::connect(stream, secret_key, self.peer.id).await?;
+        let (p2p_stream, their_hello) = UnauthedP2PStream::new(ecies).handshake(hello).await?;
+        println!("Connected to {}", their_hello.client_version);
+
+        // accept any fork the node can be on
+        let fork_filter =
+            chain.fork_filter(Head { number: u64::MAX, timestamp: u64::MAX, ..Default::default() });
+        let (eth_stream, their_status) = UnauthedEthStream::new(p2p_stream)
+            .with_block_range(block_range)
+            .handshake(status, fork_filter)
+            .await?;
+        println!("Completed status handshake: {their_status}");
+
+        let (mut sink, mut stream) = eth_stream.split();
+        let responses = tokio::spawn(async move {
+            while let Some(msg) = stream.next().await {
+                match msg {
+                    Ok(msg) => println!("Received {:?}", msg.message_id()),
+                    Err(err) => {
+                        println!("Session closed: {err}");
+                        break
+                    }
+                }
+            }
+        });
+
+        let mut last_timestamp = None;
+        for (timestamp, msg) in messages {
+            if self.keep_timing {
+                if let Some(last) = last_timestamp {
+                    tokio::time::sleep(Duration::from_millis(timestamp.saturating_sub(last))).await;
+                }
+                last_timestamp = Some(timestamp);
+            }
+            println!("Sending {:?}", msg.message_id());
+            sink.send(msg).await?;
+        }
+
+        tokio::time::sleep(Duration::from_secs(self.linger)).await;
+        responses.abort();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_eth_wire::{
+        capture::{CaptureHeader, CapturedCapability, CapturedMessage, CAPTURE_FORMAT_VERSION},
+        GetBlockHeaders, HeadersDirection, RequestPair,
+    };
+    use reth_network_peers::PeerId;
+    use reth_primitives::Bytes;
+
+    /// Returns the captured message with the id of the message shifted by the given offset.
+    fn captured(direction: CaptureDirection, msg: EthMessage, offset: u8) -> CapturedMessage {
+        let mut buf = Vec::new();
+        ProtocolMessage::from(msg).encode(&mut buf);
+        buf[0] += offset;
+        CapturedMessage { timestamp: 1, direction, message: buf.into() }
+    }
+
+    #[test]
+    fn parse_replay_command() {
+        let enode = format!("enode://{:x}@127.0.0.1:30303", PeerId::random());
+        let cmd =
+            Command::try_parse_from(["reth", "session.capture", "--peer", &enode, "--keep-timing"])
+                .unwrap();
+        assert_eq!(cmd.path, PathBuf::from("session.capture"));
+        assert_eq!(cmd.peer.tcp_addr().port(), 30303);
+        assert!(cmd.keep_timing);
+        assert_eq!(cmd.linger, 5);
+    }
+
+    #[test]
+    fn decode_inbound_eth_messages() {
+        let status = Status::default();
+        let request = EthMessage::GetBlockHeaders(RequestPair {
+            request_id: 1,
+            message: GetBlockHeaders {
+                start_block: 1u64.into(),
+                limit: 10,
+                skip: 0,
+                direction: HeadersDirection::Rising,
+            },
+        });
+        let capture = CaptureFile {
+            header: CaptureHeader {
+                format_version: CAPTURE_FORMAT_VERSION,
+                peer_id: PeerId::random(),
+                client_version: "bsc/v1.4.11".to_string(),
+                capabilities: vec![
+                    CapturedCapability { name: "bsc".to_string(), version: 1, offset: 0 },
+                    CapturedCapability { name: "eth".to_string(), version: 68, offset: 2 },
+                ],
+            },
+            messages: vec![
+                captured(CaptureDirection::Outbound, EthMessage::Status(status), 2),
+                captured(CaptureDirection::Inbound, EthMessage::Status(status), 2),
+                // a message of the `bsc` capability
+                CapturedMessage {
+                    timestamp: 1,
+                    direction: CaptureDirection::Inbound,
+                    message: Bytes::from_static(&[0x00, 0xc0]),
+                },
+                captured(CaptureDirection::Inbound, request.clone(), 2),
+            ],
+        };
+
+        let session = ReplaySession::decode(&capture).unwrap();
+        assert_eq!(session.version, EthVersion::Eth68);
+        assert_eq!(session.status, status);
+        assert_eq!(session.block_range, None);
+        assert_eq!(session.messages, vec![(1, request)]);
+    }
+
+    #[test]
+    fn decode_without_status() {
+        let capture = CaptureFile {
+            header: CaptureHeader {
+                format_version: CAPTURE_FORMAT_VERSION,
+                peer_id: PeerId::random(),
+                client_version: "reth".to_string(),
+                capabilities: vec![CapturedCapability {
+                    name: "eth".to_string(),
+                    version: 68,
+                    offset: 0,
+                }],
+            },
+            messages: Vec::new(),
+        };
+        assert!(ReplaySession::decode(&capture).is_err());
+    }
+}
@@ -102,6 +102,13 @@ impl Command {
                     StageId::Execution.to_string(),
                     Default::default(),
                 )?;
+                // the state of a snap synced database is gone with the plain state, the rest of
+                // the state is rebuilt by executing from genesis
+                tx.delete::<tables::StageCheckpointProgresses>(
+                    StageId::SnapSync.to_string(),
+                    None,
+                )?;
+                tx.delete::<tables::StageCheckpoints>(StageId::SnapSync.to_string(), None)?;
                 let alloc = &self.env.chain.genesis().alloc;
                 insert_genesis_state(&provider_rw, alloc.len(), alloc.iter())?;
             }
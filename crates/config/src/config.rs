@@ -89,6 +89,14 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Address Transactions stage configuration.
+    pub index_address_transactions: IndexAddressTransactionsConfig,
+    /// Preimages stage configuration.
+    pub preimages: PreimagesConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
+    /// Snap Sync stage configuration.
+    pub snap_sync: SnapSyncConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -342,6 +350,71 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Address transactions index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexAddressTransactionsConfig {
+    /// Whether to index the transactions each address appears in.
+    pub enabled: bool,
+    /// The maximum number of transactions to process before writing to disk.
+    pub chunk_size: u64,
+}
+
+impl Default for IndexAddressTransactionsConfig {
+    fn default() -> Self {
+        Self { enabled: false, chunk_size: 500_000 }
+    }
+}
+
+/// Preimages stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct PreimagesConfig {
+    /// Whether to record the preimages of hashed state keys.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for PreimagesConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexLogsConfig {
+    /// Whether to index the blocks with logs of each address and topic.
+    pub enabled: bool,
+    /// The maximum number of transactions to process before writing to disk.
+    pub chunk_size: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, chunk_size: 500_000 }
+    }
+}
+
+/// Snap sync stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct SnapSyncConfig {
+    /// Whether to download the state of a recent block over the `snap` protocol instead of
+    /// executing all blocks from genesis.
+    pub enabled: bool,
+    /// The distance of the pivot block, the state is downloaded for, to the sync target.
+    pub pivot_distance: u64,
+}
+
+impl Default for SnapSyncConfig {
+    fn default() -> Self {
+        Self { enabled: false, pivot_distance: 64 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -20,8 +20,8 @@ use reth_primitives::{
     B256,
 };
 use reth_provider::{
-    BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider, ProviderError,
-    StageCheckpointReader,
+    BadBlockWriter, BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider,
+    ProviderError, StageCheckpointReader,
 };
 use reth_rpc_types::engine::{
     CancunPayloadFields, ExecutionPayload, ForkchoiceState, PayloadStatus, PayloadStatusEnum,
@@ -233,6 +233,7 @@ where
         + CanonChainTracker
         + StageCheckpointReader
         + ChainSpecProvider
+        + BadBlockWriter
         + 'static,
     Client: HeadersClient + BodiesClient + Clone + Unpin + 'static,
     EngineT: EngineTypes + Unpin + 'static,
@@ -717,6 +718,15 @@ where
         None
     }
 
+    /// Persists the given invalid block so it can later be inspected via `debug_getBadBlocks`.
+    ///
+    /// Failing to record the block is not fatal for the engine and is only logged.
+    fn record_bad_block(&self, block: &SealedBlock, error: &InsertBlockErrorKind) {
+        if let Err(err) = self.blockchain.insert_bad_block(block.clone(), error.to_string()) {
+            warn!(target: "consensus::engine", %err, hash=?block.hash(), "Failed to record bad block");
+        }
+    }
+
     /// Returns how far the local tip is from the given block. If the local tip is at the same
     /// height or its block number is greater than the given block, this returns None.
     #[inline]
@@ -1663,6 +1673,7 @@ where
                             self.latest_valid_hash_for_invalid_payload(block.parent_hash)?
                         };
                         // keep track of the invalid header
+                        self.record_bad_block(&block, &error);
                         self.invalid_headers.insert(block.header);
                         PayloadStatus::new(
                             PayloadStatusEnum::Invalid { validation_error: error.to_string() },
@@ -1781,6 +1792,7 @@ where
                             let (block, err) = err.split();
                             warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash(), %err, "Marking block as invalid");
 
+                            self.record_bad_block(&block, &err);
                             self.invalid_headers.insert(block.header);
                         }
                     }
@@ -1808,6 +1820,7 @@ where
         + CanonChainTracker
         + StageCheckpointReader
         + ChainSpecProvider
+        + BadBlockWriter
         + Unpin
         + 'static,
     EngineT: EngineTypes + Unpin + 'static,
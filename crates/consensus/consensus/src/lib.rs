@@ -10,9 +10,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use reth_primitives::{
-    constants::MINIMUM_GAS_LIMIT, BlockHash, BlockNumber, BlockWithSenders, Bloom, GotExpected,
-    GotExpectedBoxed, Header, InvalidTransactionError, Receipt, Request, SealedBlock, SealedHeader,
-    B256, U256,
+    constants::MINIMUM_GAS_LIMIT, Address, BlockHash, BlockNumber, BlockWithSenders, Bloom,
+    GotExpected, GotExpectedBoxed, Header, InvalidTransactionError, Receipt, Request, SealedBlock,
+    SealedHeader, B256, U256,
 };
 
 #[cfg(feature = "std")]
@@ -353,6 +353,17 @@ pub enum ConsensusError {
     #[error("invalid header extra")]
     InvalidHeaderExtra,
 
+    /// Error when the signer recovered from the header seal is not the block beneficiary.
+    #[error("wrong header signer: {0}")]
+    WrongHeaderSigner(GotExpectedBoxed<Address>),
+
+    /// Error when the header is sealed by a signer that is not in the validator set.
+    #[error("header signer {signer} is not authorized")]
+    UnauthorizedSigner {
+        /// The recovered signer.
+        signer: Address,
+    },
+
     /// Error for a transaction that violates consensus.
     #[error(transparent)]
     InvalidTransaction(#[from] InvalidTransactionError),
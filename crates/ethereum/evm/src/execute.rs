@@ -7,6 +7,7 @@ use crate::{
 use reth_chainspec::{ChainSpec, EthereumHardforks, MAINNET};
 use reth_ethereum_consensus::validate_block_post_execution;
 use reth_evm::{
+    call_addresses::CallAddressInspector,
     execute::{
         BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
         BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
@@ -19,7 +20,7 @@ use reth_evm::{
 };
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    BlockNumber, BlockWithSenders, EthereumHardfork, Header, Receipt, Request, U256,
+    Address, BlockNumber, BlockWithSenders, EthereumHardfork, Header, Receipt, Request, U256,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::{
@@ -105,6 +106,18 @@ where
             stats: BlockExecutorStats::default(),
         }
     }
+
+    fn call_addresses<DB>(
+        &self,
+        db: DB,
+        input: BlockExecutionInput<'_, BlockWithSenders, Header>,
+    ) -> Result<Option<Vec<Vec<Address>>>, BlockExecutionError>
+    where
+        DB: Database<Error: Into<ProviderError> + Display>,
+    {
+        let BlockExecutionInput { block, total_difficulty, .. } = input;
+        self.eth_executor(db).trace_call_addresses(block, total_difficulty).map(Some)
+    }
 }
 
 /// Helper type for the output of executing a block.
@@ -321,6 +334,30 @@ where
         Ok(output)
     }
 
+    /// Executes the transactions of the block like [`Self::execute_without_verification`] and
+    /// returns the addresses reached by the calls of every transaction, see
+    /// [`CallAddressInspector`].
+    fn trace_call_addresses(
+        &mut self,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+    ) -> Result<Vec<Vec<Address>>, BlockExecutionError> {
+        self.on_new_block(&block.header);
+
+        let env = self.evm_env_for_block(&block.header, total_difficulty);
+        let mut inspector = CallAddressInspector::default();
+        {
+            let evm = self.executor.evm_config.evm_with_env_and_inspector(
+                &mut self.state,
+                env,
+                &mut inspector,
+            );
+            self.executor.execute_state_transitions(block, evm)
+        }?;
+
+        Ok(inspector.into_transaction_addresses(block))
+    }
+
     /// Apply settings before a new block is executed.
     pub(crate) fn on_new_block(&mut self, header: &Header) {
         // Set state clear flag if the block is after the Spurious Dragon hardfork.
@@ -1354,6 +1391,71 @@ mod tests {
         assert_eq!(withdrawal_request.amount, u64::from_be_bytes(withdrawal_amount.into()));
     }
 
+    #[test]
+    fn call_addresses() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        // a contract that calls another account
+        let contract = Address::with_last_byte(0xc0);
+        let callee = Address::with_last_byte(0xca);
+        let code: Bytes = [
+            &[0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73][..],
+            &callee[..],
+            &[0x5a, 0xf1, 0x00][..],
+        ]
+        .concat()
+        .into();
+
+        let mut db = StateProviderTest::default();
+        db.insert_account(
+            contract,
+            Account { nonce: 1, balance: U256::ZERO, bytecode_hash: Some(keccak256(&code)) },
+            Some(code),
+            HashMap::new(),
+        );
+
+        let secp = Secp256k1::new();
+        let sender_key_pair = Keypair::new(&secp, &mut generators::rng());
+        let sender_address = public_key_to_address(sender_key_pair.public_key());
+        db.insert_account(
+            sender_address,
+            Account { nonce: 1, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+
+        let tx = sign_tx_with_key_pair(
+            sender_key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 1,
+                gas_price: 0,
+                gas_limit: 100_000,
+                to: TxKind::Call(contract),
+                value: U256::ZERO,
+                input: Bytes::new(),
+            }),
+        );
+        let block = Block {
+            header: Header { gas_limit: 1_000_000, ..Header::default() },
+            body: vec![tx],
+            ommers: vec![],
+            withdrawals: None,
+            sidecars: None,
+            requests: None,
+        }
+        .with_recovered_senders()
+        .unwrap();
+
+        let addresses = executor_provider(chain_spec)
+            .call_addresses(StateProviderDatabase::new(&db), (&block, U256::ZERO, None).into())
+            .unwrap();
+
+        let mut expected = vec![sender_address, contract, callee];
+        expected.sort_unstable();
+        assert_eq!(addresses, Some(vec![expected]));
+    }
+
     #[test]
     fn block_gas_limit_error() {
         // Create a chain specification with fork conditions set for Prague
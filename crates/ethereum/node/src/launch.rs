@@ -134,6 +134,7 @@ where
         let pipeline = reth_node_builder::setup::build_networked_pipeline(
             &ctx.toml_config().stages,
             network_client.clone(),
+            ctx.components().network().snap().cloned(),
             ctx.consensus(),
             ctx.provider_factory().clone(),
             ctx.task_executor(),
@@ -234,6 +235,7 @@ where
             ctx.node_adapter().clone(),
             engine_api,
             ctx.node_config(),
+            ctx.toml_config().parlia.clone(),
             jwt_secret,
             rpc,
         )
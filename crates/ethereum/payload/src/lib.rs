@@ -274,6 +274,7 @@ where
         initialized_block_env,
         initialized_cfg,
         parent_block,
+        min_priority_fee,
         attributes,
         chain_spec,
         ..
@@ -328,6 +329,95 @@ where
     .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
 
     let mut receipts = Vec::new();
+
+    // execute the private bundles first, either all transactions of a bundle are included, in
+    // order, or none of them
+    let bundles = pool
+        .bundle_pool()
+        .map(|bundles| bundles.bundles_for_block(block_number, attributes.timestamp))
+        .unwrap_or_default();
+    for bundle in bundles {
+        // check if the job was cancelled, if so we can exit early
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        let mut bundle_gas_used = 0;
+        let mut bundle_fees = U256::ZERO;
+        let mut bundle_receipts = Vec::new();
+        let mut bundle_txs = Vec::new();
+        let mut bundle_states = Vec::new();
+
+        let included = 'bundle: {
+            // the bundle is executed on top of the block's state so far and its state changes are
+            // only merged into it once all of its transactions can be included
+            let mut bundle_db = State::builder().with_database(&mut db).build();
+            for pool_tx in bundle.transactions() {
+                let tx = pool_tx.to_recovered_transaction();
+                if cumulative_gas_used + bundle_gas_used + tx.gas_limit() > block_gas_limit {
+                    break 'bundle false
+                }
+
+                let env = EnvWithHandlerCfg::new_with_cfg_env(
+                    initialized_cfg.clone(),
+                    initialized_block_env.clone(),
+                    evm_config.tx_env(&tx),
+                );
+                let mut evm = evm_config.evm_with_env(&mut bundle_db, env);
+                let res = evm.transact();
+                // drop evm so db is released.
+                drop(evm);
+
+                let ResultAndState { result, state } = match res {
+                    Ok(res) => res,
+                    Err(EVMError::Transaction(err)) => {
+                        trace!(target: "payload_builder", %err, ?tx, bundle=?bundle.hash(), "skipping bundle with invalid transaction");
+                        break 'bundle false
+                    }
+                    Err(err) => return Err(PayloadBuilderError::EvmExecutionError(err)),
+                };
+                if !result.is_success() && !bundle.can_revert(&tx.hash()) {
+                    trace!(target: "payload_builder", ?tx, bundle=?bundle.hash(), "skipping bundle with reverted transaction");
+                    break 'bundle false
+                }
+                bundle_db.commit(state.clone());
+                bundle_states.push(state);
+
+                let gas_used = result.gas_used();
+                bundle_gas_used += gas_used;
+
+                #[allow(clippy::needless_update)] // side-effect of optimism fields
+                bundle_receipts.push(Some(Receipt {
+                    tx_type: tx.tx_type(),
+                    success: result.is_success(),
+                    cumulative_gas_used: cumulative_gas_used + bundle_gas_used,
+                    logs: result.into_logs().into_iter().map(Into::into).collect(),
+                    ..Default::default()
+                }));
+
+                let Some(miner_fee) = tx.effective_tip_per_gas(Some(base_fee)) else {
+                    break 'bundle false
+                };
+                bundle_fees += U256::from(miner_fee) * U256::from(gas_used);
+                bundle_txs.push(tx.into_signed());
+            }
+            true
+        };
+
+        if !included {
+            // the state changes of the bundle are discarded with the bundle's database
+            continue
+        }
+
+        for state in bundle_states {
+            db.commit(state);
+        }
+        cumulative_gas_used += bundle_gas_used;
+        total_fees += bundle_fees;
+        receipts.extend(bundle_receipts);
+        executed_txs.extend(bundle_txs);
+    }
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -338,6 +428,12 @@ where
             continue
         }
 
+        // skip transactions that don't pay the configured minimum priority fee
+        if pool_tx.effective_tip_per_gas(base_fee).unwrap_or_default() < min_priority_fee {
+            best_txs.mark_invalid(&pool_tx);
+            continue
+        }
+
         // check if the job was cancelled, if so we can exit early
         if cancel.is_cancelled() {
             return Ok(BuildOutcome::Cancelled)
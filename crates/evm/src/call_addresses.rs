@@ -0,0 +1,104 @@
+//! Tracing of the addresses reached by the calls of transactions.
+
+use reth_primitives::{Address, BlockWithSenders, U256};
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    Database, EvmContext, Inspector,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// An [`Inspector`] that records the addresses reached by every top level call it inspects.
+///
+/// These are the callers, callees and code addresses of all nested calls, the created contracts
+/// and the beneficiaries of self-destructs.
+#[derive(Debug, Default)]
+pub struct CallAddressInspector {
+    /// The caller of every top level call and the addresses reached by it, in execution order.
+    calls: Vec<(Address, Vec<Address>)>,
+}
+
+impl CallAddressInspector {
+    /// Records the addresses of a call frame entered at the given depth.
+    fn enter(
+        &mut self,
+        depth: usize,
+        caller: Address,
+        addresses: impl IntoIterator<Item = Address>,
+    ) {
+        if depth == 0 {
+            self.calls.push((caller, Vec::new()));
+        }
+        if let Some((_, reached)) = self.calls.last_mut() {
+            reached.push(caller);
+            reached.extend(addresses);
+        }
+    }
+
+    /// Assigns the recorded top level calls to the transactions of the block and returns the
+    /// addresses reached by every transaction, sorted and deduplicated.
+    ///
+    /// Calls that weren't made by the sender of the next transaction are skipped, e.g. the system
+    /// calls made before the transactions. Transactions without a recorded call, e.g. system
+    /// transactions that are executed outside the inspected EVM, reach no addresses.
+    pub fn into_transaction_addresses(self, block: &BlockWithSenders) -> Vec<Vec<Address>> {
+        let mut calls = self.calls;
+        let mut next = 0;
+        block
+            .senders
+            .iter()
+            .map(|sender| {
+                let position = calls[next..].iter().position(|(caller, _)| caller == sender);
+                let Some(offset) = position else { return Vec::new() };
+                let mut addresses = core::mem::take(&mut calls[next + offset].1);
+                next += offset + 1;
+                addresses.sort_unstable();
+                addresses.dedup();
+                addresses
+            })
+            .collect()
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallAddressInspector {
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.enter(
+            context.journaled_state.depth,
+            inputs.caller,
+            [inputs.target_address, inputs.bytecode_address],
+        );
+        None
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.enter(context.journaled_state.depth, inputs.caller, []);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if let Some((_, reached)) = self.calls.last_mut() {
+            reached.extend(outcome.address);
+        }
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        if let Some((_, reached)) = self.calls.last_mut() {
+            reached.extend([contract, target]);
+        }
+    }
+}
@@ -7,11 +7,14 @@ use crate::execute::{
 };
 use reth_execution_errors::BlockExecutionError;
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives::{BlockNumber, BlockWithSenders, Header, Receipt};
+use reth_primitives::{Address, BlockNumber, BlockWithSenders, Header, Receipt};
 use reth_prune_types::PruneModes;
 use reth_storage_errors::provider::ProviderError;
 use revm_primitives::db::Database;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 // re-export Either
 pub use futures_util::future::Either;
 
@@ -45,6 +48,20 @@ where
             Self::Right(b) => Either::Right(b.batch_executor(db)),
         }
     }
+
+    fn call_addresses<DB>(
+        &self,
+        db: DB,
+        input: BlockExecutionInput<'_, BlockWithSenders, Header>,
+    ) -> Result<Option<Vec<Vec<Address>>>, BlockExecutionError>
+    where
+        DB: Database<Error: Into<ProviderError> + Display>,
+    {
+        match self {
+            Self::Left(a) => a.call_addresses(db, input),
+            Self::Right(b) => b.call_addresses(db, input),
+        }
+    }
 }
 
 impl<A, B, DB> Executor<DB> for Either<A, B>
@@ -2,7 +2,7 @@
 
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    parlia::Snapshot, BlockNumber, BlockWithSenders, Header, Receipt, Request, B256, U256,
+    parlia::Snapshot, Address, BlockNumber, BlockWithSenders, Header, Receipt, Request, B256, U256,
 };
 use reth_prune_types::PruneModes;
 use revm::db::BundleState;
@@ -198,6 +198,23 @@ pub trait BlockExecutorProvider: Send + Sync + Clone + Unpin + 'static {
     fn batch_executor<DB>(&self, db: DB) -> Self::BatchExecutor<DB>
     where
         DB: Database<Error: Into<ProviderError> + Display>;
+
+    /// Executes the block on top of the given database and returns the addresses reached by the
+    /// calls of every transaction of the block, in transaction order, see
+    /// [`CallAddressInspector`](crate::call_addresses::CallAddressInspector).
+    ///
+    /// The block is not verified. Returns `None` if the executor can't trace calls.
+    fn call_addresses<DB>(
+        &self,
+        db: DB,
+        input: BlockExecutionInput<'_, BlockWithSenders, Header>,
+    ) -> Result<Option<Vec<Vec<Address>>>, BlockExecutionError>
+    where
+        DB: Database<Error: Into<ProviderError> + Display>,
+    {
+        let _ = (db, input);
+        Ok(None)
+    }
 }
 
 #[cfg(test)]
@@ -359,10 +359,14 @@ pub struct EnrResponse {
 impl EnrResponse {
     /// Returns the [`ForkId`] if set
     ///
+    /// Falls back to the `bsc` entry, which BSC nodes may set instead of the `eth` entry.
+    ///
     /// See also <https://github.com/ethereum/go-ethereum/blob/9244d5cd61f3ea5a7645fdf2a1a96d53421e412f/eth/protocols/eth/discovery.go#L36>
     pub fn eth_fork_id(&self) -> Option<ForkId> {
-        let mut maybe_fork_id = self.enr.get_raw_rlp(b"eth")?;
-        EnrForkIdEntry::decode(&mut maybe_fork_id).ok().map(Into::into)
+        [b"eth".as_slice(), b"bsc"].into_iter().find_map(|key| {
+            let mut maybe_fork_id = self.enr.get_raw_rlp(key)?;
+            EnrForkIdEntry::decode(&mut maybe_fork_id).ok().map(Into::into)
+        })
     }
 }
 
@@ -776,6 +780,19 @@ mod tests {
 
         let fork_id_decoded = decoded.eth_fork_id().unwrap();
         assert_eq!(fork_id, fork_id_decoded);
+
+        // BSC nodes may only set the `bsc` entry
+        let enr = {
+            let mut builder = Enr::builder();
+            builder.ip(ip.into());
+            builder.tcp4(tcp);
+            let mut buf = Vec::new();
+            EnrForkIdEntry { fork_id }.encode(&mut buf);
+            builder.add_value_rlp("bsc", buf.into());
+            builder.build(&key).unwrap()
+        };
+        let enr_response = EnrResponse { request_hash: rng.gen(), enr };
+        assert_eq!(enr_response.eth_fork_id(), Some(fork_id));
     }
 
     // test vector from the enr library rlp encoding tests
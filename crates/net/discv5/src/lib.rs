@@ -362,6 +362,8 @@ impl Discv5 {
 
     /// Returns the [`ForkId`] of the given [`Enr`](discv5::Enr) w.r.t. the local node's network
     /// stack, if field is set.
+    ///
+    /// For the `eth` network stack, the `bsc` field is used if the `eth` field is missing.
     pub fn get_fork_id<K: discv5::enr::EnrKey>(
         &self,
         enr: &discv5::enr::Enr<K>,
@@ -369,6 +371,11 @@ impl Discv5 {
         let Some(key) = self.fork_key else { return Err(Error::NetworkStackIdNotConfigured) };
         let fork_id = enr
             .get_decodable::<EnrForkIdEntry>(key)
+            .or_else(|| {
+                (key == NetworkStackId::ETH)
+                    .then(|| enr.get_decodable::<EnrForkIdEntry>(NetworkStackId::BSC))
+                    .flatten()
+            })
             .ok_or(Error::ForkMissing(key))?
             .map(Into::into)?;
 
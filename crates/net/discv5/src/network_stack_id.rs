@@ -20,12 +20,17 @@ impl NetworkStackId {
     /// ENR fork ID kv-pair key, for an Optimism CL node.
     pub const OPSTACK: &'static [u8] = b"opstack";
 
+    /// ENR fork ID kv-pair key, which BSC nodes may set instead of [`ETH`](Self::ETH).
+    pub const BSC: &'static [u8] = b"bsc";
+
     #[allow(clippy::missing_const_for_fn)]
     /// Returns the [`NetworkStackId`] that matches the given [`ChainSpec`].
+    ///
+    /// BSC nodes advertise the BSC fork id under the `eth` key, or the [`BSC`](Self::BSC) key.
     pub fn id(chain: &ChainSpec) -> Option<&'static [u8]> {
         if chain.is_optimism() {
             return Some(Self::OPEL)
-        } else if chain.is_eth() {
+        } else if chain.is_eth() || chain.is_bsc() {
             return Some(Self::ETH)
         }
 
@@ -401,8 +401,11 @@ fn convert_enr_node_record(enr: &Enr<SecretKey>) -> Option<DnsNodeRecordUpdate>
     }
     .into_ipv4_mapped();
 
-    let fork_id =
-        enr.get_decodable::<EnrForkIdEntry>(b"eth").transpose().ok().flatten().map(Into::into);
+    // BSC nodes may set the `bsc` entry instead of the `eth` entry
+    let fork_id = [b"eth".as_slice(), b"bsc"]
+        .into_iter()
+        .find_map(|key| enr.get_decodable::<EnrForkIdEntry>(key).transpose().ok().flatten())
+        .map(Into::into);
 
     Some(DnsNodeRecordUpdate { node_record, fork_id, enr: enr.clone() })
 }
@@ -441,6 +444,22 @@ mod tests {
         assert_eq!(node_record_update.enr, enr);
     }
 
+    #[test]
+    fn test_convert_bsc_enr_node_record() {
+        let secret_key = SecretKey::new(&mut secp256k1::rand::thread_rng());
+        let fork_id = ForkId { hash: ForkHash(rand::random()), next: 0 };
+        let enr = Enr::builder()
+            .ip("127.0.0.1".parse().unwrap())
+            .udp4(9000)
+            .tcp4(30303)
+            .add_value(b"bsc", &EnrForkIdEntry::from(fork_id))
+            .build(&secret_key)
+            .unwrap();
+
+        let node_record_update = convert_enr_node_record(&enr).unwrap();
+        assert_eq!(node_record_update.fork_id, Some(fork_id));
+    }
+
     #[test]
     fn test_decode_and_convert_enr_node_record() {
         // rig
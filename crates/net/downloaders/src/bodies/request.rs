@@ -7,10 +7,11 @@ use reth_network_p2p::{
     priority::Priority,
 };
 use reth_network_peers::{PeerId, WithPeerId};
-use reth_primitives::{BlockBody, GotExpected, SealedBlock, SealedHeader, B256};
+use reth_primitives::{BlockBody, BlockNumber, GotExpected, SealedBlock, SealedHeader, B256};
 use std::{
     collections::VecDeque,
     mem,
+    ops::RangeInclusive,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
@@ -35,6 +36,10 @@ use std::{
 /// All errors regarding the response cause the peer to get penalized, meaning that adversaries
 /// that try to give us bodies that do not match the requested order are going to be penalized
 /// and eventually disconnected.
+///
+/// The range of the requested blocks is passed to the client, so the request is only sent to peers
+/// that still serve them. The network client handles the empty responses of peers that expired the
+/// requested blocks itself, any other empty response is penalized.
 pub(crate) struct BodiesRequestFuture<B: BodiesClient> {
     client: Arc<B>,
     consensus: Arc<dyn Consensus>,
@@ -96,19 +101,31 @@ where
         );
     }
 
-    /// Retrieve header hashes for the next request.
-    fn next_request(&self) -> Option<Vec<B256>> {
-        let mut hashes =
-            self.pending_headers.iter().filter(|h| !h.is_empty()).map(|h| h.hash()).peekable();
-        hashes.peek().is_some().then(|| hashes.collect())
+    /// Retrieve header hashes and the range of block numbers for the next request.
+    fn next_request(&self) -> Option<(Vec<B256>, RangeInclusive<BlockNumber>)> {
+        let mut headers = self.pending_headers.iter().filter(|h| !h.is_empty()).peekable();
+        let first = headers.peek()?.number;
+        let mut last = first;
+        let hashes = headers
+            .map(|h| {
+                last = h.number;
+                h.hash()
+            })
+            .collect();
+        Some((hashes, first..=last))
     }
 
     /// Submit the request with the given priority.
-    fn submit_request(&mut self, req: Vec<B256>, priority: Priority) {
-        tracing::trace!(target: "downloaders::bodies", request_len = req.len(), "Requesting bodies");
+    fn submit_request(
+        &mut self,
+        req: (Vec<B256>, RangeInclusive<BlockNumber>),
+        priority: Priority,
+    ) {
+        let (hashes, range) = req;
+        tracing::trace!(target: "downloaders::bodies", request_len = hashes.len(), ?range, "Requesting bodies");
         let client = Arc::clone(&self.client);
-        self.last_request_len = Some(req.len());
-        self.fut = Some(client.get_block_bodies_with_priority(req, priority));
+        self.last_request_len = Some(hashes.len());
+        self.fut = Some(client.get_block_bodies_in_range_with_priority(hashes, range, priority));
     }
 
     /// Process block response.
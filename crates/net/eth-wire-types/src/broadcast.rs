@@ -69,6 +69,34 @@ impl From<NewBlockHashes> for Vec<BlockHashNumber> {
     }
 }
 
+/// Announces the range of blocks a peer can serve, introduced in `eth/69`.
+///
+/// Sent whenever the range changes, e.g. because new blocks were imported or old history was
+/// pruned.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockRangeUpdate {
+    /// The number of the earliest block that can be served
+    pub earliest: u64,
+    /// The number of the latest block
+    pub latest: u64,
+    /// The hash of the latest block
+    pub latest_hash: B256,
+}
+
+impl BlockRangeUpdate {
+    /// Returns true if the earliest block is not after the latest block.
+    pub const fn is_valid(&self) -> bool {
+        self.earliest <= self.latest
+    }
+
+    /// Returns true if the block with the given number is within the range.
+    pub const fn contains(&self, number: u64) -> bool {
+        self.earliest <= number && number <= self.latest
+    }
+}
+
 /// A new block with the current total difficulty, which includes the difficulty of the returned
 /// block.
 #[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
@@ -155,7 +183,7 @@ impl NewPooledTransactionHashes {
                 matches!(version, EthVersion::Eth67 | EthVersion::Eth66)
             }
             Self::Eth68(_) => {
-                matches!(version, EthVersion::Eth68)
+                matches!(version, EthVersion::Eth68 | EthVersion::Eth69)
             }
         }
     }
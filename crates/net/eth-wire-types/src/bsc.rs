@@ -0,0 +1,168 @@
+//! Implements the message types of the bsc protocol, which gossips the fast finality votes of the
+//! validators: <https://github.com/bnb-chain/bsc/tree/master/eth/protocols/bsc>
+
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_primitives::{
+    bytes::{Buf, BufMut},
+    parlia::VoteEnvelope,
+    Bytes,
+};
+
+/// The version of the bsc protocol.
+pub const BSC_PROTOCOL_VERSION: u64 = 1;
+
+/// The handshake of the bsc protocol, sent by both sides once the connection is established.
+///
+/// The default handshake announces the [`BSC_PROTOCOL_VERSION`].
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BscCapPacket {
+    /// The version of the protocol
+    pub protocol_version: u64,
+    /// Reserved for future usage, a single RLP string
+    pub extra: Bytes,
+}
+
+impl Default for BscCapPacket {
+    fn default() -> Self {
+        Self { protocol_version: BSC_PROTOCOL_VERSION, extra: Bytes::from_static(&[0x00]) }
+    }
+}
+
+/// Gossips the votes of the validators.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VotesPacket {
+    /// The votes
+    pub votes: Vec<VoteEnvelope>,
+}
+
+/// Identifies the bsc protocol messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BscMessageId {
+    /// Handshake message.
+    Capability = 0x00,
+    /// Votes message.
+    Votes = 0x01,
+}
+
+impl BscMessageId {
+    /// Returns the max value of the message ids, the bsc protocol reserves `max() + 1` ids.
+    pub const fn max() -> u8 {
+        Self::Votes as u8
+    }
+}
+
+impl Encodable for BscMessageId {
+    fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(*self as u8);
+    }
+    fn length(&self) -> usize {
+        1
+    }
+}
+
+impl Decodable for BscMessageId {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let id = match buf.first().ok_or(alloy_rlp::Error::InputTooShort)? {
+            0x00 => Self::Capability,
+            0x01 => Self::Votes,
+            _ => return Err(alloy_rlp::Error::Custom("Invalid message ID")),
+        };
+        buf.advance(1);
+        Ok(id)
+    }
+}
+
+/// A message of the bsc protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BscMessage {
+    /// Represents a [`BscCapPacket`] handshake.
+    Capability(BscCapPacket),
+    /// Represents a [`VotesPacket`].
+    Votes(VotesPacket),
+}
+
+impl BscMessage {
+    /// Returns the message's ID.
+    pub const fn message_id(&self) -> BscMessageId {
+        match self {
+            Self::Capability(_) => BscMessageId::Capability,
+            Self::Votes(_) => BscMessageId::Votes,
+        }
+    }
+
+    /// Encodes the message, prefixed with its message id.
+    pub fn encoded(&self) -> bytes::BytesMut {
+        let mut buf = bytes::BytesMut::with_capacity(self.length());
+        self.encode(&mut buf);
+        buf
+    }
+
+    /// Decodes a message, prefixed with its message id.
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let message = match BscMessageId::decode(buf)? {
+            BscMessageId::Capability => Self::Capability(BscCapPacket::decode(buf)?),
+            BscMessageId::Votes => Self::Votes(VotesPacket::decode(buf)?),
+        };
+        Ok(message)
+    }
+}
+
+impl Encodable for BscMessage {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.message_id().encode(out);
+        match self {
+            Self::Capability(msg) => msg.encode(out),
+            Self::Votes(msg) => msg.encode(out),
+        }
+    }
+    fn length(&self) -> usize {
+        let payload_len = match self {
+            Self::Capability(msg) => msg.length(),
+            Self::Votes(msg) => msg.length(),
+        };
+        self.message_id().length() + payload_len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{
+        hex,
+        parlia::{VoteAddress, VoteData, VoteSignature},
+        B256,
+    };
+
+    #[test]
+    fn encode_capability() {
+        let message = BscMessage::Capability(BscCapPacket::default());
+        let encoded = message.encoded();
+        // message id, followed by the list of the version and the extra byte
+        assert_eq!(&encoded[..], &hex!("00c20100"));
+        assert_eq!(BscMessage::decode_message(&mut &encoded[..]).unwrap(), message);
+    }
+
+    #[test]
+    fn roundtrip_votes() {
+        let vote = VoteEnvelope {
+            vote_address: VoteAddress::repeat_byte(1),
+            signature: VoteSignature::repeat_byte(2),
+            data: VoteData {
+                source_number: 1,
+                source_hash: B256::repeat_byte(3),
+                target_number: 2,
+                target_hash: B256::repeat_byte(4),
+            },
+        };
+        let message = BscMessage::Votes(VotesPacket { votes: vec![vote.clone(), vote] });
+        let encoded = message.encoded();
+        assert_eq!(encoded[0], 0x01);
+        assert_eq!(BscMessage::decode_message(&mut &encoded[..]).unwrap(), message);
+
+        assert!(BscMessage::decode_message(&mut &[0x02, 0xc0][..]).is_err());
+    }
+}
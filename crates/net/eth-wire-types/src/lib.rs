@@ -9,7 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod status;
-pub use status::{Status, StatusBuilder};
+pub use status::{Status, StatusBuilder, StatusEth69};
 
 pub mod version;
 pub use version::EthVersion;
@@ -37,3 +37,9 @@ pub use receipts::*;
 
 pub mod upgrade_status;
 pub use upgrade_status::*;
+
+pub mod snap;
+pub use snap::*;
+
+pub mod bsc;
+pub use bsc::*;
@@ -2,7 +2,7 @@
 
 use alloy_rlp::{RlpDecodableWrapper, RlpEncodableWrapper};
 use reth_codecs_derive::derive_arbitrary;
-use reth_primitives::{ReceiptWithBloom, B256};
+use reth_primitives::{Receipt, ReceiptWithBloom, B256};
 
 /// A request for transaction receipts from the given block hashes.
 #[derive_arbitrary(rlp)]
@@ -23,9 +23,45 @@ pub struct Receipts(
     pub Vec<Vec<ReceiptWithBloom>>,
 );
 
+/// The `eth/69` response to [`GetReceipts`].
+///
+/// The receipts are encoded without their bloom filter, which the receiver recomputes from the
+/// logs.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Receipts69(
+    /// Each receipt list should correspond to a block hash in the request.
+    pub Vec<Vec<Receipt>>,
+);
+
+impl Receipts69 {
+    /// Converts the receipts into [`Receipts`], computing the bloom filter of every receipt.
+    pub fn into_receipts(self) -> Receipts {
+        Receipts(
+            self.0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(Receipt::with_bloom).collect())
+                .collect(),
+        )
+    }
+}
+
+impl From<Receipts> for Receipts69 {
+    fn from(receipts: Receipts) -> Self {
+        Self(
+            receipts
+                .0
+                .into_iter()
+                .map(|receipts| receipts.into_iter().map(|receipt| receipt.receipt).collect())
+                .collect(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{message::RequestPair, GetReceipts, Receipts};
+    use crate::{message::RequestPair, GetReceipts, Receipts, Receipts69};
     use alloy_rlp::{Decodable, Encodable};
     use reth_primitives::{hex, Log, Receipt, ReceiptWithBloom, TxType};
 
@@ -45,6 +81,35 @@ mod tests {
         assert_eq!(receipts, decoded);
     }
 
+    #[test]
+    fn roundtrip_eth69_receipts() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21000,
+            logs: vec![Log::new_unchecked(
+                hex!("0000000000000000000000000000000000000011").into(),
+                vec![
+                    hex!("000000000000000000000000000000000000000000000000000000000000dead").into()
+                ],
+                hex!("0100ff")[..].into(),
+            )],
+            ..Default::default()
+        };
+        let receipts = Receipts(vec![vec![receipt.clone().with_bloom()]]);
+
+        let receipts69 = Receipts69::from(receipts.clone());
+        assert_eq!(receipts69, Receipts69(vec![vec![receipt]]));
+
+        let mut out = vec![];
+        receipts69.encode(&mut out);
+        let decoded = Receipts69::decode(&mut out.as_slice()).unwrap();
+        assert_eq!(decoded, receipts69);
+
+        // the bloom is recomputed from the logs
+        assert_eq!(decoded.into_receipts(), receipts);
+    }
+
     #[test]
     // Test vector from: https://eips.ethereum.org/EIPS/eip-2481
     fn encode_get_receipts() {
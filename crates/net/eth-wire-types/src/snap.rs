@@ -0,0 +1,502 @@
+//! Implements the message types of the snap protocol:
+//! <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+//!
+//! Unlike the `eth` messages, the request id of the snap messages is not wrapped in a
+//! [`RequestPair`](crate::message::RequestPair) but is the first field of each message.
+
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_codecs_derive::derive_arbitrary;
+use reth_primitives::{
+    bytes::{Buf, BufMut},
+    constants::EMPTY_ROOT_HASH,
+    Bytes, B256, KECCAK_EMPTY, U256,
+};
+
+/// The soft limit of the response size, requested when no other limit is known.
+pub const SNAP_SOFT_RESPONSE_LIMIT: u64 = 512 * 1024;
+
+/// Requests the accounts of the state trie with the given root, starting at the given account
+/// hash and up to the limit hash.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GetAccountRange {
+    /// The id of the request
+    pub request_id: u64,
+    /// The root of the account trie to serve
+    pub root_hash: B256,
+    /// The hash of the first account to retrieve
+    pub starting_hash: B256,
+    /// The hash after which to stop serving data
+    pub limit_hash: B256,
+    /// The soft limit at which to stop returning data
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetAccountRange`], containing consecutive accounts and the merkle proofs of
+/// the first and the last account of the range.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountRange {
+    /// The id of the request
+    pub request_id: u64,
+    /// The accounts, ordered by their hash
+    pub accounts: Vec<AccountData>,
+    /// The trie nodes proving the boundaries of the range
+    pub proof: Vec<Bytes>,
+}
+
+/// An account of an [`AccountRange`] response.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountData {
+    /// The hash of the account address
+    pub hash: B256,
+    /// The account in the slim format
+    pub body: SlimAccount,
+}
+
+/// An account in the slim format of the snap protocol, in which the empty storage root and the
+/// empty code hash are encoded as empty bytes.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlimAccount {
+    /// The nonce of the account
+    pub nonce: u64,
+    /// The balance of the account
+    pub balance: U256,
+    /// The storage root, empty if the account has no storage
+    pub storage_root: Bytes,
+    /// The code hash, empty if the account has no code
+    pub code_hash: Bytes,
+}
+
+impl SlimAccount {
+    /// Creates the slim account from the full storage root and code hash.
+    pub fn new(nonce: u64, balance: U256, storage_root: B256, code_hash: B256) -> Self {
+        let storage_root = if storage_root == EMPTY_ROOT_HASH {
+            Bytes::new()
+        } else {
+            Bytes::copy_from_slice(storage_root.as_slice())
+        };
+        let code_hash = if code_hash == KECCAK_EMPTY {
+            Bytes::new()
+        } else {
+            Bytes::copy_from_slice(code_hash.as_slice())
+        };
+        Self { nonce, balance, storage_root, code_hash }
+    }
+
+    /// Returns the storage root of the account.
+    ///
+    /// Returns `None` if the encoded root is neither empty nor 32 bytes long.
+    pub fn storage_root(&self) -> Option<B256> {
+        match self.storage_root.len() {
+            0 => Some(EMPTY_ROOT_HASH),
+            32 => Some(B256::from_slice(&self.storage_root)),
+            _ => None,
+        }
+    }
+
+    /// Returns the code hash of the account.
+    ///
+    /// Returns `None` if the encoded hash is neither empty nor 32 bytes long.
+    pub fn code_hash(&self) -> Option<B256> {
+        match self.code_hash.len() {
+            0 => Some(KECCAK_EMPTY),
+            32 => Some(B256::from_slice(&self.code_hash)),
+            _ => None,
+        }
+    }
+}
+
+/// Requests the storage slots of the given accounts of the state trie with the given root.
+///
+/// The starting and limit hash only apply to the first account, and are empty if the whole
+/// storage is requested.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GetStorageRanges {
+    /// The id of the request
+    pub request_id: u64,
+    /// The root of the account trie to serve
+    pub root_hash: B256,
+    /// The hashes of the account addresses whose storage to retrieve
+    pub account_hashes: Vec<B256>,
+    /// The hash of the first storage slot to retrieve
+    pub starting_hash: Bytes,
+    /// The hash after which to stop serving data
+    pub limit_hash: Bytes,
+    /// The soft limit at which to stop returning data
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetStorageRanges`], containing the storage slots of each requested account
+/// and the merkle proofs of the boundaries of the last, possibly incomplete, range.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageRanges {
+    /// The id of the request
+    pub request_id: u64,
+    /// The storage slots of each account, ordered by their hash
+    pub slots: Vec<Vec<StorageData>>,
+    /// The trie nodes proving the boundaries of the last range
+    pub proof: Vec<Bytes>,
+}
+
+/// A storage slot of a [`StorageRanges`] response.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageData {
+    /// The hash of the storage key
+    pub hash: B256,
+    /// The RLP encoded storage value
+    pub data: Bytes,
+}
+
+/// Requests the contract bytecodes with the given hashes.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GetByteCodes {
+    /// The id of the request
+    pub request_id: u64,
+    /// The code hashes to retrieve
+    pub hashes: Vec<B256>,
+    /// The soft limit at which to stop returning data
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetByteCodes`], containing the requested bytecodes in request order.
+///
+/// Not all bytecodes are guaranteed to be returned, missing codes are skipped.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ByteCodes {
+    /// The id of the request
+    pub request_id: u64,
+    /// The requested bytecodes
+    pub codes: Vec<Bytes>,
+}
+
+/// Requests the trie nodes at the given paths of the state trie with the given root.
+///
+/// Each path set starts with the path of an account trie node, followed by the paths of the
+/// storage trie nodes of that account, all in compact encoding.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GetTrieNodes {
+    /// The id of the request
+    pub request_id: u64,
+    /// The root of the account trie to serve
+    pub root_hash: B256,
+    /// The paths of the trie nodes to retrieve
+    pub paths: Vec<Vec<Bytes>>,
+    /// The soft limit at which to stop returning data
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetTrieNodes`], containing the RLP encoded trie nodes in request order.
+#[derive_arbitrary(rlp)]
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TrieNodes {
+    /// The id of the request
+    pub request_id: u64,
+    /// The requested trie nodes
+    pub nodes: Vec<Bytes>,
+}
+
+/// Represents message IDs for snap protocol messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMessageId {
+    /// Get account range message.
+    GetAccountRange = 0x00,
+    /// Account range message.
+    AccountRange = 0x01,
+    /// Get storage ranges message.
+    GetStorageRanges = 0x02,
+    /// Storage ranges message.
+    StorageRanges = 0x03,
+    /// Get byte codes message.
+    GetByteCodes = 0x04,
+    /// Byte codes message.
+    ByteCodes = 0x05,
+    /// Get trie nodes message.
+    GetTrieNodes = 0x06,
+    /// Trie nodes message.
+    TrieNodes = 0x07,
+}
+
+impl SnapMessageId {
+    /// Returns the max value of the message ids, the snap protocol reserves `max() + 1` ids.
+    pub const fn max() -> u8 {
+        Self::TrieNodes as u8
+    }
+}
+
+impl Encodable for SnapMessageId {
+    fn encode(&self, out: &mut dyn BufMut) {
+        out.put_u8(*self as u8);
+    }
+    fn length(&self) -> usize {
+        1
+    }
+}
+
+impl Decodable for SnapMessageId {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let id = match buf.first().ok_or(alloy_rlp::Error::InputTooShort)? {
+            0x00 => Self::GetAccountRange,
+            0x01 => Self::AccountRange,
+            0x02 => Self::GetStorageRanges,
+            0x03 => Self::StorageRanges,
+            0x04 => Self::GetByteCodes,
+            0x05 => Self::ByteCodes,
+            0x06 => Self::GetTrieNodes,
+            0x07 => Self::TrieNodes,
+            _ => return Err(alloy_rlp::Error::Custom("Invalid message ID")),
+        };
+        buf.advance(1);
+        Ok(id)
+    }
+}
+
+/// A message of the snap protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMessage {
+    /// Represents a [`GetAccountRange`] request.
+    GetAccountRange(GetAccountRange),
+    /// Represents an [`AccountRange`] response.
+    AccountRange(AccountRange),
+    /// Represents a [`GetStorageRanges`] request.
+    GetStorageRanges(GetStorageRanges),
+    /// Represents a [`StorageRanges`] response.
+    StorageRanges(StorageRanges),
+    /// Represents a [`GetByteCodes`] request.
+    GetByteCodes(GetByteCodes),
+    /// Represents a [`ByteCodes`] response.
+    ByteCodes(ByteCodes),
+    /// Represents a [`GetTrieNodes`] request.
+    GetTrieNodes(GetTrieNodes),
+    /// Represents a [`TrieNodes`] response.
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Returns the message's ID.
+    pub const fn message_id(&self) -> SnapMessageId {
+        match self {
+            Self::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            Self::AccountRange(_) => SnapMessageId::AccountRange,
+            Self::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            Self::StorageRanges(_) => SnapMessageId::StorageRanges,
+            Self::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            Self::ByteCodes(_) => SnapMessageId::ByteCodes,
+            Self::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            Self::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the request id of the message.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id,
+            Self::AccountRange(msg) => msg.request_id,
+            Self::GetStorageRanges(msg) => msg.request_id,
+            Self::StorageRanges(msg) => msg.request_id,
+            Self::GetByteCodes(msg) => msg.request_id,
+            Self::ByteCodes(msg) => msg.request_id,
+            Self::GetTrieNodes(msg) => msg.request_id,
+            Self::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Returns true if the message is a request.
+    pub const fn is_request(&self) -> bool {
+        matches!(
+            self,
+            Self::GetAccountRange(_) |
+                Self::GetStorageRanges(_) |
+                Self::GetByteCodes(_) |
+                Self::GetTrieNodes(_)
+        )
+    }
+
+    /// Encodes the message, prefixed with its message id.
+    pub fn encoded(&self) -> bytes::BytesMut {
+        let mut buf = bytes::BytesMut::with_capacity(self.length());
+        self.encode(&mut buf);
+        buf
+    }
+
+    /// Decodes a message, prefixed with its message id.
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let message = match SnapMessageId::decode(buf)? {
+            SnapMessageId::GetAccountRange => Self::GetAccountRange(GetAccountRange::decode(buf)?),
+            SnapMessageId::AccountRange => Self::AccountRange(AccountRange::decode(buf)?),
+            SnapMessageId::GetStorageRanges => {
+                Self::GetStorageRanges(GetStorageRanges::decode(buf)?)
+            }
+            SnapMessageId::StorageRanges => Self::StorageRanges(StorageRanges::decode(buf)?),
+            SnapMessageId::GetByteCodes => Self::GetByteCodes(GetByteCodes::decode(buf)?),
+            SnapMessageId::ByteCodes => Self::ByteCodes(ByteCodes::decode(buf)?),
+            SnapMessageId::GetTrieNodes => Self::GetTrieNodes(GetTrieNodes::decode(buf)?),
+            SnapMessageId::TrieNodes => Self::TrieNodes(TrieNodes::decode(buf)?),
+        };
+        Ok(message)
+    }
+}
+
+impl Encodable for SnapMessage {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.message_id().encode(out);
+        match self {
+            Self::GetAccountRange(msg) => msg.encode(out),
+            Self::AccountRange(msg) => msg.encode(out),
+            Self::GetStorageRanges(msg) => msg.encode(out),
+            Self::StorageRanges(msg) => msg.encode(out),
+            Self::GetByteCodes(msg) => msg.encode(out),
+            Self::ByteCodes(msg) => msg.encode(out),
+            Self::GetTrieNodes(msg) => msg.encode(out),
+            Self::TrieNodes(msg) => msg.encode(out),
+        }
+    }
+    fn length(&self) -> usize {
+        let payload_len = match self {
+            Self::GetAccountRange(msg) => msg.length(),
+            Self::AccountRange(msg) => msg.length(),
+            Self::GetStorageRanges(msg) => msg.length(),
+            Self::StorageRanges(msg) => msg.length(),
+            Self::GetByteCodes(msg) => msg.length(),
+            Self::ByteCodes(msg) => msg.length(),
+            Self::GetTrieNodes(msg) => msg.length(),
+            Self::TrieNodes(msg) => msg.length(),
+        };
+        self.message_id().length() + payload_len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::hex;
+
+    #[test]
+    fn slim_account_empty_fields() {
+        let account = SlimAccount::new(1, U256::from(2), EMPTY_ROOT_HASH, KECCAK_EMPTY);
+        assert!(account.storage_root.is_empty());
+        assert!(account.code_hash.is_empty());
+        assert_eq!(account.storage_root(), Some(EMPTY_ROOT_HASH));
+        assert_eq!(account.code_hash(), Some(KECCAK_EMPTY));
+
+        let mut buf = Vec::new();
+        account.encode(&mut buf);
+        assert_eq!(buf, hex!("c401028080"));
+        assert_eq!(SlimAccount::decode(&mut &buf[..]).unwrap(), account);
+    }
+
+    #[test]
+    fn slim_account_full_fields() {
+        let root = B256::repeat_byte(0x11);
+        let code_hash = B256::repeat_byte(0x22);
+        let account = SlimAccount::new(0, U256::ZERO, root, code_hash);
+        assert_eq!(account.storage_root(), Some(root));
+        assert_eq!(account.code_hash(), Some(code_hash));
+
+        let account = SlimAccount { storage_root: Bytes::from_static(&[1]), ..account };
+        assert_eq!(account.storage_root(), None);
+    }
+
+    #[test]
+    fn encode_get_byte_codes() {
+        let message = SnapMessage::GetByteCodes(GetByteCodes {
+            request_id: 1111,
+            hashes: vec![B256::repeat_byte(0xaa)],
+            response_bytes: 1024,
+        });
+        let encoded = message.encoded();
+        // message id, followed by the flat list of the request id, the hashes and the limit
+        assert_eq!(encoded[0], 0x04);
+        assert_eq!(&encoded[1..6], &hex!("e8820457e1"));
+        assert_eq!(SnapMessage::decode_message(&mut &encoded[..]).unwrap(), message);
+        assert_eq!(message.request_id(), 1111);
+        assert!(message.is_request());
+    }
+
+    #[test]
+    fn roundtrip_messages() {
+        let messages = vec![
+            SnapMessage::GetAccountRange(GetAccountRange {
+                request_id: 1,
+                root_hash: B256::repeat_byte(1),
+                starting_hash: B256::ZERO,
+                limit_hash: B256::repeat_byte(0xff),
+                response_bytes: SNAP_SOFT_RESPONSE_LIMIT,
+            }),
+            SnapMessage::AccountRange(AccountRange {
+                request_id: 1,
+                accounts: vec![AccountData {
+                    hash: B256::repeat_byte(2),
+                    body: SlimAccount::new(1, U256::from(3), EMPTY_ROOT_HASH, KECCAK_EMPTY),
+                }],
+                proof: vec![Bytes::from_static(&[0xc0])],
+            }),
+            SnapMessage::GetStorageRanges(GetStorageRanges {
+                request_id: 2,
+                root_hash: B256::repeat_byte(1),
+                account_hashes: vec![B256::repeat_byte(2)],
+                starting_hash: Bytes::new(),
+                limit_hash: Bytes::new(),
+                response_bytes: SNAP_SOFT_RESPONSE_LIMIT,
+            }),
+            SnapMessage::StorageRanges(StorageRanges {
+                request_id: 2,
+                slots: vec![vec![StorageData {
+                    hash: B256::repeat_byte(3),
+                    data: Bytes::from_static(&[0x01]),
+                }]],
+                proof: vec![],
+            }),
+            SnapMessage::GetByteCodes(GetByteCodes {
+                request_id: 3,
+                hashes: vec![B256::repeat_byte(4)],
+                response_bytes: SNAP_SOFT_RESPONSE_LIMIT,
+            }),
+            SnapMessage::ByteCodes(ByteCodes {
+                request_id: 3,
+                codes: vec![Bytes::from_static(&[0x60, 0x00])],
+            }),
+            SnapMessage::GetTrieNodes(GetTrieNodes {
+                request_id: 4,
+                root_hash: B256::repeat_byte(1),
+                paths: vec![vec![Bytes::from_static(&[0x00])]],
+                response_bytes: SNAP_SOFT_RESPONSE_LIMIT,
+            }),
+            SnapMessage::TrieNodes(TrieNodes { request_id: 4, nodes: vec![] }),
+        ];
+
+        for message in messages {
+            let encoded = message.encoded();
+            assert_eq!(encoded.len(), message.length());
+            assert_eq!(encoded[0], message.message_id() as u8);
+            assert_eq!(SnapMessage::decode_message(&mut &encoded[..]).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn decode_invalid_message_id() {
+        let buf = [SnapMessageId::max() + 1, 0xc0];
+        assert!(SnapMessage::decode_message(&mut &buf[..]).is_err());
+    }
+}
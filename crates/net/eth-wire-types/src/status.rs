@@ -1,4 +1,4 @@
-use crate::EthVersion;
+use crate::{BlockRangeUpdate, EthVersion};
 use alloy_chains::{Chain, NamedChain};
 use alloy_genesis::Genesis;
 use alloy_rlp::{RlpDecodable, RlpEncodable};
@@ -81,6 +81,20 @@ impl Status {
             .total_difficulty(head.total_difficulty)
             .forkid(spec.fork_id(head))
     }
+
+    /// Converts the status into the [`StatusEth69`] message, which announces the given range of
+    /// available blocks instead of the total difficulty.
+    pub const fn into_eth69(self, block_range: BlockRangeUpdate) -> StatusEth69 {
+        StatusEth69 {
+            version: self.version,
+            chain: self.chain,
+            genesis: self.genesis,
+            forkid: self.forkid,
+            earliest: block_range.earliest,
+            latest: block_range.latest,
+            blockhash: block_range.latest_hash,
+        }
+    }
 }
 
 impl Display for Status {
@@ -130,6 +144,81 @@ impl Debug for Status {
     }
 }
 
+/// The status message of `eth/69`.
+///
+/// Compared to [`Status`], the total difficulty is dropped and the range of blocks the peer can
+/// serve is announced instead.
+///
+/// See also <https://eips.ethereum.org/EIPS/eip-7642>
+#[derive_arbitrary(rlp)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatusEth69 {
+    /// The current protocol version.
+    pub version: u8,
+
+    /// The chain id, as introduced in
+    /// [EIP155](https://eips.ethereum.org/EIPS/eip-155#list-of-chain-ids).
+    pub chain: Chain,
+
+    /// The genesis hash of the peer's chain.
+    pub genesis: B256,
+
+    /// The fork identifier as defined by
+    /// [EIP-2124](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2124.md).
+    pub forkid: ForkId,
+
+    /// The number of the earliest block the peer can serve.
+    pub earliest: u64,
+
+    /// The number of the latest block of the peer.
+    pub latest: u64,
+
+    /// The hash of the latest block of the peer.
+    pub blockhash: B256,
+}
+
+impl StatusEth69 {
+    /// Returns the range of blocks announced by the status.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        BlockRangeUpdate {
+            earliest: self.earliest,
+            latest: self.latest,
+            latest_hash: self.blockhash,
+        }
+    }
+}
+
+impl From<StatusEth69> for Status {
+    /// Converts the `eth/69` status into a [`Status`], with a total difficulty of zero.
+    fn from(status: StatusEth69) -> Self {
+        Self {
+            version: status.version,
+            chain: status.chain,
+            total_difficulty: U256::ZERO,
+            blockhash: status.blockhash,
+            genesis: status.genesis,
+            forkid: status.forkid,
+        }
+    }
+}
+
+impl Display for StatusEth69 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "StatusEth69 {{ version: {}, chain: {}, genesis: {}, forkid: {:X?}, earliest: {}, latest: {}, blockhash: {} }}",
+            self.version,
+            self.chain,
+            hex::encode(self.genesis),
+            self.forkid,
+            self.earliest,
+            self.latest,
+            hex::encode(self.blockhash),
+        )
+    }
+}
+
 // <https://etherscan.io/block/0>
 impl Default for Status {
     fn default() -> Self {
@@ -227,7 +316,7 @@ impl StatusBuilder {
 
 #[cfg(test)]
 mod tests {
-    use crate::{EthVersion, Status};
+    use crate::{BlockRangeUpdate, EthVersion, Status, StatusEth69};
     use alloy_genesis::Genesis;
     use alloy_rlp::{Decodable, Encodable};
     use rand::Rng;
@@ -347,6 +436,25 @@ mod tests {
         assert_eq!(status, expected);
     }
 
+    #[test]
+    fn roundtrip_eth69_status_message() {
+        let status = Status { version: EthVersion::Eth69 as u8, ..Default::default() };
+        let block_range =
+            BlockRangeUpdate { earliest: 100, latest: 200, latest_hash: B256::repeat_byte(0xaa) };
+        let status_eth69 = status.into_eth69(block_range);
+
+        let mut rlp_status = vec![];
+        status_eth69.encode(&mut rlp_status);
+        let decoded = StatusEth69::decode(&mut &rlp_status[..]).unwrap();
+        assert_eq!(decoded, status_eth69);
+        assert_eq!(decoded.block_range(), block_range);
+
+        let status = Status::from(decoded);
+        assert_eq!(status.genesis, status_eth69.genesis);
+        assert_eq!(status.blockhash, block_range.latest_hash);
+        assert_eq!(status.total_difficulty, U256::ZERO);
+    }
+
     #[test]
     fn init_custom_status_fields() {
         let mut rng = rand::thread_rng();
@@ -0,0 +1,178 @@
+//! Accounting of the bytes exchanged over an [`EthStream`](crate::EthStream), per message type.
+//!
+//! Sizes are those of the RLP encoded messages, including the message id, before snappy
+//! compression.
+
+use crate::EthMessageID;
+use alloy_rlp::Decodable;
+use reth_metrics::{metrics::Counter, Metrics};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+/// The number of distinct message ids of the `eth` protocol.
+const NUM_MESSAGE_IDS: usize = EthMessageID::BlockRangeUpdate as usize + 1;
+
+/// Bandwidth metrics of a single message type, across all sessions.
+#[derive(Metrics)]
+#[metrics(scope = "network.bandwidth")]
+struct MessageBandwidthMetrics {
+    /// Total number of messages received
+    ingress_messages: Counter,
+    /// Total number of bytes received
+    ingress_bytes: Counter,
+    /// Total number of messages sent
+    egress_messages: Counter,
+    /// Total number of bytes sent
+    egress_bytes: Counter,
+}
+
+/// Bandwidth metrics of all message types, labeled by message type.
+///
+/// This is shared by the [`BandwidthMeter`]s of all sessions.
+#[derive(Debug)]
+pub struct BandwidthMetrics {
+    /// The metrics of every message type, indexed by message id.
+    messages: Vec<Option<MessageBandwidthMetrics>>,
+}
+
+impl Default for BandwidthMetrics {
+    fn default() -> Self {
+        let messages = (0..NUM_MESSAGE_IDS)
+            .map(|id| {
+                let id = message_id(id)?;
+                Some(MessageBandwidthMetrics::new_with_labels(&[("message", format!("{id:?}"))]))
+            })
+            .collect();
+        Self { messages }
+    }
+}
+
+/// Returns the message type of the given message id, if it is known.
+fn message_id(id: usize) -> Option<EthMessageID> {
+    EthMessageID::decode(&mut [u8::try_from(id).ok()?].as_slice()).ok()
+}
+
+/// Counters of a single message type.
+#[derive(Debug, Default)]
+struct MessageCounters {
+    ingress_messages: AtomicU64,
+    ingress_bytes: AtomicU64,
+    egress_messages: AtomicU64,
+    egress_bytes: AtomicU64,
+}
+
+/// Counts the messages and bytes received and sent in a session, per message type.
+///
+/// The meter can be shared with other tasks to read the current usage with
+/// [`BandwidthMeter::stats`].
+#[derive(Debug)]
+pub struct BandwidthMeter {
+    /// The counters of every message type, indexed by message id.
+    counters: [MessageCounters; NUM_MESSAGE_IDS],
+    /// Metrics shared by all sessions.
+    metrics: Arc<BandwidthMetrics>,
+}
+
+impl BandwidthMeter {
+    /// Creates a new meter that also records into the given metrics.
+    pub fn new(metrics: Arc<BandwidthMetrics>) -> Self {
+        Self { counters: Default::default(), metrics }
+    }
+
+    /// Records a received message with the given id and encoded size.
+    pub fn record_ingress(&self, message_id: u8, bytes: usize) {
+        let Some(counters) = self.counters.get(message_id as usize) else { return };
+        counters.ingress_messages.fetch_add(1, Ordering::Relaxed);
+        counters.ingress_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        if let Some(metrics) = &self.metrics.messages[message_id as usize] {
+            metrics.ingress_messages.increment(1);
+            metrics.ingress_bytes.increment(bytes as u64);
+        }
+    }
+
+    /// Records a sent message with the given id and encoded size.
+    pub fn record_egress(&self, message_id: u8, bytes: usize) {
+        let Some(counters) = self.counters.get(message_id as usize) else { return };
+        counters.egress_messages.fetch_add(1, Ordering::Relaxed);
+        counters.egress_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        if let Some(metrics) = &self.metrics.messages[message_id as usize] {
+            metrics.egress_messages.increment(1);
+            metrics.egress_bytes.increment(bytes as u64);
+        }
+    }
+
+    /// Returns the usage of every known message type that was received or sent at least once.
+    pub fn stats(&self) -> Vec<MessageBandwidth> {
+        self.counters
+            .iter()
+            .enumerate()
+            .filter_map(|(id, counters)| {
+                let stats = MessageBandwidth {
+                    message_id: message_id(id)?,
+                    ingress_messages: counters.ingress_messages.load(Ordering::Relaxed),
+                    ingress_bytes: counters.ingress_bytes.load(Ordering::Relaxed),
+                    egress_messages: counters.egress_messages.load(Ordering::Relaxed),
+                    egress_bytes: counters.egress_bytes.load(Ordering::Relaxed),
+                };
+                (stats.ingress_messages > 0 || stats.egress_messages > 0).then_some(stats)
+            })
+            .collect()
+    }
+}
+
+impl Default for BandwidthMeter {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+/// The usage of a single message type in a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageBandwidth {
+    /// The message type.
+    pub message_id: EthMessageID,
+    /// Number of messages received.
+    pub ingress_messages: u64,
+    /// Number of bytes received.
+    pub ingress_bytes: u64,
+    /// Number of messages sent.
+    pub egress_messages: u64,
+    /// Number of bytes sent.
+    pub egress_bytes: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_per_message_type() {
+        let meter = BandwidthMeter::default();
+        assert!(meter.stats().is_empty());
+
+        meter.record_ingress(EthMessageID::GetBlockBodies as u8, 40);
+        meter.record_egress(EthMessageID::BlockBodies as u8, 1000);
+        meter.record_egress(EthMessageID::BlockBodies as u8, 500);
+        // unknown ids are not reported
+        meter.record_ingress(0x0c, 10);
+        meter.record_ingress(0x20, 10);
+
+        let stats = meter.stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            stats[0],
+            MessageBandwidth {
+                message_id: EthMessageID::GetBlockBodies,
+                ingress_messages: 1,
+                ingress_bytes: 40,
+                egress_messages: 0,
+                egress_bytes: 0,
+            }
+        );
+        assert_eq!(stats[1].message_id, EthMessageID::BlockBodies);
+        assert_eq!(stats[1].egress_messages, 2);
+        assert_eq!(stats[1].egress_bytes, 1500);
+    }
+}
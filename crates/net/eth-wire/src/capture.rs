@@ -0,0 +1,333 @@
+//! Capture of the messages exchanged over a [`P2PStream`](crate::P2PStream), for debugging.
+//!
+//! A capture file starts with a [`CaptureHeader`] that describes the session, followed by one
+//! [`CapturedMessage`] per subprotocol message, all RLP encoded. Messages are captured after
+//! decryption and snappy decompression, so they can be decoded with the message types of this
+//! crate.
+
+use crate::capability::SharedCapabilities;
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use reth_network_peers::PeerId;
+use reth_primitives::Bytes;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    thread::JoinHandle,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+
+/// The version of the capture file format.
+pub const CAPTURE_FORMAT_VERSION: u8 = 1;
+
+/// The direction of a captured message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureDirection {
+    /// The message was received from the peer.
+    Inbound,
+    /// The message was sent to the peer.
+    Outbound,
+}
+
+impl Encodable for CaptureDirection {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        (*self as u8).encode(out)
+    }
+
+    fn length(&self) -> usize {
+        (*self as u8).length()
+    }
+}
+
+impl Decodable for CaptureDirection {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        match u8::decode(buf)? {
+            0 => Ok(Self::Inbound),
+            1 => Ok(Self::Outbound),
+            _ => Err(alloy_rlp::Error::Custom("invalid capture direction")),
+        }
+    }
+}
+
+/// A capability shared with the peer of a captured session.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct CapturedCapability {
+    /// The name of the capability.
+    pub name: String,
+    /// The negotiated version of the capability.
+    pub version: u8,
+    /// The offset of the first message id of the capability, relative to the end of the reserved
+    /// `p2p` message id space.
+    pub offset: u8,
+}
+
+/// Describes the session of a capture file.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct CaptureHeader {
+    /// The version of the capture file format, see [`CAPTURE_FORMAT_VERSION`].
+    pub format_version: u8,
+    /// The identifier of the peer.
+    pub peer_id: PeerId,
+    /// The client version the peer announced in its `Hello` message.
+    pub client_version: String,
+    /// The capabilities shared with the peer, in message id order.
+    pub capabilities: Vec<CapturedCapability>,
+}
+
+impl CaptureHeader {
+    /// Creates the header of a session with the given peer.
+    pub fn new(
+        peer_id: PeerId,
+        client_version: String,
+        shared_capabilities: &SharedCapabilities,
+    ) -> Self {
+        let capabilities = shared_capabilities
+            .iter_caps()
+            .map(|cap| CapturedCapability {
+                name: cap.name().to_string(),
+                version: cap.version(),
+                offset: cap.relative_message_id_offset(),
+            })
+            .collect();
+        Self { format_version: CAPTURE_FORMAT_VERSION, peer_id, client_version, capabilities }
+    }
+
+    /// Returns the capability the given message id belongs to.
+    pub fn find_capability(&self, message_id: u8) -> Option<&CapturedCapability> {
+        self.capabilities.iter().rev().find(|cap| cap.offset <= message_id)
+    }
+}
+
+/// A subprotocol message of a captured session.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct CapturedMessage {
+    /// Unix timestamp in milliseconds at which the message was received or sent.
+    pub timestamp: u64,
+    /// Whether the message was received or sent.
+    pub direction: CaptureDirection,
+    /// The message id, relative to the end of the reserved `p2p` message id space, followed by
+    /// the RLP encoded message.
+    pub message: Bytes,
+}
+
+impl CapturedMessage {
+    /// Returns the message id, relative to the end of the reserved `p2p` message id space.
+    pub fn message_id(&self) -> Option<u8> {
+        self.message.first().copied()
+    }
+}
+
+/// Writes the capture files of all sessions on a dedicated thread, so that recording a message
+/// never blocks the session.
+///
+/// The thread exits once the writer and all [`MessageCapture`]s it started are dropped.
+#[derive(Debug, Clone)]
+pub struct CaptureWriter {
+    /// Sends the commands to the writer thread.
+    tx: mpsc::Sender<CaptureCommand>,
+    /// The identifier of the next capture.
+    next_id: Arc<AtomicU64>,
+}
+
+impl CaptureWriter {
+    /// Spawns the thread that writes the capture files to the given directory.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        Self::spawn(dir.into()).map(|(writer, _)| writer)
+    }
+
+    /// Spawns the writer thread and returns its handle.
+    fn spawn(dir: PathBuf) -> io::Result<(Self, JoinHandle<()>)> {
+        let (tx, rx) = mpsc::channel();
+        let handle = std::thread::Builder::new()
+            .name("net-capture".to_string())
+            .spawn(move || write_captures(&dir, rx))?;
+        Ok((Self { tx, next_id: Default::default() }, handle))
+    }
+
+    /// Starts the capture of a session, in a new file named after the current time and the peer
+    /// id.
+    pub fn start(&self, header: CaptureHeader) -> MessageCapture {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let _ = self.tx.send(CaptureCommand::Start { id, header });
+        MessageCapture { id, tx: self.tx.clone() }
+    }
+}
+
+/// Records the messages of a session, see [`CaptureWriter::start`].
+///
+/// The capture file is closed when this is dropped.
+#[derive(Debug)]
+pub struct MessageCapture {
+    /// The identifier of the capture on the writer thread.
+    id: u64,
+    /// Sends the commands to the writer thread.
+    tx: mpsc::Sender<CaptureCommand>,
+}
+
+impl MessageCapture {
+    /// Queues the message to be appended to the capture file.
+    pub fn record(&self, direction: CaptureDirection, message: &[u8]) {
+        let message = CapturedMessage {
+            timestamp: unix_millis(),
+            direction,
+            message: Bytes::copy_from_slice(message),
+        };
+        let _ = self.tx.send(CaptureCommand::Record { id: self.id, message });
+    }
+}
+
+impl Drop for MessageCapture {
+    fn drop(&mut self) {
+        let _ = self.tx.send(CaptureCommand::Stop { id: self.id });
+    }
+}
+
+/// A command for the writer thread.
+#[derive(Debug)]
+enum CaptureCommand {
+    /// Creates the capture file and writes the header.
+    Start { id: u64, header: CaptureHeader },
+    /// Appends the message to the capture file.
+    Record { id: u64, message: CapturedMessage },
+    /// Closes the capture file.
+    Stop { id: u64 },
+}
+
+/// Runs the writer thread until all senders are dropped.
+///
+/// Files are flushed after every message, so a capture is complete even if the process is killed.
+/// If creating or writing a file fails, its capture is stopped.
+fn write_captures(dir: &Path, rx: mpsc::Receiver<CaptureCommand>) {
+    let mut files = HashMap::<u64, BufWriter<File>>::new();
+    while let Ok(command) = rx.recv() {
+        match command {
+            CaptureCommand::Start { id, header } => {
+                let path = dir.join(format!("{}-{}.capture", unix_millis(), header.peer_id));
+                match create_capture_file(dir, &path, &header) {
+                    Ok(file) => {
+                        files.insert(id, file);
+                    }
+                    Err(err) => {
+                        warn!(target: "net::capture", %err, ?path, "Failed to create message capture")
+                    }
+                }
+            }
+            CaptureCommand::Record { id, message } => {
+                let Some(file) = files.get_mut(&id) else { continue };
+                let res = file.write_all(&alloy_rlp::encode(&message)).and_then(|_| file.flush());
+                if let Err(err) = res {
+                    warn!(target: "net::capture", %err, "Failed to write message capture, stopping capture");
+                    files.remove(&id);
+                }
+            }
+            CaptureCommand::Stop { id } => {
+                files.remove(&id);
+            }
+        }
+    }
+}
+
+/// Creates the capture file at the given path and writes the header.
+fn create_capture_file(
+    dir: &Path,
+    path: &Path,
+    header: &CaptureHeader,
+) -> io::Result<BufWriter<File>> {
+    std::fs::create_dir_all(dir)?;
+    let mut file = BufWriter::new(File::create(path)?);
+    file.write_all(&alloy_rlp::encode(header))?;
+    file.flush()?;
+    Ok(file)
+}
+
+/// The content of a capture file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureFile {
+    /// The header describing the session.
+    pub header: CaptureHeader,
+    /// The captured messages, in the order they were received or sent.
+    pub messages: Vec<CapturedMessage>,
+}
+
+impl CaptureFile {
+    /// Reads the capture file at the given path.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    /// Decodes the content of a capture file.
+    ///
+    /// A truncated last message, e.g. because the process was killed while writing it, is
+    /// ignored.
+    pub fn decode(mut buf: &[u8]) -> io::Result<Self> {
+        let header = CaptureHeader::decode(&mut buf)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if header.format_version != CAPTURE_FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported capture format version {}", header.format_version),
+            ))
+        }
+
+        let mut messages = Vec::new();
+        while !buf.is_empty() {
+            match CapturedMessage::decode(&mut buf) {
+                Ok(message) => messages.push(message),
+                Err(alloy_rlp::Error::InputTooShort) => break,
+                Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err)),
+            }
+        }
+
+        Ok(Self { header, messages })
+    }
+}
+
+/// Returns the current unix timestamp in milliseconds.
+fn unix_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_roundtrip() {
+        let header = CaptureHeader {
+            format_version: CAPTURE_FORMAT_VERSION,
+            peer_id: PeerId::random(),
+            client_version: "bsc/v1.4.11".to_string(),
+            capabilities: vec![
+                CapturedCapability { name: "bsc".to_string(), version: 1, offset: 0 },
+                CapturedCapability { name: "eth".to_string(), version: 68, offset: 2 },
+            ],
+        };
+        assert_eq!(header.find_capability(1).unwrap().name, "bsc");
+        assert_eq!(header.find_capability(2).unwrap().name, "eth");
+
+        let dir = std::env::temp_dir().join(format!("capture-{}", header.peer_id));
+        let (writer, handle) = CaptureWriter::spawn(dir.clone()).unwrap();
+        let capture = writer.start(header.clone());
+        capture.record(CaptureDirection::Inbound, &[0x02, 0xc0]);
+        capture.record(CaptureDirection::Outbound, &[0x03, 0xc1, 0x80]);
+        drop(capture);
+        drop(writer);
+        handle.join().unwrap();
+
+        let path = std::fs::read_dir(&dir).unwrap().next().unwrap().unwrap().path();
+        let file = CaptureFile::read(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(file.header, header);
+        assert_eq!(file.messages.len(), 2);
+        assert_eq!(file.messages[0].direction, CaptureDirection::Inbound);
+        assert_eq!(file.messages[0].message_id(), Some(0x02));
+        assert_eq!(file.messages[1].direction, CaptureDirection::Outbound);
+        assert_eq!(file.messages[1].message, Bytes::from_static(&[0x03, 0xc1, 0x80]));
+    }
+}
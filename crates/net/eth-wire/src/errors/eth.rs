@@ -35,6 +35,14 @@ pub enum EthStreamError {
         /// The number of transaction sizes.
         sizes_len: usize,
     },
+    #[error("BlockRangeUpdate invalid range: earliest {earliest}, latest {latest}")]
+    /// Received a `BlockRangeUpdate` message whose earliest block is after the latest block.
+    InvalidBlockRangeUpdate {
+        /// The announced earliest block.
+        earliest: u64,
+        /// The announced latest block.
+        latest: u64,
+    },
     /// Error when data is not received from peer for a prolonged period.
     #[error("never received data from remote peer")]
     StreamTimeout,
@@ -91,6 +99,14 @@ pub enum EthHandshakeError {
     #[error("mismatched chain in status message: {0}")]
     /// Mismatch in chain details in status messages.
     MismatchedChain(GotExpected<Chain>),
+    #[error("invalid block range in status message: earliest {earliest}, latest {latest}")]
+    /// The earliest block of the announced range is after the latest block.
+    InvalidBlockRange {
+        /// The announced earliest block.
+        earliest: u64,
+        /// The announced latest block.
+        latest: u64,
+    },
     #[error("total difficulty bitlen is too large: got {got}, maximum {maximum}")]
     /// Excessively large total difficulty bit lengths.
     TotalDifficultyBitLenTooLarge {
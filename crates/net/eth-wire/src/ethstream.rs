@@ -1,8 +1,10 @@
 use crate::{
+    bandwidth::BandwidthMeter,
     errors::{EthHandshakeError, EthStreamError},
-    message::{EthBroadcastMessage, ProtocolBroadcastMessage},
+    message::{EthBroadcastMessage, ProtocolBroadcastMessage, RequestPair},
     p2pstream::HANDSHAKE_TIMEOUT,
-    CanDisconnect, DisconnectReason, EthMessage, EthVersion, ProtocolMessage, Status,
+    BlockRangeUpdate, CanDisconnect, DisconnectReason, EthMessage, EthVersion, ProtocolMessage,
+    Status,
 };
 #[cfg(feature = "bsc")]
 use crate::{UpgradeStatus, UpgradeStatusExtension};
@@ -14,6 +16,7 @@ use reth_primitives::{
 };
 use std::{
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -32,12 +35,22 @@ pub const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
 pub struct UnauthedEthStream<S> {
     #[pin]
     inner: S,
+    /// The range of blocks announced in the `eth/69` status.
+    block_range: Option<BlockRangeUpdate>,
 }
 
 impl<S> UnauthedEthStream<S> {
     /// Create a new `UnauthedEthStream` from a type `S` which implements `Stream` and `Sink`.
     pub const fn new(inner: S) -> Self {
-        Self { inner }
+        Self { inner, block_range: None }
+    }
+
+    /// Sets the range of blocks to announce in the status, if `eth/69` is negotiated.
+    ///
+    /// If not set, only the head block of the status is announced.
+    pub const fn with_block_range(mut self, block_range: Option<BlockRangeUpdate>) -> Self {
+        self.block_range = block_range;
+        self
     }
 
     /// Consumes the type and returns the wrapped stream
@@ -54,6 +67,10 @@ where
     /// Consumes the [`UnauthedEthStream`] and returns an [`EthStream`] after the `Status`
     /// handshake is completed successfully. This also returns the `Status` message sent by the
     /// remote peer.
+    ///
+    /// If `eth/69` is negotiated, the [`StatusEth69`] is exchanged instead and the returned
+    /// `Status` has a total difficulty of zero. The range of blocks announced by the peer is
+    /// available with [`EthStream::peer_block_range`].
     pub async fn handshake(
         self,
         status: Status,
@@ -80,16 +97,31 @@ where
         status: Status,
         fork_filter: ForkFilter,
     ) -> Result<(EthStream<S>, Status), EthStreamError> {
-        trace!(
-            %status,
-            "sending eth status to peer"
-        );
+        let version = EthVersion::try_from(status.version)?;
+
+        let our_status = if version >= EthVersion::Eth69 {
+            let block_range = self.block_range.unwrap_or(BlockRangeUpdate {
+                earliest: 0,
+                latest: 0,
+                latest_hash: status.blockhash,
+            });
+            let status = status.into_eth69(block_range);
+            trace!(
+                %status,
+                "sending eth status to peer"
+            );
+            EthMessage::StatusEth69(status)
+        } else {
+            trace!(
+                %status,
+                "sending eth status to peer"
+            );
+            EthMessage::Status(status)
+        };
 
         // we need to encode and decode here on our own because we don't have an `EthStream` yet
         // The max length for a status with TTD is: <msg id = 1 byte> + <rlp(status) = 88 byte>
-        self.inner
-            .send(alloy_rlp::encode(ProtocolMessage::from(EthMessage::Status(status))).into())
-            .await?;
+        self.inner.send(alloy_rlp::encode(ProtocolMessage::from(our_status)).into()).await?;
 
         let their_msg_res = self.inner.next().await;
 
@@ -106,7 +138,6 @@ where
             return Err(EthStreamError::MessageTooBig(their_msg.len()));
         }
 
-        let version = EthVersion::try_from(status.version)?;
         let msg = match ProtocolMessage::decode_message(version, &mut their_msg.as_ref()) {
             Ok(m) => m,
             Err(err) => {
@@ -116,143 +147,150 @@ where
             }
         };
 
-        // The following checks should match the checks in go-ethereum:
-        // https://github.com/ethereum/go-ethereum/blob/9244d5cd61f3ea5a7645fdf2a1a96d53421e412f/eth/protocols/eth/handshake.go#L87-L89
-        match msg.message {
+        let (resp, peer_block_range) = match msg.message {
             EthMessage::Status(resp) => {
                 trace!(
                     status=%resp,
                     "validating incoming eth status from peer"
                 );
-                if status.genesis != resp.genesis {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedGenesis(
-                        GotExpected { expected: status.genesis, got: resp.genesis }.into(),
-                    )
-                    .into());
-                }
-
-                if status.version != resp.version {
+                (resp, None)
+            }
+            EthMessage::StatusEth69(resp) => {
+                trace!(
+                    status=%resp,
+                    "validating incoming eth status from peer"
+                );
+                let block_range = resp.block_range();
+                if !block_range.is_valid() {
                     self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
-                        got: resp.version,
-                        expected: status.version,
-                    })
+                    return Err(EthHandshakeError::InvalidBlockRange {
+                        earliest: block_range.earliest,
+                        latest: block_range.latest,
+                    }
                     .into());
                 }
+                (resp.into(), Some(block_range))
+            }
+            _ => {
+                self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+                return Err(EthStreamError::EthHandshakeError(
+                    EthHandshakeError::NonStatusMessageInHandshake,
+                ))
+            }
+        };
 
-                if status.chain != resp.chain {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::MismatchedChain(GotExpected {
-                        got: resp.chain,
-                        expected: status.chain,
-                    })
-                    .into());
-                }
+        // The following checks should match the checks in go-ethereum:
+        // https://github.com/ethereum/go-ethereum/blob/9244d5cd61f3ea5a7645fdf2a1a96d53421e412f/eth/protocols/eth/handshake.go#L87-L89
+        if status.genesis != resp.genesis {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedGenesis(
+                GotExpected { expected: status.genesis, got: resp.genesis }.into(),
+            )
+            .into());
+        }
 
-                // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million times
-                // larger, it will still fit within 100 bits
-                if status.total_difficulty.bit_len() > 100 {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
-                        got: status.total_difficulty.bit_len(),
-                        maximum: 100,
-                    }
-                    .into());
-                }
+        if status.version != resp.version {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedProtocolVersion(GotExpected {
+                got: resp.version,
+                expected: status.version,
+            })
+            .into());
+        }
 
-                if let Err(err) =
-                    fork_filter.validate(resp.forkid).map_err(EthHandshakeError::InvalidFork)
-                {
-                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                    return Err(err.into());
-                }
+        if status.chain != resp.chain {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::MismatchedChain(GotExpected {
+                got: resp.chain,
+                expected: status.chain,
+            })
+            .into());
+        }
 
-                // For BSC, UpgradeStatus message should be sent during handshake.
-                #[cfg(feature = "bsc")]
-                {
-                    if version > EthVersion::Eth66 {
-                        self.inner
-                            .send(
-                                alloy_rlp::encode(ProtocolMessage::from(
-                                    EthMessage::UpgradeStatus(UpgradeStatus {
-                                        extension: UpgradeStatusExtension {
-                                            disable_peer_tx_broadcast: false,
-                                        },
-                                    }),
-                                ))
-                                .into(),
-                            )
-                            .await?;
-                        let their_msg_res = self.inner.next().await;
-                        let their_msg = match their_msg_res {
-                            Some(msg) => msg,
-                            None => {
-                                self.inner
-                                    .disconnect(DisconnectReason::DisconnectRequested)
-                                    .await?;
-                                return Err(EthStreamError::EthHandshakeError(
-                                    EthHandshakeError::NoResponse,
-                                ));
-                            }
-                        }?;
-                        let msg =
-                            match ProtocolMessage::decode_message(version, &mut their_msg.as_ref())
-                            {
-                                Ok(m) => m,
-                                Err(err) => {
-                                    debug!("decode error in eth handshake: msg={their_msg:x}");
-                                    self.inner
-                                        .disconnect(DisconnectReason::DisconnectRequested)
-                                        .await?;
-                                    return Err(EthStreamError::InvalidMessage(err));
-                                }
-                            };
-                        match msg.message {
-                            EthMessage::UpgradeStatus(_) => {
-                                // TODO: support disable_peer_tx_broadcast flag
-                                let stream = EthStream::new(version, self.inner);
-                                Ok((stream, resp))
-                            }
-                            _ => {
-                                self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                                Err(EthStreamError::EthHandshakeError(
-                                    EthHandshakeError::NonStatusMessageInHandshake,
-                                ))
-                            }
-                        }
-                    } else {
-                        let stream = EthStream::new(version, self.inner);
-                        Ok((stream, resp))
-                    }
-                }
+        // TD at mainnet block #7753254 is 76 bits. If it becomes 100 million times
+        // larger, it will still fit within 100 bits
+        if version < EthVersion::Eth69 && status.total_difficulty.bit_len() > 100 {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(EthHandshakeError::TotalDifficultyBitLenTooLarge {
+                got: status.total_difficulty.bit_len(),
+                maximum: 100,
+            }
+            .into());
+        }
 
-                #[cfg(not(feature = "bsc"))]
-                {
-                    // now we can create the `EthStream` because the peer has successfully completed
-                    // the handshake
-                    let stream = EthStream::new(version, self.inner);
+        if let Err(err) = fork_filter.validate(resp.forkid).map_err(EthHandshakeError::InvalidFork)
+        {
+            self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+            return Err(err.into());
+        }
 
-                    Ok((stream, resp))
+        // For BSC, UpgradeStatus message should be sent during handshake.
+        #[cfg(feature = "bsc")]
+        {
+            if version > EthVersion::Eth66 {
+                self.inner
+                    .send(
+                        alloy_rlp::encode(ProtocolMessage::from(EthMessage::UpgradeStatus(
+                            UpgradeStatus {
+                                extension: UpgradeStatusExtension {
+                                    disable_peer_tx_broadcast: false,
+                                },
+                            },
+                        )))
+                        .into(),
+                    )
+                    .await?;
+                let their_msg_res = self.inner.next().await;
+                let their_msg = match their_msg_res {
+                    Some(msg) => msg,
+                    None => {
+                        self.inner.disconnect(DisconnectReason::DisconnectRequested).await?;
+                        return Err(EthStreamError::EthHandshakeError(
+                            EthHandshakeError::NoResponse,
+                        ));
+                    }
+                }?;
+                let msg = match ProtocolMessage::decode_message(version, &mut their_msg.as_ref()) {
+                    Ok(m) => m,
+                    Err(err) => {
+                        debug!("decode error in eth handshake: msg={their_msg:x}");
+                        self.inner.disconnect(DisconnectReason::DisconnectRequested).await?;
+                        return Err(EthStreamError::InvalidMessage(err));
+                    }
+                };
+                // TODO: support disable_peer_tx_broadcast flag
+                if !matches!(msg.message, EthMessage::UpgradeStatus(_)) {
+                    self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
+                    return Err(EthStreamError::EthHandshakeError(
+                        EthHandshakeError::NonStatusMessageInHandshake,
+                    ))
                 }
             }
-            _ => {
-                self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-                Err(EthStreamError::EthHandshakeError(
-                    EthHandshakeError::NonStatusMessageInHandshake,
-                ))
-            }
         }
+
+        // now we can create the `EthStream` because the peer has successfully completed
+        // the handshake
+        let mut stream = EthStream::new(version, self.inner);
+        stream.peer_block_range = peer_block_range;
+
+        Ok((stream, resp))
     }
 }
 
 /// An `EthStream` wraps over any `Stream` that yields bytes and makes it
 /// compatible with eth-networking protocol messages, which get RLP encoded/decoded.
+///
+/// For `eth/69`, receipts are sent and received without their bloom filter. This is transparent
+/// to the user of the stream, which always sends and receives [`EthMessage::Receipts`].
 #[pin_project]
 #[derive(Debug)]
 pub struct EthStream<S> {
     /// Negotiated eth version.
     version: EthVersion,
+    /// The range of blocks the peer announced in the `eth/69` status.
+    peer_block_range: Option<BlockRangeUpdate>,
+    /// Counts the bytes received and sent, if enabled for this stream.
+    bandwidth: Option<Arc<BandwidthMeter>>,
     #[pin]
     inner: S,
 }
@@ -262,7 +300,13 @@ impl<S> EthStream<S> {
     /// to manually handshake a peer.
     #[inline]
     pub const fn new(version: EthVersion, inner: S) -> Self {
-        Self { version, inner }
+        Self { version, peer_block_range: None, bandwidth: None, inner }
+    }
+
+    /// Records the size of all messages received and sent from now on with the given meter.
+    #[inline]
+    pub fn set_bandwidth_meter(&mut self, meter: Arc<BandwidthMeter>) {
+        self.bandwidth = Some(meter);
     }
 
     /// Returns the eth version.
@@ -271,6 +315,13 @@ impl<S> EthStream<S> {
         self.version
     }
 
+    /// Returns the range of blocks the peer announced during the handshake, if `eth/69` was
+    /// negotiated.
+    #[inline]
+    pub const fn peer_block_range(&self) -> Option<BlockRangeUpdate> {
+        self.peer_block_range
+    }
+
     /// Returns the underlying stream.
     #[inline]
     pub const fn inner(&self) -> &S {
@@ -300,9 +351,11 @@ where
         &mut self,
         item: EthBroadcastMessage,
     ) -> Result<(), EthStreamError> {
-        self.inner.start_send_unpin(Bytes::from(alloy_rlp::encode(
-            ProtocolBroadcastMessage::from(item),
-        )))?;
+        let bytes = Bytes::from(alloy_rlp::encode(ProtocolBroadcastMessage::from(item)));
+        if let Some(meter) = &self.bandwidth {
+            meter.record_egress(bytes[0], bytes.len());
+        }
+        self.inner.start_send_unpin(bytes)?;
 
         Ok(())
     }
@@ -328,6 +381,10 @@ where
             return Poll::Ready(Some(Err(EthStreamError::MessageTooBig(bytes.len()))));
         }
 
+        if let (Some(meter), Some(id)) = (this.bandwidth.as_ref(), bytes.first()) {
+            meter.record_ingress(*id, bytes.len());
+        }
+
         let msg = match ProtocolMessage::decode_message(*this.version, &mut bytes.as_ref()) {
             Ok(m) => m,
             Err(err) => {
@@ -345,13 +402,19 @@ where
             }
         };
 
-        if matches!(msg.message, EthMessage::Status(_)) {
-            return Poll::Ready(Some(Err(EthStreamError::EthHandshakeError(
-                EthHandshakeError::StatusNotInHandshake,
-            ))));
+        match msg.message {
+            EthMessage::Status(_) | EthMessage::StatusEth69(_) => Poll::Ready(Some(Err(
+                EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake),
+            ))),
+            EthMessage::Receipts69(resp) => {
+                // recompute the blooms of the receipts
+                Poll::Ready(Some(Ok(EthMessage::Receipts(RequestPair {
+                    request_id: resp.request_id,
+                    message: resp.message.into_receipts(),
+                }))))
+            }
+            message => Poll::Ready(Some(Ok(message))),
         }
-
-        Poll::Ready(Some(Ok(msg.message)))
     }
 }
 
@@ -367,7 +430,7 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: EthMessage) -> Result<(), Self::Error> {
-        if matches!(item, EthMessage::Status(_)) {
+        if matches!(item, EthMessage::Status(_) | EthMessage::StatusEth69(_)) {
             // TODO: to disconnect here we would need to do something similar to P2PStream's
             // start_disconnect, which would ideally be a part of the CanDisconnect trait, or at
             // least similar.
@@ -380,9 +443,23 @@ where
             return Err(EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake));
         }
 
-        self.project()
-            .inner
-            .start_send(Bytes::from(alloy_rlp::encode(ProtocolMessage::from(item))))?;
+        // receipts are sent without their bloom since eth/69
+        let item = match item {
+            EthMessage::Receipts(resp) if self.version >= EthVersion::Eth69 => {
+                EthMessage::Receipts69(RequestPair {
+                    request_id: resp.request_id,
+                    message: resp.message.into(),
+                })
+            }
+            item => item,
+        };
+
+        let bytes = Bytes::from(alloy_rlp::encode(ProtocolMessage::from(item)));
+        let this = self.project();
+        if let Some(meter) = this.bandwidth.as_ref() {
+            meter.record_egress(bytes[0], bytes.len());
+        }
+        this.inner.start_send(bytes)?;
 
         Ok(())
     }
@@ -414,7 +491,8 @@ mod tests {
         errors::{EthHandshakeError, EthStreamError},
         hello::DEFAULT_TCP_PORT,
         p2pstream::{ProtocolVersion, UnauthedP2PStream},
-        EthMessage, EthStream, EthVersion, HelloMessageWithProtocols, PassthroughCodec, Status,
+        BlockRangeUpdate, EthMessage, EthStream, EthVersion, HelloMessageWithProtocols,
+        PassthroughCodec, Status,
     };
     use futures::{SinkExt, StreamExt};
     use reth_chainspec::NamedChain;
@@ -473,6 +551,59 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn can_handshake_eth69() {
+        let genesis = B256::random();
+        let fork_filter = ForkFilter::new(Head::default(), genesis, 0, Vec::new());
+
+        let status = Status {
+            version: EthVersion::Eth69 as u8,
+            chain: NamedChain::Mainnet.into(),
+            total_difficulty: U256::from(100),
+            blockhash: B256::random(),
+            genesis,
+            // Pass the current fork id.
+            forkid: fork_filter.current(),
+        };
+        let block_range =
+            BlockRangeUpdate { earliest: 10, latest: 100, latest_hash: status.blockhash };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let status_clone = status;
+        let fork_filter_clone = fork_filter.clone();
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let (stream, their_status) = UnauthedEthStream::new(stream)
+                .with_block_range(Some(block_range))
+                .handshake(status_clone, fork_filter_clone)
+                .await
+                .unwrap();
+
+            // the total difficulty is not exchanged
+            assert_eq!(their_status, Status { total_difficulty: U256::ZERO, ..status_clone });
+            assert_eq!(stream.peer_block_range(), Some(block_range));
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+
+        let (stream, their_status) = UnauthedEthStream::new(sink)
+            .with_block_range(Some(block_range))
+            .handshake(status, fork_filter)
+            .await
+            .unwrap();
+
+        assert_eq!(their_status, Status { total_difficulty: U256::ZERO, ..status });
+        assert_eq!(stream.version(), EthVersion::Eth69);
+        assert_eq!(stream.peer_block_range(), Some(block_range));
+
+        // wait for it to finish
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn pass_handshake_on_low_td_bitlen() {
         let genesis = B256::random();
@@ -13,7 +13,9 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod bandwidth;
 pub mod capability;
+pub mod capture;
 mod disconnect;
 pub mod errors;
 mod ethstream;
@@ -1,5 +1,6 @@
 use crate::{
     capability::SharedCapabilities,
+    capture::{CaptureDirection, MessageCapture},
     disconnect::CanDisconnect,
     errors::{P2PHandshakeError, P2PStreamError},
     pinger::{Pinger, PingerEvent},
@@ -250,6 +251,9 @@ pub struct P2PStream<S> {
     /// Whether this stream is currently in the process of disconnecting by sending a disconnect
     /// message.
     disconnecting: bool,
+
+    /// Records the subprotocol messages, if capturing is enabled for this stream.
+    capture: Option<MessageCapture>,
 }
 
 impl<S> P2PStream<S> {
@@ -266,6 +270,7 @@ impl<S> P2PStream<S> {
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
             disconnecting: false,
+            capture: None,
         }
     }
 
@@ -283,6 +288,11 @@ impl<S> P2PStream<S> {
         self.outgoing_message_buffer_capacity = capacity;
     }
 
+    /// Records all subprotocol messages sent and received from now on with the given capture.
+    pub fn set_capture(&mut self, capture: MessageCapture) {
+        self.capture = Some(capture);
+    }
+
     /// Returns the shared capabilities for this stream.
     ///
     /// This includes all the shared capabilities that were negotiated during the handshake and
@@ -515,6 +525,10 @@ where
                     //
                     decompress_buf[0] = bytes[0] - MAX_RESERVED_MESSAGE_ID - 1;
 
+                    if let Some(capture) = this.capture.as_ref() {
+                        capture.record(CaptureDirection::Inbound, &decompress_buf);
+                    }
+
                     return Poll::Ready(Some(Ok(decompress_buf)))
                 }
             }
@@ -587,6 +601,10 @@ where
 
         let this = self.project();
 
+        if let Some(capture) = this.capture.as_ref() {
+            capture.record(CaptureDirection::Outbound, &item);
+        }
+
         let mut compressed = BytesMut::zeroed(1 + snap::raw::max_compress_len(item.len() - 1));
         let compressed_size =
             this.encoder.compress(&item[1..], &mut compressed[1..]).map_err(|err| {
@@ -15,7 +15,8 @@
 
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 pub use error::NetworkError;
-pub use reputation::{Reputation, ReputationChangeKind};
+pub use reputation::{PeerRequestStats, Reputation, ReputationChangeKind};
+pub use reth_eth_wire::bandwidth::MessageBandwidth;
 use reth_eth_wire::{capability::Capabilities, DisconnectReason, EthVersion, Status};
 use reth_network_peers::NodeRecord;
 use std::{future::Future, net::SocketAddr, sync::Arc, time::Instant};
@@ -212,6 +213,12 @@ pub struct PeerInfo {
     pub session_established: Instant,
     /// The peer's connection kind
     pub kind: PeerKind,
+    /// The reputation of the peer
+    pub reputation: Reputation,
+    /// Statistics about the requests sent to the peer
+    pub request_stats: PeerRequestStats,
+    /// The bytes exchanged with the peer in this session, per message type
+    pub bandwidth: Vec<MessageBandwidth>,
 }
 
 /// The direction of the connection.
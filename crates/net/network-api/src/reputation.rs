@@ -50,3 +50,60 @@ impl ReputationChangeKind {
         matches!(self, Self::Dropped)
     }
 }
+
+/// Statistics about the requests sent to a peer.
+///
+/// Used to prefer peers that answer fast and reliably.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
+pub struct PeerRequestStats {
+    /// Number of requests answered with a valid response.
+    pub successful_requests: u64,
+    /// Number of requests that failed, timed out or were answered with a bad response.
+    pub failed_requests: u64,
+    /// Moving average of the response latency of successful requests, in milliseconds.
+    pub avg_latency_ms: u64,
+}
+
+impl PeerRequestStats {
+    /// The weight of a new latency sample in the moving average, in percent.
+    const LATENCY_SAMPLE_WEIGHT: u64 = 20;
+
+    /// Records a successful request that took the given time.
+    pub fn on_success(&mut self, latency: std::time::Duration) {
+        let latency = latency.as_millis().min(u64::MAX as u128) as u64;
+        self.avg_latency_ms = if self.successful_requests == 0 {
+            latency
+        } else {
+            (self.avg_latency_ms * (100 - Self::LATENCY_SAMPLE_WEIGHT) +
+                latency * Self::LATENCY_SAMPLE_WEIGHT) /
+                100
+        };
+        self.successful_requests += 1;
+    }
+
+    /// Records a failed request.
+    pub fn on_failure(&mut self) {
+        self.failed_requests += 1;
+    }
+
+    /// Returns the total number of recorded requests.
+    pub const fn total_requests(&self) -> u64 {
+        self.successful_requests + self.failed_requests
+    }
+
+    /// Returns the share of successful requests in percent, or `None` if there are no recorded
+    /// requests.
+    pub const fn success_rate(&self) -> Option<u64> {
+        match self.total_requests() {
+            0 => None,
+            total => Some(self.successful_requests * 100 / total),
+        }
+    }
+
+    /// Returns `true` if no request has been recorded.
+    pub const fn is_empty(&self) -> bool {
+        self.total_requests() == 0
+    }
+}
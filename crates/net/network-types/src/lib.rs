@@ -14,7 +14,9 @@
 
 /// Types related to peering.
 pub mod peers;
-pub use peers::{ConnectionsConfig, PeersConfig, ReputationChangeWeights};
+pub use peers::{
+    ConnectionsConfig, PeeringMode, PeersConfig, PersistedPeerState, ReputationChangeWeights,
+};
 
 pub mod session;
 pub use session::{SessionLimits, SessionsConfig};
@@ -1,12 +1,14 @@
 //! Configuration for peering.
 
-use crate::{BackoffKind, ReputationChangeWeights};
+use crate::{BackoffKind, PersistedPeerState, ReputationChangeWeights};
 use reth_net_banlist::BanList;
 use reth_network_peers::NodeRecord;
 use std::{
     collections::HashSet,
+    fmt,
     io::{self, ErrorKind},
     path::Path,
+    str::FromStr,
     time::Duration,
 };
 use tracing::info;
@@ -90,6 +92,60 @@ impl Default for PeerBackoffDurations {
     }
 }
 
+/// The role of the node in a validator/sentry deployment.
+///
+/// Validators usually don't expose themselves to the network and only talk to a few sentry nodes
+/// they control, which in turn peer with the rest of the network.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum PeeringMode {
+    /// Regular peering with any discovered peer.
+    #[default]
+    Default,
+    /// The node only connects to and accepts from the peers of the allow-list, doesn't run
+    /// discovery and ignores inbound transaction gossip.
+    Validator,
+    /// The node peers with the network and shields the validators of the allow-list: they are
+    /// always connected and receive new blocks and votes first.
+    Sentry,
+}
+
+impl PeeringMode {
+    /// Returns `true` if the node runs as a validator behind sentries.
+    pub const fn is_validator(&self) -> bool {
+        matches!(self, Self::Validator)
+    }
+
+    /// Returns `true` if the node runs as a sentry for validators.
+    pub const fn is_sentry(&self) -> bool {
+        matches!(self, Self::Sentry)
+    }
+}
+
+impl fmt::Display for PeeringMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => f.write_str("default"),
+            Self::Validator => f.write_str("validator"),
+            Self::Sentry => f.write_str("sentry"),
+        }
+    }
+}
+
+impl FromStr for PeeringMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(Self::Default),
+            "validator" => Ok(Self::Validator),
+            "sentry" => Ok(Self::Sentry),
+            _ => Err(format!("unknown peering mode: {s}, expected default, validator or sentry")),
+        }
+    }
+}
+
 /// Tracks stats about connected nodes
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
@@ -153,6 +209,17 @@ pub struct PeersConfig {
     ///
     /// The backoff duration increases with number of backoff attempts.
     pub backoff_durations: PeerBackoffDurations,
+    /// The role of the node in a validator/sentry deployment.
+    pub peering_mode: PeeringMode,
+    /// The peers of a validator/sentry deployment.
+    ///
+    /// In [`PeeringMode::Validator`] these are the only peers to connect to or accept from,
+    /// usually the sentries. In [`PeeringMode::Sentry`] these are the protected validators.
+    /// Ignored in [`PeeringMode::Default`].
+    pub allow_list: HashSet<NodeRecord>,
+    /// Reputation, bans and request statistics of peers persisted by a previous run.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub peer_states: Vec<PersistedPeerState>,
 }
 
 impl Default for PeersConfig {
@@ -169,6 +236,9 @@ impl Default for PeersConfig {
             trusted_nodes_only: false,
             basic_nodes: Default::default(),
             max_backoff_count: 5,
+            peering_mode: Default::default(),
+            allow_list: Default::default(),
+            peer_states: Default::default(),
         }
     }
 }
@@ -259,6 +329,24 @@ impl PeersConfig {
         self
     }
 
+    /// Configures the role of the node in a validator/sentry deployment.
+    pub const fn with_peering_mode(mut self, peering_mode: PeeringMode) -> Self {
+        self.peering_mode = peering_mode;
+        self
+    }
+
+    /// The peers of a validator/sentry deployment, see [`PeersConfig::allow_list`].
+    pub fn with_allow_list(mut self, allow_list: HashSet<NodeRecord>) -> Self {
+        self.allow_list = allow_list;
+        self
+    }
+
+    /// Peer states to restore at launch.
+    pub fn with_peer_states(mut self, peer_states: Vec<PersistedPeerState>) -> Self {
+        self.peer_states = peer_states;
+        self
+    }
+
     /// Returns the maximum number of peers, inbound and outbound.
     pub const fn max_peers(&self) -> usize {
         self.connection_info.max_outbound + self.connection_info.max_inbound
@@ -280,6 +368,23 @@ impl PeersConfig {
         Ok(self.with_basic_nodes(nodes))
     }
 
+    /// Read from file the peer states to restore at launch. Ignored if None.
+    #[cfg(feature = "serde")]
+    pub fn with_peer_states_from_file(
+        self,
+        optional_file: Option<impl AsRef<Path>>,
+    ) -> Result<Self, io::Error> {
+        let Some(file_path) = optional_file else { return Ok(self) };
+        let reader = match std::fs::File::open(file_path.as_ref()) {
+            Ok(file) => io::BufReader::new(file),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(self),
+            Err(e) => Err(e)?,
+        };
+        info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peer reputations");
+        let peer_states: Vec<PersistedPeerState> = serde_json::from_reader(reader)?;
+        Ok(self.with_peer_states(peer_states))
+    }
+
     /// Returns settings for testing
     #[cfg(any(test, feature = "test-utils"))]
     pub fn test() -> Self {
@@ -2,4 +2,7 @@ pub mod reputation;
 pub use reputation::ReputationChangeWeights;
 
 pub mod config;
-pub use config::{ConnectionsConfig, PeersConfig};
+pub use config::{ConnectionsConfig, PeeringMode, PeersConfig};
+
+pub mod state;
+pub use state::PersistedPeerState;
@@ -0,0 +1,87 @@
+//! Peer state that is persisted across restarts.
+
+use reth_network_api::{PeerRequestStats, Reputation};
+use reth_network_peers::PeerId;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::reputation::DEFAULT_REPUTATION;
+
+/// The reputation, ban and request statistics of a peer, as persisted in the datadir.
+///
+/// Restored by the `PeersManager` on startup, so that misbehaving peers are not dialed again
+/// after a restart and fast peers are preferred right away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PersistedPeerState {
+    /// The identifier of the peer.
+    pub peer_id: PeerId,
+    /// The reputation of the peer.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reputation: Reputation,
+    /// Unix timestamp in seconds until which the peer is banned, if any.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub banned_until: Option<u64>,
+    /// Statistics about the requests sent to the peer.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub request_stats: PeerRequestStats,
+}
+
+impl PersistedPeerState {
+    /// Creates a new state with the default reputation, no ban and no request statistics.
+    pub fn new(peer_id: PeerId) -> Self {
+        Self {
+            peer_id,
+            reputation: DEFAULT_REPUTATION,
+            banned_until: None,
+            request_stats: PeerRequestStats::default(),
+        }
+    }
+
+    /// Sets the ban to expire after the given duration from now.
+    pub fn with_ban_remaining(mut self, remaining: Duration) -> Self {
+        self.banned_until = Some(unix_now().saturating_add(remaining.as_secs()));
+        self
+    }
+
+    /// Returns how long the ban is still in effect, or `None` if the peer is not banned or the
+    /// ban already expired.
+    pub fn ban_remaining(&self) -> Option<Duration> {
+        let remaining = self.banned_until?.checked_sub(unix_now())?;
+        (remaining > 0).then(|| Duration::from_secs(remaining))
+    }
+
+    /// Returns `true` if the state doesn't differ from the one of a newly discovered peer and
+    /// doesn't need to be persisted.
+    pub fn is_default(&self) -> bool {
+        self.reputation == DEFAULT_REPUTATION &&
+            self.ban_remaining().is_none() &&
+            self.request_stats.is_empty()
+    }
+}
+
+/// Returns the current unix timestamp in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ban_remaining() {
+        let state = PersistedPeerState::new(PeerId::with_last_byte(1));
+        assert!(state.is_default());
+        assert_eq!(state.ban_remaining(), None);
+
+        let state = state.with_ban_remaining(Duration::from_secs(60 * 60));
+        assert!(!state.is_default());
+        let remaining = state.ban_remaining().unwrap();
+        assert!(remaining <= Duration::from_secs(60 * 60));
+        assert!(remaining > Duration::from_secs(60 * 59));
+
+        let expired = PersistedPeerState { banned_until: Some(1), ..state };
+        assert_eq!(expired.ban_remaining(), None);
+    }
+}
@@ -1,7 +1,7 @@
 //! Configuration types for peer sessions manager.
 
 use crate::peers::config::{DEFAULT_MAX_COUNT_PEERS_INBOUND, DEFAULT_MAX_COUNT_PEERS_OUTBOUND};
-use std::time::Duration;
+use std::{path::PathBuf, time::Duration};
 
 /// Default request timeout for a single request.
 ///
@@ -50,6 +50,10 @@ pub struct SessionsConfig {
     pub protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pub pending_session_timeout: Duration,
+    /// The directory to write a capture of the messages of every session to, for debugging.
+    ///
+    /// By default, no messages are captured.
+    pub capture_dir: Option<PathBuf>,
 }
 
 impl Default for SessionsConfig {
@@ -69,6 +73,7 @@ impl Default for SessionsConfig {
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
             pending_session_timeout: PENDING_SESSION_TIMEOUT,
+            capture_dir: None,
         }
     }
 }
@@ -84,6 +89,12 @@ impl SessionsConfig {
         self
     }
 
+    /// Sets the directory to write a capture of the messages of every session to.
+    pub fn with_capture_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.capture_dir = Some(dir.into());
+        self
+    }
+
     /// Helper function to set the buffer size for the bounded communication channel between the
     /// manager and its sessions for events emitted by the sessions.
     ///
@@ -0,0 +1,399 @@
+//! Support for the `bsc` protocol: <https://github.com/bnb-chain/bsc/tree/master/eth/protocols/bsc>
+//!
+//! The protocol runs as an RLPx subprotocol next to `eth` and gossips the fast finality votes of
+//! the validators. It is installed with
+//! [`NetworkConfigBuilder::bsc`](crate::NetworkConfigBuilder::bsc). Votes received from a peer
+//! are relayed to all other peers and published through the [`BscHandle`], which also broadcasts
+//! the votes of the local validator.
+//!
+//! A sentry node relays the votes to the validators it protects, see
+//! [`PeeringMode::Sentry`](crate::PeeringMode::Sentry), before the other peers.
+
+use crate::{
+    cache::LruCache,
+    protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler},
+};
+use futures::{Stream, StreamExt};
+use parking_lot::Mutex;
+use reth_eth_wire::{
+    capability::{Capability, SharedCapabilities},
+    multiplex::ProtocolConnection,
+    protocol::Protocol,
+    BscCapPacket, BscMessage, BscMessageId, VotesPacket, BSC_PROTOCOL_VERSION,
+};
+use reth_network_api::Direction;
+use reth_network_peers::PeerId;
+use reth_primitives::{parlia::VoteEnvelope, BytesMut, B256};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{debug, trace};
+
+/// The number of recently seen votes that are not relayed again.
+const MAX_KNOWN_VOTES: u32 = 4096;
+
+/// The capacity of the channel publishing the votes received from the peers.
+const VOTES_CHANNEL_SIZE: usize = 1024;
+
+/// Returns the `bsc/1` protocol.
+pub const fn bsc_protocol() -> Protocol {
+    Protocol::new(
+        Capability::new_static("bsc", BSC_PROTOCOL_VERSION as usize),
+        BscMessageId::max() + 1,
+    )
+}
+
+/// The state shared by the connections of the `bsc` protocol.
+struct BscState {
+    /// The senders of the votes to the connected peers, and the recently seen votes
+    peers: Mutex<BscPeers>,
+    /// The validators protected by this sentry node, which receive the votes first
+    protected_peers: HashSet<PeerId>,
+    /// Publishes the votes received from the peers
+    votes: broadcast::Sender<VoteEnvelope>,
+}
+
+/// The connected peers of the `bsc` protocol.
+struct BscPeers {
+    /// The senders of the votes to the connected peers
+    connections: HashMap<PeerId, mpsc::UnboundedSender<Vec<VoteEnvelope>>>,
+    /// The hashes of the recently seen votes
+    known_votes: LruCache<B256>,
+}
+
+impl BscState {
+    /// Relays the votes that were not seen before to all connected peers but the sender, the
+    /// protected validators first.
+    ///
+    /// The votes received from a peer are also published through the [`BscHandle`].
+    fn relay(&self, from: Option<PeerId>, votes: Vec<VoteEnvelope>) {
+        let mut peers = self.peers.lock();
+        let votes = votes
+            .into_iter()
+            .filter(|vote| peers.known_votes.insert(vote.hash()))
+            .collect::<Vec<_>>();
+        if votes.is_empty() {
+            return
+        }
+
+        if from.is_some() {
+            for vote in &votes {
+                let _ = self.votes.send(vote.clone());
+            }
+        }
+
+        let mut recipients = peers
+            .connections
+            .iter()
+            .filter(|(peer_id, _)| Some(**peer_id) != from)
+            .map(|(peer_id, tx)| (self.protected_peers.contains(peer_id), *peer_id, tx))
+            .collect::<Vec<_>>();
+        recipients.sort_by_key(|(is_protected, _, _)| !is_protected);
+
+        for (_, peer_id, tx) in recipients {
+            trace!(target: "net::bsc", ?peer_id, votes = votes.len(), "Relaying votes");
+            let _ = tx.send(votes.clone());
+        }
+    }
+}
+
+impl fmt::Debug for BscState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BscState")
+            .field("peers", &self.peers.lock().connections.len())
+            .field("protected_peers", &self.protected_peers)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The [`ProtocolHandler`] of the `bsc` protocol.
+#[derive(Debug)]
+pub struct BscProtocolHandler {
+    state: Arc<BscState>,
+}
+
+impl BscProtocolHandler {
+    /// Creates a new handler, relaying the votes to the given protected validators first.
+    pub fn new(protected_peers: HashSet<PeerId>) -> Self {
+        let (votes, _) = broadcast::channel(VOTES_CHANNEL_SIZE);
+        let peers = BscPeers {
+            connections: Default::default(),
+            known_votes: LruCache::new(MAX_KNOWN_VOTES),
+        };
+        Self { state: Arc::new(BscState { peers: Mutex::new(peers), protected_peers, votes }) }
+    }
+
+    /// Returns a handle to broadcast votes and to receive the votes of the peers.
+    pub fn handle(&self) -> BscHandle {
+        BscHandle { state: self.state.clone() }
+    }
+
+    fn connection_handler(&self) -> BscConnectionHandler {
+        BscConnectionHandler { state: self.state.clone() }
+    }
+}
+
+impl ProtocolHandler for BscProtocolHandler {
+    type ConnectionHandler = BscConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+}
+
+/// The [`ConnectionHandler`] of the `bsc` protocol.
+#[derive(Debug)]
+pub struct BscConnectionHandler {
+    state: Arc<BscState>,
+}
+
+impl ConnectionHandler for BscConnectionHandler {
+    type Connection = BscConnection;
+
+    fn protocol(&self) -> Protocol {
+        bsc_protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        // bsc is only a satellite protocol of eth
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        BscConnection {
+            peer_id,
+            conn,
+            state: self.state,
+            to_connection: tx,
+            votes: UnboundedReceiverStream::new(rx),
+            sent_handshake: false,
+            received_handshake: false,
+        }
+    }
+}
+
+/// The `bsc` connection to a peer.
+///
+/// The peer only receives votes once it completed the handshake.
+pub struct BscConnection {
+    /// The connected peer
+    peer_id: PeerId,
+    /// The messages of the peer
+    conn: ProtocolConnection,
+    /// The shared state, this connection is removed from on drop
+    state: Arc<BscState>,
+    /// The sender of the votes to this connection
+    to_connection: mpsc::UnboundedSender<Vec<VoteEnvelope>>,
+    /// The votes to send to the peer
+    votes: UnboundedReceiverStream<Vec<VoteEnvelope>>,
+    /// Whether the handshake was sent to the peer
+    sent_handshake: bool,
+    /// Whether the handshake of the peer was received
+    received_handshake: bool,
+}
+
+impl BscConnection {
+    /// Handles a message received from the peer.
+    ///
+    /// Returns `false` if the connection should be closed.
+    fn on_message(&mut self, message: BscMessage) -> bool {
+        match message {
+            BscMessage::Capability(BscCapPacket { protocol_version, .. })
+                if !self.received_handshake =>
+            {
+                if protocol_version != BSC_PROTOCOL_VERSION {
+                    debug!(
+                        target: "net::bsc",
+                        peer_id = ?self.peer_id,
+                        protocol_version,
+                        "Unsupported bsc protocol version"
+                    );
+                    return false
+                }
+                self.received_handshake = true;
+                let mut peers = self.state.peers.lock();
+                peers.connections.insert(self.peer_id, self.to_connection.clone());
+                true
+            }
+            BscMessage::Votes(VotesPacket { votes }) if self.received_handshake => {
+                self.state.relay(Some(self.peer_id), votes);
+                true
+            }
+            message => {
+                debug!(
+                    target: "net::bsc",
+                    peer_id = ?self.peer_id,
+                    id = ?message.message_id(),
+                    "Received unexpected bsc message"
+                );
+                false
+            }
+        }
+    }
+}
+
+impl Stream for BscConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        if !this.sent_handshake {
+            this.sent_handshake = true;
+            return Poll::Ready(Some(BscMessage::Capability(BscCapPacket::default()).encoded()))
+        }
+
+        loop {
+            // send the relayed votes to the peer
+            if let Poll::Ready(Some(votes)) = this.votes.poll_next_unpin(cx) {
+                return Poll::Ready(Some(BscMessage::Votes(VotesPacket { votes }).encoded()))
+            }
+
+            let Poll::Ready(msg) = this.conn.poll_next_unpin(cx) else { return Poll::Pending };
+            let Some(msg) = msg else { return Poll::Ready(None) };
+
+            match BscMessage::decode_message(&mut &msg[..]) {
+                Ok(message) => {
+                    if !this.on_message(message) {
+                        return Poll::Ready(None)
+                    }
+                }
+                Err(err) => {
+                    debug!(
+                        target: "net::bsc",
+                        %err,
+                        peer_id = ?this.peer_id,
+                        "Failed to decode bsc message"
+                    );
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl Drop for BscConnection {
+    fn drop(&mut self) {
+        let mut peers = self.state.peers.lock();
+        if peers
+            .connections
+            .get(&self.peer_id)
+            .is_some_and(|tx| tx.same_channel(&self.to_connection))
+        {
+            peers.connections.remove(&self.peer_id);
+        }
+    }
+}
+
+impl fmt::Debug for BscConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BscConnection")
+            .field("peer_id", &self.peer_id)
+            .field("received_handshake", &self.received_handshake)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A handle to broadcast votes to the connected peers and to receive the votes of the peers.
+#[derive(Debug, Clone)]
+pub struct BscHandle {
+    state: Arc<BscState>,
+}
+
+impl BscHandle {
+    /// Returns the peers that are connected over `bsc`.
+    pub fn peers(&self) -> Vec<PeerId> {
+        self.state.peers.lock().connections.keys().copied().collect()
+    }
+
+    /// Broadcasts the votes to all connected peers, the protected validators first.
+    ///
+    /// Votes that were already seen are not sent again.
+    pub fn broadcast_votes(&self, votes: Vec<VoteEnvelope>) {
+        self.state.relay(None, votes)
+    }
+
+    /// Returns a receiver of the votes received from the peers.
+    ///
+    /// Every vote is only published once, no matter how many peers sent it.
+    pub fn subscribe_votes(&self) -> broadcast::Receiver<VoteEnvelope> {
+        self.state.votes.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::parlia::VoteData;
+
+    fn vote(target_number: u64) -> VoteEnvelope {
+        VoteEnvelope {
+            vote_address: Default::default(),
+            signature: Default::default(),
+            data: VoteData { target_number, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn relays_votes_to_protected_peers_first() {
+        let validator = PeerId::random();
+        let handler = BscProtocolHandler::new(HashSet::from([validator]));
+        let handle = handler.handle();
+        let mut votes = handle.subscribe_votes();
+
+        let sender = PeerId::random();
+        let mut receivers = HashMap::new();
+        for peer_id in (0..10).map(|_| PeerId::random()).chain([validator, sender]) {
+            let (tx, rx) = mpsc::unbounded_channel();
+            handler.state.peers.lock().connections.insert(peer_id, tx);
+            receivers.insert(peer_id, rx);
+        }
+
+        handler.state.relay(Some(sender), vec![vote(1), vote(2)]);
+
+        // the sender doesn't get its votes back
+        let validator_rx = receivers.get_mut(&validator).unwrap();
+        assert_eq!(validator_rx.try_recv().unwrap(), vec![vote(1), vote(2)]);
+        assert!(receivers.get_mut(&sender).unwrap().try_recv().is_err());
+        for rx in receivers.values_mut() {
+            let _ = rx.try_recv();
+        }
+        assert_eq!(votes.try_recv().unwrap(), vote(1));
+        assert_eq!(votes.try_recv().unwrap(), vote(2));
+
+        // known votes are not relayed again, local votes are not published
+        handler.state.relay(Some(sender), vec![vote(1)]);
+        handle.broadcast_votes(vec![vote(2), vote(3)]);
+        for (peer_id, rx) in &mut receivers {
+            assert_eq!(rx.try_recv().unwrap(), vec![vote(3)], "{peer_id}");
+            assert!(rx.try_recv().is_err());
+        }
+        assert!(votes.try_recv().is_err());
+    }
+}
@@ -74,7 +74,8 @@ impl<Tx, Eth> NetworkBuilder<Tx, Eth> {
         let (tx, rx) = mpsc::channel(ETH_REQUEST_CHANNEL_CAPACITY);
         network.set_eth_request_handler(tx);
         let peers = network.handle().peers_handle().clone();
-        let request_handler = EthRequestHandler::new(client, peers, rx);
+        let request_handler =
+            EthRequestHandler::new(client, peers, rx).with_limits(network.eth_request_limits());
         NetworkBuilder { network, request_handler, transactions }
     }
 }
@@ -1,8 +1,11 @@
 //! Network config support
 
 use crate::{
+    bsc::{BscHandle, BscProtocolHandler},
     error::NetworkError,
+    eth_requests::EthRequestLimits,
     import::{BlockImport, ProofOfStakeBlockImport},
+    snap::{SnapHandle, SnapProtocolHandler},
     transactions::TransactionsManagerConfig,
     NetworkHandle, NetworkManager,
 };
@@ -14,7 +17,7 @@ use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
 use reth_network_peers::{mainnet_nodes, pk2id, sepolia_nodes, PeerId, TrustedPeer};
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_primitives::{ForkFilter, Head};
-use reth_storage_api::{BlockNumReader, BlockReader, HeaderProvider};
+use reth_storage_api::{BlockNumReader, BlockReader, HeaderProvider, SnapStateReader};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use secp256k1::SECP256K1;
 use std::{collections::HashSet, net::SocketAddr, sync::Arc};
@@ -77,8 +80,18 @@ pub struct NetworkConfig<C> {
     pub extra_protocols: RlpxSubProtocols,
     /// Whether to disable transaction gossip
     pub tx_gossip_disabled: bool,
+    /// Whether to ignore transactions gossiped by peers, while still propagating local
+    /// transactions.
+    pub inbound_tx_gossip_disabled: bool,
     /// How to instantiate transactions manager.
     pub transactions_manager_config: TransactionsManagerConfig,
+    /// Limits on the bytes served by the
+    /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler).
+    pub eth_request_limits: EthRequestLimits,
+    /// The handle to send requests over the `snap` protocol, if it is installed.
+    pub snap: Option<SnapHandle>,
+    /// The handle to gossip votes over the `bsc` protocol, if it is installed.
+    pub bsc: Option<BscHandle>,
 }
 
 // === impl NetworkConfig ===
@@ -183,10 +196,18 @@ pub struct NetworkConfigBuilder {
     head: Option<Head>,
     /// Whether tx gossip is disabled
     tx_gossip_disabled: bool,
+    /// Whether inbound tx gossip is disabled
+    inbound_tx_gossip_disabled: bool,
     /// The block importer type
     block_import: Option<Box<dyn BlockImport>>,
     /// How to instantiate transactions manager.
     transactions_manager_config: TransactionsManagerConfig,
+    /// Limits on the bytes served by the request handler.
+    eth_request_limits: EthRequestLimits,
+    /// The handle of the installed `snap` protocol.
+    snap: Option<SnapHandle>,
+    /// Whether to install the `bsc` protocol.
+    bsc: bool,
 }
 
 // === impl NetworkConfigBuilder ===
@@ -217,8 +238,12 @@ impl NetworkConfigBuilder {
             extra_protocols: Default::default(),
             head: None,
             tx_gossip_disabled: false,
+            inbound_tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
+            eth_request_limits: Default::default(),
+            snap: None,
+            bsc: false,
         }
     }
 
@@ -298,11 +323,17 @@ impl NetworkConfigBuilder {
     }
 
     /// Configures the transactions manager with the given config.
-    pub const fn transactions_manager_config(mut self, config: TransactionsManagerConfig) -> Self {
+    pub fn transactions_manager_config(mut self, config: TransactionsManagerConfig) -> Self {
         self.transactions_manager_config = config;
         self
     }
 
+    /// Sets the limits on the bytes served in response to `eth` requests.
+    pub const fn eth_request_limits(mut self, limits: EthRequestLimits) -> Self {
+        self.eth_request_limits = limits;
+        self
+    }
+
     /// Sets the discovery and listener address
     ///
     /// This is a convenience function for both [`NetworkConfigBuilder::listener_addr`] and
@@ -441,12 +472,41 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Installs the `snap` protocol, serving the state of the given client.
+    ///
+    /// The handle to send `snap` requests to the connected peers is available with
+    /// [`NetworkHandle::snap`].
+    pub fn snap<C>(mut self, client: C) -> Self
+    where
+        C: SnapStateReader + 'static,
+    {
+        let handler = SnapProtocolHandler::new(client);
+        self.snap = Some(handler.handle());
+        self.add_rlpx_sub_protocol(handler)
+    }
+
+    /// Installs the `bsc` protocol, which gossips the fast finality votes of the validators.
+    ///
+    /// In [`PeeringMode::Sentry`](reth_network_types::PeeringMode::Sentry) the votes are relayed
+    /// to the validators of the allow-list first. The handle to broadcast and receive votes is
+    /// available with [`NetworkHandle::bsc`].
+    pub const fn bsc(mut self) -> Self {
+        self.bsc = true;
+        self
+    }
+
     /// Sets whether tx gossip is disabled.
     pub const fn disable_tx_gossip(mut self, disable_tx_gossip: bool) -> Self {
         self.tx_gossip_disabled = disable_tx_gossip;
         self
     }
 
+    /// Sets whether transactions gossiped by peers are ignored.
+    pub const fn disable_inbound_tx_gossip(mut self, disable_inbound_tx_gossip: bool) -> Self {
+        self.inbound_tx_gossip_disabled = disable_inbound_tx_gossip;
+        self
+    }
+
     /// Sets the block import type.
     pub fn block_import(mut self, block_import: Box<dyn BlockImport>) -> Self {
         self.block_import = Some(block_import);
@@ -472,7 +532,7 @@ impl NetworkConfigBuilder {
         let Self {
             secret_key,
             mut dns_discovery_config,
-            discovery_v4_builder,
+            mut discovery_v4_builder,
             mut discovery_v5_builder,
             boot_nodes,
             discovery_addr,
@@ -483,13 +543,40 @@ impl NetworkConfigBuilder {
             network_mode,
             executor,
             hello_message,
-            extra_protocols,
+            mut extra_protocols,
             head,
             tx_gossip_disabled,
+            mut inbound_tx_gossip_disabled,
             block_import,
             transactions_manager_config,
+            eth_request_limits,
+            snap,
+            bsc,
         } = self;
 
+        let peers_config = peers_config.unwrap_or_default();
+
+        // a validator only talks to the peers of its allow-list
+        if peers_config.peering_mode.is_validator() {
+            dns_discovery_config = None;
+            discovery_v4_builder = None;
+            discovery_v5_builder = None;
+            inbound_tx_gossip_disabled = true;
+        }
+
+        // a sentry relays the votes to the validators it protects first
+        let bsc = bsc.then(|| {
+            let protected_peers = if peers_config.peering_mode.is_sentry() {
+                peers_config.allow_list.iter().map(|node| node.id).collect()
+            } else {
+                HashSet::new()
+            };
+            let handler = BscProtocolHandler::new(protected_peers);
+            let handle = handler.handle();
+            extra_protocols.push(handler);
+            handle
+        });
+
         discovery_v5_builder = discovery_v5_builder.map(|mut builder| {
             if let Some(network_stack_id) = NetworkStackId::id(&chain_spec) {
                 let fork_id = chain_spec.latest_fork_id();
@@ -539,7 +626,7 @@ impl NetworkConfigBuilder {
             discovery_v5_config: discovery_v5_builder.map(|builder| builder.build()),
             discovery_v4_addr: discovery_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS),
             listener_addr,
-            peers_config: peers_config.unwrap_or_default(),
+            peers_config,
             sessions_config: sessions_config.unwrap_or_default(),
             chain_spec,
             block_import: block_import.unwrap_or_else(|| Box::<ProofOfStakeBlockImport>::default()),
@@ -550,7 +637,11 @@ impl NetworkConfigBuilder {
             extra_protocols,
             fork_filter,
             tx_gossip_disabled,
+            inbound_tx_gossip_disabled,
             transactions_manager_config,
+            eth_request_limits,
+            snap,
+            bsc,
         }
     }
 }
@@ -14,7 +14,7 @@ use reth_dns_discovery::{
     DnsDiscoveryConfig, DnsDiscoveryHandle, DnsDiscoveryService, DnsNodeRecordUpdate, DnsResolver,
 };
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_primitives::{EnrForkIdEntry, ForkId};
+use reth_primitives::{EnrForkIdEntry, ForkFilter, ForkId, Head};
 use secp256k1::SecretKey;
 use std::{
     collections::VecDeque,
@@ -60,6 +60,11 @@ pub struct Discovery {
     dns_discovery_updates: Option<ReceiverStream<DnsNodeRecordUpdate>>,
     /// The handle to the spawned DNS discovery service
     _dns_disc_service: Option<JoinHandle<()>>,
+    /// Filter for the fork ids advertised by the discovered nodes.
+    ///
+    /// Nodes of other chains or forks are dropped before they reach the peer set, so no
+    /// connection slot is wasted on dialing them.
+    fork_filter: ForkFilter,
     /// Events buffered until polled.
     queued_events: VecDeque<DiscoveryEvent>,
     /// List of listeners subscribed to discovery events.
@@ -78,6 +83,7 @@ impl Discovery {
         discv4_config: Option<Discv4Config>,
         discv5_config: Option<reth_discv5::Config>, // contains discv5 listen address
         dns_discovery_config: Option<DnsDiscoveryConfig>,
+        fork_filter: ForkFilter,
     ) -> Result<Self, NetworkError> {
         // setup discv4 with the discovery address and tcp port
         let local_enr =
@@ -130,6 +136,7 @@ impl Discovery {
             discv5,
             discv5_updates,
             discovered_nodes: LruMap::new(DEFAULT_MAX_CAPACITY_DISCOVERED_PEERS_CACHE),
+            fork_filter,
             queued_events: Default::default(),
             _dns_disc_service,
             _dns_discovery,
@@ -148,13 +155,36 @@ impl Discovery {
         self.discovery_listeners.retain_mut(|listener| listener.send(event.clone()).is_ok());
     }
 
-    /// Updates the `eth:ForkId` field in discv4.
-    pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
+    /// Updates the head of the fork filter and, if a new fork was activated, the fork id entry in
+    /// the local ENRs of discv4 and discv5.
+    pub(crate) fn on_status_update(&mut self, head: Head) {
+        if let Some(transition) = self.fork_filter.set_head(head) {
+            self.update_fork_id(transition.current)
+        }
+    }
+
+    /// Updates the `eth:ForkId` field in discv4 and the fork id field in discv5.
+    fn update_fork_id(&self, fork_id: ForkId) {
         if let Some(discv4) = &self.discv4 {
             // use forward-compatible forkid entry
             discv4.set_eip868_rlp(b"eth".to_vec(), EnrForkIdEntry::from(fork_id))
         }
-        // todo: update discv5 enr
+        if let Some(discv5) = &self.discv5 {
+            if let Some(fork_key) = discv5.fork_key() {
+                discv5.encode_and_set_eip868_in_local_enr(
+                    fork_key.to_vec(),
+                    EnrForkIdEntry::from(fork_id),
+                )
+            }
+        }
+    }
+
+    /// Returns `true` if the fork id advertised by a node is compatible with the local chain.
+    ///
+    /// Nodes that don't advertise a fork id are accepted, they are checked in the `Status`
+    /// handshake.
+    fn is_valid_fork_id(&self, fork_id: Option<ForkId>) -> bool {
+        fork_id.map_or(true, |fork_id| self.fork_filter.validate(fork_id).is_ok())
     }
 
     /// Bans the [`IpAddr`] in the discovery service.
@@ -205,6 +235,10 @@ impl Discovery {
 
     /// Processes an incoming [`NodeRecord`] update from a discovery service
     fn on_node_record_update(&mut self, record: NodeRecord, fork_id: Option<ForkId>) {
+        if !self.is_valid_fork_id(fork_id) {
+            trace!(target: "net::discovery", peer_id=?record.id, ?fork_id, "ignoring node with incompatible fork id");
+            return
+        }
         let peer_id = record.id;
         let tcp_addr = record.tcp_addr();
         let udp_addr = record.udp_addr();
@@ -225,6 +259,11 @@ impl Discovery {
                 self.on_node_record_update(record, None);
             }
             DiscoveryUpdate::EnrForkId(node, fork_id) => {
+                if !self.is_valid_fork_id(Some(fork_id)) {
+                    // the node was already reported without a fork id, the event removes it
+                    // from the peer set again
+                    self.discovered_nodes.remove(&node.id);
+                }
                 self.queued_events.push_back(DiscoveryEvent::EnrForkId(node.id, fork_id))
             }
             DiscoveryUpdate::Removed(peer_id) => {
@@ -270,6 +309,10 @@ impl Discovery {
             while let Some(Poll::Ready(Some(update))) =
                 self.dns_discovery_updates.as_mut().map(|updates| updates.poll_next_unpin(cx))
             {
+                if !self.is_valid_fork_id(update.fork_id) {
+                    trace!(target: "net::discovery", peer_id=?update.node_record.id, fork_id=?update.fork_id, "ignoring node from dns with incompatible fork id");
+                    continue
+                }
                 self.add_discv4_node(update.node_record);
                 if let Err(err) = self.add_discv5_node(update.enr) {
                     trace!(target: "net::discovery",
@@ -316,6 +359,7 @@ impl Discovery {
             discv4_updates: Default::default(),
             discv5: None,
             discv5_updates: None,
+            fork_filter: reth_chainspec::MAINNET.fork_filter(Head::default()),
             queued_events: Default::default(),
             _discv4_service: Default::default(),
             _dns_discovery: None,
@@ -339,6 +383,8 @@ pub enum DiscoveryEvent {
 mod tests {
     use super::*;
     use rand::thread_rng;
+    use reth_chainspec::MAINNET;
+    use reth_primitives::ForkHash;
     use secp256k1::SECP256K1;
     use std::net::{Ipv4Addr, SocketAddrV4};
 
@@ -354,11 +400,35 @@ mod tests {
             Default::default(),
             None,
             Default::default(),
+            MAINNET.fork_filter(Head::default()),
         )
         .await
         .unwrap();
     }
 
+    #[test]
+    fn test_discovered_node_fork_id_filter() {
+        let mut discovery = Discovery::noop();
+        discovery.discovered_nodes = LruMap::new(1);
+        let node = NodeRecord::new("127.0.0.1:30303".parse().unwrap(), PeerId::random());
+
+        // a node of another chain is dropped
+        let foreign = ForkId { hash: ForkHash([0xde, 0xad, 0xbe, 0xef]), next: 0 };
+        discovery.on_node_record_update(node, Some(foreign));
+        assert!(discovery.queued_events.is_empty());
+        assert_eq!(discovery.discovered_nodes.len(), 0);
+
+        // a node of the local chain is reported
+        let fork_id = discovery.fork_filter.current();
+        discovery.on_node_record_update(node, Some(fork_id));
+        assert_eq!(discovery.queued_events.len(), 1);
+        assert_eq!(discovery.discovered_nodes.len(), 1);
+
+        // an incompatible fork id learned later evicts the node again
+        discovery.on_discv4_update(DiscoveryUpdate::EnrForkId(node, foreign));
+        assert_eq!(discovery.discovered_nodes.len(), 0);
+    }
+
     use reth_discv4::Discv4ConfigBuilder;
     use reth_discv5::{enr::EnrCombinedKeyWrapper, enr_to_discv4_id};
     use tracing::trace;
@@ -384,6 +454,7 @@ mod tests {
             Some(discv4_config),
             Some(discv5_config),
             None,
+            MAINNET.fork_filter(Head::default()),
         )
         .await
         .expect("should build discv5 with discv4 downgrade")
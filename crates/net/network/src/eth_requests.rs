@@ -7,21 +7,26 @@ use crate::{
 use alloy_rlp::Encodable;
 use futures::StreamExt;
 use reth_eth_wire::{
-    BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders, GetNodeData, GetReceipts,
-    HeadersDirection, NodeData, Receipts,
+    BlockBodies, BlockHeaders, EthMessageID, GetBlockBodies, GetBlockHeaders, GetNodeData,
+    GetReceipts, HeadersDirection, NodeData, Receipts,
 };
 use reth_network_p2p::error::RequestResult;
 use reth_network_peers::PeerId;
 use reth_primitives::{BlockBody, BlockHashOrNumber, Header};
 use reth_storage_api::{BlockReader, HeaderProvider, ReceiptProvider};
 use std::{
+    collections::{HashMap, VecDeque},
     future::Future,
     pin::Pin,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{mpsc::Receiver, oneshot},
+    time::Sleep,
 };
-use tokio::sync::{mpsc::Receiver, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
+use tracing::trace;
 
 // Limits: <https://github.com/ethereum/go-ethereum/blob/b0d44338bbcefee044f1f635a84487cbbd8f0538/eth/protocols/eth/handler.go#L34-L56>
 
@@ -44,6 +49,38 @@ const MAX_BODIES_SERVE: usize = 1024;
 /// Maximum size of replies to data retrievals.
 const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 
+/// Maximum number of requests that wait for the [`EthRequestLimits`].
+///
+/// Requests beyond that are dropped.
+const MAX_DEFERRED_REQUESTS: usize = 1024;
+
+/// Maximum number of requests of a single peer that wait for the [`EthRequestLimits`].
+///
+/// The oldest request of the peer is dropped to make room for a new one, so a single peer can't
+/// crowd out the requests of the other peers.
+const MAX_DEFERRED_REQUESTS_PER_PEER: usize = 16;
+
+/// Limits on the bytes served by the [`EthRequestHandler`], in bytes per second.
+///
+/// Every limit is enforced with a token bucket that holds up to one second worth of bytes. Like
+/// [`SOFT_RESPONSE_LIMIT`], the limits are soft: a response is cut short once it exceeds the
+/// available bytes, and the bytes it exceeds them by are paid back before the next response.
+/// Requests are queued while the bucket is empty, up to [`MAX_DEFERRED_REQUESTS_PER_PEER`] per
+/// peer and [`MAX_DEFERRED_REQUESTS`] in total, instead of being answered with empty responses,
+/// since peers treat empty responses as missing data. `None` disables the limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EthRequestLimits {
+    /// Bytes per second served to a single peer, across all request types.
+    pub peer_bytes_per_sec: Option<u64>,
+    /// Bytes per second served in `BlockHeaders` responses, across all peers.
+    pub headers_bytes_per_sec: Option<u64>,
+    /// Bytes per second served in `BlockBodies` responses, across all peers.
+    pub bodies_bytes_per_sec: Option<u64>,
+    /// Bytes per second served in `Receipts` responses, across all peers.
+    pub receipts_bytes_per_sec: Option<u64>,
+}
+
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
@@ -60,6 +97,12 @@ pub struct EthRequestHandler<C> {
     incoming_requests: ReceiverStream<IncomingEthRequest>,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
+    /// Enforces the limits on the served bytes.
+    limiter: ServeLimiter,
+    /// Requests that wait for the limits, with the time their budget is available again.
+    deferred_requests: VecDeque<(Instant, IncomingEthRequest)>,
+    /// Wakes the handler once the budget of the next deferred request is available again.
+    deferred_requests_timer: Option<Pin<Box<Sleep>>>,
 }
 
 // === impl EthRequestHandler ===
@@ -71,7 +114,33 @@ impl<C> EthRequestHandler<C> {
             peers,
             incoming_requests: ReceiverStream::new(incoming),
             metrics: Default::default(),
+            limiter: ServeLimiter::new(EthRequestLimits::default()),
+            deferred_requests: Default::default(),
+            deferred_requests_timer: None,
+        }
+    }
+
+    /// Sets the limits on the served bytes.
+    pub fn with_limits(mut self, limits: EthRequestLimits) -> Self {
+        self.limiter = ServeLimiter::new(limits);
+        self
+    }
+
+    /// Polls the timer of the deferred request whose budget is available first.
+    fn poll_deferred_requests_timer(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let Some(deadline) = self.deferred_requests.iter().map(|(at, _)| *at).min() else {
+            self.deferred_requests_timer = None;
+            return Poll::Pending
+        };
+        let deadline = tokio::time::Instant::from_std(deadline);
+
+        let timer = self
+            .deferred_requests_timer
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+        if timer.deadline() != deadline {
+            timer.as_mut().reset(deadline);
         }
+        timer.as_mut().poll(cx)
     }
 }
 
@@ -79,8 +148,8 @@ impl<C> EthRequestHandler<C>
 where
     C: BlockReader + HeaderProvider + ReceiptProvider,
 {
-    /// Returns the list of requested headers
-    fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<Header> {
+    /// Returns the list of requested headers, up to roughly `max_bytes` in total.
+    fn get_headers_response(&self, request: GetBlockHeaders, max_bytes: usize) -> Vec<Header> {
         let GetBlockHeaders { start_block, limit, skip, direction } = request;
 
         let mut headers = Vec::new();
@@ -128,7 +197,7 @@ where
                 total_bytes += header.length();
                 headers.push(header);
 
-                if headers.len() >= MAX_HEADERS_SERVE || total_bytes > SOFT_RESPONSE_LIMIT {
+                if headers.len() >= MAX_HEADERS_SERVE || total_bytes > max_bytes {
                     break
                 }
             } else {
@@ -139,24 +208,118 @@ where
         headers
     }
 
+    /// Serves the request, or queues it until its response budget is available again.
+    fn on_request(&mut self, request: IncomingEthRequest, now: Instant) {
+        let Err(deferred) = self.try_serve_request(request, now) else { return };
+
+        self.metrics.eth_requests_rate_limited_total.increment(1);
+        let peer_id = deferred.1.peer_id();
+        let is_peer_request =
+            |(_, request): &(Instant, IncomingEthRequest)| request.peer_id() == peer_id;
+        if self.deferred_requests.iter().filter(|req| is_peer_request(req)).count() >=
+            MAX_DEFERRED_REQUESTS_PER_PEER
+        {
+            trace!(
+                target: "net::eth",
+                ?peer_id,
+                "Dropping oldest request of peer, too many of its requests wait for the limits"
+            );
+            // the queue keeps the order of arrival, so this is the peer's oldest request
+            let oldest = self.deferred_requests.iter().position(is_peer_request);
+            self.deferred_requests.remove(oldest.expect("peer has deferred requests"));
+        } else if self.deferred_requests.len() >= MAX_DEFERRED_REQUESTS {
+            trace!(target: "net::eth", "Dropping request, too many requests wait for the limits");
+            return
+        }
+        self.deferred_requests.push_back(deferred);
+    }
+
+    /// Serves the deferred requests whose response budget is available again.
+    ///
+    /// Requests whose response channel is closed, for example because the peer disconnected, are
+    /// dropped.
+    fn serve_deferred_requests(&mut self, now: Instant) {
+        self.deferred_requests.retain(|(_, request)| !request.is_closed());
+
+        for _ in 0..self.deferred_requests.len() {
+            let Some((available_at, request)) = self.deferred_requests.pop_front() else { break };
+            if available_at > now {
+                self.deferred_requests.push_back((available_at, request));
+                continue
+            }
+            if let Err(deferred) = self.try_serve_request(request, now) {
+                self.deferred_requests.push_back(deferred);
+            }
+        }
+    }
+
+    /// Serves the request if the budget of its response isn't exhausted.
+    ///
+    /// Otherwise returns the request, with the time the budget is available again.
+    fn try_serve_request(
+        &mut self,
+        request: IncomingEthRequest,
+        now: Instant,
+    ) -> Result<(), (Instant, IncomingEthRequest)> {
+        let (peer_id, response) = match &request {
+            IncomingEthRequest::GetBlockHeaders { peer_id, .. } => {
+                (*peer_id, EthMessageID::BlockHeaders)
+            }
+            IncomingEthRequest::GetBlockBodies { peer_id, .. } => {
+                (*peer_id, EthMessageID::BlockBodies)
+            }
+            IncomingEthRequest::GetReceipts { peer_id, .. } => (*peer_id, EthMessageID::Receipts),
+            IncomingEthRequest::GetNodeData { .. } => {
+                self.metrics.eth_node_data_requests_received_total.increment(1);
+                return Ok(())
+            }
+        };
+
+        let budget = self.limiter.budget(peer_id, response, now);
+        if budget == 0 {
+            return Err((self.limiter.available_at(peer_id, response, now), request))
+        }
+
+        match request {
+            IncomingEthRequest::GetBlockHeaders { peer_id, request, response } => {
+                self.on_headers_request(peer_id, request, response, budget)
+            }
+            IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
+                self.on_bodies_request(peer_id, request, response, budget)
+            }
+            IncomingEthRequest::GetReceipts { peer_id, request, response } => {
+                self.on_receipts_request(peer_id, request, response, budget)
+            }
+            // not served, see above
+            IncomingEthRequest::GetNodeData { .. } => {}
+        }
+        Ok(())
+    }
+
     fn on_headers_request(
-        &self,
-        _peer_id: PeerId,
+        &mut self,
+        peer_id: PeerId,
         request: GetBlockHeaders,
         response: oneshot::Sender<RequestResult<BlockHeaders>>,
+        budget: usize,
     ) {
         self.metrics.eth_headers_requests_received_total.increment(1);
-        let headers = self.get_headers_response(request);
+
+        let headers = self.get_headers_response(request, budget);
+        let total_bytes = headers.iter().map(|header| header.length()).sum();
+        self.limiter.consume(peer_id, EthMessageID::BlockHeaders, total_bytes);
         let _ = response.send(Ok(BlockHeaders(headers)));
     }
 
     fn on_bodies_request(
-        &self,
-        _peer_id: PeerId,
+        &mut self,
+        peer_id: PeerId,
         request: GetBlockBodies,
         response: oneshot::Sender<RequestResult<BlockBodies>>,
+        budget: usize,
     ) {
         self.metrics.eth_bodies_requests_received_total.increment(1);
+
         let mut bodies = Vec::new();
 
         let mut total_bytes = 0;
@@ -168,7 +331,7 @@ where
                 total_bytes += body.length();
                 bodies.push(body);
 
-                if bodies.len() >= MAX_BODIES_SERVE || total_bytes > SOFT_RESPONSE_LIMIT {
+                if bodies.len() >= MAX_BODIES_SERVE || total_bytes > budget {
                     break
                 }
             } else {
@@ -176,14 +339,16 @@ where
             }
         }
 
+        self.limiter.consume(peer_id, EthMessageID::BlockBodies, total_bytes);
         let _ = response.send(Ok(BlockBodies(bodies)));
     }
 
     fn on_receipts_request(
-        &self,
-        _peer_id: PeerId,
+        &mut self,
+        peer_id: PeerId,
         request: GetReceipts,
         response: oneshot::Sender<RequestResult<Receipts>>,
+        budget: usize,
     ) {
         self.metrics.eth_receipts_requests_received_total.increment(1);
 
@@ -203,7 +368,7 @@ where
                 total_bytes += receipt.length();
                 receipts.push(receipt);
 
-                if receipts.len() >= MAX_RECEIPTS_SERVE || total_bytes > SOFT_RESPONSE_LIMIT {
+                if receipts.len() >= MAX_RECEIPTS_SERVE || total_bytes > budget {
                     break
                 }
             } else {
@@ -211,6 +376,7 @@ where
             }
         }
 
+        self.limiter.consume(peer_id, EthMessageID::Receipts, total_bytes);
         let _ = response.send(Ok(Receipts(receipts)));
     }
 }
@@ -227,6 +393,8 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
+        this.serve_deferred_requests(Instant::now());
+
         let mut acc = Duration::ZERO;
         let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
             acc,
@@ -234,26 +402,16 @@ where
             "Incoming eth requests stream",
             DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
             this.incoming_requests.poll_next_unpin(cx),
-            |incoming| {
-                match incoming {
-                    IncomingEthRequest::GetBlockHeaders { peer_id, request, response } => {
-                        this.on_headers_request(peer_id, request, response)
-                    }
-                    IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
-                        this.on_bodies_request(peer_id, request, response)
-                    }
-                    IncomingEthRequest::GetNodeData { .. } => {
-                        this.metrics.eth_node_data_requests_received_total.increment(1);
-                    }
-                    IncomingEthRequest::GetReceipts { peer_id, request, response } => {
-                        this.on_receipts_request(peer_id, request, response)
-                    }
-                }
-            },
+            |incoming| this.on_request(incoming, Instant::now()),
         );
 
         this.metrics.acc_duration_poll_eth_req_handler.set(acc.as_secs_f64());
 
+        // wake up once the budget of the next deferred request is available again
+        if this.poll_deferred_requests_timer(cx).is_ready() {
+            cx.waker().wake_by_ref();
+        }
+
         // stream is fully drained and import futures pending
         if maybe_more_incoming_requests {
             // make sure we're woken up again
@@ -265,6 +423,136 @@ where
     }
 }
 
+/// Enforces the [`EthRequestLimits`] with token buckets.
+#[derive(Debug)]
+struct ServeLimiter {
+    /// The configured limits.
+    limits: EthRequestLimits,
+    /// The buckets of the peers that were served recently.
+    peers: HashMap<PeerId, TokenBucket>,
+    /// The bucket of `BlockHeaders` responses.
+    headers: Option<TokenBucket>,
+    /// The bucket of `BlockBodies` responses.
+    bodies: Option<TokenBucket>,
+    /// The bucket of `Receipts` responses.
+    receipts: Option<TokenBucket>,
+}
+
+impl ServeLimiter {
+    fn new(limits: EthRequestLimits) -> Self {
+        let now = Instant::now();
+        let bucket = |rate: Option<u64>| rate.map(|rate| TokenBucket::new(rate, now));
+        Self {
+            limits,
+            peers: Default::default(),
+            headers: bucket(limits.headers_bytes_per_sec),
+            bodies: bucket(limits.bodies_bytes_per_sec),
+            receipts: bucket(limits.receipts_bytes_per_sec),
+        }
+    }
+
+    /// Returns the bucket of the given response type, if limited.
+    fn response_bucket(&mut self, response: EthMessageID) -> Option<&mut TokenBucket> {
+        match response {
+            EthMessageID::BlockHeaders => self.headers.as_mut(),
+            EthMessageID::BlockBodies => self.bodies.as_mut(),
+            EthMessageID::Receipts => self.receipts.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of bytes that can be served to the peer in a response of the given
+    /// type.
+    fn budget(&mut self, peer_id: PeerId, response: EthMessageID, now: Instant) -> usize {
+        let mut budget = SOFT_RESPONSE_LIMIT;
+
+        if let Some(bucket) = self.response_bucket(response) {
+            bucket.refill(now);
+            budget = budget.min(bucket.available());
+        }
+
+        if let Some(rate) = self.limits.peer_bytes_per_sec {
+            if !self.peers.contains_key(&peer_id) {
+                // forget the peers that are back to a full bucket
+                self.peers.retain(|_, bucket| {
+                    bucket.refill(now);
+                    !bucket.is_full()
+                });
+            }
+            let bucket = self.peers.entry(peer_id).or_insert_with(|| TokenBucket::new(rate, now));
+            bucket.refill(now);
+            budget = budget.min(bucket.available());
+        }
+
+        budget
+    }
+
+    /// Returns when the exhausted budget of the peer for a response of the given type is
+    /// available again.
+    fn available_at(&mut self, peer_id: PeerId, response: EthMessageID, now: Instant) -> Instant {
+        let response_bucket = self.response_bucket(response).map(|bucket| bucket.available_at());
+        let peer_bucket = self.peers.get(&peer_id).map(TokenBucket::available_at);
+        response_bucket.into_iter().chain(peer_bucket).fold(now, Instant::max)
+    }
+
+    /// Records the bytes served to the peer in a response of the given type.
+    fn consume(&mut self, peer_id: PeerId, response: EthMessageID, bytes: usize) {
+        if let Some(bucket) = self.response_bucket(response) {
+            bucket.consume(bytes);
+        }
+        if let Some(bucket) = self.peers.get_mut(&peer_id) {
+            bucket.consume(bytes);
+        }
+    }
+}
+
+/// A token bucket of bytes that refills at a fixed rate, up to one second worth of bytes.
+#[derive(Debug)]
+struct TokenBucket {
+    /// Bytes added per second, also the capacity of the bucket.
+    rate: u64,
+    /// Available bytes, negative if the last response exceeded the available bytes.
+    tokens: i64,
+    /// When the bucket was last refilled.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        Self { rate, tokens: rate as i64, last_refill: now }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refill = (elapsed.as_secs_f64() * self.rate as f64) as i64;
+        if refill > 0 {
+            self.tokens = self.tokens.saturating_add(refill).min(self.rate as i64);
+            self.last_refill = now;
+        }
+    }
+
+    fn available(&self) -> usize {
+        self.tokens.max(0) as usize
+    }
+
+    /// Returns when the bucket holds available bytes again.
+    fn available_at(&self) -> Instant {
+        if self.tokens > 0 {
+            return self.last_refill
+        }
+        let missing = (1 - self.tokens) as f64;
+        self.last_refill + Duration::from_secs_f64(missing / self.rate.max(1) as f64)
+    }
+
+    fn consume(&mut self, bytes: usize) {
+        self.tokens = self.tokens.saturating_sub(bytes as i64);
+    }
+
+    fn is_full(&self) -> bool {
+        self.tokens >= self.rate as i64
+    }
+}
+
 /// All `eth` request related to blocks delegated by the network.
 #[derive(Debug)]
 pub enum IncomingEthRequest {
@@ -313,3 +601,184 @@ pub enum IncomingEthRequest {
         response: oneshot::Sender<RequestResult<Receipts>>,
     },
 }
+
+impl IncomingEthRequest {
+    /// Returns the ID of the peer that sent the request.
+    pub const fn peer_id(&self) -> PeerId {
+        match self {
+            Self::GetBlockHeaders { peer_id, .. } |
+            Self::GetBlockBodies { peer_id, .. } |
+            Self::GetNodeData { peer_id, .. } |
+            Self::GetReceipts { peer_id, .. } => *peer_id,
+        }
+    }
+
+    /// Returns `true` if the response channel is closed, because the requester is gone.
+    pub fn is_closed(&self) -> bool {
+        match self {
+            Self::GetBlockHeaders { response, .. } => response.is_closed(),
+            Self::GetBlockBodies { response, .. } => response.is_closed(),
+            Self::GetNodeData { response, .. } => response.is_closed(),
+            Self::GetReceipts { response, .. } => response.is_closed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peers::PeersManager;
+    use reth_primitives::{Block, Header, B256};
+    use reth_provider::test_utils::MockEthProvider;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_serve_limiter() {
+        let limits = EthRequestLimits {
+            peer_bytes_per_sec: Some(1000),
+            bodies_bytes_per_sec: Some(1500),
+            ..Default::default()
+        };
+        let mut limiter = ServeLimiter::new(limits);
+        let (peer1, peer2) = (PeerId::random(), PeerId::random());
+        let now = Instant::now();
+
+        assert_eq!(limiter.budget(peer1, EthMessageID::BlockHeaders, now), 1000);
+        assert_eq!(limiter.budget(peer1, EthMessageID::BlockBodies, now), 1000);
+
+        // the response exceeded the budget, the peer is in debt
+        limiter.consume(peer1, EthMessageID::BlockBodies, 1200);
+        assert_eq!(limiter.budget(peer1, EthMessageID::BlockHeaders, now), 0);
+
+        // the bodies bucket is shared by all peers
+        assert_eq!(limiter.budget(peer2, EthMessageID::BlockBodies, now), 300);
+        assert_eq!(limiter.budget(peer2, EthMessageID::Receipts, now), 1000);
+
+        let later = now + Duration::from_millis(500);
+        assert_eq!(limiter.budget(peer1, EthMessageID::BlockHeaders, later), 300);
+        assert_eq!(limiter.budget(peer1, EthMessageID::BlockBodies, later), 300);
+
+        // buckets are capped at one second worth of bytes
+        let later = now + Duration::from_secs(10);
+        assert_eq!(limiter.budget(peer1, EthMessageID::BlockBodies, later), 1000);
+        assert_eq!(limiter.budget(peer1, EthMessageID::Receipts, later), 1000);
+    }
+
+    #[test]
+    fn test_exhausted_budget_defers_request() {
+        let provider = MockEthProvider::default();
+        let hashes = (1..=3u8).map(B256::with_last_byte).collect::<Vec<_>>();
+        provider.extend_blocks(hashes.iter().map(|hash| (*hash, Block::default())));
+
+        let (_tx, rx) = mpsc::channel(1);
+        let mut handler = EthRequestHandler::new(provider, PeersManager::default().handle(), rx)
+            .with_limits(EthRequestLimits { peer_bytes_per_sec: Some(1), ..Default::default() });
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+        let request = |response| IncomingEthRequest::GetBlockBodies {
+            peer_id,
+            request: GetBlockBodies(hashes.clone()),
+            response,
+        };
+
+        // the first response exhausts the budget
+        let (response, mut rx) = oneshot::channel();
+        handler.on_request(request(response), now);
+        assert_eq!(rx.try_recv().unwrap().unwrap().0.len(), 1);
+
+        // the next one waits until the budget is available again
+        let (response, mut rx) = oneshot::channel();
+        handler.on_request(request(response), now);
+        assert!(rx.try_recv().is_err());
+
+        handler.serve_deferred_requests(now);
+        assert!(rx.try_recv().is_err());
+
+        let (available_at, _) = handler.deferred_requests[0];
+        handler.serve_deferred_requests(available_at);
+        assert_eq!(rx.try_recv().unwrap().unwrap().0.len(), 1);
+        assert!(handler.deferred_requests.is_empty());
+    }
+
+    #[test]
+    fn test_deferred_requests_per_peer() {
+        let provider = MockEthProvider::default();
+        let hashes = (1..=3u8).map(B256::with_last_byte).collect::<Vec<_>>();
+        provider.extend_blocks(hashes.iter().map(|hash| (*hash, Block::default())));
+
+        let (_tx, rx) = mpsc::channel(1);
+        let mut handler = EthRequestHandler::new(provider, PeersManager::default().handle(), rx)
+            .with_limits(EthRequestLimits { peer_bytes_per_sec: Some(1), ..Default::default() });
+        let (peer1, peer2) = (PeerId::random(), PeerId::random());
+        let now = Instant::now();
+        let mut request = |peer_id| {
+            let (response, rx) = oneshot::channel();
+            let request = GetBlockBodies(hashes.clone());
+            let request = IncomingEthRequest::GetBlockBodies { peer_id, request, response };
+            handler.on_request(request, now);
+            rx
+        };
+
+        // exhaust the budgets of both peers
+        drop(request(peer1));
+        drop(request(peer2));
+
+        // a full queue drops the peer's own oldest request
+        let mut peer1_requests =
+            (0..MAX_DEFERRED_REQUESTS_PER_PEER).map(|_| request(peer1)).collect::<Vec<_>>();
+        let mut peer2_request = request(peer2);
+        let mut newest = request(peer1);
+        assert!(matches!(peer1_requests[0].try_recv(), Err(oneshot::error::TryRecvError::Closed)));
+        assert!(matches!(peer2_request.try_recv(), Err(oneshot::error::TryRecvError::Empty)));
+        assert!(matches!(newest.try_recv(), Err(oneshot::error::TryRecvError::Empty)));
+        assert_eq!(handler.deferred_requests.len(), MAX_DEFERRED_REQUESTS_PER_PEER + 1);
+
+        // the requests of a disconnected peer are purged
+        drop(peer1_requests);
+        drop(newest);
+        handler.serve_deferred_requests(now);
+        assert_eq!(handler.deferred_requests.len(), 1);
+        assert_eq!(handler.deferred_requests[0].1.peer_id(), peer2);
+    }
+
+    #[test]
+    fn test_served_bytes_stay_near_rate() {
+        const RATE: u64 = 100_000;
+        const SECS: u64 = 10;
+
+        let block = Block { ommers: vec![Header::default(); 10], ..Default::default() };
+        let body_len = BlockBody::from(block.clone()).length() as u64;
+        let provider = MockEthProvider::default();
+        let hashes = (1..=16u8).map(B256::with_last_byte).collect::<Vec<_>>();
+        provider.extend_blocks(hashes.iter().map(|hash| (*hash, block.clone())));
+
+        let (_tx, rx) = mpsc::channel(1);
+        let limits = EthRequestLimits { bodies_bytes_per_sec: Some(RATE), ..Default::default() };
+        let mut handler = EthRequestHandler::new(provider, PeersManager::default().handle(), rx)
+            .with_limits(limits);
+        let peer_id = PeerId::random();
+        let start = Instant::now();
+
+        // a request every 10ms, each for more bytes than the limit allows per second
+        let mut responses = Vec::new();
+        for tick in 0..SECS * 100 {
+            let now = start + Duration::from_millis(tick * 10);
+            handler.serve_deferred_requests(now);
+            let (response, rx) = oneshot::channel();
+            let request = GetBlockBodies(hashes.clone());
+            let request = IncomingEthRequest::GetBlockBodies { peer_id, request, response };
+            handler.on_request(request, now);
+            responses.push(rx);
+        }
+        let served = responses
+            .iter_mut()
+            .filter_map(|rx| rx.try_recv().ok())
+            .map(|response| response.unwrap().0.len() as u64 * body_len)
+            .sum::<u64>();
+
+        // the bucket starts with one second worth of bytes, and the last response may exceed the
+        // available bytes by a body
+        assert!(served >= RATE * SECS, "served {served} bytes");
+        assert!(served <= RATE * (SECS + 1) + body_len, "served {served} bytes");
+    }
+}
@@ -12,10 +12,14 @@ use reth_network_p2p::{
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_primitives::{Header, B256};
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc,
+use reth_primitives::{BlockNumber, Header, B256};
+use std::{
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
 };
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
 
@@ -81,10 +85,39 @@ impl BodiesClient for FetchClient {
         request: Vec<B256>,
         priority: Priority,
     ) -> Self::Output {
+        self.send_bodies_request(request, None, priority)
+    }
+
+    /// Sends a `GetBlockBodies` request to an available peer that still serves the given range of
+    /// blocks.
+    fn get_block_bodies_in_range_with_priority(
+        &self,
+        request: Vec<B256>,
+        range: RangeInclusive<BlockNumber>,
+        priority: Priority,
+    ) -> Self::Output {
+        self.send_bodies_request(request, Some(range), priority)
+    }
+}
+
+impl FetchClient {
+    /// Queues a `GetBlockBodies` request for the given blocks.
+    fn send_bodies_request(
+        &self,
+        request: Vec<B256>,
+        range: Option<RangeInclusive<BlockNumber>>,
+        priority: Priority,
+    ) -> BodiesFut {
         let (response, rx) = oneshot::channel();
         if self
             .request_tx
-            .send(DownloadRequest::GetBlockBodies { request, response, priority })
+            .send(DownloadRequest::GetBlockBodies {
+                request,
+                range,
+                response,
+                priority,
+                queued_at: Instant::now(),
+            })
             .is_ok()
         {
             Box::pin(FlattenedResponse::from(rx))
@@ -1,30 +1,47 @@
 //! Fetch data from the network.
 
-use crate::{message::BlockRequest, peers::PeersHandle};
+use crate::{message::BlockRequest, metrics::StateFetcherMetrics, peers::PeersHandle};
 use futures::StreamExt;
 use reth_eth_wire::{GetBlockBodies, GetBlockHeaders};
-use reth_network_api::ReputationChangeKind;
+use reth_network_api::{PeerRequestStats, ReputationChangeKind};
 use reth_network_p2p::{
     error::{EthResponseValidator, PeerRequestResult, RequestError, RequestResult},
     headers::client::HeadersRequest,
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_primitives::{BlockBody, Header, B256};
+use reth_primitives::{BlockBody, BlockNumber, Header, B256};
 use std::{
     collections::{HashMap, VecDeque},
+    future::Future,
+    ops::RangeInclusive,
+    pin::Pin,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc,
     },
     task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{mpsc, mpsc::UnboundedSender, oneshot},
+    time::Sleep,
 };
-use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 mod client;
 pub use client::FetchClient;
 
+/// Minimum number of successful requests before a peer's latency is trusted for prioritization.
+const MIN_REQUESTS_FOR_LATENCY: u64 = 3;
+
+/// Minimum success rate in percent a peer needs to be preferred for high priority requests.
+const MIN_SUCCESS_RATE_FOR_PRIORITY: u64 = 80;
+
+/// How long a request for blocks that none of the connected peers serves stays queued before it
+/// is rejected.
+const UNSERVABLE_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Manages data fetching operations.
 ///
 /// This type is hooked into the staged sync pipeline and delegates download request to available
@@ -51,6 +68,11 @@ pub struct StateFetcher {
     download_requests_rx: UnboundedReceiverStream<DownloadRequest>,
     /// Sender for download requests, used to detach a [`FetchClient`]
     download_requests_tx: UnboundedSender<DownloadRequest>,
+    /// Metrics for the requests sent to peers.
+    metrics: StateFetcherMetrics,
+    /// Fires when the oldest queued request for blocks that none of the connected peers serves
+    /// reaches the [`UNSERVABLE_REQUEST_TIMEOUT`].
+    unservable_request_timer: Option<Pin<Box<Sleep>>>,
 }
 
 // === impl StateSyncer ===
@@ -67,16 +89,21 @@ impl StateFetcher {
             queued_requests: Default::default(),
             download_requests_rx: UnboundedReceiverStream::new(download_requests_rx),
             download_requests_tx,
+            metrics: Default::default(),
+            unservable_request_timer: None,
         }
     }
 
     /// Invoked when connected to a new peer.
+    ///
+    /// The given request stats are the ones recorded for the peer in previous sessions.
     pub(crate) fn new_active_peer(
         &mut self,
         peer_id: PeerId,
         best_hash: B256,
         best_number: u64,
         timeout: Arc<AtomicU64>,
+        request_stats: PeerRequestStats,
     ) {
         self.peers.insert(
             peer_id,
@@ -86,10 +113,28 @@ impl StateFetcher {
                 best_number,
                 timeout,
                 last_response_likely_bad: false,
+                request_stats,
+                request_started: None,
+                earliest_block: 0,
+                announced_earliest_block: false,
+                requested_range: None,
             },
         );
     }
 
+    /// Updates the earliest block the peer can serve, as announced by the peer.
+    pub(crate) fn update_peer_earliest_block(&mut self, peer_id: &PeerId, earliest: BlockNumber) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.earliest_block = earliest;
+            peer.announced_earliest_block = true;
+        }
+    }
+
+    /// Returns the request stats recorded for the peer, if it is connected.
+    pub(crate) fn request_stats(&self, peer_id: &PeerId) -> Option<PeerRequestStats> {
+        self.peers.get(peer_id).map(|peer| peer.request_stats)
+    }
+
     /// Removes the peer from the peer list, after which it is no longer available for future
     /// requests.
     ///
@@ -127,11 +172,12 @@ impl StateFetcher {
         }
     }
 
-    /// Returns the _next_ idle peer that's ready to accept a request,
-    /// prioritizing those with the lowest timeout/latency and those that recently responded with
-    /// adequate data.
-    fn next_best_peer(&self) -> Option<PeerId> {
-        let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
+    /// Returns the _next_ idle peer that's ready to accept a request for the given range of
+    /// blocks, prioritizing those with the lowest timeout/latency and those that recently
+    /// responded with adequate data.
+    fn next_best_peer(&self, range: Option<&RangeInclusive<BlockNumber>>) -> Option<PeerId> {
+        let mut idle =
+            self.peers.iter().filter(|(_, peer)| peer.state.is_idle() && peer.can_serve(range));
 
         let mut best_peer = idle.next()?;
 
@@ -153,21 +199,100 @@ impl StateFetcher {
         Some(*best_peer.0)
     }
 
+    /// Returns the idle peer with the lowest average response latency, considering only peers
+    /// that answered enough requests reliably.
+    ///
+    /// Used for high priority requests, for which the measured latency is a better predictor
+    /// than the current timeout.
+    fn next_fastest_peer(&self, range: Option<&RangeInclusive<BlockNumber>>) -> Option<PeerId> {
+        self.peers
+            .iter()
+            .filter(|(_, peer)| {
+                peer.state.is_idle() &&
+                    !peer.last_response_likely_bad &&
+                    peer.is_reliable() &&
+                    peer.can_serve(range)
+            })
+            .min_by_key(|(_, peer)| peer.request_stats.avg_latency_ms)
+            .map(|(peer_id, _)| *peer_id)
+    }
+
     /// Returns the next action to return
+    ///
+    /// Requests for blocks that none of the idle peers can serve stay queued, so they don't hold
+    /// up the requests behind them. If none of the connected peers serves these blocks for
+    /// [`UNSERVABLE_REQUEST_TIMEOUT`], the request is rejected.
     fn poll_action(&mut self) -> PollAction {
+        self.reject_unservable_requests();
+
         // we only check and not pop here since we don't know yet whether a peer is available.
         if self.queued_requests.is_empty() {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
+        let next = self.queued_requests.iter().enumerate().find_map(|(idx, req)| {
+            let range = req.block_range();
+            let peer_id = if req.get_priority().is_high() {
+                self.next_fastest_peer(range).or_else(|| self.next_best_peer(range))
+            } else {
+                self.next_best_peer(range)
+            };
+            peer_id.map(|peer_id| (idx, peer_id))
+        });
+        let Some((idx, peer_id)) = next else { return PollAction::NoPeersAvailable };
 
-        let request = self.queued_requests.pop_front().expect("not empty");
+        let request = self.queued_requests.remove(idx).expect("exists");
         let request = self.prepare_block_request(peer_id, request);
 
         PollAction::Ready(FetchAction::BlockRequest { peer_id, request })
     }
 
+    /// Rejects the queued requests that waited [`UNSERVABLE_REQUEST_TIMEOUT`] for blocks that none
+    /// of the connected peers serves.
+    fn reject_unservable_requests(&mut self) {
+        let now = Instant::now();
+        let mut idx = 0;
+        while let Some(req) = self.queued_requests.get(idx) {
+            let unservable =
+                req.queued_at().is_some_and(|queued_at| {
+                    now.duration_since(queued_at) >= UNSERVABLE_REQUEST_TIMEOUT
+                }) && !self.peers.values().any(|peer| peer.can_serve(req.block_range()));
+            if unservable {
+                let req = self.queued_requests.remove(idx).expect("exists");
+                req.send_err_response(RequestError::Timeout);
+                self.metrics.unservable_requests.increment(1);
+            } else {
+                idx += 1;
+            }
+        }
+    }
+
+    /// Polls the timer of the oldest queued request for blocks that none of the connected peers
+    /// serves, so the fetcher is woken up to reject the request once it times out.
+    fn poll_unservable_request_timer(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let deadline = self
+            .queued_requests
+            .iter()
+            .filter(|req| !self.peers.values().any(|peer| peer.can_serve(req.block_range())))
+            .filter_map(|req| req.queued_at())
+            .min()
+            .map(|queued_at| {
+                tokio::time::Instant::from_std(queued_at + UNSERVABLE_REQUEST_TIMEOUT)
+            });
+        let Some(deadline) = deadline else {
+            self.unservable_request_timer = None;
+            return Poll::Pending
+        };
+
+        let timer = self
+            .unservable_request_timer
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+        if timer.deadline() != deadline {
+            timer.as_mut().reset(deadline);
+        }
+        timer.as_mut().poll(cx)
+    }
+
     /// Advance the state the syncer
     pub(crate) fn poll(&mut self, cx: &mut Context<'_>) -> Poll<FetchAction> {
         // drain buffered actions first
@@ -204,6 +329,9 @@ impl StateFetcher {
             }
 
             if self.queued_requests.is_empty() || no_peers_available {
+                if self.poll_unservable_request_timer(cx).is_ready() {
+                    continue
+                }
                 return Poll::Pending
             }
         }
@@ -216,6 +344,8 @@ impl StateFetcher {
         // update the peer's state
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             peer.state = req.peer_state();
+            peer.request_started = Some(Instant::now());
+            peer.requested_range = req.block_range().cloned().zip(req.queued_at());
         }
 
         match req {
@@ -238,11 +368,14 @@ impl StateFetcher {
         }
     }
 
-    /// Returns a new followup request for the peer.
+    /// Returns a new followup request for the peer, skipping the queued requests for blocks the
+    /// peer can't serve.
     ///
     /// Caution: this expects that the peer is _not_ closed.
     fn followup_request(&mut self, peer_id: PeerId) -> Option<BlockResponseOutcome> {
-        let req = self.queued_requests.pop_front()?;
+        let peer = self.peers.get(&peer_id)?;
+        let idx = self.queued_requests.iter().position(|req| peer.can_serve(req.block_range()))?;
+        let req = self.queued_requests.remove(idx)?;
         let req = self.prepare_block_request(peer_id, req);
         Some(BlockResponseOutcome::Request(peer_id, req))
     }
@@ -275,6 +408,7 @@ impl StateFetcher {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            peer.on_response(!is_error && !is_likely_bad_response, &self.metrics);
 
             // If the peer is still ready to accept new requests, we try to send a followup
             // request immediately.
@@ -290,19 +424,55 @@ impl StateFetcher {
     }
 
     /// Called on a `GetBlockBodies` response from a peer
+    ///
+    /// If the peer announced the earliest block it serves, an empty response to a request for
+    /// blocks below the peer's best block means the peer no longer serves these blocks. The request
+    /// is queued again for a peer that still serves them, instead of handing the empty response to
+    /// the requester, which penalizes empty responses. The request keeps its original queue time,
+    /// so it still runs into the [`UNSERVABLE_REQUEST_TIMEOUT`] if no peer serves the blocks.
     pub(crate) fn on_block_bodies_response(
         &mut self,
         peer_id: PeerId,
         res: RequestResult<Vec<BlockBody>>,
     ) -> Option<BlockResponseOutcome> {
+        let is_empty_response = res.as_ref().is_ok_and(|bodies| bodies.is_empty());
         let is_likely_bad_response = res.as_ref().map_or(true, |bodies| bodies.is_empty());
 
-        if let Some(resp) = self.inflight_bodies_requests.remove(&peer_id) {
+        let resp = self.inflight_bodies_requests.remove(&peer_id);
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            let expires_history = is_empty_response && peer.announced_earliest_block;
+            let expired_range = peer
+                .requested_range
+                .take()
+                .filter(|(range, _)| expires_history && *range.end() < peer.best_number);
+            if let Some((range, queued_at)) = expired_range {
+                // the peer expired the history, which is not held against it
+                peer.earliest_block = peer.earliest_block.max(range.end() + 1);
+                peer.request_started = None;
+                self.metrics.expired_history_responses.increment(1);
+                if let Some(resp) = resp {
+                    self.queued_requests.push_front(DownloadRequest::GetBlockBodies {
+                        request: resp.request,
+                        range: Some(range),
+                        response: resp.response,
+                        priority: Priority::High,
+                        queued_at,
+                    });
+                }
+                if peer.state.on_request_finished() {
+                    return self.followup_request(peer_id)
+                }
+                return None
+            }
+        }
+
+        if let Some(resp) = resp {
             let _ = resp.response.send(res.map(|b| (peer_id, b).into()));
         }
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             // update the peer's response state
             peer.last_response_likely_bad = is_likely_bad_response;
+            peer.on_response(!is_likely_bad_response, &self.metrics);
 
             if peer.state.on_request_finished() && !is_likely_bad_response {
                 return self.followup_request(peer_id)
@@ -346,12 +516,54 @@ struct Peer {
     /// downloaded), but we still want to avoid requesting from the same peer again if it has the
     /// lowest timeout.
     last_response_likely_bad: bool,
+    /// Statistics about the requests sent to the peer, including previous sessions.
+    request_stats: PeerRequestStats,
+    /// When the inflight request was sent to the peer.
+    request_started: Option<Instant>,
+    /// The earliest block the peer can serve, either announced by the peer or learned from its
+    /// responses.
+    earliest_block: BlockNumber,
+    /// Whether the peer announced the earliest block it serves, in its `eth/69` status or a
+    /// `BlockRangeUpdate`.
+    announced_earliest_block: bool,
+    /// The range of blocks requested by the inflight request, if known, and when the request was
+    /// queued.
+    requested_range: Option<(RangeInclusive<BlockNumber>, Instant)>,
 }
 
 impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns `true` if the peer can serve the given range of blocks, which is assumed for
+    /// requests without a known range.
+    fn can_serve(&self, range: Option<&RangeInclusive<BlockNumber>>) -> bool {
+        range.map_or(true, |range| *range.start() >= self.earliest_block)
+    }
+
+    /// Returns `true` if the peer answered enough requests, and most of them successfully, for
+    /// its average latency to be meaningful.
+    fn is_reliable(&self) -> bool {
+        self.request_stats.successful_requests >= MIN_REQUESTS_FOR_LATENCY &&
+            self.request_stats
+                .success_rate()
+                .is_some_and(|rate| rate >= MIN_SUCCESS_RATE_FOR_PRIORITY)
+    }
+
+    /// Records the outcome of the inflight request, if any.
+    fn on_response(&mut self, success: bool, metrics: &StateFetcherMetrics) {
+        let Some(started) = self.request_started.take() else { return };
+        if success {
+            let latency = started.elapsed();
+            self.request_stats.on_success(latency);
+            metrics.successful_requests.increment(1);
+            metrics.request_latency.record(latency.as_secs_f64());
+        } else {
+            self.request_stats.on_failure();
+            metrics.failed_requests.increment(1);
+        }
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -412,8 +624,15 @@ pub(crate) enum DownloadRequest {
     /// Download the requested headers and send response through channel
     GetBlockBodies {
         request: Vec<B256>,
+        /// The range of the requested blocks, if known.
+        ///
+        /// The blob sidecars are part of the bodies, so they are routed by the same range. Receipts
+        /// are not downloaded from peers.
+        range: Option<RangeInclusive<BlockNumber>>,
         response: oneshot::Sender<PeerRequestResult<Vec<BlockBody>>>,
         priority: Priority,
+        /// When the request was queued.
+        queued_at: Instant,
     },
 }
 
@@ -428,6 +647,30 @@ impl DownloadRequest {
         }
     }
 
+    /// Returns the range of blocks this request is for, if known.
+    const fn block_range(&self) -> Option<&RangeInclusive<BlockNumber>> {
+        match self {
+            Self::GetBlockHeaders { .. } => None,
+            Self::GetBlockBodies { range, .. } => range.as_ref(),
+        }
+    }
+
+    /// Returns when the request was queued, if it is a request for a range of blocks.
+    const fn queued_at(&self) -> Option<Instant> {
+        match self {
+            Self::GetBlockHeaders { .. } => None,
+            Self::GetBlockBodies { queued_at, .. } => Some(*queued_at),
+        }
+    }
+
+    /// Sends the error as the response of the request.
+    fn send_err_response(self, err: RequestError) {
+        let _ = match self {
+            Self::GetBlockHeaders { response, .. } => response.send(Err(err)),
+            Self::GetBlockBodies { response, .. } => response.send(Err(err)),
+        };
+    }
+
     /// Returns the requested priority of this request
     const fn get_priority(&self) -> &Priority {
         match self {
@@ -482,8 +725,10 @@ mod tests {
             let (tx, _rx) = oneshot::channel();
             fetcher.queued_requests.push_back(DownloadRequest::GetBlockBodies {
                 request: vec![],
+                range: None,
                 response: tx,
                 priority: Priority::default(),
+                queued_at: Instant::now(),
             });
             assert!(fetcher.poll(cx).is_pending());
 
@@ -499,20 +744,32 @@ mod tests {
         // Add a few random peers
         let peer1 = B512::random();
         let peer2 = B512::random();
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)));
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(1)),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            Arc::new(AtomicU64::new(1)),
+            Default::default(),
+        );
 
-        let first_peer = fetcher.next_best_peer().unwrap();
+        let first_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         // Pending disconnect for first_peer
         fetcher.on_pending_disconnect(&first_peer);
         // first_peer now isn't idle, so we should get other peer
-        let second_peer = fetcher.next_best_peer().unwrap();
+        let second_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         assert_ne!(first_peer, second_peer);
         // without idle peers, returns None
         fetcher.on_pending_disconnect(&second_peer);
-        assert_eq!(fetcher.next_best_peer(), None);
+        assert_eq!(fetcher.next_best_peer(None), None);
     }
 
     #[tokio::test]
@@ -526,18 +783,338 @@ mod tests {
 
         let peer2_timeout = Arc::new(AtomicU64::new(300));
 
-        fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(30)));
-        fetcher.new_active_peer(peer2, B256::random(), 2, Arc::clone(&peer2_timeout));
-        fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)));
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(30)),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            Arc::clone(&peer2_timeout),
+            Default::default(),
+        );
+        fetcher.new_active_peer(
+            peer3,
+            B256::random(),
+            3,
+            Arc::new(AtomicU64::new(50)),
+            Default::default(),
+        );
 
         // Must always get peer1 (lowest timeout)
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
         // peer2's timeout changes below peer1's
         peer2_timeout.store(10, Ordering::Relaxed);
         // Then we get peer 2 always (now lowest)
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_prefers_fast_peer() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer1 = B512::random();
+        let peer2 = B512::random();
+        let peer3 = B512::random();
+
+        let stats = |successful_requests, failed_requests, avg_latency_ms| PeerRequestStats {
+            successful_requests,
+            failed_requests,
+            avg_latency_ms,
+        };
+
+        // peer1 has the lowest timeout, but is slower than peer2
+        fetcher.new_active_peer(
+            peer1,
+            B256::random(),
+            1,
+            Arc::new(AtomicU64::new(10)),
+            stats(10, 0, 500),
+        );
+        fetcher.new_active_peer(
+            peer2,
+            B256::random(),
+            2,
+            Arc::new(AtomicU64::new(50)),
+            stats(10, 1, 100),
+        );
+        // peer3 is the fastest but unreliable
+        fetcher.new_active_peer(
+            peer3,
+            B256::random(),
+            3,
+            Arc::new(AtomicU64::new(50)),
+            stats(10, 10, 10),
+        );
+
+        let (tx, _rx) = oneshot::channel();
+        fetcher.queued_requests.push_back(DownloadRequest::GetBlockHeaders {
+            request: HeadersRequest { start: 0u64.into(), limit: 1, direction: Default::default() },
+            response: tx,
+            priority: Priority::High,
+        });
+        let (tx, _rx) = oneshot::channel();
+        fetcher.queued_requests.push_back(DownloadRequest::GetBlockBodies {
+            request: vec![],
+            range: None,
+            response: tx,
+            priority: Priority::Normal,
+            queued_at: Instant::now(),
+        });
+
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            unreachable!()
+        };
+        assert_eq!(peer_id, peer2);
+        assert!(fetcher.peers[&peer2].request_started.is_some());
+
+        // normal priority requests still go to the peer with the lowest timeout
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            unreachable!()
+        };
+        assert_eq!(peer_id, peer1);
+
+        // the response updates the stats of the peer
+        let outcome = fetcher.on_block_headers_response(peer2, Ok(vec![Header::default()]));
+        assert!(outcome.is_none());
+        let stats = fetcher.request_stats(&peer2).unwrap();
+        assert_eq!(stats.successful_requests, 11);
+        assert!(fetcher.peers[&peer2].request_started.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bodies_routed_to_peers_serving_range() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let pruned = B512::random();
+        let archive = B512::random();
+
+        // the pruned peer has the lower timeout, but expired the blocks before 50
+        fetcher.new_active_peer(
+            pruned,
+            B256::random(),
+            100,
+            Arc::new(AtomicU64::new(10)),
+            Default::default(),
+        );
+        fetcher.update_peer_earliest_block(&pruned, 50);
+        fetcher.new_active_peer(
+            archive,
+            B256::random(),
+            100,
+            Arc::new(AtomicU64::new(50)),
+            Default::default(),
+        );
+
+        let bodies_request = |range| {
+            let (tx, _rx) = oneshot::channel();
+            DownloadRequest::GetBlockBodies {
+                request: vec![B256::random()],
+                range: Some(range),
+                response: tx,
+                priority: Priority::Normal,
+                queued_at: Instant::now(),
+            }
+        };
+
+        fetcher.queued_requests.push_back(bodies_request(10..=20));
+        fetcher.queued_requests.push_back(bodies_request(60..=70));
+
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            unreachable!()
+        };
+        assert_eq!(peer_id, archive);
+        let PollAction::Ready(FetchAction::BlockRequest { peer_id, .. }) = fetcher.poll_action()
+        else {
+            unreachable!()
+        };
+        assert_eq!(peer_id, pruned);
+
+        // a request for old blocks is neither sent as followup to the pruned peer nor dispatched
+        // to it once idle
+        fetcher.queued_requests.push_back(bodies_request(10..=20));
+        assert_eq!(fetcher.on_block_bodies_response(pruned, Ok(vec![BlockBody::default()])), None);
+        assert!(fetcher.peers[&pruned].state.is_idle());
+        assert!(matches!(fetcher.poll_action(), PollAction::NoPeersAvailable));
+        assert_eq!(fetcher.queued_requests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_empty_bodies_response_learns_expired_range() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer_id = B512::random();
+        fetcher.new_active_peer(
+            peer_id,
+            B256::random(),
+            100,
+            Arc::new(AtomicU64::new(10)),
+            Default::default(),
+        );
+        fetcher.update_peer_earliest_block(&peer_id, 0);
+
+        let (tx, mut rx) = oneshot::channel();
+        let queued_at = Instant::now();
+        fetcher.queued_requests.push_back(DownloadRequest::GetBlockBodies {
+            request: vec![B256::random()],
+            range: Some(10..=20),
+            response: tx,
+            priority: Priority::Normal,
+            queued_at,
+        });
+        assert!(matches!(fetcher.poll_action(), PollAction::Ready(_)));
+
+        assert_eq!(fetcher.on_block_bodies_response(peer_id, Ok(vec![])), None);
+        let peer = &fetcher.peers[&peer_id];
+        assert_eq!(peer.earliest_block, 21);
+        assert!(!peer.last_response_likely_bad);
+        assert_eq!(peer.request_stats.failed_requests, 0);
+        assert!(peer.state.is_idle());
+        assert!(!peer.can_serve(Some(&(10..=20))));
+        assert!(peer.can_serve(Some(&(21..=30))));
+
+        // the empty response is not handed to the requester, the request waits for another peer
+        assert!(rx.try_recv().is_err());
+        assert_eq!(fetcher.queued_requests.len(), 1);
+        assert_eq!(fetcher.queued_requests[0].block_range(), Some(&(10..=20)));
+        assert_eq!(fetcher.queued_requests[0].queued_at(), Some(queued_at));
+        assert!(fetcher.queued_requests[0].get_priority().is_high());
+
+        // an empty response for blocks the peer may not have yet is handed to the requester
+        let (tx, mut rx) = oneshot::channel();
+        fetcher.queued_requests.push_front(DownloadRequest::GetBlockBodies {
+            request: vec![B256::random()],
+            range: Some(90..=100),
+            response: tx,
+            priority: Priority::Normal,
+            queued_at: Instant::now(),
+        });
+        assert!(matches!(fetcher.poll_action(), PollAction::Ready(_)));
+        assert_eq!(fetcher.on_block_bodies_response(peer_id, Ok(vec![])), None);
+        assert!(rx.try_recv().unwrap().unwrap().1.is_empty());
+        assert!(fetcher.peers[&peer_id].last_response_likely_bad);
+    }
+
+    #[tokio::test]
+    async fn test_empty_bodies_response_without_announced_range() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer_id = B512::random();
+        fetcher.new_active_peer(
+            peer_id,
+            B256::random(),
+            100,
+            Arc::new(AtomicU64::new(10)),
+            Default::default(),
+        );
+
+        let (tx, mut rx) = oneshot::channel();
+        fetcher.queued_requests.push_back(DownloadRequest::GetBlockBodies {
+            request: vec![B256::random()],
+            range: Some(10..=20),
+            response: tx,
+            priority: Priority::Normal,
+            queued_at: Instant::now(),
+        });
+        assert!(matches!(fetcher.poll_action(), PollAction::Ready(_)));
+
+        // the peer didn't announce a pruned history, so the empty response is a bad response
+        assert_eq!(fetcher.on_block_bodies_response(peer_id, Ok(vec![])), None);
+        assert!(rx.try_recv().unwrap().unwrap().1.is_empty());
+        let peer = &fetcher.peers[&peer_id];
+        assert_eq!(peer.earliest_block, 0);
+        assert!(peer.last_response_likely_bad);
+        assert_eq!(peer.request_stats.failed_requests, 1);
+        assert!(fetcher.queued_requests.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unservable_bodies_request_rejected() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer_id = B512::random();
+        fetcher.new_active_peer(
+            peer_id,
+            B256::random(),
+            100,
+            Arc::new(AtomicU64::new(10)),
+            Default::default(),
+        );
+        fetcher.update_peer_earliest_block(&peer_id, 50);
+
+        let bodies_request = |queued_at| {
+            let (tx, rx) = oneshot::channel();
+            let request = DownloadRequest::GetBlockBodies {
+                request: vec![B256::random()],
+                range: Some(10..=20),
+                response: tx,
+                priority: Priority::Normal,
+                queued_at,
+            };
+            (request, rx)
+        };
+
+        // a recent request waits for a peer that serves the blocks
+        let (request, mut rx) = bodies_request(Instant::now());
+        fetcher.queued_requests.push_back(request);
+        assert!(matches!(fetcher.poll_action(), PollAction::NoPeersAvailable));
+        assert!(rx.try_recv().is_err());
+        fetcher.queued_requests.clear();
+
+        // a request that waited too long is rejected
+        let Some(queued_at) = Instant::now().checked_sub(UNSERVABLE_REQUEST_TIMEOUT) else {
+            return
+        };
+        let (request, mut rx) = bodies_request(queued_at);
+        fetcher.queued_requests.push_back(request);
+        assert!(matches!(fetcher.poll_action(), PollAction::NoRequests));
+        assert!(matches!(rx.try_recv(), Ok(Err(RequestError::Timeout))));
+    }
+
+    #[tokio::test]
+    async fn test_unservable_bodies_request_rejected_without_new_events() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher = StateFetcher::new(manager.handle(), Default::default());
+        let peer_id = B512::random();
+        fetcher.new_active_peer(
+            peer_id,
+            B256::random(),
+            100,
+            Arc::new(AtomicU64::new(10)),
+            Default::default(),
+        );
+        fetcher.update_peer_earliest_block(&peer_id, 50);
+
+        let Some(queued_at) =
+            Instant::now().checked_sub(UNSERVABLE_REQUEST_TIMEOUT - Duration::from_millis(100))
+        else {
+            return
+        };
+        let (tx, rx) = oneshot::channel();
+        fetcher.queued_requests.push_back(DownloadRequest::GetBlockBodies {
+            request: vec![B256::random()],
+            range: Some(10..=20),
+            response: tx,
+            priority: Priority::Normal,
+            queued_at,
+        });
+
+        // the fetcher is only polled again if it is woken up
+        tokio::spawn(poll_fn(move |cx| {
+            while fetcher.poll(cx).is_ready() {}
+            Poll::<()>::Pending
+        }));
+        let response = tokio::time::timeout(Duration::from_secs(5), rx).await;
+        assert!(matches!(response, Ok(Ok(Err(RequestError::Timeout)))));
     }
 
     #[tokio::test]
@@ -596,6 +1173,7 @@ mod tests {
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
         );
 
         let (req, header) = request_pair();
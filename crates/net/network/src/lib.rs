@@ -114,6 +114,7 @@
 /// Common helpers for network testing.
 pub mod test_utils;
 
+pub mod bsc;
 mod budget;
 mod builder;
 pub mod cache;
@@ -132,6 +133,7 @@ mod network;
 pub mod peers;
 pub mod protocol;
 mod session;
+pub mod snap;
 mod state;
 mod swarm;
 pub mod transactions;
@@ -155,6 +157,6 @@ pub use flattened_response::FlattenedResponse;
 pub use manager::DiscoveredEvent;
 pub use metrics::TxTypesCounter;
 pub use reth_eth_wire::{DisconnectReason, HelloMessageWithProtocols};
-pub use reth_network_types::{PeersConfig, SessionsConfig};
+pub use reth_network_types::{PeeringMode, PeersConfig, SessionsConfig};
 pub use session::EthRlpxConnection;
 pub use swarm::NetworkConnectionState;
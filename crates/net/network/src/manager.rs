@@ -20,7 +20,7 @@ use crate::{
     config::NetworkConfig,
     discovery::Discovery,
     error::{NetworkError, ServiceKind},
-    eth_requests::IncomingEthRequest,
+    eth_requests::{EthRequestLimits, IncomingEthRequest},
     import::{BlockImport, BlockImportOutcome, BlockValidation},
     listener::ConnectionListener,
     message::{
@@ -34,7 +34,7 @@ use crate::{
     peers::{PeerAddr, PeersHandle, PeersManager},
     poll_nested_stream_with_budget,
     protocol::IntoRlpxSubProtocol,
-    session::SessionManager,
+    session::{ActiveSessionHandle, SessionManager},
     state::NetworkState,
     swarm::{Swarm, SwarmEvent},
     transactions::NetworkTransactionEvent,
@@ -44,7 +44,7 @@ use futures::{Future, StreamExt};
 use parking_lot::Mutex;
 use reth_eth_wire::{
     capability::{Capabilities, CapabilityMessage},
-    DisconnectReason, EthVersion, Status,
+    BlockRangeUpdate, DisconnectReason, EthVersion, Status,
 };
 use reth_fs_util::{self as fs, FsPathError};
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
@@ -108,6 +108,9 @@ pub struct NetworkManager {
     /// requests. This channel size is set at
     /// [`ETH_REQUEST_CHANNEL_CAPACITY`](crate::builder::ETH_REQUEST_CHANNEL_CAPACITY)
     to_eth_request_handler: Option<mpsc::Sender<IncomingEthRequest>>,
+    /// Limits on the bytes served by the
+    /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler) of this network.
+    eth_request_limits: EthRequestLimits,
     to_engine: Option<UnboundedMeteredSender<EngineMessage>>,
     /// Tracks the number of active session (connected peers).
     ///
@@ -135,6 +138,12 @@ impl NetworkManager {
         self.to_eth_request_handler = Some(tx);
     }
 
+    /// Returns the configured limits on the bytes served by the
+    /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler).
+    pub const fn eth_request_limits(&self) -> EthRequestLimits {
+        self.eth_request_limits
+    }
+
     /// Adds an additional protocol handler to the `RLPx` sub-protocol list.
     pub fn add_rlpx_sub_protocol(&mut self, protocol: impl IntoRlpxSubProtocol) {
         self.swarm.add_rlpx_sub_protocol(protocol)
@@ -194,10 +203,17 @@ impl NetworkManager {
             dns_discovery_config,
             extra_protocols,
             tx_gossip_disabled,
+            inbound_tx_gossip_disabled,
             transactions_manager_config: _,
+            eth_request_limits,
+            snap,
+            bsc,
         } = config;
 
+        let metrics = NetworkMetrics::default();
+        metrics.restored_peer_states.set(peers_config.peer_states.len() as f64);
         let peers_manager = PeersManager::new(peers_config);
+        metrics.banned_peers.set(peers_manager.num_banned_peers() as f64);
         let peers_handle = peers_manager.handle();
 
         let incoming = ConnectionListener::bind(listener_addr).await.map_err(|err| {
@@ -228,6 +244,7 @@ impl NetworkManager {
             discovery_v4_config,
             discovery_v5_config,
             dns_discovery_config,
+            fork_filter.clone(),
         )
         .await?;
         // need to retrieve the addr here since provided port could be `0`
@@ -236,11 +253,20 @@ impl NetworkManager {
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
+        // the range of blocks announced to `eth/69` peers
+        let latest = client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
+        let block_range = BlockRangeUpdate {
+            earliest: client.earliest_block_number().unwrap_or_default().min(latest),
+            latest,
+            latest_hash: status.blockhash,
+        };
+
         let sessions = SessionManager::new(
             secret_key,
             sessions_config,
             executor,
             status,
+            block_range,
             hello_message,
             fork_filter,
             extra_protocols,
@@ -273,8 +299,11 @@ impl NetworkManager {
             network_mode,
             Arc::new(AtomicU64::new(chain_spec.chain.id())),
             tx_gossip_disabled,
+            inbound_tx_gossip_disabled,
             discv4,
             event_sender.clone(),
+            snap,
+            bsc,
         );
 
         Ok(Self {
@@ -285,9 +314,10 @@ impl NetworkManager {
             event_sender,
             to_transactions_manager: None,
             to_eth_request_handler: None,
+            eth_request_limits,
             to_engine: Some(UnboundedMeteredSender::new(engine_task_tx, NETWORK_PEER_SCOPE)),
             num_active_peers,
-            metrics: Default::default(),
+            metrics,
             disconnect_metrics: Default::default(),
         })
     }
@@ -373,6 +403,21 @@ impl NetworkManager {
         Ok(())
     }
 
+    /// Collect the reputation, bans and request stats of the peers from the [`NetworkManager`]
+    /// and write them to the given `peer_reputations_file`.
+    ///
+    /// See also [`PeersConfig::with_peer_states_from_file`](crate::PeersConfig).
+    #[cfg(feature = "serde")]
+    pub fn write_peer_states_to_file(
+        &self,
+        peer_reputations_file: &Path,
+    ) -> Result<(), FsPathError> {
+        let peer_states = self.swarm.state().peers().persisted_peer_states();
+        peer_reputations_file.parent().map(fs::create_dir_all).transpose()?;
+        reth_fs_util::write_json_file(peer_reputations_file, &peer_states)?;
+        Ok(())
+    }
+
     /// Returns a new [`FetchClient`] that can be cloned and shared.
     ///
     /// The [`FetchClient`] is the entrypoint for sending requests to the network.
@@ -589,6 +634,9 @@ impl NetworkManager {
                     msg,
                 });
             }
+            PeerMessage::BlockRangeUpdate(range) => {
+                self.swarm.state_mut().on_block_range_update(peer_id, range);
+            }
             PeerMessage::SendTransactions(_) => {
                 unreachable!("Not emitted by session")
             }
@@ -670,9 +718,9 @@ impl NetworkManager {
                 let _ = tx.send(self.status());
             }
             NetworkHandleMessage::StatusUpdate { head } => {
-                if let Some(transition) = self.swarm.sessions_mut().on_status_update(head) {
-                    self.swarm.state_mut().update_fork_id(transition.current);
-                }
+                let earliest = self.swarm.state().earliest_block();
+                self.swarm.sessions_mut().on_status_update(head, earliest);
+                self.swarm.state_mut().on_status_update(head);
             }
             NetworkHandleMessage::GetPeerInfos(tx) => {
                 let _ = tx.send(self.get_peer_infos());
@@ -942,13 +990,7 @@ impl NetworkManager {
             .sessions()
             .active_sessions()
             .iter()
-            .filter_map(|(&peer_id, session)| {
-                self.swarm
-                    .state()
-                    .peers()
-                    .peer_by_id(peer_id)
-                    .map(|(record, kind)| session.peer_info(&record, kind))
-            })
+            .filter_map(|(&peer_id, session)| self.peer_info(peer_id, session))
             .collect()
     }
 
@@ -956,13 +998,19 @@ impl NetworkManager {
     ///
     /// Returns `None` if there's no active session to the peer.
     fn get_peer_info_by_id(&self, peer_id: PeerId) -> Option<PeerInfo> {
-        self.swarm.sessions().active_sessions().get(&peer_id).and_then(|session| {
-            self.swarm
-                .state()
-                .peers()
-                .peer_by_id(peer_id)
-                .map(|(record, kind)| session.peer_info(&record, kind))
-        })
+        self.swarm
+            .sessions()
+            .active_sessions()
+            .get(&peer_id)
+            .and_then(|session| self.peer_info(peer_id, session))
+    }
+
+    /// Returns the [`PeerInfo`] of the active session, including the peer's score.
+    fn peer_info(&self, peer_id: PeerId, session: &ActiveSessionHandle) -> Option<PeerInfo> {
+        let peers = self.swarm.state().peers();
+        let (record, kind) = peers.peer_by_id(peer_id)?;
+        let (reputation, request_stats) = peers.peer_score(&peer_id)?;
+        Some(session.peer_info(&record, kind, reputation, request_stats))
     }
 
     /// Returns [`PeerInfo`] for a given peers.
@@ -981,6 +1029,7 @@ impl NetworkManager {
         self.metrics
             .outgoing_connections
             .set(self.swarm.state().peers().num_outbound_connections() as f64);
+        self.metrics.banned_peers.set(self.swarm.state().peers().num_banned_peers() as f64);
     }
 
     /// Updates the metrics for pending connections
@@ -6,9 +6,9 @@
 use futures::FutureExt;
 use reth_eth_wire::{
     capability::RawCapabilityMessage, message::RequestPair, BlockBodies, BlockHashNumber,
-    BlockHeaders, EthMessage, GetBlockBodies, GetBlockHeaders, GetNodeData, GetPooledTransactions,
-    GetReceipts, NewBlock, NewBlockHashes, NewPooledTransactionHashes, NodeData,
-    PooledTransactions, Receipts, SharedTransactions, Transactions,
+    BlockHeaders, BlockRangeUpdate, EthMessage, GetBlockBodies, GetBlockHeaders, GetNodeData,
+    GetPooledTransactions, GetReceipts, NewBlock, NewBlockHashes, NewPooledTransactionHashes,
+    NodeData, PooledTransactions, Receipts, SharedTransactions, Transactions,
 };
 use reth_network_p2p::error::{RequestError, RequestResult};
 use reth_network_peers::PeerId;
@@ -56,6 +56,8 @@ pub enum PeerMessage {
     PooledTransactions(NewPooledTransactionHashes),
     /// All `eth` request variants.
     EthRequest(PeerRequest),
+    /// The range of blocks the peer can serve, announced by `eth/69` peers.
+    BlockRangeUpdate(BlockRangeUpdate),
     /// Other than eth namespace message
     Other(RawCapabilityMessage),
 }
@@ -25,6 +25,12 @@ pub struct NetworkMetrics {
     /// Number of peers known to the node
     pub(crate) tracked_peers: Gauge,
 
+    /// Number of currently banned peers
+    pub(crate) banned_peers: Gauge,
+
+    /// Number of peer states restored from the persisted peer reputations
+    pub(crate) restored_peer_states: Gauge,
+
     /// Cumulative number of failures of pending sessions
     pub(crate) pending_session_failures: Counter,
 
@@ -80,6 +86,23 @@ pub struct NetworkMetrics {
     pub(crate) acc_duration_poll_swarm: Gauge,
 }
 
+/// Metrics for the requests sent by the `StateFetcher`.
+#[derive(Metrics)]
+#[metrics(scope = "network.fetch")]
+pub struct StateFetcherMetrics {
+    /// Total number of requests answered with a valid response
+    pub(crate) successful_requests: Counter,
+    /// Total number of requests that failed or were answered with a bad response
+    pub(crate) failed_requests: Counter,
+    /// Latency in seconds of successful requests
+    pub(crate) request_latency: Histogram,
+    /// Total number of empty responses from peers that expired the requested blocks
+    pub(crate) expired_history_responses: Counter,
+    /// Total number of requests rejected because none of the connected peers serves the
+    /// requested blocks
+    pub(crate) unservable_requests: Counter,
+}
+
 /// Metrics for `SessionManager`
 #[derive(Metrics)]
 #[metrics(scope = "network")]
@@ -325,6 +348,9 @@ pub struct EthRequestHandlerMetrics {
     /// Number of `GetNodeData` requests received
     pub(crate) eth_node_data_requests_received_total: Counter,
 
+    /// Number of requests queued because a limit on the served bytes was exhausted
+    pub(crate) eth_requests_rate_limited_total: Counter,
+
     /// Duration in seconds of call to poll
     /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler).
     pub(crate) acc_duration_poll_eth_req_handler: Gauge,
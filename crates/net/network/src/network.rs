@@ -1,10 +1,12 @@
 use crate::{
+    bsc::BscHandle,
     config::NetworkMode,
     discovery::DiscoveryEvent,
     manager::NetworkEvent,
     message::{EngineMessage, PeerRequest},
     peers::{PeerAddr, PeersHandle},
     protocol::RlpxSubProtocol,
+    snap::SnapHandle,
     swarm::NetworkConnectionState,
     transactions::TransactionsHandle,
     FetchClient,
@@ -60,8 +62,11 @@ impl NetworkHandle {
         network_mode: NetworkMode,
         chain_id: Arc<AtomicU64>,
         tx_gossip_disabled: bool,
+        inbound_tx_gossip_disabled: bool,
         discv4: Option<Discv4>,
         event_sender: EventSender<NetworkEvent>,
+        snap: Option<SnapHandle>,
+        bsc: Option<BscHandle>,
     ) -> Self {
         let inner = NetworkInner {
             num_active_peers,
@@ -76,8 +81,11 @@ impl NetworkHandle {
             initial_sync_done: Arc::new(AtomicBool::new(false)),
             chain_id,
             tx_gossip_disabled,
+            inbound_tx_gossip_disabled,
             discv4,
             event_sender,
+            snap,
+            bsc,
         };
         Self { inner: Arc::new(inner) }
     }
@@ -114,6 +122,18 @@ impl NetworkHandle {
         rx.await
     }
 
+    /// Returns the handle to send `snap` requests to the connected peers, if the `snap` protocol
+    /// was installed with [`NetworkConfigBuilder::snap`](crate::NetworkConfigBuilder::snap).
+    pub fn snap(&self) -> Option<&SnapHandle> {
+        self.inner.snap.as_ref()
+    }
+
+    /// Returns the handle to gossip votes over the `bsc` protocol, if the `bsc` protocol was
+    /// installed with [`NetworkConfigBuilder::bsc`](crate::NetworkConfigBuilder::bsc).
+    pub fn bsc(&self) -> Option<&BscHandle> {
+        self.inner.bsc.as_ref()
+    }
+
     /// Returns the mode of the network, either pow, or pos
     pub fn mode(&self) -> &NetworkMode {
         &self.inner.network_mode
@@ -199,6 +219,11 @@ impl NetworkHandle {
         self.inner.tx_gossip_disabled
     }
 
+    /// Whether transactions gossiped by peers are ignored
+    pub fn inbound_tx_gossip_disabled(&self) -> bool {
+        self.inner.inbound_tx_gossip_disabled
+    }
+
     /// Returns the secret key used for authenticating sessions.
     pub fn secret_key(&self) -> &SecretKey {
         &self.inner.secret_key
@@ -416,10 +441,16 @@ struct NetworkInner {
     chain_id: Arc<AtomicU64>,
     /// Whether to disable transaction gossip
     tx_gossip_disabled: bool,
+    /// Whether to ignore transactions gossiped by peers
+    inbound_tx_gossip_disabled: bool,
     /// The instance of the discv4 service
     discv4: Option<Discv4>,
     /// Sender for high level network events.
     event_sender: EventSender<NetworkEvent>,
+    /// The handle of the installed `snap` protocol.
+    snap: Option<SnapHandle>,
+    /// The handle of the installed `bsc` protocol.
+    bsc: Option<BscHandle>,
 }
 
 /// Provides event subscription for the network.
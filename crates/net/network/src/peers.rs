@@ -8,7 +8,7 @@ use crate::{
 use futures::StreamExt;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_net_banlist::BanList;
-use reth_network_api::{PeerKind, ReputationChangeKind};
+use reth_network_api::{PeerKind, PeerRequestStats, Reputation, ReputationChangeKind};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
     peers::{
@@ -17,7 +17,7 @@ use reth_network_types::{
             is_banned_reputation, DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE,
         },
     },
-    ConnectionsConfig, PeersConfig, ReputationChangeWeights,
+    ConnectionsConfig, PeeringMode, PeersConfig, PersistedPeerState, ReputationChangeWeights,
 };
 use reth_primitives::ForkId;
 use std::{
@@ -129,6 +129,8 @@ pub struct PeersManager {
     max_backoff_count: u8,
     /// Tracks the connection state of the node
     net_connection_state: NetworkConnectionState,
+    /// The validators a sentry node protects, see [`PeeringMode::Sentry`].
+    protected_peer_ids: HashSet<PeerId>,
 }
 
 impl PeersManager {
@@ -141,10 +143,13 @@ impl PeersManager {
             ban_list,
             ban_duration,
             backoff_durations,
-            trusted_nodes,
-            trusted_nodes_only,
+            mut trusted_nodes,
+            mut trusted_nodes_only,
             basic_nodes,
             max_backoff_count,
+            peering_mode,
+            allow_list,
+            peer_states,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -152,6 +157,20 @@ impl PeersManager {
         // We use half of the interval to decrease the max duration to `150%` in worst case
         let unban_interval = ban_duration.min(backoff_durations.low) / 2;
 
+        // the peers of the allow-list are always connected, a validator doesn't talk to anyone else
+        let mut protected_peer_ids = HashSet::new();
+        match peering_mode {
+            PeeringMode::Default => {}
+            PeeringMode::Validator => {
+                trusted_nodes_only = true;
+                trusted_nodes.extend(allow_list);
+            }
+            PeeringMode::Sentry => {
+                protected_peer_ids.extend(allow_list.iter().map(|node| node.id));
+                trusted_nodes.extend(allow_list);
+            }
+        }
+
         let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
         let mut trusted_peer_ids = HashSet::with_capacity(trusted_nodes.len());
 
@@ -168,6 +187,22 @@ impl PeersManager {
             });
         }
 
+        let mut ban_list = ban_list;
+        for state in peer_states {
+            let ban_remaining = state.ban_remaining();
+            if let Some(remaining) = ban_remaining {
+                ban_list.ban_peer_until(state.peer_id, std::time::Instant::now() + remaining);
+            }
+            if let Some(peer) = peers.get_mut(&state.peer_id) {
+                peer.request_stats = state.request_stats;
+                // a banned reputation is only restored together with an unexpired ban, otherwise
+                // the peer would never be unbanned
+                if ban_remaining.is_some() || !is_banned_reputation(state.reputation) {
+                    peer.reputation = state.reputation;
+                }
+            }
+        }
+
         Self {
             peers,
             trusted_peer_ids,
@@ -186,6 +221,7 @@ impl PeersManager {
             last_tick: Instant::now(),
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
+            protected_peer_ids,
         }
     }
 
@@ -227,6 +263,63 @@ impl PeersManager {
         })
     }
 
+    /// Returns `true` if the peer is a validator protected by this sentry node.
+    pub(crate) fn is_protected_peer(&self, peer_id: &PeerId) -> bool {
+        self.protected_peer_ids.contains(peer_id)
+    }
+
+    /// Returns the reputation and request stats of the given peer.
+    pub(crate) fn peer_score(&self, peer_id: &PeerId) -> Option<(Reputation, PeerRequestStats)> {
+        self.peers.get(peer_id).map(|peer| (peer.reputation, peer.request_stats))
+    }
+
+    /// Returns the request stats recorded for the given peer.
+    pub(crate) fn request_stats(&self, peer_id: &PeerId) -> Option<PeerRequestStats> {
+        self.peers.get(peer_id).map(|peer| peer.request_stats)
+    }
+
+    /// Updates the request stats recorded for the given peer.
+    pub(crate) fn set_request_stats(&mut self, peer_id: &PeerId, request_stats: PeerRequestStats) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.request_stats = request_stats;
+        }
+    }
+
+    /// Returns the number of banned peers.
+    pub(crate) fn num_banned_peers(&self) -> usize {
+        self.ban_list.banned_peers().count()
+    }
+
+    /// Returns the reputation, unexpired ban and request stats of all peers that differ from the
+    /// ones of a newly discovered peer, so that they can be restored after a restart.
+    ///
+    /// Peers that are banned indefinitely are part of the configured ban list and are skipped.
+    pub(crate) fn persisted_peer_states(&self) -> Vec<PersistedPeerState> {
+        let mut states = self
+            .peers
+            .iter()
+            .map(|(peer_id, peer)| {
+                let state = PersistedPeerState {
+                    reputation: peer.reputation,
+                    request_stats: peer.request_stats,
+                    ..PersistedPeerState::new(*peer_id)
+                };
+                (*peer_id, state)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let now = std::time::Instant::now();
+        for (peer_id, until) in self.ban_list.banned_peers() {
+            let Some(remaining) = until.and_then(|until| until.checked_duration_since(now)) else {
+                continue
+            };
+            let state = states.remove(&peer_id).unwrap_or_else(|| PersistedPeerState::new(peer_id));
+            states.insert(peer_id, state.with_ban_remaining(remaining));
+        }
+
+        states.into_values().filter(|state| !state.is_default()).collect()
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -1064,6 +1157,8 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`reth_network_types::BackoffKind`].
     severe_backoff_counter: u8,
+    /// Statistics about the requests sent to the peer.
+    request_stats: PeerRequestStats,
 }
 
 // === impl Peer ===
@@ -1082,6 +1177,11 @@ impl Peer {
         self.reputation
     }
 
+    /// Returns the statistics about the requests sent to the peer
+    pub const fn request_stats(&self) -> &PeerRequestStats {
+        &self.request_stats
+    }
+
     fn with_state(addr: PeerAddr, state: PeerConnectionState) -> Self {
         Self {
             addr,
@@ -1092,6 +1192,7 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            request_stats: Default::default(),
         }
     }
 
@@ -1334,9 +1435,12 @@ mod tests {
         DisconnectReason,
     };
     use reth_net_banlist::BanList;
-    use reth_network_api::{Direction, ReputationChangeKind};
+    use reth_network_api::{Direction, PeerRequestStats, ReputationChangeKind};
     use reth_network_peers::PeerId;
-    use reth_network_types::{peers::reputation::DEFAULT_REPUTATION, BackoffKind};
+    use reth_network_types::{
+        peers::reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION},
+        BackoffKind, PersistedPeerState,
+    };
     use reth_primitives::B512;
     use std::{
         collections::HashSet,
@@ -1441,6 +1545,70 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_restore_peer_states() {
+        let banned = PeerId::random();
+        let slow = PeerId::random();
+        let expired = PeerId::random();
+        let unknown = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let basic_nodes = [banned, slow, expired]
+            .into_iter()
+            .map(|id| NodeRecord::new(socket_addr, id))
+            .collect::<HashSet<_>>();
+
+        let request_stats =
+            PeerRequestStats { successful_requests: 1, failed_requests: 9, avg_latency_ms: 5_000 };
+        let peer_states = vec![
+            PersistedPeerState {
+                reputation: BANNED_REPUTATION - 1,
+                ..PersistedPeerState::new(banned)
+            }
+            .with_ban_remaining(Duration::from_secs(60 * 60)),
+            PersistedPeerState {
+                reputation: DEFAULT_REPUTATION - 100,
+                request_stats,
+                ..PersistedPeerState::new(slow)
+            },
+            PersistedPeerState {
+                reputation: BANNED_REPUTATION - 1,
+                banned_until: Some(1),
+                ..PersistedPeerState::new(expired)
+            },
+            PersistedPeerState::new(unknown).with_ban_remaining(Duration::from_secs(60 * 60)),
+        ];
+
+        let config =
+            PeersConfig::test().with_basic_nodes(basic_nodes).with_peer_states(peer_states);
+        let peers = PeersManager::new(config);
+
+        assert!(peers.ban_list.is_banned_peer(&banned));
+        assert_eq!(peers.get_reputation(&banned), Some(BANNED_REPUTATION - 1));
+        assert!(peers.ban_list.is_banned_peer(&unknown));
+        assert_eq!(peers.num_banned_peers(), 2);
+
+        assert_eq!(peers.get_reputation(&slow), Some(DEFAULT_REPUTATION - 100));
+        assert_eq!(peers.request_stats(&slow), Some(request_stats));
+
+        // the ban expired while the node was offline
+        assert!(!peers.ban_list.is_banned_peer(&expired));
+        assert_eq!(peers.get_reputation(&expired), Some(DEFAULT_REPUTATION));
+
+        let mut persisted = peers.persisted_peer_states();
+        persisted.sort_by_key(|state| state.peer_id);
+        let mut expected = vec![banned, slow, unknown];
+        expected.sort();
+        assert_eq!(persisted.iter().map(|state| state.peer_id).collect::<Vec<_>>(), expected);
+        for state in persisted {
+            if state.peer_id == slow {
+                assert_eq!(state.ban_remaining(), None);
+                assert_eq!(state.request_stats, request_stats);
+            } else {
+                assert!(state.ban_remaining().is_some());
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_unban() {
         let peer = PeerId::random();
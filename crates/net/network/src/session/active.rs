@@ -14,7 +14,7 @@ use reth_eth_wire::{
     capability::Capabilities,
     errors::{EthHandshakeError, EthStreamError, P2PStreamError},
     message::{EthBroadcastMessage, RequestPair},
-    DisconnectP2P, DisconnectReason, EthMessage,
+    DisconnectP2P, DisconnectReason, EthMessage, EthVersion,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_p2p::error::RequestError;
@@ -170,14 +170,12 @@ impl ActiveSession {
         }
 
         match msg {
-            message @ EthMessage::Status(_) | message @ EthMessage::UpgradeStatus(_) => {
-                OnIncomingMessageOutcome::BadMessage {
-                    error: EthStreamError::EthHandshakeError(
-                        EthHandshakeError::StatusNotInHandshake,
-                    ),
-                    message,
-                }
-            }
+            message @ EthMessage::Status(_) |
+            message @ EthMessage::StatusEth69(_) |
+            message @ EthMessage::UpgradeStatus(_) => OnIncomingMessageOutcome::BadMessage {
+                error: EthStreamError::EthHandshakeError(EthHandshakeError::StatusNotInHandshake),
+                message,
+            },
             EthMessage::NewBlockHashes(msg) => {
                 self.try_emit_broadcast(PeerMessage::NewBlockHashes(msg)).into()
             }
@@ -235,6 +233,26 @@ impl ActiveSession {
             EthMessage::Receipts(resp) => {
                 on_response!(resp, GetReceipts)
             }
+            EthMessage::Receipts69(resp) => {
+                // converted by the stream already, but handled the same way for completeness
+                let resp = RequestPair {
+                    request_id: resp.request_id,
+                    message: resp.message.into_receipts(),
+                };
+                on_response!(resp, GetReceipts)
+            }
+            EthMessage::BlockRangeUpdate(msg) => {
+                if !msg.is_valid() {
+                    return OnIncomingMessageOutcome::BadMessage {
+                        error: EthStreamError::InvalidBlockRangeUpdate {
+                            earliest: msg.earliest,
+                            latest: msg.latest,
+                        },
+                        message: EthMessage::BlockRangeUpdate(msg),
+                    }
+                }
+                self.try_emit_broadcast(PeerMessage::BlockRangeUpdate(msg)).into()
+            }
         }
     }
 
@@ -272,6 +290,11 @@ impl ActiveSession {
             PeerMessage::SendTransactions(msg) => {
                 self.queued_outgoing.push_back(EthBroadcastMessage::Transactions(msg).into());
             }
+            PeerMessage::BlockRangeUpdate(msg) => {
+                if self.conn.version() >= EthVersion::Eth69 {
+                    self.queued_outgoing.push_back(EthMessage::BlockRangeUpdate(msg).into());
+                }
+            }
             PeerMessage::ReceivedTransaction(_) => {
                 unreachable!("Not emitted by network")
             }
@@ -826,6 +849,8 @@ mod tests {
 
                 let (p2p_stream, _) = UnauthedP2PStream::new(sink).handshake(hello).await.unwrap();
 
+                let mut status = status;
+                status.set_eth_version(p2p_stream.shared_capabilities().eth_version().unwrap());
                 let (client_stream, _) = UnauthedEthStream::new(p2p_stream)
                     .handshake(status, fork_filter)
                     .await
@@ -849,7 +874,9 @@ mod tests {
                 self.secret_key,
                 self.hello.clone(),
                 self.status,
+                Default::default(),
                 self.fork_filter.clone(),
+                None,
                 Default::default(),
             ));
 
@@ -3,6 +3,7 @@
 use futures::{Sink, Stream};
 use reth_ecies::stream::ECIESStream;
 use reth_eth_wire::{
+    bandwidth::BandwidthMeter,
     errors::EthStreamError,
     message::EthBroadcastMessage,
     multiplex::{ProtocolProxy, RlpxSatelliteStream},
@@ -10,6 +11,7 @@ use reth_eth_wire::{
 };
 use std::{
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 use tokio::net::TcpStream;
@@ -84,6 +86,15 @@ impl EthRlpxConnection {
             Self::Satellite(conn) => conn.primary_mut().start_send_broadcast(item),
         }
     }
+
+    /// Records the size of all `eth` messages received and sent with the given meter.
+    #[inline]
+    pub(crate) fn set_bandwidth_meter(&mut self, meter: Arc<BandwidthMeter>) {
+        match self {
+            Self::EthOnly(conn) => conn.set_bandwidth_meter(meter),
+            Self::Satellite(conn) => conn.primary_mut().set_bandwidth_meter(meter),
+        }
+    }
 }
 
 impl From<EthPeerConnection> for EthRlpxConnection {
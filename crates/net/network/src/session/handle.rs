@@ -7,11 +7,12 @@ use crate::{
 };
 use reth_ecies::ECIESError;
 use reth_eth_wire::{
+    bandwidth::BandwidthMeter,
     capability::{Capabilities, CapabilityMessage},
     errors::EthStreamError,
-    DisconnectReason, EthVersion, Status,
+    BlockRangeUpdate, DisconnectReason, EthVersion, Status,
 };
-use reth_network_api::{PeerInfo, PeerKind};
+use reth_network_api::{PeerInfo, PeerKind, PeerRequestStats, Reputation};
 use reth_network_peers::{NodeRecord, PeerId};
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
 use tokio::sync::{
@@ -75,6 +76,8 @@ pub struct ActiveSessionHandle {
     pub(crate) local_addr: Option<SocketAddr>,
     /// The Status message the peer sent for the `eth` handshake
     pub(crate) status: Arc<Status>,
+    /// Counts the bytes exchanged in the session.
+    pub(crate) bandwidth: Arc<BandwidthMeter>,
 }
 
 // === impl ActiveSessionHandle ===
@@ -136,7 +139,13 @@ impl ActiveSessionHandle {
     }
 
     /// Extracts the [`PeerInfo`] from the session handle.
-    pub(crate) fn peer_info(&self, record: &NodeRecord, kind: PeerKind) -> PeerInfo {
+    pub(crate) fn peer_info(
+        &self,
+        record: &NodeRecord,
+        kind: PeerKind,
+        reputation: Reputation,
+        request_stats: PeerRequestStats,
+    ) -> PeerInfo {
         PeerInfo {
             remote_id: self.remote_id,
             direction: self.direction,
@@ -150,6 +159,9 @@ impl ActiveSessionHandle {
             status: self.status.clone(),
             session_established: self.established,
             kind,
+            reputation,
+            request_stats,
+            bandwidth: self.bandwidth.stats(),
         }
     }
 }
@@ -175,6 +187,8 @@ pub enum PendingSessionEvent {
         capabilities: Arc<Capabilities>,
         /// The Status message the peer sent for the `eth` handshake
         status: Arc<Status>,
+        /// The range of blocks the peer announced during an `eth/69` handshake
+        block_range: Option<BlockRangeUpdate>,
         /// The actual connection stream which can be used to send and receive `eth` protocol
         /// messages
         conn: EthRlpxConnection,
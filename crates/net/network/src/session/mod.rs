@@ -5,15 +5,17 @@ use counter::SessionCounter;
 use futures::{future::Either, io, FutureExt, StreamExt};
 use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
+    bandwidth::{BandwidthMeter, BandwidthMetrics},
     capability::{Capabilities, CapabilityMessage},
+    capture::{CaptureHeader, CaptureWriter},
     errors::EthStreamError,
-    DisconnectReason, EthVersion, HelloMessageWithProtocols, Status, UnauthedEthStream,
-    UnauthedP2PStream,
+    BlockRangeUpdate, DisconnectReason, EthVersion, HelloMessageWithProtocols, Status,
+    UnauthedEthStream, UnauthedP2PStream,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_peers::PeerId;
 use reth_network_types::SessionsConfig;
-use reth_primitives::{ForkFilter, ForkId, ForkTransition, Head};
+use reth_primitives::{BlockNumber, ForkFilter, ForkId, ForkTransition, Head};
 use reth_tasks::TaskSpawner;
 use rustc_hash::FxHashMap;
 use secp256k1::SecretKey;
@@ -32,7 +34,7 @@ use tokio::{
 };
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::PollSender;
-use tracing::{debug, instrument, trace};
+use tracing::{debug, instrument, trace, warn};
 
 mod active;
 mod conn;
@@ -47,6 +49,10 @@ pub use handle::{
 };
 use reth_eth_wire::multiplex::RlpxProtocolMultiplexer;
 pub use reth_network_api::{Direction, PeerInfo};
+
+/// The number of blocks after which the updated block range is announced to `eth/69` peers.
+const BLOCK_RANGE_UPDATE_INTERVAL: u64 = 32;
+
 /// Internal identifier for active sessions.
 #[derive(Debug, Clone, Copy, PartialOrd, PartialEq, Eq, Hash)]
 pub struct SessionId(usize);
@@ -71,12 +77,18 @@ pub struct SessionManager {
     secret_key: SecretKey,
     /// The `Status` message to send to peers.
     status: Status,
+    /// The range of blocks we can serve, announced to `eth/69` peers.
+    block_range: BlockRangeUpdate,
+    /// The latest block of the last range announced to active `eth/69` sessions.
+    block_range_announced: u64,
     /// The `HelloMessage` message to send to peers.
     hello_message: HelloMessageWithProtocols,
     /// The [`ForkFilter`] used to validate the peer's `Status` message.
     fork_filter: ForkFilter,
     /// Size of the command buffer per session.
     session_command_buffer: usize,
+    /// Writes the messages of every session to the capture directory, if enabled.
+    capture_writer: Option<CaptureWriter>,
     /// The executor for spawned tasks.
     executor: Box<dyn TaskSpawner>,
     /// All pending session that are currently handshaking, exchanging `Hello`s.
@@ -104,6 +116,8 @@ pub struct SessionManager {
     extra_protocols: RlpxSubProtocols,
     /// Metrics for the session manager.
     metrics: SessionManagerMetrics,
+    /// Bandwidth metrics shared by the meters of all sessions.
+    bandwidth_metrics: Arc<BandwidthMetrics>,
 }
 
 // === impl SessionManager ===
@@ -116,6 +130,7 @@ impl SessionManager {
         config: SessionsConfig,
         executor: Box<dyn TaskSpawner>,
         status: Status,
+        block_range: BlockRangeUpdate,
         hello_message: HelloMessageWithProtocols,
         fork_filter: ForkFilter,
         extra_protocols: RlpxSubProtocols,
@@ -123,6 +138,13 @@ impl SessionManager {
         let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(config.session_event_buffer);
         let (active_session_tx, active_session_rx) = mpsc::channel(config.session_event_buffer);
         let active_session_tx = PollSender::new(active_session_tx);
+        let capture_writer = config.capture_dir.and_then(|dir| {
+            CaptureWriter::new(dir)
+                .inspect_err(
+                    |err| warn!(target: "net::session", %err, "Failed to start message capture"),
+                )
+                .ok()
+        });
 
         Self {
             next_id: 0,
@@ -132,9 +154,12 @@ impl SessionManager {
             pending_session_timeout: config.pending_session_timeout,
             secret_key,
             status,
+            block_range,
+            block_range_announced: block_range.latest,
             hello_message,
             fork_filter,
             session_command_buffer: config.session_command_buffer,
+            capture_writer,
             executor,
             pending_sessions: Default::default(),
             active_sessions: Default::default(),
@@ -144,6 +169,7 @@ impl SessionManager {
             active_session_rx: ReceiverStream::new(active_session_rx),
             extra_protocols,
             metrics: Default::default(),
+            bandwidth_metrics: Default::default(),
         }
     }
 
@@ -165,6 +191,11 @@ impl SessionManager {
         self.status
     }
 
+    /// Returns the range of blocks announced to `eth/69` peers.
+    pub const fn block_range(&self) -> BlockRangeUpdate {
+        self.block_range
+    }
+
     /// Returns the secret key used for authenticating sessions.
     pub const fn secret_key(&self) -> SecretKey {
         self.secret_key
@@ -204,14 +235,39 @@ impl SessionManager {
     ///
     /// If the updated activated another fork, this will return a [`ForkTransition`] and updates the
     /// active [`ForkId`]. See also [`ForkFilter::set_head`].
-    pub(crate) fn on_status_update(&mut self, head: Head) -> Option<ForkTransition> {
+    ///
+    /// The `earliest` block is the first one whose body and receipts are still available, it
+    /// moves forward as the node prunes its history. Active `eth/69` sessions are sent a
+    /// `BlockRangeUpdate` once the head moved by [`BLOCK_RANGE_UPDATE_INTERVAL`] blocks since the
+    /// last announcement.
+    pub(crate) fn on_status_update(
+        &mut self,
+        head: Head,
+        earliest: BlockNumber,
+    ) -> Option<ForkTransition> {
         self.status.blockhash = head.hash;
         self.status.total_difficulty = head.total_difficulty;
+        self.block_range.latest = head.number;
+        self.block_range.latest_hash = head.hash;
+        self.block_range.earliest = earliest.min(head.number);
+        if head.number.abs_diff(self.block_range_announced) >= BLOCK_RANGE_UPDATE_INTERVAL {
+            self.block_range_announced = head.number;
+            self.announce_block_range();
+        }
         let transition = self.fork_filter.set_head(head);
         self.status.forkid = self.fork_filter.current();
         transition
     }
 
+    /// Sends the current block range to all active `eth/69` sessions.
+    fn announce_block_range(&self) {
+        for session in self.active_sessions.values().filter(|s| s.version >= EthVersion::Eth69) {
+            let _ = session
+                .commands_to_session
+                .try_send(SessionCommand::Message(PeerMessage::BlockRangeUpdate(self.block_range)));
+        }
+    }
+
     /// An incoming TCP connection was received. This starts the authentication process to turn this
     /// stream into an active peer session.
     ///
@@ -237,7 +293,9 @@ impl SessionManager {
         let secret_key = self.secret_key;
         let hello_message = self.hello_message.clone();
         let status = self.status;
+        let block_range = self.block_range;
         let fork_filter = self.fork_filter.clone();
+        let capture_writer = self.capture_writer.clone();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
         self.spawn(pending_session_with_timeout(
             self.pending_session_timeout,
@@ -254,7 +312,9 @@ impl SessionManager {
                 secret_key,
                 hello_message,
                 status,
+                block_range,
                 fork_filter,
+                capture_writer,
                 extra_handlers,
             ),
         ));
@@ -279,6 +339,8 @@ impl SessionManager {
             let hello_message = self.hello_message.clone();
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
+            let block_range = self.block_range;
+            let capture_writer = self.capture_writer.clone();
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
             self.spawn(pending_session_with_timeout(
                 self.pending_session_timeout,
@@ -295,7 +357,9 @@ impl SessionManager {
                     secret_key,
                     hello_message,
                     status,
+                    block_range,
                     fork_filter,
+                    capture_writer,
                     extra_handlers,
                 ),
             ));
@@ -420,8 +484,9 @@ impl SessionManager {
                 local_addr,
                 peer_id,
                 capabilities,
-                conn,
+                mut conn,
                 status,
+                block_range,
                 direction,
                 client_id,
             } => {
@@ -465,6 +530,9 @@ impl SessionManager {
                 // negotiated version
                 let version = conn.version();
 
+                let bandwidth = Arc::new(BandwidthMeter::new(Arc::clone(&self.bandwidth_metrics)));
+                conn.set_bandwidth_meter(Arc::clone(&bandwidth));
+
                 let session = ActiveSession {
                     next_id: 0,
                     remote_peer_id: peer_id,
@@ -502,6 +570,7 @@ impl SessionManager {
                     client_version: Arc::clone(&client_version),
                     remote_addr,
                     local_addr,
+                    bandwidth,
                 };
 
                 self.active_sessions.insert(peer_id, handle);
@@ -518,6 +587,7 @@ impl SessionManager {
                     version,
                     capabilities,
                     status,
+                    block_range,
                     messages,
                     direction,
                     timeout,
@@ -613,6 +683,8 @@ pub enum SessionEvent {
         version: EthVersion,
         /// The Status message the peer sent during the `eth` handshake
         status: Arc<Status>,
+        /// The range of blocks the peer announced during an `eth/69` handshake
+        block_range: Option<BlockRangeUpdate>,
         /// The channel for sending messages to the peer with the session
         messages: PeerRequestSender,
         /// The direction of the session, either `Inbound` or `Outgoing`
@@ -765,7 +837,9 @@ pub(crate) async fn start_pending_incoming_session(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
+    capture_writer: Option<CaptureWriter>,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
     authenticate(
@@ -778,7 +852,9 @@ pub(crate) async fn start_pending_incoming_session(
         Direction::Incoming,
         hello,
         status,
+        block_range,
         fork_filter,
+        capture_writer,
         extra_handlers,
     )
     .await
@@ -796,7 +872,9 @@ async fn start_pending_outbound_session(
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
+    capture_writer: Option<CaptureWriter>,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
     let stream = match TcpStream::connect(remote_addr).await {
@@ -828,7 +906,9 @@ async fn start_pending_outbound_session(
         Direction::Outgoing(remote_peer_id),
         hello,
         status,
+        block_range,
         fork_filter,
+        capture_writer,
         extra_handlers,
     )
     .await
@@ -846,7 +926,9 @@ async fn authenticate(
     direction: Direction,
     hello: HelloMessageWithProtocols,
     status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
+    capture_writer: Option<CaptureWriter>,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
     let local_addr = stream.local_addr().ok();
@@ -875,7 +957,9 @@ async fn authenticate(
         direction,
         hello,
         status,
+        block_range,
         fork_filter,
+        capture_writer,
         extra_handlers,
     )
     .boxed();
@@ -927,14 +1011,16 @@ async fn authenticate_stream(
     direction: Direction,
     mut hello: HelloMessageWithProtocols,
     mut status: Status,
+    block_range: BlockRangeUpdate,
     fork_filter: ForkFilter,
+    capture_writer: Option<CaptureWriter>,
     mut extra_handlers: RlpxSubProtocolHandlers,
 ) -> PendingSessionEvent {
     // Add extra protocols to the hello message
     extra_handlers.retain(|handler| hello.try_add_protocol(handler.protocol()).is_ok());
 
     // conduct the p2p handshake and return the authenticated stream
-    let (p2p_stream, their_hello) = match stream.handshake(hello).await {
+    let (mut p2p_stream, their_hello) = match stream.handshake(hello).await {
         Ok(stream_res) => stream_res,
         Err(err) => {
             return PendingSessionEvent::Disconnected {
@@ -946,6 +1032,15 @@ async fn authenticate_stream(
         }
     };
 
+    if let Some(writer) = capture_writer {
+        let header = CaptureHeader::new(
+            their_hello.id,
+            their_hello.client_version.clone(),
+            p2p_stream.shared_capabilities(),
+        );
+        p2p_stream.set_capture(writer.start(header));
+    }
+
     // Ensure we negotiated mandatory eth protocol
     let eth_version = match p2p_stream.shared_capabilities().eth_version() {
         Ok(version) => version,
@@ -959,12 +1054,12 @@ async fn authenticate_stream(
         }
     };
 
-    let (conn, their_status) = if p2p_stream.shared_capabilities().len() == 1 {
+    let (conn, their_status, their_block_range) = if p2p_stream.shared_capabilities().len() == 1 {
         // if the hello handshake was successful we can try status handshake
         //
         // Before trying status handshake, set up the version to negotiated shared version
         status.set_eth_version(eth_version);
-        let eth_unauthed = UnauthedEthStream::new(p2p_stream);
+        let eth_unauthed = UnauthedEthStream::new(p2p_stream).with_block_range(Some(block_range));
         let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
             Ok(stream_res) => stream_res,
             Err(err) => {
@@ -976,7 +1071,8 @@ async fn authenticate_stream(
                 }
             }
         };
-        (eth_stream.into(), their_status)
+        let their_block_range = eth_stream.peer_block_range();
+        (eth_stream.into(), their_status, their_block_range)
     } else {
         // Multiplex the stream with the extra protocols
         let mut multiplex_stream = RlpxProtocolMultiplexer::new(p2p_stream);
@@ -992,20 +1088,23 @@ async fn authenticate_stream(
                 .ok();
         }
 
-        let (multiplex_stream, their_status) =
-            match multiplex_stream.into_eth_satellite_stream(status, fork_filter).await {
-                Ok((multiplex_stream, their_status)) => (multiplex_stream, their_status),
-                Err(err) => {
-                    return PendingSessionEvent::Disconnected {
-                        remote_addr,
-                        session_id,
-                        direction,
-                        error: Some(PendingSessionHandshakeError::Eth(err)),
-                    }
+        let (multiplex_stream, their_status) = match multiplex_stream
+            .into_eth_satellite_stream(status, Some(block_range), fork_filter)
+            .await
+        {
+            Ok((multiplex_stream, their_status)) => (multiplex_stream, their_status),
+            Err(err) => {
+                return PendingSessionEvent::Disconnected {
+                    remote_addr,
+                    session_id,
+                    direction,
+                    error: Some(PendingSessionHandshakeError::Eth(err)),
                 }
-            };
+            }
+        };
 
-        (multiplex_stream.into(), their_status)
+        let their_block_range = multiplex_stream.primary().peer_block_range();
+        (multiplex_stream.into(), their_status, their_block_range)
     };
 
     PendingSessionEvent::Established {
@@ -1015,6 +1114,7 @@ async fn authenticate_stream(
         peer_id: their_hello.id,
         capabilities: Arc::new(Capabilities::from(their_hello.capabilities)),
         status: Arc::new(their_status),
+        block_range: their_block_range,
         conn,
         direction,
         client_id: their_hello.client_version,
@@ -0,0 +1,504 @@
+//! Support for the `snap` protocol: <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+//!
+//! The protocol runs as an RLPx subprotocol next to `eth`. It is installed with
+//! [`NetworkConfigBuilder::snap`](crate::NetworkConfigBuilder::snap) and serves the hashed state of
+//! the recent canonical blocks to its peers. Requests to the connected peers are sent through the
+//! [`SnapHandle`], which implements the [`SnapClient`] used by the snap sync stage.
+
+use crate::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use futures::{stream::FuturesUnordered, Stream, StreamExt};
+use parking_lot::Mutex;
+use reth_eth_wire::{
+    capability::{Capability, SharedCapabilities},
+    multiplex::ProtocolConnection,
+    protocol::Protocol,
+    AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges, GetTrieNodes,
+    SnapMessage, SnapMessageId, StorageRanges, TrieNodes,
+};
+use reth_network_api::Direction;
+use reth_network_p2p::{
+    error::RequestError,
+    snap::client::{SnapClient, SnapFut},
+};
+use reth_network_peers::PeerId;
+use reth_primitives::{Bytes, BytesMut, B256};
+use reth_storage_api::SnapStateReader;
+use std::{
+    collections::HashMap,
+    fmt,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::{mpsc, oneshot},
+    task::JoinHandle,
+};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{debug, trace};
+
+mod server;
+use server::SnapRequestServer;
+
+/// The maximum number of requests of a peer that are served concurrently.
+///
+/// Further requests of the peer are only read from the connection once one of them is answered.
+const MAX_CONCURRENT_REQUESTS_PER_PEER: usize = 4;
+
+/// The time to wait for the response to a request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns the `snap/1` protocol.
+pub const fn snap_protocol() -> Protocol {
+    Protocol::new(Capability::new_static("snap", 1), SnapMessageId::max() + 1)
+}
+
+/// Error returned by the requests of the [`SnapHandle`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SnapRequestError {
+    /// The peer is not connected or doesn't support the `snap` protocol.
+    #[error("peer {0} is not connected over snap")]
+    PeerNotConnected(PeerId),
+    /// The connection closed before the response was received.
+    #[error("connection closed")]
+    ConnectionClosed,
+    /// No response was received in time.
+    #[error("request timed out")]
+    Timeout,
+    /// The peer answered with a message that doesn't match the request.
+    #[error("received unexpected response")]
+    BadResponse,
+}
+
+impl From<SnapRequestError> for RequestError {
+    fn from(err: SnapRequestError) -> Self {
+        match err {
+            SnapRequestError::PeerNotConnected(_) | SnapRequestError::ConnectionClosed => {
+                Self::ConnectionDropped
+            }
+            SnapRequestError::Timeout => Self::Timeout,
+            SnapRequestError::BadResponse => Self::BadResponse,
+        }
+    }
+}
+
+/// A request to a connected peer.
+#[derive(Debug)]
+struct SnapCommand {
+    /// The request to send
+    request: SnapMessage,
+    /// The channel for the response
+    response: oneshot::Sender<SnapMessage>,
+}
+
+/// The senders of the commands to the connected peers.
+type SnapPeers = Arc<Mutex<HashMap<PeerId, mpsc::UnboundedSender<SnapCommand>>>>;
+
+/// The [`ProtocolHandler`] of the `snap` protocol, serving the state of the given client.
+pub struct SnapProtocolHandler<C> {
+    /// Answers the requests of the peers
+    server: Arc<SnapRequestServer<C>>,
+    /// The connected peers
+    peers: SnapPeers,
+}
+
+impl<C> SnapProtocolHandler<C> {
+    /// Creates a new handler that serves the hashed state of the given client.
+    pub fn new(client: C) -> Self {
+        Self { server: Arc::new(SnapRequestServer::new(client)), peers: Default::default() }
+    }
+
+    /// Returns a handle to send requests to the connected peers.
+    pub fn handle(&self) -> SnapHandle {
+        SnapHandle { peers: self.peers.clone(), next_request_id: Default::default() }
+    }
+
+    fn connection_handler(&self) -> SnapConnectionHandler<C> {
+        SnapConnectionHandler { server: self.server.clone(), peers: self.peers.clone() }
+    }
+}
+
+impl<C> fmt::Debug for SnapProtocolHandler<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapProtocolHandler").field("peers", &self.peers.lock().len()).finish()
+    }
+}
+
+impl<C> ProtocolHandler for SnapProtocolHandler<C>
+where
+    C: SnapStateReader + 'static,
+{
+    type ConnectionHandler = SnapConnectionHandler<C>;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(self.connection_handler())
+    }
+}
+
+/// The [`ConnectionHandler`] of the `snap` protocol.
+#[derive(Debug)]
+pub struct SnapConnectionHandler<C> {
+    server: Arc<SnapRequestServer<C>>,
+    peers: SnapPeers,
+}
+
+impl<C> ConnectionHandler for SnapConnectionHandler<C>
+where
+    C: SnapStateReader + 'static,
+{
+    type Connection = SnapConnection<C>;
+
+    fn protocol(&self) -> Protocol {
+        snap_protocol()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        // snap is only a satellite protocol of eth
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.peers.lock().insert(peer_id, tx.clone());
+        SnapConnection {
+            peer_id,
+            conn,
+            server: self.server,
+            peers: self.peers,
+            to_connection: tx,
+            commands: UnboundedReceiverStream::new(rx),
+            inflight_requests: HashMap::new(),
+            serving: FuturesUnordered::new(),
+        }
+    }
+}
+
+/// The `snap` connection to a peer.
+///
+/// Serves the requests of the peer on the blocking pool and matches the responses of the peer to
+/// the requests sent through the [`SnapHandle`].
+pub struct SnapConnection<C> {
+    /// The connected peer
+    peer_id: PeerId,
+    /// The messages of the peer
+    conn: ProtocolConnection,
+    /// Answers the requests of the peer
+    server: Arc<SnapRequestServer<C>>,
+    /// The connected peers, this connection is removed from on drop
+    peers: SnapPeers,
+    /// The sender of the commands to this connection
+    to_connection: mpsc::UnboundedSender<SnapCommand>,
+    /// The requests to send to the peer
+    commands: UnboundedReceiverStream<SnapCommand>,
+    /// The requests sent to the peer, by request id
+    inflight_requests: HashMap<u64, oneshot::Sender<SnapMessage>>,
+    /// The requests of the peer that are being served
+    serving: FuturesUnordered<JoinHandle<Option<SnapMessage>>>,
+}
+
+impl<C> SnapConnection<C>
+where
+    C: SnapStateReader + 'static,
+{
+    /// Handles a message received from the peer.
+    fn on_message(&mut self, message: SnapMessage) {
+        if message.is_request() {
+            let server = self.server.clone();
+            self.serving.push(tokio::task::spawn_blocking(move || server.on_request(message)));
+        } else if let Some(tx) = self.inflight_requests.remove(&message.request_id()) {
+            let _ = tx.send(message);
+        } else {
+            trace!(target: "net::snap", peer_id = ?self.peer_id, request_id = message.request_id(), "Received unsolicited response");
+        }
+    }
+}
+
+impl<C> Stream for SnapConnection<C>
+where
+    C: SnapStateReader + 'static,
+{
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // send the responses to the served requests
+            if let Poll::Ready(Some(response)) = this.serving.poll_next_unpin(cx) {
+                match response {
+                    Ok(Some(response)) => return Poll::Ready(Some(response.encoded())),
+                    Ok(None) => {}
+                    Err(err) => {
+                        debug!(target: "net::snap", %err, "Failed to serve snap request");
+                    }
+                }
+                continue
+            }
+
+            // send the requests to the peer
+            if let Poll::Ready(Some(SnapCommand { request, response })) =
+                this.commands.poll_next_unpin(cx)
+            {
+                // forget the requests that timed out
+                this.inflight_requests.retain(|_, tx| !tx.is_closed());
+                this.inflight_requests.insert(request.request_id(), response);
+                return Poll::Ready(Some(request.encoded()))
+            }
+
+            // only read further requests once the served ones are answered
+            if this.serving.len() >= MAX_CONCURRENT_REQUESTS_PER_PEER {
+                return Poll::Pending
+            }
+
+            let Poll::Ready(msg) = this.conn.poll_next_unpin(cx) else { return Poll::Pending };
+            let Some(msg) = msg else { return Poll::Ready(None) };
+
+            match SnapMessage::decode_message(&mut &msg[..]) {
+                Ok(message) => this.on_message(message),
+                Err(err) => {
+                    debug!(target: "net::snap", %err, peer_id = ?this.peer_id, "Failed to decode snap message");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl<C> Drop for SnapConnection<C> {
+    fn drop(&mut self) {
+        let mut peers = self.peers.lock();
+        if peers.get(&self.peer_id).is_some_and(|tx| tx.same_channel(&self.to_connection)) {
+            peers.remove(&self.peer_id);
+        }
+    }
+}
+
+impl<C> fmt::Debug for SnapConnection<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("inflight_requests", &self.inflight_requests.len())
+            .field("serving", &self.serving.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A handle to send `snap` requests to the connected peers.
+#[derive(Debug, Clone)]
+pub struct SnapHandle {
+    /// The connected peers
+    peers: SnapPeers,
+    /// The id of the next request
+    next_request_id: Arc<AtomicU64>,
+}
+
+impl SnapHandle {
+    /// Returns the peers that are connected over `snap`.
+    pub fn peers(&self) -> Vec<PeerId> {
+        self.peers.lock().keys().copied().collect()
+    }
+
+    /// Requests the accounts of the state trie with the given root, starting at the given hash
+    /// and up to the limit hash.
+    pub async fn get_account_range(
+        &self,
+        peer_id: PeerId,
+        root_hash: B256,
+        starting_hash: B256,
+        limit_hash: B256,
+        response_bytes: u64,
+    ) -> Result<AccountRange, SnapRequestError> {
+        let request = SnapMessage::GetAccountRange(GetAccountRange {
+            request_id: self.next_request_id(),
+            root_hash,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        });
+        match self.send_request(peer_id, request).await? {
+            SnapMessage::AccountRange(response) => Ok(response),
+            _ => Err(SnapRequestError::BadResponse),
+        }
+    }
+
+    /// Requests the storage slots of the given accounts of the state trie with the given root.
+    ///
+    /// The starting and limit hash only apply to the first account, and are empty if the whole
+    /// storage is requested.
+    pub async fn get_storage_ranges(
+        &self,
+        peer_id: PeerId,
+        root_hash: B256,
+        account_hashes: Vec<B256>,
+        starting_hash: Bytes,
+        limit_hash: Bytes,
+        response_bytes: u64,
+    ) -> Result<StorageRanges, SnapRequestError> {
+        let request = SnapMessage::GetStorageRanges(GetStorageRanges {
+            request_id: self.next_request_id(),
+            root_hash,
+            account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        });
+        match self.send_request(peer_id, request).await? {
+            SnapMessage::StorageRanges(response) => Ok(response),
+            _ => Err(SnapRequestError::BadResponse),
+        }
+    }
+
+    /// Requests the contract bytecodes with the given hashes.
+    pub async fn get_byte_codes(
+        &self,
+        peer_id: PeerId,
+        hashes: Vec<B256>,
+        response_bytes: u64,
+    ) -> Result<ByteCodes, SnapRequestError> {
+        let request = SnapMessage::GetByteCodes(GetByteCodes {
+            request_id: self.next_request_id(),
+            hashes,
+            response_bytes,
+        });
+        match self.send_request(peer_id, request).await? {
+            SnapMessage::ByteCodes(response) => Ok(response),
+            _ => Err(SnapRequestError::BadResponse),
+        }
+    }
+
+    /// Requests the trie nodes at the given paths of the state trie with the given root.
+    pub async fn get_trie_nodes(
+        &self,
+        peer_id: PeerId,
+        root_hash: B256,
+        paths: Vec<Vec<Bytes>>,
+        response_bytes: u64,
+    ) -> Result<TrieNodes, SnapRequestError> {
+        let request = SnapMessage::GetTrieNodes(GetTrieNodes {
+            request_id: self.next_request_id(),
+            root_hash,
+            paths,
+            response_bytes,
+        });
+        match self.send_request(peer_id, request).await? {
+            SnapMessage::TrieNodes(response) => Ok(response),
+            _ => Err(SnapRequestError::BadResponse),
+        }
+    }
+
+    fn next_request_id(&self) -> u64 {
+        self.next_request_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    async fn send_request(
+        &self,
+        peer_id: PeerId,
+        request: SnapMessage,
+    ) -> Result<SnapMessage, SnapRequestError> {
+        let (tx, rx) = oneshot::channel();
+        self.peers
+            .lock()
+            .get(&peer_id)
+            .ok_or(SnapRequestError::PeerNotConnected(peer_id))?
+            .send(SnapCommand { request, response: tx })
+            .map_err(|_| SnapRequestError::ConnectionClosed)?;
+
+        match tokio::time::timeout(REQUEST_TIMEOUT, rx).await {
+            Ok(Ok(response)) => Ok(response),
+            Ok(Err(_)) => Err(SnapRequestError::ConnectionClosed),
+            Err(_) => Err(SnapRequestError::Timeout),
+        }
+    }
+}
+
+impl SnapClient for SnapHandle {
+    fn snap_peers(&self) -> Vec<PeerId> {
+        self.peers()
+    }
+
+    fn get_account_range(
+        &self,
+        peer_id: PeerId,
+        root_hash: B256,
+        starting_hash: B256,
+        limit_hash: B256,
+        response_bytes: u64,
+    ) -> SnapFut<AccountRange> {
+        let this = self.clone();
+        Box::pin(async move {
+            Ok(this
+                .get_account_range(peer_id, root_hash, starting_hash, limit_hash, response_bytes)
+                .await?)
+        })
+    }
+
+    fn get_storage_ranges(
+        &self,
+        peer_id: PeerId,
+        root_hash: B256,
+        account_hashes: Vec<B256>,
+        starting_hash: Bytes,
+        limit_hash: Bytes,
+        response_bytes: u64,
+    ) -> SnapFut<StorageRanges> {
+        let this = self.clone();
+        Box::pin(async move {
+            Ok(this
+                .get_storage_ranges(
+                    peer_id,
+                    root_hash,
+                    account_hashes,
+                    starting_hash,
+                    limit_hash,
+                    response_bytes,
+                )
+                .await?)
+        })
+    }
+
+    fn get_byte_codes(
+        &self,
+        peer_id: PeerId,
+        hashes: Vec<B256>,
+        response_bytes: u64,
+    ) -> SnapFut<ByteCodes> {
+        let this = self.clone();
+        Box::pin(async move { Ok(this.get_byte_codes(peer_id, hashes, response_bytes).await?) })
+    }
+
+    fn get_trie_nodes(
+        &self,
+        peer_id: PeerId,
+        root_hash: B256,
+        paths: Vec<Vec<Bytes>>,
+        response_bytes: u64,
+    ) -> SnapFut<TrieNodes> {
+        let this = self.clone();
+        Box::pin(async move {
+            Ok(this.get_trie_nodes(peer_id, root_hash, paths, response_bytes).await?)
+        })
+    }
+}
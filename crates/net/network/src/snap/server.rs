@@ -0,0 +1,199 @@
+//! Serves the requests of the `snap` protocol from the hashed state.
+
+use reth_eth_wire::{
+    AccountData, AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges,
+    GetTrieNodes, SlimAccount, SnapMessage, StorageData, StorageRanges, TrieNodes,
+};
+use reth_primitives::{Bytes, B256};
+use reth_storage_api::SnapStateReader;
+use tracing::debug;
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/v1.14.3/eth/protocols/snap/handler.go#L35-L57>
+
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+const MAX_BYTECODES_SERVE: usize = 1024;
+
+/// Maximum size of replies to data retrievals.
+const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
+
+/// The approximate encoded size of an account in an [`AccountRange`] response.
+///
+/// Used to translate the byte limit of a request into a number of accounts, since the range proof
+/// depends on the last returned account.
+const ACCOUNT_SIZE_ESTIMATE: usize = 110;
+
+/// The approximate encoded size of a storage slot in a [`StorageRanges`] response.
+const SLOT_SIZE_ESTIMATE: usize = 70;
+
+/// Answers the requests of the `snap` protocol.
+///
+/// Requests for a state that is not available are answered with empty responses, as required by
+/// the protocol.
+#[derive(Debug)]
+pub(crate) struct SnapRequestServer<C> {
+    /// The client type that can interact with the hashed state.
+    client: C,
+}
+
+impl<C> SnapRequestServer<C>
+where
+    C: SnapStateReader,
+{
+    /// Creates a new server for the given client.
+    pub(crate) const fn new(client: C) -> Self {
+        Self { client }
+    }
+
+    /// Returns the response to the given request, or `None` if the message is not a request.
+    pub(crate) fn on_request(&self, request: SnapMessage) -> Option<SnapMessage> {
+        let response = match request {
+            SnapMessage::GetAccountRange(request) => {
+                SnapMessage::AccountRange(self.on_get_account_range(request))
+            }
+            SnapMessage::GetStorageRanges(request) => {
+                SnapMessage::StorageRanges(self.on_get_storage_ranges(request))
+            }
+            SnapMessage::GetByteCodes(request) => {
+                SnapMessage::ByteCodes(self.on_get_byte_codes(request))
+            }
+            SnapMessage::GetTrieNodes(request) => {
+                SnapMessage::TrieNodes(self.on_get_trie_nodes(request))
+            }
+            _ => return None,
+        };
+        Some(response)
+    }
+
+    fn on_get_account_range(&self, request: GetAccountRange) -> AccountRange {
+        let GetAccountRange { request_id, root_hash, starting_hash, limit_hash, response_bytes } =
+            request;
+        let max_bytes = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        let range = match self.client.snap_account_range(
+            root_hash,
+            starting_hash,
+            limit_hash,
+            (max_bytes / ACCOUNT_SIZE_ESTIMATE).max(1),
+        ) {
+            Ok(Some(range)) => range,
+            Ok(None) => return AccountRange { request_id, ..Default::default() },
+            Err(err) => {
+                debug!(target: "net::snap", %err, "Failed to serve account range");
+                return AccountRange { request_id, ..Default::default() }
+            }
+        };
+
+        let accounts = range
+            .accounts
+            .into_iter()
+            .map(|account| AccountData {
+                hash: account.hashed_address,
+                body: SlimAccount::new(
+                    account.account.nonce,
+                    account.account.balance,
+                    account.storage_root,
+                    account.account.get_bytecode_hash(),
+                ),
+            })
+            .collect();
+
+        AccountRange { request_id, accounts, proof: range.proof }
+    }
+
+    fn on_get_storage_ranges(&self, request: GetStorageRanges) -> StorageRanges {
+        let GetStorageRanges {
+            request_id,
+            root_hash,
+            account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = request;
+        let mut remaining_bytes = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        // the origin and the limit only apply to the first account
+        let mut origin = hash_or(&starting_hash, B256::ZERO);
+        let mut limit = hash_or(&limit_hash, B256::repeat_byte(0xff));
+
+        let mut response = StorageRanges { request_id, ..Default::default() };
+        for hashed_address in account_hashes {
+            if remaining_bytes == 0 {
+                break
+            }
+
+            let range = match self.client.snap_storage_range(
+                root_hash,
+                hashed_address,
+                origin,
+                limit,
+                (remaining_bytes / SLOT_SIZE_ESTIMATE).max(1),
+            ) {
+                Ok(Some(range)) => range,
+                Ok(None) => break,
+                Err(err) => {
+                    debug!(target: "net::snap", %err, "Failed to serve storage range");
+                    break
+                }
+            };
+
+            let slots = range
+                .slots
+                .into_iter()
+                .map(|(hash, value)| StorageData { hash, data: alloy_rlp::encode(value).into() })
+                .collect::<Vec<_>>();
+            let size = slots.iter().map(|slot| slot.hash.len() + slot.data.len()).sum::<usize>();
+            remaining_bytes = remaining_bytes.saturating_sub(size);
+            response.slots.push(slots);
+
+            // a proof is only attached to an incomplete range, which is always the last one
+            if !range.proof.is_empty() {
+                response.proof = range.proof;
+                break
+            }
+
+            origin = B256::ZERO;
+            limit = B256::repeat_byte(0xff);
+        }
+
+        response
+    }
+
+    fn on_get_byte_codes(&self, request: GetByteCodes) -> ByteCodes {
+        let GetByteCodes { request_id, mut hashes, response_bytes } = request;
+        hashes.truncate(MAX_BYTECODES_SERVE);
+        let max_bytes = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        let codes = self.client.snap_bytecodes(&hashes, max_bytes).unwrap_or_else(|err| {
+            debug!(target: "net::snap", %err, "Failed to serve bytecodes");
+            Vec::new()
+        });
+
+        ByteCodes { request_id, codes }
+    }
+
+    fn on_get_trie_nodes(&self, request: GetTrieNodes) -> TrieNodes {
+        let GetTrieNodes { request_id, root_hash, paths, response_bytes } = request;
+        let max_bytes = (response_bytes as usize).min(SOFT_RESPONSE_LIMIT);
+
+        let nodes = match self.client.snap_trie_nodes(root_hash, &paths, max_bytes) {
+            Ok(nodes) => nodes.unwrap_or_default(),
+            Err(err) => {
+                debug!(target: "net::snap", %err, "Failed to serve trie nodes");
+                Vec::new()
+            }
+        };
+
+        TrieNodes { request_id, nodes }
+    }
+}
+
+/// Returns the hash encoded in the given bytes, or the default if the bytes are empty or invalid.
+fn hash_or(bytes: &Bytes, default: B256) -> B256 {
+    if bytes.len() == B256::len_bytes() {
+        B256::from_slice(bytes)
+    } else {
+        default
+    }
+}
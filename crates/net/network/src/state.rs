@@ -15,11 +15,12 @@ use crate::{
 use rand::seq::SliceRandom;
 
 use reth_eth_wire::{
-    capability::Capabilities, BlockHashNumber, DisconnectReason, NewBlockHashes, Status,
+    capability::Capabilities, BlockHashNumber, BlockRangeUpdate, DisconnectReason, NewBlockHashes,
+    Status,
 };
 use reth_network_api::PeerKind;
 use reth_network_peers::PeerId;
-use reth_primitives::{ForkId, B256};
+use reth_primitives::{BlockNumber, ForkId, Head, B256};
 use std::{
     collections::{HashMap, VecDeque},
     fmt,
@@ -147,15 +148,27 @@ impl NetworkState {
         peer: PeerId,
         capabilities: Arc<Capabilities>,
         status: Arc<Status>,
+        block_range: Option<BlockRangeUpdate>,
         request_tx: PeerRequestSender,
         timeout: Arc<AtomicU64>,
     ) {
         debug_assert!(!self.active_peers.contains_key(&peer), "Already connected; not possible");
 
-        // find the corresponding block number
-        let block_number =
-            self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default();
-        self.state_fetcher.new_active_peer(peer, status.blockhash, block_number, timeout);
+        // find the corresponding block number, unless announced by the peer
+        let block_number = block_range.map(|range| range.latest).unwrap_or_else(|| {
+            self.client.block_number(status.blockhash).ok().flatten().unwrap_or_default()
+        });
+        let request_stats = self.peers_manager.request_stats(&peer).unwrap_or_default();
+        self.state_fetcher.new_active_peer(
+            peer,
+            status.blockhash,
+            block_number,
+            timeout,
+            request_stats,
+        );
+        if let Some(range) = block_range {
+            self.state_fetcher.update_peer_earliest_block(&peer, range.earliest);
+        }
 
         self.active_peers.insert(
             peer,
@@ -185,6 +198,9 @@ impl NetworkState {
     /// > the total number of peers) using the `NewBlock` message.
     ///
     /// See also <https://github.com/ethereum/devp2p/blob/master/caps/eth.md>
+    ///
+    /// The validators protected by a sentry node always receive the block first, in addition to
+    /// the fraction of the other peers.
     pub(crate) fn announce_new_block(&mut self, msg: NewBlockMessage) {
         // send a `NewBlock` message to a fraction of the connected peers (square root of the total
         // number of peers)
@@ -196,6 +212,8 @@ impl NetworkState {
         // Shuffle to propagate to a random sample of peers on every block announcement
         let mut peers: Vec<_> = self.active_peers.iter_mut().collect();
         peers.shuffle(&mut rand::thread_rng());
+        // the sort is stable, so the remaining peers are still in random order
+        peers.sort_by_key(|(peer_id, _)| !self.peers_manager.is_protected_peer(peer_id));
 
         for (peer_id, peer) in peers {
             if peer.blocks.contains(&msg.hash) {
@@ -203,8 +221,10 @@ impl NetworkState {
                 continue
             }
 
+            let is_protected = self.peers_manager.is_protected_peer(peer_id);
+
             // Queue a `NewBlock` message for the peer
-            if count < num_propagate {
+            if count < num_propagate || is_protected {
                 self.queued_messages
                     .push_back(StateAction::NewBlock { peer_id: *peer_id, block: msg.clone() });
 
@@ -216,7 +236,9 @@ impl NetworkState {
                 // mark the block as seen by the peer
                 peer.blocks.insert(msg.hash);
 
-                count += 1;
+                if !is_protected {
+                    count += 1;
+                }
             }
 
             if count >= num_propagate {
@@ -255,9 +277,15 @@ impl NetworkState {
         self.state_fetcher.update_peer_block(peer_id, hash, number);
     }
 
-    /// Invoked when a new [`ForkId`] is activated.
-    pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
-        self.discovery.update_fork_id(fork_id)
+    /// Returns the earliest block whose body and receipts are still available.
+    pub(crate) fn earliest_block(&self) -> BlockNumber {
+        self.client.earliest_block_number().unwrap_or_default()
+    }
+
+    /// Invoked when the local head changed, keeps the fork id used by the discovery services up to
+    /// date.
+    pub(crate) fn on_status_update(&mut self, head: Head) {
+        self.discovery.on_status_update(head)
     }
 
     /// Invoked after a `NewBlock` message was received by the peer.
@@ -278,6 +306,14 @@ impl NetworkState {
         }
     }
 
+    /// Invoked for a `BlockRangeUpdate` message of an `eth/69` peer.
+    pub(crate) fn on_block_range_update(&mut self, peer_id: PeerId, range: BlockRangeUpdate) {
+        if self.active_peers.contains_key(&peer_id) {
+            self.state_fetcher.update_peer_earliest_block(&peer_id, range.earliest);
+            self.update_peer_block(&peer_id, range.latest_hash, range.latest);
+        }
+    }
+
     /// Bans the [`IpAddr`] in the discovery service.
     pub(crate) fn ban_ip_discovery(&self, ip: IpAddr) {
         trace!(target: "net", ?ip, "Banning discovery");
@@ -397,18 +433,25 @@ impl NetworkState {
     /// Delegates the response result to the fetcher which may return an outcome specific
     /// instruction that needs to be handled in [`Self::on_block_response_outcome`]. This could be
     /// a follow-up request or an instruction to slash the peer's reputation.
+    ///
+    /// The request stats the fetcher recorded for the peer are synced to the peers manager, so
+    /// they outlive the session.
     fn on_eth_response(&mut self, peer: PeerId, resp: PeerResponseResult) -> Option<StateAction> {
-        match resp {
+        let outcome = match resp {
             PeerResponseResult::BlockHeaders(res) => {
-                let outcome = self.state_fetcher.on_block_headers_response(peer, res)?;
-                self.on_block_response_outcome(outcome)
+                self.state_fetcher.on_block_headers_response(peer, res)
             }
             PeerResponseResult::BlockBodies(res) => {
-                let outcome = self.state_fetcher.on_block_bodies_response(peer, res)?;
-                self.on_block_response_outcome(outcome)
+                self.state_fetcher.on_block_bodies_response(peer, res)
             }
-            _ => None,
+            _ => return None,
+        };
+
+        if let Some(request_stats) = self.state_fetcher.request_stats(&peer) {
+            self.peers_manager.set_request_stats(&peer, request_stats);
         }
+
+        self.on_block_response_outcome(outcome?)
     }
 
     /// Advances the state
@@ -547,21 +590,23 @@ mod tests {
     use crate::{
         discovery::Discovery,
         fetch::StateFetcher,
-        message::PeerRequestSender,
+        message::{NewBlockMessage, PeerRequestSender},
         peers::PeersManager,
-        state::{BlockNumReader, NetworkState},
-        PeerRequest,
+        state::{BlockNumReader, NetworkState, StateAction},
+        PeerRequest, PeeringMode, PeersConfig,
     };
     use reth_eth_wire::{
         capability::{Capabilities, Capability},
-        BlockBodies, EthVersion,
+        BlockBodies, EthVersion, NewBlock,
     };
     use reth_network_p2p::{bodies::client::BodiesClient, error::RequestError};
-    use reth_network_peers::PeerId;
+    use reth_network_peers::{NodeRecord, PeerId};
     use reth_primitives::{BlockBody, Header, B256};
     use reth_provider::test_utils::NoopProvider;
     use std::{
+        collections::HashSet,
         future::poll_fn,
+        net::SocketAddr,
         sync::{atomic::AtomicU64, Arc},
     };
     use tokio::sync::mpsc;
@@ -600,6 +645,7 @@ mod tests {
             peer_id,
             capabilities(),
             Arc::default(),
+            None,
             peer_tx,
             Arc::new(AtomicU64::new(1)),
         );
@@ -641,4 +687,42 @@ mod tests {
         assert!(resp.is_err());
         assert_eq!(resp.unwrap_err(), RequestError::ConnectionDropped);
     }
+
+    #[test]
+    fn test_sentry_announces_block_to_protected_validator_first() {
+        let validator = PeerId::random();
+        let config = PeersConfig::test().with_peering_mode(PeeringMode::Sentry).with_allow_list(
+            HashSet::from([NodeRecord::new(SocketAddr::from(([127, 0, 0, 1], 30303)), validator)]),
+        );
+        let mut state = state();
+        state.peers_manager = PeersManager::new(config);
+
+        let peers = (0..15).map(|_| PeerId::random()).chain([validator]).collect::<Vec<_>>();
+        for peer_id in &peers {
+            let (tx, _session_rx) = mpsc::channel(1);
+            state.on_session_activated(
+                *peer_id,
+                capabilities(),
+                Arc::default(),
+                None,
+                PeerRequestSender::new(*peer_id, tx),
+                Arc::new(AtomicU64::new(1)),
+            );
+        }
+
+        let block = Arc::new(NewBlock::default());
+        state.announce_new_block(NewBlockMessage { hash: B256::random(), block });
+
+        let recipients = state
+            .queued_messages
+            .iter()
+            .filter_map(|action| match action {
+                StateAction::NewBlock { peer_id, .. } => Some(*peer_id),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        // the validator in addition to the square root of the number of peers
+        assert_eq!(recipients.len(), 5 + 1);
+        assert_eq!(recipients[0], validator);
+    }
 }
@@ -2,14 +2,43 @@ use super::{
     DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
+use crate::transactions::policy::{
+    DefaultTransactionPropagationPolicy, TransactionPropagationPolicy,
+};
 use derive_more::Constructor;
+use std::sync::Arc;
 
 /// Configuration for managing transactions within the network.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TransactionsManagerConfig {
     /// Configuration for fetching transactions.
     pub transaction_fetcher_config: TransactionFetcherConfig,
+    /// Decides how transactions are propagated to the individual peers.
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_propagation_policy"))]
+    pub propagation_policy: Arc<dyn TransactionPropagationPolicy>,
+}
+
+impl TransactionsManagerConfig {
+    /// Sets the policy that decides how transactions are propagated to the individual peers.
+    pub fn with_propagation_policy(mut self, policy: impl TransactionPropagationPolicy) -> Self {
+        self.propagation_policy = Arc::new(policy);
+        self
+    }
+}
+
+impl Default for TransactionsManagerConfig {
+    fn default() -> Self {
+        Self {
+            transaction_fetcher_config: TransactionFetcherConfig::default(),
+            propagation_policy: default_propagation_policy(),
+        }
+    }
+}
+
+/// Returns the [`DefaultTransactionPropagationPolicy`].
+fn default_propagation_policy() -> Arc<dyn TransactionPropagationPolicy> {
+    Arc::new(DefaultTransactionPropagationPolicy)
 }
 
 /// Configuration for fetching transactions.
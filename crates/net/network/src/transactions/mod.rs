@@ -28,7 +28,8 @@ use reth_network_p2p::{
 };
 use reth_network_peers::PeerId;
 use reth_primitives::{
-    FromRecoveredPooledTransaction, PooledTransactionsElement, TransactionSigned, TxHash, B256,
+    Address, FromRecoveredPooledTransaction, PooledTransactionsElement, TransactionSigned, TxHash,
+    B256,
 };
 use reth_tokio_util::EventStream;
 use reth_transaction_pool::{
@@ -56,8 +57,13 @@ pub mod config;
 pub mod constants;
 /// Component responsible for fetching transactions from [`NewPooledTransactionHashes`].
 pub mod fetcher;
+pub mod policy;
 pub mod validation;
 pub use config::{TransactionFetcherConfig, TransactionsManagerConfig};
+pub use policy::{
+    DefaultTransactionPropagationPolicy, PrivateSendersPropagationPolicy, PropagationCandidate,
+    TransactionPropagationKind, TransactionPropagationPolicy,
+};
 
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
@@ -244,6 +250,8 @@ pub struct TransactionsManager<Pool> {
     pending_transactions: ReceiverStream<TxHash>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent>,
+    /// Decides how transactions are propagated to the individual peers.
+    propagation_policy: Arc<dyn TransactionPropagationPolicy>,
     /// `TransactionsManager` metrics
     metrics: TransactionsManagerMetrics,
 }
@@ -294,6 +302,7 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
             ),
+            propagation_policy: transactions_manager_config.propagation_policy,
             metrics,
         }
     }
@@ -342,6 +351,9 @@ where
     }
 
     /// Request handler for an incoming request for transactions
+    ///
+    /// Transactions that the propagation policy withholds from the peer are not served either, so
+    /// they can't be requested by hash.
     fn on_get_pooled_transactions(
         &mut self,
         peer_id: PeerId,
@@ -353,8 +365,22 @@ where
                 let _ = response.send(Ok(PooledTransactions::default()));
                 return
             }
+            let hashes = self
+                .pool
+                .get_all(request.0)
+                .into_iter()
+                .map(PropagateTransaction::new)
+                .filter(|tx| {
+                    self.propagation_policy.propagation_kind(
+                        &peer_id,
+                        tx.candidate(),
+                        TransactionPropagationKind::Hash,
+                    ) != TransactionPropagationKind::Withhold
+                })
+                .map(|tx| tx.hash())
+                .collect();
             let transactions = self.pool.get_pooled_transaction_elements(
-                request.0,
+                hashes,
                 GetPooledTransactionLimit::ResponseSizeSoftLimit(
                     self.transaction_fetcher.info.soft_limit_byte_size_pooled_transactions_response,
                 ),
@@ -426,13 +452,25 @@ where
             let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
             let mut full_transactions = FullTransactionsBuilder::default();
 
+            // transactions are sent in full to a fraction of the peers and announced to the rest,
+            // unless the propagation policy decides otherwise
+            let default_kind = if peer_idx > max_num_full {
+                TransactionPropagationKind::Hash
+            } else {
+                TransactionPropagationKind::Full
+            };
+
             // Iterate through the transactions to propagate and fill the hashes and full
-            // transaction lists, before deciding whether or not to send full transactions to the
-            // peer.
+            // transaction lists.
             for tx in &to_propagate {
-                if peer.seen_transactions.insert(tx.hash()) {
-                    hashes.push(tx);
+                if peer.seen_transactions.contains(&tx.hash()) {
+                    continue
+                }
 
+                let kind =
+                    self.propagation_policy.propagation_kind(peer_id, tx.candidate(), default_kind);
+                match kind {
+                    TransactionPropagationKind::Withhold => continue,
                     // Do not send full 4844 transaction hashes to peers.
                     //
                     //  Nodes MUST NOT automatically broadcast blob transactions to their peers.
@@ -441,47 +479,42 @@ where
                     //  via `GetPooledTransactions`.
                     //
                     // From: <https://eips.ethereum.org/EIPS/eip-4844#networking>
-                    if !tx.transaction.is_eip4844() {
-                        full_transactions.push(tx);
+                    TransactionPropagationKind::Full if !tx.transaction.is_eip4844() => {
+                        full_transactions.push(tx)
                     }
+                    _ => hashes.push(tx),
                 }
+                peer.seen_transactions.insert(tx.hash());
             }
-            let mut new_pooled_hashes = hashes.build();
 
-            if !new_pooled_hashes.is_empty() {
-                // determine whether to send full tx objects or hashes. If there are no full
-                // transactions, try to send hashes.
-                if peer_idx > max_num_full || full_transactions.is_empty() {
-                    // enforce tx soft limit per message for the (unlikely) event the number of
-                    // hashes exceeds it
-                    new_pooled_hashes.truncate(
-                        SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE,
-                    );
-
-                    for hash in new_pooled_hashes.iter_hashes().copied() {
-                        propagated.0.entry(hash).or_default().push(PropagateKind::Hash(*peer_id));
-                    }
+            if !full_transactions.is_empty() {
+                let new_full_transactions = full_transactions.build();
 
-                    trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
+                for tx in &new_full_transactions {
+                    propagated.0.entry(tx.hash()).or_default().push(PropagateKind::Full(*peer_id));
+                }
 
-                    // send hashes of transactions
-                    self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
-                } else {
-                    let new_full_transactions = full_transactions.build();
+                trace!(target: "net::tx", ?peer_id, num_txs=?new_full_transactions.len(), "Propagating full transactions to peer");
 
-                    for tx in &new_full_transactions {
-                        propagated
-                            .0
-                            .entry(tx.hash())
-                            .or_default()
-                            .push(PropagateKind::Full(*peer_id));
-                    }
+                // send full transactions
+                self.network.send_transactions(*peer_id, new_full_transactions);
+            }
 
-                    trace!(target: "net::tx", ?peer_id, num_txs=?new_full_transactions.len(), "Propagating full transactions to peer");
+            let mut new_pooled_hashes = hashes.build();
+            if !new_pooled_hashes.is_empty() {
+                // enforce tx soft limit per message for the (unlikely) event the number of
+                // hashes exceeds it
+                new_pooled_hashes
+                    .truncate(SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE);
 
-                    // send full transactions
-                    self.network.send_transactions(*peer_id, new_full_transactions);
+                for hash in new_pooled_hashes.iter_hashes().copied() {
+                    propagated.0.entry(hash).or_default().push(PropagateKind::Hash(*peer_id));
                 }
+
+                trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
+
+                // send hashes of transactions
+                self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
             }
         }
 
@@ -516,7 +549,16 @@ where
 
         // Iterate through the transactions to propagate and fill the hashes and full transaction
         for tx in to_propagate {
-            if peer.seen_transactions.insert(tx.hash()) {
+            if peer.seen_transactions.contains(&tx.hash()) {
+                continue
+            }
+            let kind = self.propagation_policy.propagation_kind(
+                &peer_id,
+                tx.candidate(),
+                TransactionPropagationKind::Full,
+            );
+            if kind != TransactionPropagationKind::Withhold {
+                peer.seen_transactions.insert(tx.hash());
                 full_transactions.push(&tx);
             }
         }
@@ -562,6 +604,14 @@ where
             let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
 
             for tx in to_propagate {
+                let kind = self.propagation_policy.propagation_kind(
+                    &peer_id,
+                    tx.candidate(),
+                    TransactionPropagationKind::Hash,
+                );
+                if kind == TransactionPropagationKind::Withhold {
+                    continue
+                }
                 if !peer.seen_transactions.insert(tx.hash()) {
                     hashes.push(&tx);
                 }
@@ -601,7 +651,7 @@ where
         if self.network.is_initially_syncing() {
             return
         }
-        if self.network.tx_gossip_disabled() {
+        if self.network.tx_gossip_disabled() || self.network.inbound_tx_gossip_disabled() {
             return
         }
 
@@ -917,11 +967,23 @@ where
 
                 let mut msg_builder = PooledTransactionsHashesBuilder::new(version);
                 for pooled_tx in pooled_txs {
-                    peer.seen_transactions.insert(*pooled_tx.hash());
-                    msg_builder.push_pooled(pooled_tx);
+                    let tx = PropagateTransaction::new(pooled_tx);
+                    let kind = self.propagation_policy.propagation_kind(
+                        &peer_id,
+                        tx.candidate(),
+                        TransactionPropagationKind::Hash,
+                    );
+                    if kind == TransactionPropagationKind::Withhold {
+                        continue
+                    }
+                    peer.seen_transactions.insert(tx.hash());
+                    msg_builder.push(&tx);
                 }
 
                 let msg = msg_builder.build();
+                if msg.is_empty() {
+                    return
+                }
                 self.network.send_transactions_hashes(peer_id, msg);
             }
             _ => {}
@@ -939,7 +1001,7 @@ where
         if self.network.is_initially_syncing() {
             return
         }
-        if self.network.tx_gossip_disabled() {
+        if self.network.tx_gossip_disabled() || self.network.inbound_tx_gossip_disabled() {
             return
         }
 
@@ -1360,6 +1422,7 @@ where
 /// A transaction that's about to be propagated to multiple peers.
 struct PropagateTransaction {
     size: usize,
+    sender: Address,
     transaction: Arc<TransactionSigned>,
 }
 
@@ -1370,11 +1433,17 @@ impl PropagateTransaction {
         self.transaction.hash()
     }
 
+    /// Returns the transaction as input for the [`TransactionPropagationPolicy`].
+    fn candidate(&self) -> PropagationCandidate<'_> {
+        PropagationCandidate { sender: self.sender, transaction: &self.transaction }
+    }
+
     /// Create a new instance from a pooled transaction
     fn new<T: PoolTransaction>(tx: Arc<ValidPoolTransaction<T>>) -> Self {
         let size = tx.encoded_length();
+        let sender = tx.sender();
         let transaction = Arc::new(tx.transaction.to_recovered_transaction().into_signed());
-        Self { size, transaction }
+        Self { size, sender, transaction }
     }
 }
 
@@ -1427,18 +1496,6 @@ enum PooledTransactionsHashesBuilder {
 // === impl PooledTransactionsHashesBuilder ===
 
 impl PooledTransactionsHashesBuilder {
-    /// Push a transaction from the pool to the list.
-    fn push_pooled<T: PoolTransaction>(&mut self, pooled_tx: Arc<ValidPoolTransaction<T>>) {
-        match self {
-            Self::Eth66(msg) => msg.0.push(*pooled_tx.hash()),
-            Self::Eth68(msg) => {
-                msg.hashes.push(*pooled_tx.hash());
-                msg.sizes.push(pooled_tx.encoded_length());
-                msg.types.push(pooled_tx.transaction.tx_type());
-            }
-        }
-    }
-
     fn push(&mut self, tx: &PropagateTransaction) {
         match self {
             Self::Eth66(msg) => msg.0.push(tx.hash()),
@@ -1454,7 +1511,7 @@ impl PooledTransactionsHashesBuilder {
     fn new(version: EthVersion) -> Self {
         match version {
             EthVersion::Eth66 | EthVersion::Eth67 => Self::Eth66(Default::default()),
-            EthVersion::Eth68 => Self::Eth68(Default::default()),
+            EthVersion::Eth68 | EthVersion::Eth69 => Self::Eth68(Default::default()),
         }
     }
 
@@ -2020,6 +2077,65 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_withheld_transactions_are_not_propagated_or_served() {
+        reth_tracing::init_test_tracing();
+
+        let mut tx_manager = new_tx_manager().await;
+        let trusted = PeerId::new([1; 64]);
+        let untrusted = PeerId::new([2; 64]);
+        for peer_id in [trusted, untrusted] {
+            let (peer, _to_mock_session_rx) = new_mock_session(peer_id, EthVersion::Eth68);
+            tx_manager.peers.insert(peer_id, peer);
+        }
+
+        let private_tx = MockTransaction::eip1559();
+        let public_tx = MockTransaction::eip1559();
+        tx_manager.propagation_policy = Arc::new(PrivateSendersPropagationPolicy::new(
+            HashSet::from([private_tx.get_sender()]),
+            HashSet::from([trusted]),
+        ));
+        for tx in [&private_tx, &public_tx] {
+            tx_manager
+                .pool
+                .add_transaction(reth_transaction_pool::TransactionOrigin::External, tx.clone())
+                .await
+                .unwrap();
+        }
+        let hashes = vec![private_tx.get_hash(), public_tx.get_hash()];
+
+        // not broadcast to the untrusted peer, neither in full nor as hash
+        let to_propagate =
+            tx_manager.pool.get_all(hashes.clone()).into_iter().map(PropagateTransaction::new);
+        let propagated = tx_manager.propagate_transactions(to_propagate.collect());
+        let peers = |hash: &TxHash| {
+            let mut peers = propagated.0[hash].iter().map(|kind| *kind.peer()).collect::<Vec<_>>();
+            peers.sort();
+            peers
+        };
+        assert_eq!(peers(&hashes[0]), vec![trusted]);
+        assert_eq!(peers(&hashes[1]), vec![trusted, untrusted]);
+
+        // not announced to the untrusted peer
+        tx_manager.propagate_hashes_to(hashes.clone(), untrusted);
+        assert!(!tx_manager.peers[&untrusted].seen_transactions.contains(&hashes[0]));
+
+        // not served to the untrusted peer
+        let (send, receive) = oneshot::channel();
+        tx_manager.on_get_pooled_transactions(
+            untrusted,
+            GetPooledTransactions(hashes.clone()),
+            send,
+        );
+        let PooledTransactions(served) = receive.await.unwrap().unwrap();
+        assert_eq!(served.iter().map(|tx| *tx.hash()).collect::<Vec<_>>(), vec![hashes[1]]);
+
+        let (send, receive) = oneshot::channel();
+        tx_manager.on_get_pooled_transactions(trusted, GetPooledTransactions(hashes), send);
+        let PooledTransactions(served) = receive.await.unwrap().unwrap();
+        assert_eq!(served.len(), 2);
+    }
+
     #[tokio::test]
     async fn test_max_retries_tx_request() {
         reth_tracing::init_test_tracing();
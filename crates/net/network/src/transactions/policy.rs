@@ -0,0 +1,132 @@
+//! Policies that decide how transactions are propagated to peers.
+//!
+//! The [`TransactionsManager`](super::TransactionsManager) picks a
+//! [`TransactionPropagationKind`] for every transaction and peer by its own rules and then asks
+//! the configured [`TransactionPropagationPolicy`] for the final decision. This allows a node to
+//! accept private order flow without leaking it to the network, while still gossiping public
+//! transactions.
+
+use reth_network_peers::PeerId;
+use reth_primitives::{Address, TransactionSigned};
+use std::{collections::HashSet, fmt};
+
+/// How a transaction is propagated to a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionPropagationKind {
+    /// Broadcast the full transaction in a `Transactions` message.
+    Full,
+    /// Announce the transaction hash in a `NewPooledTransactionHashes` message, so the peer can
+    /// request the transaction.
+    Hash,
+    /// Don't propagate the transaction to the peer.
+    Withhold,
+}
+
+/// A transaction that is about to be propagated.
+#[derive(Debug, Clone, Copy)]
+pub struct PropagationCandidate<'a> {
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The transaction.
+    pub transaction: &'a TransactionSigned,
+}
+
+/// Decides per transaction and per peer how a pool transaction is propagated.
+///
+/// The policy applies to the broadcast of new pending transactions, to the announcement of the
+/// pool to new peers and to transactions propagated through the
+/// [`TransactionsHandle`](super::TransactionsHandle). Withheld transactions are not served to the
+/// peer's `GetPooledTransactions` requests either.
+///
+/// EIP-4844 transactions are never broadcast in full: if the policy returns
+/// [`TransactionPropagationKind::Full`] for them, they are announced instead.
+pub trait TransactionPropagationPolicy: fmt::Debug + Send + Sync + 'static {
+    /// Returns how the transaction is propagated to the given peer.
+    ///
+    /// `default` is what the manager would do without a policy.
+    fn propagation_kind(
+        &self,
+        peer_id: &PeerId,
+        transaction: PropagationCandidate<'_>,
+        default: TransactionPropagationKind,
+    ) -> TransactionPropagationKind;
+}
+
+/// The default policy, which keeps the decision of the
+/// [`TransactionsManager`](super::TransactionsManager).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultTransactionPropagationPolicy;
+
+impl TransactionPropagationPolicy for DefaultTransactionPropagationPolicy {
+    fn propagation_kind(
+        &self,
+        _peer_id: &PeerId,
+        _transaction: PropagationCandidate<'_>,
+        default: TransactionPropagationKind,
+    ) -> TransactionPropagationKind {
+        default
+    }
+}
+
+/// A policy that keeps the transactions of private senders within a set of trusted peers.
+///
+/// Transactions of the private senders are withheld from all other peers, the transactions of
+/// all other senders are propagated as usual.
+#[derive(Debug, Clone, Default)]
+pub struct PrivateSendersPropagationPolicy {
+    /// Senders whose transactions are only propagated to the trusted peers.
+    private_senders: HashSet<Address>,
+    /// Peers that receive the transactions of the private senders.
+    trusted_peers: HashSet<PeerId>,
+}
+
+impl PrivateSendersPropagationPolicy {
+    /// Creates a new policy for the given private senders and trusted peers.
+    pub const fn new(private_senders: HashSet<Address>, trusted_peers: HashSet<PeerId>) -> Self {
+        Self { private_senders, trusted_peers }
+    }
+}
+
+impl TransactionPropagationPolicy for PrivateSendersPropagationPolicy {
+    fn propagation_kind(
+        &self,
+        peer_id: &PeerId,
+        transaction: PropagationCandidate<'_>,
+        default: TransactionPropagationKind,
+    ) -> TransactionPropagationKind {
+        if self.private_senders.contains(&transaction.sender) &&
+            !self.trusted_peers.contains(peer_id)
+        {
+            return TransactionPropagationKind::Withhold
+        }
+        default
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn private_senders_are_withheld_from_untrusted_peers() {
+        let private = Address::with_last_byte(1);
+        let public = Address::with_last_byte(2);
+        let trusted = PeerId::with_last_byte(1);
+        let untrusted = PeerId::with_last_byte(2);
+        let policy = PrivateSendersPropagationPolicy::new(
+            HashSet::from([private]),
+            HashSet::from([trusted]),
+        );
+
+        let transaction = TransactionSigned::default();
+        let candidate = |sender| PropagationCandidate { sender, transaction: &transaction };
+        let full = TransactionPropagationKind::Full;
+
+        assert_eq!(policy.propagation_kind(&trusted, candidate(private), full), full);
+        assert_eq!(
+            policy.propagation_kind(&untrusted, candidate(private), full),
+            TransactionPropagationKind::Withhold
+        );
+        assert_eq!(policy.propagation_kind(&untrusted, candidate(public), full), full);
+    }
+}
@@ -0,0 +1,81 @@
+//! Testing the `bsc` protocol.
+
+use reth_network::{
+    bsc::{BscHandle, BscProtocolHandler},
+    test_utils::Testnet,
+};
+use reth_network_api::{PeerId, Peers};
+use reth_primitives::parlia::{VoteData, VoteEnvelope};
+use reth_provider::test_utils::MockEthProvider;
+use std::{collections::HashSet, time::Duration};
+use tokio::sync::broadcast;
+
+fn vote(target_number: u64) -> VoteEnvelope {
+    VoteEnvelope {
+        vote_address: Default::default(),
+        signature: Default::default(),
+        data: VoteData { target_number, ..Default::default() },
+    }
+}
+
+/// Waits until the handle is connected to the peers over `bsc`.
+async fn wait_for_bsc_peers(handle: &BscHandle, peer_ids: &[PeerId]) {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !peer_ids.iter().all(|peer_id| handle.peers().contains(peer_id)) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("bsc connections not established");
+}
+
+/// Waits for the next vote received from the peers.
+async fn next_vote(votes: &mut broadcast::Receiver<VoteEnvelope>) -> VoteEnvelope {
+    tokio::time::timeout(Duration::from_secs(10), votes.recv())
+        .await
+        .expect("vote not received")
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sentry_relays_votes() {
+    reth_tracing::init_test_tracing();
+    let mut net = Testnet::create_with(3, MockEthProvider::default()).await;
+    let validator_id = net.peers()[0].peer_id();
+
+    // the validator is only connected to the sentry, which is connected to the network
+    let validator = BscProtocolHandler::new(HashSet::new());
+    let sentry = BscProtocolHandler::new(HashSet::from([validator_id]));
+    let other = BscProtocolHandler::new(HashSet::new());
+    let (validator_bsc, sentry_bsc, other_bsc) =
+        (validator.handle(), sentry.handle(), other.handle());
+    net.peers_mut()[0].add_rlpx_sub_protocol(validator);
+    net.peers_mut()[1].add_rlpx_sub_protocol(sentry);
+    net.peers_mut()[2].add_rlpx_sub_protocol(other);
+
+    let handle = net.spawn();
+    let [validator_peer, sentry_peer, other_peer] = handle.peers() else { unreachable!() };
+    for peer in [validator_peer, other_peer] {
+        sentry_peer.network().add_peer(*peer.peer_id(), peer.local_addr());
+    }
+    wait_for_bsc_peers(&sentry_bsc, &[*validator_peer.peer_id(), *other_peer.peer_id()]).await;
+    wait_for_bsc_peers(&validator_bsc, &[*sentry_peer.peer_id()]).await;
+    wait_for_bsc_peers(&other_bsc, &[*sentry_peer.peer_id()]).await;
+
+    // the votes of the network reach the validator through the sentry
+    let mut validator_votes = validator_bsc.subscribe_votes();
+    let mut sentry_votes = sentry_bsc.subscribe_votes();
+    other_bsc.broadcast_votes(vec![vote(1)]);
+    assert_eq!(next_vote(&mut sentry_votes).await, vote(1));
+    assert_eq!(next_vote(&mut validator_votes).await, vote(1));
+
+    // and the votes of the validator reach the network
+    let mut other_votes = other_bsc.subscribe_votes();
+    validator_bsc.broadcast_votes(vec![vote(2)]);
+    assert_eq!(next_vote(&mut other_votes).await, vote(2));
+
+    // votes are only relayed once, and not back to their sender
+    other_bsc.broadcast_votes(vec![vote(1), vote(3)]);
+    assert_eq!(next_vote(&mut validator_votes).await, vote(3));
+    assert!(validator_votes.try_recv().is_err());
+}
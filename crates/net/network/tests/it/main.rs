@@ -1,8 +1,10 @@
 mod big_pooled_txs_req;
+mod bsc;
 mod connect;
 mod multiplex;
 mod requests;
 mod session;
+mod snap;
 mod startup;
 mod txgossip;
 
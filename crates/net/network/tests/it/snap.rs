@@ -0,0 +1,262 @@
+//! Testing the `snap` protocol.
+
+use reth_eth_wire::{SlimAccount, SNAP_SOFT_RESPONSE_LIMIT};
+use reth_network::{
+    snap::{SnapHandle, SnapProtocolHandler},
+    test_utils::Testnet,
+};
+use reth_network_api::PeerId;
+use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Account, Bytes, B256, U256};
+use reth_provider::{
+    test_utils::MockEthProvider, ProviderResult, SnapAccount, SnapAccountRange, SnapStateReader,
+    SnapStorageRange,
+};
+use std::{collections::HashMap, time::Duration};
+
+/// Serves a fixed state, with made up proofs and trie nodes.
+#[derive(Debug, Clone)]
+struct MockSnapState {
+    state_root: B256,
+    accounts: Vec<SnapAccount>,
+    storage: Vec<(B256, U256)>,
+    codes: HashMap<B256, Bytes>,
+    nodes: HashMap<Vec<Bytes>, Bytes>,
+}
+
+impl MockSnapState {
+    fn new() -> Self {
+        let code = Bytes::from_static(&[0x60, 0x00, 0x60, 0x00, 0xf3]);
+        let code_hash = keccak256(&code);
+        let mut accounts = (0..10u64)
+            .map(|i| SnapAccount {
+                hashed_address: keccak256(B256::with_last_byte(i as u8)),
+                account: Account {
+                    nonce: i,
+                    balance: U256::from(i * 100),
+                    bytecode_hash: (i == 0).then_some(code_hash),
+                },
+                storage_root: if i == 0 { B256::repeat_byte(0x11) } else { EMPTY_ROOT_HASH },
+            })
+            .collect::<Vec<_>>();
+        accounts.sort_by_key(|account| account.hashed_address);
+        let storage = (1..=5u8).map(|i| (B256::with_last_byte(i), U256::from(i))).collect();
+        Self {
+            state_root: B256::repeat_byte(0xaa),
+            accounts,
+            storage,
+            codes: HashMap::from([(code_hash, code)]),
+            nodes: HashMap::from([
+                (vec![Bytes::new()], Bytes::from_static(&[0xc1, 0x01])),
+                (vec![Bytes::from_static(&[0x1a])], Bytes::from_static(&[0xc1, 0x02])),
+            ]),
+        }
+    }
+}
+
+impl SnapStateReader for MockSnapState {
+    fn snap_state_root(&self) -> ProviderResult<B256> {
+        Ok(self.state_root)
+    }
+
+    fn snap_account_range(
+        &self,
+        state_root: B256,
+        origin: B256,
+        limit: B256,
+        max_accounts: usize,
+    ) -> ProviderResult<Option<SnapAccountRange>> {
+        if state_root != self.state_root {
+            return Ok(None)
+        }
+        let mut accounts = Vec::new();
+        for account in self.accounts.iter().filter(|account| account.hashed_address >= origin) {
+            accounts.push(*account);
+            if account.hashed_address >= limit || accounts.len() >= max_accounts {
+                break
+            }
+        }
+        Ok(Some(SnapAccountRange { accounts, proof: vec![Bytes::from_static(&[0xc0])] }))
+    }
+
+    fn snap_storage_range(
+        &self,
+        state_root: B256,
+        _hashed_address: B256,
+        origin: B256,
+        _limit: B256,
+        _max_slots: usize,
+    ) -> ProviderResult<Option<SnapStorageRange>> {
+        if state_root != self.state_root {
+            return Ok(None)
+        }
+        let slots = self.storage.iter().copied().filter(|(slot, _)| *slot >= origin).collect();
+        let proof = if origin.is_zero() { Vec::new() } else { vec![Bytes::from_static(&[0xc0])] };
+        Ok(Some(SnapStorageRange { slots, proof }))
+    }
+
+    fn snap_trie_nodes(
+        &self,
+        state_root: B256,
+        paths: &[Vec<Bytes>],
+        _max_bytes: usize,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        if state_root != self.state_root {
+            return Ok(None)
+        }
+        Ok(Some(paths.iter().map_while(|path| self.nodes.get(path).cloned()).collect()))
+    }
+
+    fn snap_bytecodes(
+        &self,
+        code_hashes: &[B256],
+        _max_bytes: usize,
+    ) -> ProviderResult<Vec<Bytes>> {
+        Ok(code_hashes.iter().filter_map(|hash| self.codes.get(hash).cloned()).collect())
+    }
+}
+
+/// Waits until the handle is connected to the peer over `snap`.
+async fn wait_for_snap_peer(handle: &SnapHandle, peer_id: PeerId) {
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while !handle.peers().contains(&peer_id) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("snap connection not established");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snap_requests() {
+    reth_tracing::init_test_tracing();
+    let mut net = Testnet::create_with(2, MockEthProvider::default()).await;
+
+    let state = MockSnapState::new();
+    let server = SnapProtocolHandler::new(state.clone());
+    net.peers_mut()[0].add_rlpx_sub_protocol(server);
+
+    let client = SnapProtocolHandler::new(MockSnapState::new());
+    let snap = client.handle();
+    net.peers_mut()[1].add_rlpx_sub_protocol(client);
+
+    let handle = net.spawn();
+    handle.connect_peers().await;
+
+    let server_id = *handle.peers()[0].peer_id();
+    wait_for_snap_peer(&snap, server_id).await;
+
+    // account range with the slim encoding of the accounts
+    let range = snap
+        .get_account_range(
+            server_id,
+            state.state_root,
+            B256::ZERO,
+            B256::repeat_byte(0xff),
+            SNAP_SOFT_RESPONSE_LIMIT,
+        )
+        .await
+        .unwrap();
+    assert_eq!(range.accounts.len(), state.accounts.len());
+    assert!(!range.proof.is_empty());
+    for (data, expected) in range.accounts.iter().zip(&state.accounts) {
+        assert_eq!(data.hash, expected.hashed_address);
+        assert_eq!(
+            data.body,
+            SlimAccount::new(
+                expected.account.nonce,
+                expected.account.balance,
+                expected.storage_root,
+                expected.account.get_bytecode_hash(),
+            )
+        );
+    }
+
+    // unknown state root is answered with an empty response
+    let range = snap
+        .get_account_range(
+            server_id,
+            B256::repeat_byte(0xbb),
+            B256::ZERO,
+            B256::repeat_byte(0xff),
+            SNAP_SOFT_RESPONSE_LIMIT,
+        )
+        .await
+        .unwrap();
+    assert!(range.accounts.is_empty());
+    assert!(range.proof.is_empty());
+
+    // whole storage of multiple accounts, without proof
+    let accounts = state.accounts.iter().take(2).map(|account| account.hashed_address).collect();
+    let ranges = snap
+        .get_storage_ranges(
+            server_id,
+            state.state_root,
+            accounts,
+            Bytes::new(),
+            Bytes::new(),
+            SNAP_SOFT_RESPONSE_LIMIT,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ranges.slots.len(), 2);
+    assert_eq!(ranges.slots[0].len(), state.storage.len());
+    assert_eq!(ranges.slots[0][0].data, Bytes::from_static(&[0x01]));
+    assert!(ranges.proof.is_empty());
+
+    // partial storage range stops at the first account
+    let accounts = state.accounts.iter().take(2).map(|account| account.hashed_address).collect();
+    let ranges = snap
+        .get_storage_ranges(
+            server_id,
+            state.state_root,
+            accounts,
+            Bytes::copy_from_slice(B256::with_last_byte(3).as_slice()),
+            Bytes::new(),
+            SNAP_SOFT_RESPONSE_LIMIT,
+        )
+        .await
+        .unwrap();
+    assert_eq!(ranges.slots.len(), 1);
+    assert_eq!(ranges.slots[0].len(), 3);
+    assert!(!ranges.proof.is_empty());
+
+    // bytecodes skip the unknown hashes
+    let code_hash = state.accounts.iter().find_map(|account| account.account.bytecode_hash);
+    let codes = snap
+        .get_byte_codes(
+            server_id,
+            vec![B256::repeat_byte(0x01), code_hash.unwrap()],
+            SNAP_SOFT_RESPONSE_LIMIT,
+        )
+        .await
+        .unwrap();
+    assert_eq!(codes.codes, state.codes.values().cloned().collect::<Vec<_>>());
+
+    // trie nodes in request order, up to the first unknown path
+    let paths = vec![
+        vec![Bytes::new()],
+        vec![Bytes::from_static(&[0x1a])],
+        vec![Bytes::from_static(&[0x1b])],
+        vec![Bytes::new()],
+    ];
+    let nodes = snap
+        .get_trie_nodes(server_id, state.state_root, paths, SNAP_SOFT_RESPONSE_LIMIT)
+        .await
+        .unwrap();
+    assert_eq!(
+        nodes.nodes,
+        vec![Bytes::from_static(&[0xc1, 0x01]), Bytes::from_static(&[0xc1, 0x02])]
+    );
+
+    // trie nodes of an unknown state root are not served
+    let nodes = snap
+        .get_trie_nodes(
+            server_id,
+            B256::repeat_byte(0xbb),
+            vec![vec![Bytes::new()]],
+            SNAP_SOFT_RESPONSE_LIMIT,
+        )
+        .await
+        .unwrap();
+    assert!(nodes.nodes.is_empty());
+}
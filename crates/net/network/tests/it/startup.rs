@@ -1,9 +1,11 @@
+use reth_chainspec::MAINNET;
 use reth_discv4::Discv4Config;
 use reth_network::{
     error::{NetworkError, ServiceKind},
     Discovery, NetworkConfigBuilder, NetworkManager,
 };
 use reth_network_api::{NetworkInfo, PeersInfo};
+use reth_primitives::Head;
 use reth_provider::test_utils::NoopProvider;
 use secp256k1::SecretKey;
 use std::{
@@ -59,10 +61,14 @@ async fn test_discovery_addr_in_use() {
     let any_port_listener = TcpListener::bind(addr).await.unwrap();
     let port = any_port_listener.local_addr().unwrap().port();
     let addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, port));
+    let fork_filter = MAINNET.fork_filter(Head::default());
     let _discovery =
-        Discovery::new(addr, addr, secret_key, Some(disc_config), None, None).await.unwrap();
+        Discovery::new(addr, addr, secret_key, Some(disc_config), None, None, fork_filter.clone())
+            .await
+            .unwrap();
     let disc_config = Discv4Config::default();
-    let result = Discovery::new(addr, addr, secret_key, Some(disc_config), None, None).await;
+    let result =
+        Discovery::new(addr, addr, secret_key, Some(disc_config), None, None, fork_filter).await;
     assert!(is_addr_in_use_kind(&result.err().unwrap(), ServiceKind::Discovery(addr)));
 }
 
@@ -1,11 +1,12 @@
 use std::{
+    ops::RangeInclusive,
     pin::Pin,
     task::{ready, Context, Poll},
 };
 
 use crate::{download::DownloadClient, error::PeerRequestResult, priority::Priority};
 use futures::{Future, FutureExt};
-use reth_primitives::{BlockBody, B256};
+use reth_primitives::{BlockBody, BlockNumber, B256};
 
 /// The bodies future type
 pub type BodiesFut = Pin<Box<dyn Future<Output = PeerRequestResult<Vec<BlockBody>>> + Send + Sync>>;
@@ -25,6 +26,21 @@ pub trait BodiesClient: DownloadClient {
     fn get_block_bodies_with_priority(&self, hashes: Vec<B256>, priority: Priority)
         -> Self::Output;
 
+    /// Fetches the block bodies for the requested blocks with priority, given the range of the
+    /// block numbers.
+    ///
+    /// The range allows the client to only ask peers that still serve these blocks. By default,
+    /// the range is ignored.
+    fn get_block_bodies_in_range_with_priority(
+        &self,
+        hashes: Vec<B256>,
+        range: RangeInclusive<BlockNumber>,
+        priority: Priority,
+    ) -> Self::Output {
+        let _ = range;
+        self.get_block_bodies_with_priority(hashes, priority)
+    }
+
     /// Fetches a single block body for the requested hash.
     fn get_block_body(&self, hash: B256) -> SingleBodyRequest<Self::Output> {
         self.get_block_body_with_priority(hash, Priority::Normal)
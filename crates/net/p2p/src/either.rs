@@ -6,7 +6,8 @@ use crate::{
     headers::client::{HeadersClient, HeadersRequest},
     priority::Priority,
 };
-use reth_primitives::B256;
+use reth_primitives::{BlockNumber, B256};
+use std::ops::RangeInclusive;
 
 pub use futures::future::Either;
 
@@ -46,6 +47,22 @@ where
             Self::Right(b) => Either::Right(b.get_block_bodies_with_priority(hashes, priority)),
         }
     }
+
+    fn get_block_bodies_in_range_with_priority(
+        &self,
+        hashes: Vec<B256>,
+        range: RangeInclusive<BlockNumber>,
+        priority: Priority,
+    ) -> Self::Output {
+        match self {
+            Self::Left(a) => {
+                Either::Left(a.get_block_bodies_in_range_with_priority(hashes, range, priority))
+            }
+            Self::Right(b) => {
+                Either::Right(b.get_block_bodies_in_range_with_priority(hashes, range, priority))
+            }
+        }
+    }
 }
 
 impl<A, B> HeadersClient for Either<A, B>
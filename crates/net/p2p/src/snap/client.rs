@@ -0,0 +1,62 @@
+use crate::error::RequestResult;
+use futures::Future;
+use reth_eth_wire_types::{AccountRange, ByteCodes, StorageRanges, TrieNodes};
+use reth_network_peers::PeerId;
+use reth_primitives::{Bytes, B256};
+use std::{fmt::Debug, pin::Pin};
+
+/// The future of a `snap` request.
+pub type SnapFut<T> = Pin<Box<dyn Future<Output = RequestResult<T>> + Send + Sync>>;
+
+/// A client capable of sending `snap` requests to the connected peers.
+///
+/// Unlike the `eth` clients, the requests are sent to a given peer: the responses of the `snap`
+/// protocol are only valid for the state root the peer serves, so the caller tracks which peers
+/// serve the requested state.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait SnapClient: Send + Sync + Debug {
+    /// Returns the peers that are connected over `snap`.
+    fn snap_peers(&self) -> Vec<PeerId>;
+
+    /// Requests the accounts of the state trie with the given root, starting at the given hash
+    /// and up to the limit hash.
+    fn get_account_range(
+        &self,
+        peer_id: PeerId,
+        root_hash: B256,
+        starting_hash: B256,
+        limit_hash: B256,
+        response_bytes: u64,
+    ) -> SnapFut<AccountRange>;
+
+    /// Requests the storage slots of the given accounts of the state trie with the given root.
+    ///
+    /// The starting and limit hash only apply to the first account, and are empty if the whole
+    /// storage is requested.
+    fn get_storage_ranges(
+        &self,
+        peer_id: PeerId,
+        root_hash: B256,
+        account_hashes: Vec<B256>,
+        starting_hash: Bytes,
+        limit_hash: Bytes,
+        response_bytes: u64,
+    ) -> SnapFut<StorageRanges>;
+
+    /// Requests the contract bytecodes with the given hashes.
+    fn get_byte_codes(
+        &self,
+        peer_id: PeerId,
+        hashes: Vec<B256>,
+        response_bytes: u64,
+    ) -> SnapFut<ByteCodes>;
+
+    /// Requests the trie nodes at the given paths of the state trie with the given root.
+    fn get_trie_nodes(
+        &self,
+        peer_id: PeerId,
+        root_hash: B256,
+        paths: Vec<Vec<Bytes>>,
+        response_bytes: u64,
+    ) -> SnapFut<TrieNodes>;
+}
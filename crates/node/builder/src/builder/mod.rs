@@ -566,6 +566,8 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.executor.spawn_critical("p2p eth request handler", eth);
 
         let default_peers_path = self.config().datadir().known_peers();
+        let peer_reputations_file =
+            self.config().network.persistent_peer_reputations_file(default_peers_path.clone());
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
@@ -583,6 +585,16 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
                             }
                         }
                     }
+                    if let Some(reputations_file) = peer_reputations_file {
+                        match network.write_peer_states_to_file(reputations_file.as_path()) {
+                            Ok(_) => {
+                                info!(target: "reth::cli", reputations_file=?reputations_file, "Wrote peer reputations to file");
+                            }
+                            Err(err) => {
+                                warn!(target: "reth::cli", %err, "Failed to write peer reputations to file");
+                            }
+                        }
+                    }
                 })
             },
         );
@@ -608,7 +620,7 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
                 self.config().chain.clone(),
                 secret_key,
                 default_peers_path,
-            )
+            )?
             .with_task_executor(Box::new(self.executor.clone()))
             .set_head(self.head);
 
@@ -215,6 +215,7 @@ where
             let pipeline = crate::setup::build_networked_pipeline(
                 &ctx.toml_config().stages,
                 client.clone(),
+                None,
                 ctx.consensus(),
                 ctx.provider_factory().clone(),
                 ctx.task_executor(),
@@ -236,6 +237,7 @@ where
             let pipeline = crate::setup::build_networked_pipeline(
                 &ctx.toml_config().stages,
                 network_client.clone(),
+                ctx.components().network().snap().cloned(),
                 ctx.consensus(),
                 ctx.provider_factory().clone(),
                 ctx.task_executor(),
@@ -349,6 +351,7 @@ where
             ctx.node_adapter().clone(),
             engine_api,
             ctx.node_config(),
+            ctx.toml_config().parlia.clone(),
             jwt_secret,
             rpc,
         )
@@ -5,6 +5,7 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+use eyre::WrapErr;
 use futures::TryFutureExt;
 use reth_network::NetworkHandle;
 use reth_node_api::{BuilderProvider, FullNodeComponents};
@@ -13,11 +14,13 @@ use reth_node_core::{
     rpc::{api::EngineApiServer, eth::FullEthApiServer},
 };
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_primitives::parlia::ParliaConfig;
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
     RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
+use reth_rpc_eth_types::StateOverridePresets;
 use reth_rpc_layer::JwtSecret;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, info};
@@ -252,10 +255,13 @@ impl<'a, Node: FullNodeComponents, EthApi> RpcContext<'a, Node, EthApi> {
 }
 
 /// Launch the rpc servers.
+///
+/// `parlia` is the configured parlia consensus config `debug` calls re-execute blocks with.
 pub async fn launch_rpc_servers<Node, Engine, EthApi>(
     node: Node,
     engine_api: Engine,
     config: &NodeConfig,
+    parlia: ParliaConfig,
     jwt_secret: JwtSecret,
     add_ons: RpcAddOns<Node, EthApi>,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node, EthApi>)>
@@ -265,7 +271,17 @@ where
     Engine: EngineApiServer<Node::Engine>,
 {
     let auth_config = config.rpc.auth_server_config(jwt_secret)?;
-    let module_config = config.rpc.transport_rpc_module_config();
+    let mut module_config = config.rpc.transport_rpc_module_config();
+    if let Some(mut rpc_module_config) = module_config.config_mut().take() {
+        let path = config.datadir().state_override_presets();
+        let presets = StateOverridePresets::load(&path).wrap_err_with(|| {
+            format!("failed to load state override presets from {}", path.display())
+        })?;
+        rpc_module_config.eth_mut().state_override_presets = presets;
+        module_config = module_config.with_config(
+            rpc_module_config.with_trace_dir(config.datadir().traces()).with_parlia(parlia),
+        );
+    }
     debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
     let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
@@ -275,6 +291,7 @@ where
         .with_events(node.provider().clone())
         .with_executor(node.task_executor().clone())
         .with_evm_config(node.evm_config().clone())
+        .with_miner(node.payload_builder().miner().cloned())
         .build_with_auth_server(module_config, engine_api, EthApi::eth_api_builder());
 
     let mut registry = RpcRegistry { registry };
@@ -9,24 +9,32 @@ use reth_downloaders::{
 };
 use reth_evm::execute::BlockExecutorProvider;
 use reth_exex::ExExManagerHandle;
+use reth_network::snap::SnapHandle;
 use reth_network_p2p::{
     bodies::{client::BodiesClient, downloader::BodyDownloader},
     headers::{client::HeadersClient, downloader::HeaderDownloader},
 };
 use reth_node_core::primitives::{BlockNumber, B256};
 use reth_provider::ProviderFactory;
-use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_stages::{
+    prelude::DefaultStages,
+    stages::{ExecutionStage, SnapSyncStage},
+    Pipeline, StageId, StageSet,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
-use reth_tracing::tracing::debug;
+use reth_tracing::tracing::{debug, warn};
 use std::sync::Arc;
 use tokio::sync::watch;
 
 /// Constructs a [Pipeline] that's wired to the network
+///
+/// The snap sync stage is only added if it's enabled and a `snap` client is given.
 #[allow(clippy::too_many_arguments)]
 pub fn build_networked_pipeline<DB, Client, Executor>(
     config: &StageConfig,
     client: Client,
+    snap_client: Option<SnapHandle>,
     consensus: Arc<dyn Consensus>,
     provider_factory: ProviderFactory<DB>,
     task_executor: &TaskExecutor,
@@ -56,6 +64,7 @@ where
         config,
         header_downloader,
         body_downloader,
+        snap_client,
         consensus,
         max_block,
         metrics_tx,
@@ -75,6 +84,7 @@ pub fn build_pipeline<DB, H, B, Executor>(
     stage_config: &StageConfig,
     header_downloader: H,
     body_downloader: B,
+    snap_client: Option<SnapHandle>,
     consensus: Arc<dyn Consensus>,
     max_block: Option<u64>,
     metrics_tx: reth_stages::MetricEventsSender,
@@ -100,31 +110,49 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    if stage_config.snap_sync.enabled && snap_client.is_none() {
+        warn!(target: "reth::cli", "Snap sync is enabled, but the network doesn't support snap");
+    }
+    let snap_sync = snap_client.filter(|_| stage_config.snap_sync.enabled).map(|client| {
+        let optional_stages = [
+            (stage_config.index_address_transactions.enabled, StageId::IndexAddressTransactions),
+            (stage_config.preimages.enabled, StageId::Preimages),
+            (stage_config.index_logs.enabled, StageId::IndexLogs),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, stage_id)| enabled.then_some(stage_id));
+        SnapSyncStage::new(client, stage_config.snap_sync, prune_modes.clone())
+            .with_optional_stages(optional_stages)
+    });
+
+    let mut stages = DefaultStages::new(
+        provider_factory.clone(),
+        tip_rx,
+        Arc::clone(&consensus),
+        header_downloader,
+        body_downloader,
+        executor.clone(),
+        stage_config.clone(),
+        prune_modes.clone(),
+    )
+    .set(
+        ExecutionStage::new(
+            executor,
+            stage_config.execution.into(),
+            stage_config.execution_external_clean_threshold(),
+            prune_modes,
+            exex_manager_handle,
+        )
+        .with_metrics_tx(metrics_tx.clone()),
+    );
+    if let Some(snap_sync) = snap_sync {
+        stages = stages.add_after(snap_sync, StageId::Bodies);
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
-        .with_metrics_tx(metrics_tx.clone())
-        .add_stages(
-            DefaultStages::new(
-                provider_factory.clone(),
-                tip_rx,
-                Arc::clone(&consensus),
-                header_downloader,
-                body_downloader,
-                executor.clone(),
-                stage_config.clone(),
-                prune_modes.clone(),
-            )
-            .set(
-                ExecutionStage::new(
-                    executor,
-                    stage_config.execution.into(),
-                    stage_config.execution_external_clean_threshold(),
-                    prune_modes,
-                    exex_manager_handle,
-                )
-                .with_metrics_tx(metrics_tx),
-            ),
-        )
+        .with_metrics_tx(metrics_tx)
+        .add_stages(stages)
         .build(provider_factory, static_file_producer);
 
     Ok(pipeline)
@@ -1,7 +1,7 @@
 //! clap [Args](clap::Args) for network related arguments.
 
 use crate::version::P2P_CLIENT_VERSION;
-use clap::Args;
+use clap::{builder::RangedU64ValueParser, Args};
 use reth_chainspec::ChainSpec;
 use reth_config::Config;
 use reth_discv4::{NodeRecord, DEFAULT_DISCOVERY_ADDR, DEFAULT_DISCOVERY_PORT};
@@ -9,14 +9,16 @@ use reth_discv5::{
     discv5::ListenConfig, DEFAULT_COUNT_BOOTSTRAP_LOOKUPS, DEFAULT_DISCOVERY_V5_PORT,
     DEFAULT_SECONDS_BOOTSTRAP_LOOKUP_INTERVAL, DEFAULT_SECONDS_LOOKUP_INTERVAL,
 };
+use reth_dns_discovery::{tree::LinkEntry, DnsDiscoveryConfig};
 use reth_net_nat::NatResolver;
 use reth_network::{
+    eth_requests::EthRequestLimits,
     transactions::{
         TransactionFetcherConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
-    HelloMessageWithProtocols, NetworkConfigBuilder, SessionsConfig,
+    HelloMessageWithProtocols, NetworkConfigBuilder, PeeringMode, PeersConfig, SessionsConfig,
 };
 use reth_network_peers::{mainnet_nodes, TrustedPeer};
 use secp256k1::SecretKey;
@@ -27,6 +29,9 @@ use std::{
     sync::Arc,
 };
 
+/// The name of the file the peer reputations are persisted to, next to the peers file.
+pub const PEER_REPUTATIONS_FILE_NAME: &str = "peer-reputations.json";
+
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Networking")]
@@ -46,6 +51,24 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub trusted_only: bool,
 
+    /// The role of the node in a validator/sentry deployment.
+    ///
+    /// A validator only connects to the peers of the allow-list, disables discovery and ignores
+    /// gossiped transactions. A sentry relays new blocks and votes to the peers of its allow-list
+    /// first.
+    ///
+    /// Overrides the peering mode of the config file.
+    #[arg(long = "peering.mode", value_name = "MODE")]
+    pub peering_mode: Option<PeeringMode>,
+
+    #[allow(clippy::doc_markdown)]
+    /// Comma separated enode URLs of the peers a validator connects to, or of the validators a
+    /// sentry protects.
+    ///
+    /// --peering.allow-list enode://abcd@192.168.0.1:30303
+    #[arg(long = "peering.allow-list", value_delimiter = ',')]
+    pub allow_list: Vec<TrustedPeer>,
+
     /// Comma separated enode URLs for P2P discovery bootstrap.
     ///
     /// Will fall back to a network-specific default if not specified.
@@ -115,9 +138,62 @@ pub struct NetworkArgs {
     /// Default is 128 KiB.
     #[arg(long = "pooled-tx-pack-soft-limit", value_name = "BYTES", default_value_t = DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ, verbatim_doc_comment)]
     pub soft_limit_byte_size_pooled_transactions_response_on_pack_request: usize,
+
+    /// Maximum bytes per second served to a single peer in response to header, body and receipt
+    /// requests. Unlimited by default.
+    #[arg(
+        long = "serve.peer-rate-limit",
+        value_name = "BYTES_PER_SEC",
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..)
+    )]
+    pub serve_peer_rate_limit: Option<u64>,
+
+    /// Maximum bytes per second served in response to header requests, across all peers.
+    /// Unlimited by default.
+    #[arg(
+        long = "serve.headers-rate-limit",
+        value_name = "BYTES_PER_SEC",
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..)
+    )]
+    pub serve_headers_rate_limit: Option<u64>,
+
+    /// Maximum bytes per second served in response to body requests, across all peers. Unlimited
+    /// by default.
+    #[arg(
+        long = "serve.bodies-rate-limit",
+        value_name = "BYTES_PER_SEC",
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..)
+    )]
+    pub serve_bodies_rate_limit: Option<u64>,
+
+    /// Maximum bytes per second served in response to receipt requests, across all peers.
+    /// Unlimited by default.
+    #[arg(
+        long = "serve.receipts-rate-limit",
+        value_name = "BYTES_PER_SEC",
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..)
+    )]
+    pub serve_receipts_rate_limit: Option<u64>,
+
+    /// Directory to write a capture of the decrypted messages of every peer session to, one file
+    /// per session.
+    ///
+    /// For debugging only, the captures grow quickly. They can be replayed with `reth p2p replay`.
+    #[arg(long = "p2p.capture-dir", value_name = "DIR")]
+    pub capture_dir: Option<PathBuf>,
 }
 
 impl NetworkArgs {
+    /// Returns the limits on the bytes served in response to `eth` requests.
+    pub const fn eth_request_limits(&self) -> EthRequestLimits {
+        EthRequestLimits {
+            peer_bytes_per_sec: self.serve_peer_rate_limit,
+            headers_bytes_per_sec: self.serve_headers_rate_limit,
+            bodies_bytes_per_sec: self.serve_bodies_rate_limit,
+            receipts_bytes_per_sec: self.serve_receipts_rate_limit,
+        }
+    }
+
     /// Build a [`NetworkConfigBuilder`] from a [`Config`] and a [`ChainSpec`], in addition to the
     /// values in this option struct.
     ///
@@ -129,13 +205,15 @@ impl NetworkArgs {
     /// 1. --bootnodes flag
     /// 2. Network preset flags (e.g. --holesky)
     /// 3. default to mainnet nodes
+    ///
+    /// Returns an error if a peer of the allow-list can't be resolved.
     pub fn network_config(
         &self,
         config: &Config,
         chain_spec: Arc<ChainSpec>,
         secret_key: SecretKey,
         default_peers_file: PathBuf,
-    ) -> NetworkConfigBuilder {
+    ) -> Result<NetworkConfigBuilder, std::io::Error> {
         let chain_bootnodes = self
             .bootnodes
             .clone()
@@ -146,14 +224,23 @@ impl NetworkArgs {
                     .collect()
             })
             .unwrap_or_else(|| chain_spec.bootnodes().unwrap_or_else(mainnet_nodes));
+        let is_bsc = chain_spec.is_bsc();
+        let peer_reputations_file =
+            self.persistent_peer_reputations_file(default_peers_file.clone());
         let peers_file = self.peers_file.clone().unwrap_or(default_peers_file);
 
         // Configure peer connections
         let peers_config = config
-            .peers
-            .clone()
+            .peers_config_with_basic_nodes_from_file(
+                self.persistent_peers_file(peers_file).as_deref(),
+            )
             .with_max_inbound_opt(self.max_inbound_peers)
             .with_max_outbound_opt(self.max_outbound_peers);
+        let peers_config = self.apply_peering_to_config(peers_config)?;
+        let peers_config = peers_config
+            .clone()
+            .with_peer_states_from_file(peer_reputations_file)
+            .unwrap_or(peers_config);
 
         // Configure transactions manager
         let transactions_manager_config = TransactionsManagerConfig {
@@ -161,21 +248,24 @@ impl NetworkArgs {
                 self.soft_limit_byte_size_pooled_transactions_response,
                 self.soft_limit_byte_size_pooled_transactions_response_on_pack_request,
             ),
+            ..Default::default()
         };
 
+        let mut sessions_config =
+            SessionsConfig::default().with_upscaled_event_buffer(peers_config.max_peers());
+        if let Some(capture_dir) = &self.capture_dir {
+            sessions_config = sessions_config.with_capture_dir(capture_dir);
+        }
+
         // Configure basic network stack
-        NetworkConfigBuilder::new(secret_key)
-            .peer_config(config.peers_config_with_basic_nodes_from_file(
-                self.persistent_peers_file(peers_file).as_deref(),
-            ))
+        let builder = NetworkConfigBuilder::new(secret_key)
             .external_ip_resolver(self.nat)
-            .sessions_config(
-                SessionsConfig::default().with_upscaled_event_buffer(peers_config.max_peers()),
-            )
+            .sessions_config(sessions_config)
             .peer_config(peers_config)
             .boot_nodes(chain_bootnodes.clone())
             .chain_spec(chain_spec)
             .transactions_manager_config(transactions_manager_config)
+            .eth_request_limits(self.eth_request_limits())
             // Configure node identity
             .apply(|builder| {
                 let peer_id = builder.get_peer_id();
@@ -188,7 +278,13 @@ impl NetworkArgs {
             // apply discovery settings
             .apply(|builder| {
                 let rlpx_socket = (self.addr, self.port).into();
-                self.discovery.apply_to_builder(builder, rlpx_socket, chain_bootnodes)
+                // discv5 records carry the fork id, which lets BSC nodes skip the peers of other
+                // BSC-like chains before dialing them
+                self.discovery.clone().with_discv5_discovery_default(is_bsc).apply_to_builder(
+                    builder,
+                    rlpx_socket,
+                    chain_bootnodes,
+                )
             })
             .listener_addr(SocketAddr::new(
                 self.addr, // set discovery port based on instance number
@@ -198,7 +294,32 @@ impl NetworkArgs {
                 self.discovery.addr,
                 // set discovery port based on instance number
                 self.discovery.port,
-            ))
+            ));
+
+        Ok(builder)
+    }
+
+    /// Applies the peering mode and the allow-list to the given peers config.
+    ///
+    /// The allow-list peers are added to the ones of the config file. Fails if one of them can't
+    /// be resolved, since a validator would otherwise silently lose its sentries.
+    fn apply_peering_to_config(
+        &self,
+        mut peers_config: PeersConfig,
+    ) -> Result<PeersConfig, std::io::Error> {
+        if let Some(peering_mode) = self.peering_mode {
+            peers_config = peers_config.with_peering_mode(peering_mode);
+        }
+        for peer in &self.allow_list {
+            let record = peer.resolve_blocking().map_err(|err| {
+                std::io::Error::new(
+                    err.kind(),
+                    format!("failed to resolve allow-list peer {peer}: {err}"),
+                )
+            })?;
+            peers_config.allow_list.insert(record);
+        }
+        Ok(peers_config)
     }
 
     /// If `no_persist_peers` is false then this returns the path to the persistent peers file path.
@@ -206,6 +327,15 @@ impl NetworkArgs {
         self.no_persist_peers.not().then_some(peers_file)
     }
 
+    /// If `no_persist_peers` is false then this returns the path to the persisted peer
+    /// reputations, which are stored next to the peers file.
+    ///
+    /// The `default_peers_file` is used if there is no provided `peers_file`.
+    pub fn persistent_peer_reputations_file(&self, default_peers_file: PathBuf) -> Option<PathBuf> {
+        let peers_file = self.peers_file.clone().unwrap_or(default_peers_file);
+        self.persistent_peers_file(peers_file.with_file_name(PEER_REPUTATIONS_FILE_NAME))
+    }
+
     /// Sets the p2p port to zero, to allow the OS to assign a random unused port when
     /// the network components bind to a socket.
     pub const fn with_unused_p2p_port(mut self) -> Self {
@@ -247,6 +377,8 @@ impl Default for NetworkArgs {
             discovery: DiscoveryArgs::default(),
             trusted_peers: vec![],
             trusted_only: false,
+            peering_mode: None,
+            allow_list: vec![],
             bootnodes: None,
             dns_retries: 0,
             peers_file: None,
@@ -261,6 +393,11 @@ impl Default for NetworkArgs {
             soft_limit_byte_size_pooled_transactions_response:
                 SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
             soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
+            serve_peer_rate_limit: None,
+            serve_headers_rate_limit: None,
+            serve_bodies_rate_limit: None,
+            serve_receipts_rate_limit: None,
+            capture_dir: None,
         }
     }
 }
@@ -281,9 +418,21 @@ pub struct DiscoveryArgs {
     pub disable_discv4_discovery: bool,
 
     /// Enable Discv5 discovery.
+    ///
+    /// Enabled by default on BSC networks.
     #[arg(long, conflicts_with = "disable_discovery")]
     pub enable_discv5_discovery: bool,
 
+    /// Disable Discv5 discovery on networks that enable it by default.
+    #[arg(long, conflicts_with_all = ["disable_discovery", "enable_discv5_discovery"])]
+    pub disable_discv5_discovery: bool,
+
+    /// The EIP-1459 DNS trees to discover peers from, e.g. `enrtree://<key>@<domain>`.
+    ///
+    /// Replaces the default tree of the chain, if any. There is no default tree for BSC networks.
+    #[arg(id = "discovery.dns-network", long = "discovery.dns-network", value_name = "ENRTREE", value_delimiter = ',', conflicts_with_all = ["disable_discovery", "disable_dns_discovery"])]
+    pub dns_networks: Vec<LinkEntry>,
+
     /// The UDP address to use for devp2p peer discovery version 4.
     #[arg(id = "discovery.addr", long = "discovery.addr", value_name = "DISCOVERY_ADDR", default_value_t = DEFAULT_DISCOVERY_ADDR)]
     pub addr: IpAddr,
@@ -341,6 +490,11 @@ impl DiscoveryArgs {
     ) -> NetworkConfigBuilder {
         if self.disable_discovery || self.disable_dns_discovery {
             network_config_builder = network_config_builder.disable_dns_discovery();
+        } else if !self.dns_networks.is_empty() {
+            network_config_builder = network_config_builder.dns_discovery(DnsDiscoveryConfig {
+                bootstrap_dns_networks: Some(self.dns_networks.iter().cloned().collect()),
+                ..Default::default()
+            });
         }
 
         if self.disable_discovery || self.disable_discv4_discovery {
@@ -355,6 +509,14 @@ impl DiscoveryArgs {
         network_config_builder
     }
 
+    /// Enables Discv5 discovery if it's enabled by default, unless it's disabled explicitly.
+    pub const fn with_discv5_discovery_default(mut self, enabled_by_default: bool) -> Self {
+        if enabled_by_default && !self.disable_discv5_discovery {
+            self.enable_discv5_discovery = true;
+        }
+        self
+    }
+
     /// Creates a [`reth_discv5::ConfigBuilder`] filling it with the values from this struct.
     pub fn discovery_v5_builder(
         &self,
@@ -423,6 +585,8 @@ impl Default for DiscoveryArgs {
             disable_dns_discovery: false,
             disable_discv4_discovery: false,
             enable_discv5_discovery: false,
+            disable_discv5_discovery: false,
+            dns_networks: Vec::new(),
             addr: DEFAULT_DISCOVERY_ADDR,
             port: DEFAULT_DISCOVERY_PORT,
             discv5_addr: None,
@@ -495,6 +659,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_peering_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--peering.mode",
+            "sentry",
+            "--peering.allow-list",
+            "enode://d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666@18.138.108.67:30303",
+        ])
+        .args;
+
+        assert_eq!(args.peering_mode, Some(PeeringMode::Sentry));
+        assert_eq!(args.allow_list.len(), 1);
+
+        let peers_config = args.apply_peering_to_config(PeersConfig::default()).unwrap();
+        assert!(peers_config.peering_mode.is_sentry());
+        assert_eq!(peers_config.allow_list.len(), 1);
+    }
+
+    #[test]
+    fn unresolvable_allow_list_peer() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--peering.allow-list",
+            "enode://d860a01f9722d78051619d1e2351aba3f43f943f6f00718d1b9baa4101932a1f5011f16bb2b1bb35db20d6fe28fa0bf09636d26a87d31de9ec6203eeedb1f666@sentry.invalid:30303",
+        ])
+        .args;
+
+        assert!(args.apply_peering_to_config(PeersConfig::default()).is_err());
+    }
+
     #[test]
     fn parse_retry_strategy_args() {
         let tests = vec![0, 10];
@@ -511,6 +706,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_dns_networks() {
+        let link =
+            "enrtree://AM5FCQLWIZX2QFPNJAP7VUERCCRNGRHWZG3YYHIUV7BVDQ5FDPRT2@nodes.example.org";
+        let args =
+            CommandParser::<NetworkArgs>::parse_from(["reth", "--discovery.dns-network", link])
+                .args;
+
+        assert_eq!(args.discovery.dns_networks, vec![link.parse().unwrap()]);
+    }
+
+    #[test]
+    fn parse_discv5_discovery_default() {
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert!(args.discovery.with_discv5_discovery_default(true).enable_discv5_discovery);
+
+        let args =
+            CommandParser::<NetworkArgs>::parse_from(["reth", "--disable-discv5-discovery"]).args;
+        assert!(!args.discovery.with_discv5_discovery_default(true).enable_discv5_discovery);
+
+        assert!(CommandParser::<NetworkArgs>::try_parse_from([
+            "reth",
+            "--enable-discv5-discovery",
+            "--disable-discv5-discovery"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_serve_rate_limits() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--serve.peer-rate-limit",
+            "1000000",
+            "--serve.bodies-rate-limit",
+            "5000000",
+        ])
+        .args;
+
+        assert_eq!(
+            args.eth_request_limits(),
+            EthRequestLimits {
+                peer_bytes_per_sec: Some(1_000_000),
+                bodies_bytes_per_sec: Some(5_000_000),
+                ..Default::default()
+            }
+        );
+    }
+
     #[cfg(not(feature = "optimism"))]
     #[test]
     fn network_args_default_sanity_test() {
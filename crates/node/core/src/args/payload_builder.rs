@@ -4,8 +4,9 @@ use clap::{
     Arg, Args, Command,
 };
 use reth_cli_util::parse_duration_from_secs;
-use reth_primitives::constants::{
-    ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION,
+use reth_primitives::{
+    constants::{ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION},
+    Address,
 };
 use std::{borrow::Cow, ffi::OsStr, time::Duration};
 
@@ -32,6 +33,18 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// The address that receives the rewards of sealed blocks, e.g. the validator address.
+    #[arg(long = "builder.coinbase", value_name = "ADDRESS")]
+    pub coinbase: Option<Address>,
+
+    /// Minimum priority fee (in wei) a transaction must pay to be included in a sealed block.
+    #[arg(long = "builder.gasprice", default_value = "0", value_name = "WEI")]
+    pub gas_price: u128,
+
+    /// Start sealing blocks on startup. Requires `--builder.coinbase`.
+    #[arg(long = "builder.mine", requires = "coinbase")]
+    pub mine: bool,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -42,6 +55,9 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            coinbase: None,
+            gas_price: 0,
+            mine: false,
         }
     }
 }
@@ -66,6 +82,18 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn coinbase(&self) -> Option<Address> {
+        self.coinbase
+    }
+
+    fn gas_price(&self) -> u128 {
+        self.gas_price
+    }
+
+    fn mine(&self) -> bool {
+        self.mine
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -148,6 +176,22 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_mine_requires_coinbase() {
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from(["reth", "--builder.mine"])
+            .is_err());
+
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.mine",
+            "--builder.coinbase",
+            "0x0000000000000000000000000000000000000001",
+        ])
+        .args;
+        assert!(args.mine);
+        assert_eq!(args.coinbase, Some(Address::with_last_byte(1)));
+    }
+
     #[test]
     fn payload_builder_args_default_sanity_check() {
         let default_args = PayloadBuilderArgs::default();
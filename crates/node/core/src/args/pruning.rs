@@ -33,6 +33,8 @@ impl PruningArgs {
                     .map(|contract| PruneMode::Before(contract.block)),
                 account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                 storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                address_transactions: None,
+                preimages: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(
                     chain_spec
                         .deposit_contract
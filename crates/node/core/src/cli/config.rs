@@ -1,7 +1,7 @@
 //! Config traits for various node components.
 
 use reth_network::protocol::IntoRlpxSubProtocol;
-use reth_primitives::Bytes;
+use reth_primitives::{Address, Bytes};
 use reth_transaction_pool::PoolConfig;
 use std::{borrow::Cow, time::Duration};
 
@@ -29,6 +29,21 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// The address that receives the rewards of sealed blocks, if any.
+    fn coinbase(&self) -> Option<Address> {
+        None
+    }
+
+    /// Minimum priority fee a transaction must pay to be included in a sealed block.
+    fn gas_price(&self) -> u128 {
+        0
+    }
+
+    /// Whether the node should start sealing blocks on startup.
+    fn mine(&self) -> bool {
+        false
+    }
 }
 
 /// A trait that represents the configured network and can be used to apply additional configuration
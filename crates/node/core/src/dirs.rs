@@ -330,6 +330,20 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the directory trace files requested via RPC are written to.
+    ///
+    /// `<DIR>/<CHAIN_ID>/traces`
+    pub fn traces(&self) -> PathBuf {
+        self.data_dir().join("traces")
+    }
+
+    /// Returns the path to the file the named RPC state override presets are stored in.
+    ///
+    /// `<DIR>/<CHAIN_ID>/state-override-presets.json`
+    pub fn state_override_presets(&self) -> PathBuf {
+        self.data_dir().join("state-override-presets.json")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -3,6 +3,7 @@
 use crate::{l1::ensure_create2_deployer, OptimismBlockExecutionError, OptimismEvmConfig};
 use reth_chainspec::{ChainSpec, EthereumHardforks, OptimismHardfork};
 use reth_evm::{
+    call_addresses::CallAddressInspector,
     execute::{
         BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
         BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
@@ -94,6 +95,18 @@ where
             stats: BlockExecutorStats::default(),
         }
     }
+
+    fn call_addresses<DB>(
+        &self,
+        db: DB,
+        input: BlockExecutionInput<'_, BlockWithSenders, Header>,
+    ) -> Result<Option<Vec<Vec<Address>>>, BlockExecutionError>
+    where
+        DB: Database<Error: Into<ProviderError> + std::fmt::Display>,
+    {
+        let BlockExecutionInput { block, total_difficulty, .. } = input;
+        self.op_executor(db).trace_call_addresses(block, total_difficulty).map(Some)
+    }
 }
 
 /// Helper container type for EVM with chain spec.
@@ -313,6 +326,30 @@ where
         Ok((receipts, gas_used))
     }
 
+    /// Executes the transactions of the block like [`Self::execute_without_verification`] and
+    /// returns the addresses reached by the calls of every transaction, see
+    /// [`CallAddressInspector`].
+    fn trace_call_addresses(
+        &mut self,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+    ) -> Result<Vec<Vec<Address>>, BlockExecutionError> {
+        self.on_new_block(&block.header);
+
+        let env = self.evm_env_for_block(&block.header, total_difficulty);
+        let mut inspector = CallAddressInspector::default();
+        {
+            let evm = self.executor.evm_config.evm_with_env_and_inspector(
+                &mut self.state,
+                env,
+                &mut inspector,
+            );
+            self.executor.execute_pre_and_transactions(block, evm)
+        }?;
+
+        Ok(inspector.into_transaction_addresses(block))
+    }
+
     /// Apply settings before a new block is executed.
     pub(crate) fn on_new_block(&mut self, header: &Header) {
         // Set state clear flag if the block is after the Spurious Dragon hardfork.
@@ -4,7 +4,7 @@ use reth_primitives::{
     Bytes,
 };
 use reth_rpc_eth_api::helpers::Call;
-use reth_rpc_eth_types::EthResult;
+use reth_rpc_eth_types::{EthResult, StateOverridePresets};
 use reth_rpc_types::TransactionRequest;
 
 use crate::OpEthApi;
@@ -18,6 +18,10 @@ impl<Eth: Call> Call for OpEthApi<Eth> {
         self.inner.evm_config()
     }
 
+    fn state_override_presets(&self) -> &StateOverridePresets {
+        self.inner.state_override_presets()
+    }
+
     fn create_txn_env(
         &self,
         block_env: &BlockEnv,
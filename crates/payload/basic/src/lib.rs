@@ -16,7 +16,7 @@ use reth_payload_builder::{
     database::CachedReads, error::PayloadBuilderError, KeepPayloadJobAlive, PayloadId, PayloadJob,
     PayloadJobGenerator,
 };
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
+use reth_payload_primitives::{BuiltPayload, MinerError, MinerHandle, PayloadBuilderAttributes};
 use reth_primitives::{
     constants::{EMPTY_WITHDRAWALS, RETH_CLIENT_VERSION, SLOT_DURATION},
     proofs, BlockNumberOrTag, Bytes, SealedBlock, Withdrawals, B256, U256,
@@ -69,6 +69,8 @@ pub struct BasicPayloadJobGenerator<Client, Pool, Tasks, Builder> {
     builder: Builder,
     /// Stored `cached_reads` for new payload jobs.
     pre_cached: Option<PrecachedState>,
+    /// Runtime settings of local block production, if the node seals blocks itself.
+    miner: Option<MinerHandle>,
 }
 
 // === impl BasicPayloadJobGenerator ===
@@ -93,9 +95,22 @@ impl<Client, Pool, Tasks, Builder> BasicPayloadJobGenerator<Client, Pool, Tasks,
             chain_spec,
             builder,
             pre_cached: None,
+            miner: None,
         }
     }
 
+    /// Applies the settings of the given [`MinerHandle`] to all new payloads.
+    ///
+    /// The coinbase, extra data, gas limit and minimum priority fee of the handle take precedence
+    /// over the payload attributes and the [`BasicPayloadJobGeneratorConfig`]. No new payload jobs
+    /// are created and running jobs stop building while sealing is stopped.
+    ///
+    /// Without a miner, a job is created for every new payload attributes.
+    pub fn with_miner(mut self, miner: MinerHandle) -> Self {
+        self.miner = Some(miner);
+        self
+    }
+
     /// Returns the maximum duration a job should be allowed to run.
     ///
     /// This adheres to the following specification:
@@ -150,6 +165,10 @@ where
         &self,
         attributes: <Self::Job as PayloadJob>::PayloadAttributes,
     ) -> Result<Self::Job, PayloadBuilderError> {
+        if self.miner.as_ref().is_some_and(|miner| !miner.is_mining()) {
+            return Err(PayloadBuilderError::other(MinerError::NotMining))
+        }
+
         let parent_block = if attributes.parent().is_zero() {
             // use latest block if parent is zero: genesis block
             self.client
@@ -166,12 +185,24 @@ where
             block.seal(attributes.parent())
         };
 
-        let config = PayloadConfig::new(
+        let mut config = PayloadConfig::new(
             Arc::new(parent_block),
             self.config.extradata.clone(),
             attributes,
             Arc::clone(&self.chain_spec),
         );
+        if let Some(miner) = &self.miner {
+            let settings = miner.settings();
+            if let Some(coinbase) = miner.coinbase() {
+                config.initialized_block_env.coinbase = coinbase;
+            }
+            if let Some(extra_data) = settings.extra_data {
+                config.extra_data = extra_data;
+            }
+            config.initialized_block_env.gas_limit =
+                U256::from(miner.gas_limit_for(config.parent_block.gas_limit));
+            config.min_priority_fee = settings.gas_price;
+        }
 
         let until = self.job_deadline(config.attributes.timestamp());
         let deadline = Box::pin(tokio::time::sleep_until(until));
@@ -191,6 +222,7 @@ where
             payload_task_guard: self.payload_task_guard.clone(),
             metrics: Default::default(),
             builder: self.builder.clone(),
+            miner: self.miner.clone(),
         };
 
         // start the first job right away
@@ -348,6 +380,8 @@ where
     ///
     /// See [`PayloadBuilder`]
     builder: Builder,
+    /// Runtime settings of local block production, if the node seals blocks itself.
+    miner: Option<MinerHandle>,
 }
 
 impl<Client, Pool, Tasks, Builder> BasicPayloadJob<Client, Pool, Tasks, Builder>
@@ -412,6 +446,12 @@ where
             return Poll::Ready(Ok(()))
         }
 
+        // stop building if sealing was stopped in the meantime
+        if this.miner.as_ref().is_some_and(|miner| !miner.is_mining()) {
+            trace!(target: "payload_builder", "sealing stopped");
+            return Poll::Ready(Ok(()))
+        }
+
         // check if the interval is reached
         while this.interval.poll_tick(cx).is_ready() {
             // start a new job if there is no pending block and we haven't reached the deadline
@@ -683,6 +723,8 @@ pub struct PayloadConfig<Attributes> {
     pub parent_block: Arc<SealedBlock>,
     /// Block extra data.
     pub extra_data: Bytes,
+    /// The minimum priority fee a transaction must pay to be included in the payload.
+    pub min_priority_fee: u128,
     /// Requested attributes for the payload.
     pub attributes: Attributes,
     /// The chain spec.
@@ -716,6 +758,7 @@ where
             initialized_cfg,
             parent_block,
             extra_data,
+            min_priority_fee: 0,
             attributes,
             chain_spec,
         }
@@ -940,3 +983,110 @@ fn duration_until(unix_timestamp_secs: u64) -> Duration {
     let timestamp = Duration::from_secs(unix_timestamp_secs);
     timestamp.saturating_sub(unix_now)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
+    use reth_primitives::{Address, Block};
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::testing_pool;
+
+    /// A payload builder that never builds a better payload.
+    #[derive(Debug, Clone)]
+    struct AbortingPayloadBuilder;
+
+    impl<Pool, Client> PayloadBuilder<Pool, Client> for AbortingPayloadBuilder
+    where
+        Pool: Send + Sync,
+        Client: Send + Sync,
+    {
+        type Attributes = EthPayloadBuilderAttributes;
+        type BuiltPayload = EthBuiltPayload;
+
+        fn try_build(
+            &self,
+            args: BuildArguments<Pool, Client, Self::Attributes, Self::BuiltPayload>,
+        ) -> Result<BuildOutcome<Self::BuiltPayload>, PayloadBuilderError> {
+            Ok(BuildOutcome::Aborted { fees: U256::ZERO, cached_reads: args.cached_reads })
+        }
+
+        fn build_empty_payload(
+            &self,
+            _client: &Client,
+            config: PayloadConfig<Self::Attributes>,
+        ) -> Result<Self::BuiltPayload, PayloadBuilderError> {
+            Ok(EthBuiltPayload::new(config.payload_id(), SealedBlock::default(), U256::ZERO))
+        }
+    }
+
+    #[tokio::test]
+    async fn stopped_miner_stops_block_production() {
+        let client = MockEthProvider::default();
+        let parent = Block::default();
+        let parent_hash = parent.header.hash_slow();
+        client.add_block(parent_hash, parent);
+
+        let miner = MinerHandle::new(Some(Address::with_last_byte(1)), Default::default());
+        let generator = BasicPayloadJobGenerator::with_builder(
+            client,
+            testing_pool(),
+            TokioTaskExecutor::default(),
+            BasicPayloadJobGeneratorConfig::default(),
+            MAINNET.clone(),
+            AbortingPayloadBuilder,
+        )
+        .with_miner(miner.clone());
+        let attributes = EthPayloadBuilderAttributes {
+            id: PayloadId::new([1; 8]),
+            parent: parent_hash,
+            timestamp: 0,
+            suggested_fee_recipient: Address::ZERO,
+            prev_randao: B256::ZERO,
+            withdrawals: Withdrawals::default(),
+            parent_beacon_block_root: None,
+        };
+
+        // no jobs are created before sealing is started
+        assert!(generator.new_payload_job(attributes.clone()).is_err());
+
+        miner.start().unwrap();
+        let job = generator.new_payload_job(attributes.clone()).unwrap();
+
+        // a running job finishes once sealing is stopped, and no new jobs are created
+        miner.stop();
+        let result = tokio::time::timeout(Duration::from_secs(5), job).await;
+        assert!(matches!(result, Ok(Ok(()))));
+        assert!(generator.new_payload_job(attributes).is_err());
+    }
+
+    #[tokio::test]
+    async fn default_generator_creates_jobs() {
+        let client = MockEthProvider::default();
+        let parent = Block::default();
+        let parent_hash = parent.header.hash_slow();
+        client.add_block(parent_hash, parent);
+
+        let generator = BasicPayloadJobGenerator::with_builder(
+            client,
+            testing_pool(),
+            TokioTaskExecutor::default(),
+            BasicPayloadJobGeneratorConfig::default(),
+            MAINNET.clone(),
+            AbortingPayloadBuilder,
+        );
+        let attributes = EthPayloadBuilderAttributes {
+            id: PayloadId::new([1; 8]),
+            parent: parent_hash,
+            timestamp: 0,
+            suggested_fee_recipient: Address::ZERO,
+            prev_randao: B256::ZERO,
+            withdrawals: Withdrawals::default(),
+            parent_beacon_block_root: None,
+        };
+
+        assert!(generator.new_payload_job(attributes).is_ok());
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     KeepPayloadJobAlive, PayloadJob,
 };
 use futures_util::{future::FutureExt, Stream, StreamExt};
-use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes, PayloadTypes};
+use reth_payload_primitives::{BuiltPayload, MinerHandle, PayloadBuilderAttributes, PayloadTypes};
 use reth_provider::CanonStateNotification;
 use reth_rpc_types::engine::PayloadId;
 use std::{
@@ -98,6 +98,8 @@ where
 pub struct PayloadBuilderHandle<Engine: PayloadTypes> {
     /// Sender half of the message channel to the [`PayloadBuilderService`].
     to_service: mpsc::UnboundedSender<PayloadServiceCommand<Engine>>,
+    /// Controls of local block production, if the installed generator seals blocks itself.
+    miner: Option<MinerHandle>,
 }
 
 // === impl PayloadBuilderHandle ===
@@ -111,7 +113,18 @@ where
     /// Note: this is only used internally by the [`PayloadBuilderService`] to manage the payload
     /// building flow See [`PayloadBuilderService::poll`] for implementation details.
     pub const fn new(to_service: mpsc::UnboundedSender<PayloadServiceCommand<Engine>>) -> Self {
-        Self { to_service }
+        Self { to_service, miner: None }
+    }
+
+    /// Attaches the [`MinerHandle`] shared with the installed [`PayloadJobGenerator`].
+    pub fn with_miner(mut self, miner: MinerHandle) -> Self {
+        self.miner = Some(miner);
+        self
+    }
+
+    /// Returns the [`MinerHandle`] of the installed [`PayloadJobGenerator`], if any.
+    pub const fn miner(&self) -> Option<&MinerHandle> {
+        self.miner.as_ref()
     }
 
     /// Resolves the payload job and returns the best payload that has been built so far.
@@ -193,7 +206,7 @@ where
     Engine: PayloadTypes,
 {
     fn clone(&self) -> Self {
-        Self { to_service: self.to_service.clone() }
+        Self { to_service: self.to_service.clone(), miner: self.miner.clone() }
     }
 }
 
@@ -20,6 +20,9 @@ pub use traits::{BuiltPayload, PayloadAttributes, PayloadBuilderAttributes};
 mod payload;
 pub use payload::PayloadOrAttributes;
 
+mod miner;
+pub use miner::{calc_gas_limit, MinerError, MinerHandle, MinerSettings, GAS_LIMIT_BOUND_DIVISOR};
+
 use reth_chainspec::{ChainSpec, EthereumHardforks};
 /// The types that are used by the engine API.
 pub trait PayloadTypes: Send + Sync + Unpin + core::fmt::Debug + Clone {
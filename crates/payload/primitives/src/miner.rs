@@ -0,0 +1,199 @@
+//! Runtime controls for local block production.
+
+use parking_lot::RwLock;
+use reth_primitives::{
+    constants::{MAXIMUM_EXTRA_DATA_SIZE, MINIMUM_GAS_LIMIT},
+    Address, Bytes,
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// The bound divisor of the gas limit, used to limit the change of the gas limit between two
+/// consecutive blocks.
+pub const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+
+/// Errors returned when updating the [`MinerHandle`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MinerError {
+    /// Thrown when sealing is started without a configured coinbase.
+    #[error("etherbase missing: etherbase must be explicitly specified")]
+    MissingCoinbase,
+    /// Thrown when a payload is requested while sealing is stopped.
+    #[error("sealing is stopped")]
+    NotMining,
+    /// Thrown when the extra data exceeds [`MAXIMUM_EXTRA_DATA_SIZE`].
+    #[error("extra data too long: {0} > {MAXIMUM_EXTRA_DATA_SIZE}")]
+    ExtraDataTooLong(usize),
+    /// Thrown when the gas limit is below [`MINIMUM_GAS_LIMIT`].
+    #[error("gas limit too low: {0} < {MINIMUM_GAS_LIMIT}")]
+    GasLimitTooLow(u64),
+}
+
+/// The settings applied to locally built payloads.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MinerSettings {
+    /// The minimum priority fee a transaction must pay to be included in a payload.
+    pub gas_price: u128,
+    /// The gas limit the payloads should target, if any.
+    ///
+    /// The gas limit of a payload can only move towards the target by `1/1024` of the parent's gas
+    /// limit, see [`MinerHandle::gas_limit_for`].
+    pub gas_limit: Option<u64>,
+    /// The extra data to include in the payloads, if any.
+    pub extra_data: Option<Bytes>,
+}
+
+/// A shared handle to the settings of local block production.
+///
+/// The handle is shared by the payload job generator, which applies the settings to new payloads,
+/// and the `miner_` RPC namespace, which updates them at runtime.
+#[derive(Debug, Clone, Default)]
+pub struct MinerHandle {
+    inner: Arc<MinerInner>,
+}
+
+#[derive(Debug, Default)]
+struct MinerInner {
+    /// The address that receives the block rewards.
+    coinbase: Option<Address>,
+    /// Whether the node is actively sealing blocks.
+    mining: AtomicBool,
+    /// The settings applied to new payloads.
+    settings: RwLock<MinerSettings>,
+}
+
+// === impl MinerHandle ===
+
+impl MinerHandle {
+    /// Creates a new handle for the given coinbase and settings.
+    ///
+    /// The node does not seal blocks until [`Self::start`] is called.
+    pub fn new(coinbase: Option<Address>, settings: MinerSettings) -> Self {
+        Self {
+            inner: Arc::new(MinerInner {
+                coinbase,
+                mining: AtomicBool::new(false),
+                settings: RwLock::new(settings),
+            }),
+        }
+    }
+
+    /// Returns the configured coinbase, if any.
+    pub fn coinbase(&self) -> Option<Address> {
+        self.inner.coinbase
+    }
+
+    /// Returns true if the node is actively sealing blocks.
+    pub fn is_mining(&self) -> bool {
+        self.inner.mining.load(Ordering::Relaxed)
+    }
+
+    /// Starts sealing blocks.
+    ///
+    /// Fails if no coinbase is configured.
+    pub fn start(&self) -> Result<(), MinerError> {
+        if self.inner.coinbase.is_none() {
+            return Err(MinerError::MissingCoinbase)
+        }
+        self.inner.mining.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Stops sealing blocks.
+    pub fn stop(&self) {
+        self.inner.mining.store(false, Ordering::Relaxed);
+    }
+
+    /// Returns a snapshot of the current settings.
+    pub fn settings(&self) -> MinerSettings {
+        self.inner.settings.read().clone()
+    }
+
+    /// Sets the minimum priority fee of included transactions.
+    pub fn set_gas_price(&self, gas_price: u128) {
+        self.inner.settings.write().gas_price = gas_price;
+    }
+
+    /// Sets the gas limit the payloads should target.
+    pub fn set_gas_limit(&self, gas_limit: u64) -> Result<(), MinerError> {
+        if gas_limit < MINIMUM_GAS_LIMIT {
+            return Err(MinerError::GasLimitTooLow(gas_limit))
+        }
+        self.inner.settings.write().gas_limit = Some(gas_limit);
+        Ok(())
+    }
+
+    /// Sets the extra data to include in the payloads.
+    pub fn set_extra_data(&self, extra_data: Bytes) -> Result<(), MinerError> {
+        if extra_data.len() > MAXIMUM_EXTRA_DATA_SIZE {
+            return Err(MinerError::ExtraDataTooLong(extra_data.len()))
+        }
+        self.inner.settings.write().extra_data = Some(extra_data);
+        Ok(())
+    }
+
+    /// Returns the gas limit of a payload built on top of a parent with the given gas limit.
+    ///
+    /// Moves from the parent's gas limit towards the configured target by at most the allowed
+    /// change, or keeps the parent's gas limit if no target is configured.
+    pub fn gas_limit_for(&self, parent_gas_limit: u64) -> u64 {
+        match self.inner.settings.read().gas_limit {
+            Some(target) => calc_gas_limit(parent_gas_limit, target),
+            None => parent_gas_limit,
+        }
+    }
+}
+
+/// Computes the gas limit of the next block, moving from the parent's gas limit towards the
+/// desired gas limit by at most `parent_gas_limit / 1024 - 1`.
+pub fn calc_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let desired = desired_gas_limit.max(MINIMUM_GAS_LIMIT);
+    if parent_gas_limit < desired {
+        (parent_gas_limit + delta).min(desired)
+    } else {
+        parent_gas_limit.saturating_sub(delta).max(desired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_limit_moves_towards_target() {
+        assert_eq!(calc_gas_limit(30_000_000, 30_000_000), 30_000_000);
+        assert_eq!(calc_gas_limit(30_000_000, 40_000_000), 30_029_295);
+        assert_eq!(calc_gas_limit(30_000_000, 30_010_000), 30_010_000);
+        assert_eq!(calc_gas_limit(30_000_000, 20_000_000), 29_970_705);
+        assert_eq!(calc_gas_limit(30_000_000, 29_990_000), 29_990_000);
+        assert_eq!(calc_gas_limit(5_000, 0), MINIMUM_GAS_LIMIT);
+    }
+
+    #[test]
+    fn start_requires_coinbase() {
+        let miner = MinerHandle::default();
+        assert_eq!(miner.start(), Err(MinerError::MissingCoinbase));
+        assert!(!miner.is_mining());
+
+        let miner = MinerHandle::new(Some(Address::with_last_byte(1)), Default::default());
+        miner.start().unwrap();
+        assert!(miner.is_mining());
+        miner.stop();
+        assert!(!miner.is_mining());
+    }
+
+    #[test]
+    fn validates_settings() {
+        let miner = MinerHandle::default();
+        assert_eq!(
+            miner.set_extra_data(vec![0; MAXIMUM_EXTRA_DATA_SIZE + 1].into()),
+            Err(MinerError::ExtraDataTooLong(MAXIMUM_EXTRA_DATA_SIZE + 1))
+        );
+        assert_eq!(miner.set_gas_limit(1), Err(MinerError::GasLimitTooLow(1)));
+        miner.set_gas_limit(40_000_000).unwrap();
+        assert_eq!(miner.gas_limit_for(30_000_000), 30_029_295);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::segments::{
-    AccountHistory, ReceiptsByLogs, Segment, SenderRecovery, StaticFileSidecars, StorageHistory,
-    TransactionLookup, UserReceipts,
+    AccountHistory, AddressTransactions, LogIndex, Preimages, ReceiptsByLogs, Segment,
+    SenderRecovery, StaticFileSidecars, StorageHistory, TransactionLookup, UserReceipts,
 };
 use reth_db_api::database::Database;
 use reth_provider::providers::StaticFileProvider;
@@ -45,6 +45,7 @@ impl<DB: Database> SegmentSet<DB> {
         static_file_provider: StaticFileProvider,
         prune_modes: PruneModes,
     ) -> Self {
+        let log_index = prune_modes.log_index();
         let PruneModes {
             sender_recovery,
             transaction_lookup,
@@ -52,6 +53,8 @@ impl<DB: Database> SegmentSet<DB> {
             account_history,
             storage_history,
             receipts_log_filter,
+            address_transactions,
+            preimages,
         } = prune_modes;
 
         Self::default()
@@ -63,10 +66,16 @@ impl<DB: Database> SegmentSet<DB> {
             .segment(StaticFileReceipts::new(static_file_provider.clone()))
             // Static file receipts
             .segment(StaticFileSidecars::new(static_file_provider))
+            // Preimages, derived from the account changesets so pruned before them
+            .segment_opt(preimages.map(Preimages::new))
             // Account history
             .segment_opt(account_history.map(AccountHistory::new))
             // Storage history
             .segment_opt(storage_history.map(StorageHistory::new))
+            // Address transactions, derived from the receipts so pruned before them
+            .segment_opt(address_transactions.map(AddressTransactions::new))
+            // Log index, derived from the receipts so pruned before them
+            .segment_opt(log_index.map(LogIndex::new))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
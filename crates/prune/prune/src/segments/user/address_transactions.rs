@@ -0,0 +1,232 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use itertools::Itertools;
+use reth_db::tables;
+use reth_db_api::{database::Database, models::ShardedKey};
+use reth_provider::{DatabaseProviderRW, TransactionsProvider};
+use reth_prune_types::{
+    PruneInterruptReason, PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput,
+    SegmentOutputCheckpoint,
+};
+use rustc_hash::FxHashMap;
+use tracing::{instrument, trace};
+
+/// Number of address transactions tables to prune in one step.
+///
+/// The index consists of two tables: [`tables::TransactionAddresses`] and
+/// [`tables::AddressTransactions`]. We want to prune them to the same transaction number.
+const ADDRESS_TRANSACTIONS_TABLES_TO_PRUNE: usize = 2;
+
+#[derive(Debug)]
+pub struct AddressTransactions {
+    mode: PruneMode,
+}
+
+impl AddressTransactions {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for AddressTransactions {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressTransactions
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        let tx_range = match input.get_next_tx_num_range(provider)? {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No address transactions to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let tx_range_end = *tx_range.end();
+
+        let mut limiter = if let Some(limit) = input.limiter.deleted_entries_limit() {
+            input.limiter.set_deleted_entries_limit(limit / ADDRESS_TRANSACTIONS_TABLES_TO_PRUNE)
+        } else {
+            input.limiter
+        };
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                PruneInterruptReason::new(&limiter),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let mut last_pruned_transaction = None;
+        // Deleted addresses with the highest transaction number deleted for that address. The
+        // addresses are read from the transactions in the range, so the addresses of logs and
+        // internal calls are pruned even if the receipts have been pruned already.
+        let mut highest_deleted_addresses = FxHashMap::default();
+        let (pruned_addresses, done) = provider
+            .prune_table_with_range::<tables::TransactionAddresses>(
+                tx_range,
+                &mut limiter,
+                |_| false,
+                |(tx_number, address)| {
+                    highest_deleted_addresses.insert(address, tx_number);
+                    last_pruned_transaction = Some(tx_number);
+                },
+            )?;
+        trace!(target: "pruner", pruned = %pruned_addresses, %done, "Pruned transaction addresses");
+
+        let last_pruned_transaction = last_pruned_transaction
+            // If there's more addresses to prune, set the checkpoint to the previous transaction,
+            // so we could finish pruning the addresses of the last one on the next run.
+            .map(|tx_number| if done { tx_range_end } else { tx_number.saturating_sub(1) })
+            .unwrap_or(tx_range_end);
+
+        let highest_sharded_keys = highest_deleted_addresses
+            .into_iter()
+            .sorted_unstable() // Unstable is fine because no equal keys exist in the map
+            .map(|(address, tx_number)| ShardedKey::new(address, tx_number));
+        let outcomes = prune_history_indices::<DB, tables::AddressTransactions, _>(
+            provider,
+            highest_sharded_keys,
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?outcomes, %done, "Pruned address transactions (indices)");
+
+        let last_pruned_block = provider
+            .transaction_block(last_pruned_transaction)?
+            .ok_or(PrunerError::InconsistentData("Block for transaction is not found"))?
+            // If there's more address transactions to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its address transactions on the next run.
+            .checked_sub(if done { 0 } else { 1 });
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: pruned_addresses + outcomes.deleted,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: Some(last_pruned_transaction),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AddressTransactions, PruneInput, Segment};
+    use alloy_primitives::{Address, Log, B256};
+    use assert_matches::assert_matches;
+    use reth_db::tables;
+    use reth_db_api::{
+        cursor::{DbCursorRO, DbCursorRW},
+        transaction::DbTxMut,
+    };
+    use reth_provider::BlockReader;
+    use reth_prune_types::{PruneLimiter, PruneMode};
+    use reth_stages::{
+        stages::IndexAddressTransactionsStage,
+        test_utils::{StorageKind, TestStageDB},
+        ExecInput, Stage,
+    };
+    use reth_testing_utils::{
+        generators,
+        generators::{random_block_range, random_receipt},
+    };
+
+    #[test]
+    fn prune_with_pruned_receipts() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // the emitter is only indexed through the logs of the receipts
+        let emitter = Address::random();
+        let mut receipts = Vec::new();
+        for transaction in blocks.iter().flat_map(|block| &block.body) {
+            let mut receipt = random_receipt(&mut rng, transaction, Some(0));
+            receipt.logs.push(Log::new_unchecked(emitter, vec![], Default::default()));
+            receipts.push((receipts.len() as u64, receipt));
+        }
+        db.insert_receipts(receipts).expect("insert receipts");
+
+        let provider = db.factory.provider_rw().unwrap();
+        IndexAddressTransactionsStage::default()
+            .execute(&provider, ExecInput { target: Some(10), checkpoint: None })
+            .unwrap();
+        provider.commit().unwrap();
+
+        // prune the receipts of the blocks to prune before the index is pruned
+        let prune_before = 6;
+        let last_pruned_tx = db
+            .factory
+            .provider()
+            .unwrap()
+            .block_body_indices(prune_before - 1)
+            .unwrap()
+            .unwrap()
+            .last_tx_num();
+        db.commit(|tx| {
+            let mut cursor = tx.cursor_write::<tables::Receipts>()?;
+            for tx_number in 0..=last_pruned_tx {
+                if cursor.seek_exact(tx_number)?.is_some() {
+                    cursor.delete_current()?;
+                }
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = AddressTransactions::new(PruneMode::Before(prune_before)).prune(
+            &provider,
+            PruneInput {
+                previous_checkpoint: None,
+                to_block: prune_before - 1,
+                limiter: PruneLimiter::default(),
+            },
+        );
+        provider.commit().unwrap();
+        assert_matches!(result, Ok(output) if output.progress.is_finished());
+
+        // no address keeps pruned transactions, including the emitter
+        for (key, list) in db.table::<tables::AddressTransactions>().unwrap() {
+            assert!(
+                list.iter().all(|tx_number| tx_number > last_pruned_tx),
+                "{} keeps pruned transactions",
+                key.key
+            );
+        }
+        assert!(db
+            .table::<tables::TransactionAddresses>()
+            .unwrap()
+            .into_iter()
+            .all(|(tx_number, _)| tx_number > last_pruned_tx));
+        let emitter_transactions = db
+            .table::<tables::AddressTransactions>()
+            .unwrap()
+            .into_iter()
+            .filter(|(key, _)| key.key == emitter)
+            .flat_map(|(_, list)| list.iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            emitter_transactions,
+            (last_pruned_tx + 1..db.table::<tables::Transactions>().unwrap().len() as u64)
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use reth_db::tables;
+use reth_db_api::{database::Database, models::ShardedKey};
+use reth_provider::{DatabaseProviderRW, LogIndexReader};
+use reth_prune_types::{
+    PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+#[derive(Debug)]
+pub struct LogIndex {
+    mode: PruneMode,
+}
+
+impl LogIndex {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for LogIndex {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::LogIndex
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        // The prune mode follows the receipts, so the segment is set up even if the index is not
+        // enabled.
+        if provider.log_index_range()?.is_none() {
+            trace!(target: "pruner", "Log index is not enabled");
+            return Ok(SegmentOutput::done())
+        }
+
+        let (start, end) = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No log indices to prune");
+                return Ok(SegmentOutput::done())
+            }
+        }
+        .into_inner();
+        let block_range = start..=
+            Some(end)
+                .min(input.limiter.deleted_entries_limit_left().map(|left| start + left as u64 - 1))
+                .unwrap();
+        let block_range_end = *block_range.end();
+
+        // Addresses and topics of the logs in the range, with the highest block number to prune
+        // for them. They are derived from the receipts the same way the index was built, so the
+        // receipts need to be pruned after this segment.
+        let (addresses, topics) = provider.block_log_keys(block_range)?;
+        let highest_address_keys = addresses.into_iter().map(|(address, block_numbers)| {
+            ShardedKey::new(address, *block_numbers.last().expect("qed"))
+        });
+        let highest_topic_keys = topics.into_iter().map(|(topic, block_numbers)| {
+            ShardedKey::new(topic, *block_numbers.last().expect("qed"))
+        });
+
+        let address_outcomes = prune_history_indices::<DB, tables::LogAddressIndex, _>(
+            provider,
+            highest_address_keys,
+            |a, b| a.key == b.key,
+        )?;
+        let topic_outcomes = prune_history_indices::<DB, tables::LogTopicIndex, _>(
+            provider,
+            highest_topic_keys,
+            |a, b| a.key == b.key,
+        )?;
+        let pruned = address_outcomes.deleted + topic_outcomes.deleted;
+
+        let mut limiter = input.limiter;
+        limiter.increment_deleted_entries_count_by(pruned);
+
+        let done = block_range_end == end;
+        trace!(target: "pruner", ?address_outcomes, ?topic_outcomes, %done, "Pruned log indices");
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(block_range_end),
+                tx_number: None,
+            }),
+        })
+    }
+}
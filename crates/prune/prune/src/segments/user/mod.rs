@@ -1,5 +1,8 @@
 mod account_history;
+mod address_transactions;
 mod history;
+mod log_index;
+mod preimages;
 mod receipts;
 mod receipts_by_logs;
 mod sender_recovery;
@@ -7,6 +10,9 @@ mod storage_history;
 mod transaction_lookup;
 
 pub use account_history::AccountHistory;
+pub use address_transactions::AddressTransactions;
+pub use log_index::LogIndex;
+pub use preimages::Preimages;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
 pub use sender_recovery::SenderRecovery;
@@ -0,0 +1,168 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use alloy_primitives::keccak256;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
+    database::Database,
+    models::AccountBeforeTx,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_provider::{AccountReader, DatabaseProviderRW};
+use reth_prune_types::{
+    PruneInterruptReason, PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput,
+    SegmentOutputCheckpoint,
+};
+use tracing::{instrument, trace};
+
+/// Prunes the preimages of accounts that were destroyed.
+///
+/// The destroyed accounts are found in the [`tables::AccountChangeSets`], so the segment needs to
+/// run before the account history is pruned. The preimages of storage slots are kept, as the same
+/// slot is usually used by many accounts.
+#[derive(Debug)]
+pub struct Preimages {
+    mode: PruneMode,
+}
+
+impl Preimages {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for Preimages {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::Preimages
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No preimages to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                PruneInterruptReason::new(&limiter),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let mut changesets = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?;
+        let mut preimages = provider.tx_ref().cursor_write::<tables::Preimages>()?;
+        let mut last_pruned_block = None;
+        let mut pruned = 0;
+        // Blocks are pruned as a whole, so the limit may be exceeded by the accounts of the last
+        // block.
+        for block_number in range {
+            if limiter.is_limit_reached() {
+                break
+            }
+
+            for entry in changesets.walk_dup(Some(block_number), None)? {
+                let (_, AccountBeforeTx { address, .. }) = entry?;
+                if provider.basic_account(address)?.is_none() &&
+                    preimages.seek_exact(keccak256(address))?.is_some()
+                {
+                    preimages.delete_current()?;
+                    limiter.increment_deleted_entries_count();
+                    pruned += 1;
+                }
+            }
+            last_pruned_block = Some(block_number);
+        }
+
+        let done = last_pruned_block == Some(range_end);
+        trace!(target: "pruner", %pruned, %done, "Pruned preimages");
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned,
+            checkpoint: last_pruned_block
+                .map(|block_number| SegmentOutputCheckpoint {
+                    block_number: Some(block_number),
+                    tx_number: None,
+                })
+                .or_else(|| {
+                    input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint)
+                }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{Preimages, PruneInput, Segment, SegmentOutput};
+    use alloy_primitives::{keccak256, Address, Bytes, B256};
+    use reth_db::tables;
+    use reth_db_api::{models::AccountBeforeTx, transaction::DbTxMut};
+    use reth_prune_types::{PruneLimiter, PruneMode, PruneProgress};
+    use reth_stages::test_utils::TestStageDB;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+
+        // the first account was destroyed, the second one still exists
+        let (destroyed, alive) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let slot = B256::with_last_byte(1);
+        db.commit(|tx| {
+            for address in [destroyed, alive] {
+                tx.put::<tables::AccountChangeSets>(
+                    1,
+                    AccountBeforeTx { address, info: Some(Default::default()) },
+                )?;
+                tx.put::<tables::Preimages>(
+                    keccak256(address),
+                    Bytes::copy_from_slice(address.as_slice()),
+                )?;
+            }
+            tx.put::<tables::PlainAccountState>(alive, Default::default())?;
+            tx.put::<tables::Preimages>(keccak256(slot), Bytes::copy_from_slice(slot.as_slice()))?;
+            Ok(())
+        })
+        .unwrap();
+
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block: 1,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(10),
+        };
+        let segment = Preimages::new(PruneMode::Full);
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(result.progress, PruneProgress::Finished);
+        assert!(matches!(result, SegmentOutput { pruned: 1, .. }));
+        let preimages = db.table::<tables::Preimages>().unwrap();
+        assert_eq!(preimages.into_iter().map(|(hash, _)| hash).collect::<Vec<_>>(), {
+            let mut hashes = vec![keccak256(alive), keccak256(slot)];
+            hashes.sort_unstable();
+            hashes
+        });
+    }
+}
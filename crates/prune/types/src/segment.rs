@@ -29,6 +29,12 @@ pub enum PruneSegment {
     Transactions,
     /// Prune segment responsible for the `Sidecars` table.
     Sidecars,
+    /// Prune segment responsible for the `AddressTransactions` table.
+    AddressTransactions,
+    /// Prune segment responsible for the `Preimages` table.
+    Preimages,
+    /// Prune segment responsible for the `LogAddressIndex` and `LogTopicIndex` tables.
+    LogIndex,
 }
 
 impl PruneSegment {
@@ -39,7 +45,10 @@ impl PruneSegment {
             Self::TransactionLookup |
             Self::Headers |
             Self::Transactions |
-            Self::Sidecars => 0,
+            Self::Sidecars |
+            Self::AddressTransactions |
+            Self::Preimages |
+            Self::LogIndex => 0,
             Self::Receipts if purpose.is_static_file() => 0,
             Self::ContractLogs | Self::AccountHistory | Self::StorageHistory => {
                 MINIMUM_PRUNING_DISTANCE
@@ -37,6 +37,15 @@ pub struct PruneModes {
         deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
     )]
     pub storage_history: Option<PruneMode>,
+    /// Address Transactions index pruning configuration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address_transactions: Option<PruneMode>,
+    /// Preimages pruning configuration.
+    ///
+    /// Only the preimages of accounts that no longer exist are pruned, see the `Preimages`
+    /// segment.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preimages: Option<PruneMode>,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -59,6 +68,8 @@ impl PruneModes {
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
+            address_transactions: Some(PruneMode::Full),
+            preimages: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
         }
     }
@@ -68,6 +79,18 @@ impl PruneModes {
         self.receipts.is_some() || !self.receipts_log_filter.is_empty()
     }
 
+    /// Returns the prune mode of the log index, which follows the receipts it is built from.
+    ///
+    /// With `receipts` pruning the index is pruned with the same mode. With `receipts_log_filter`
+    /// pruning, the receipts of all other addresses are pruned before the minimum pruning
+    /// distance, so the index only covers the blocks after it.
+    pub fn log_index(&self) -> Option<PruneMode> {
+        self.receipts.or_else(|| {
+            (!self.receipts_log_filter.is_empty())
+                .then_some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE))
+        })
+    }
+
     /// Returns true if all prune modes are set to [`None`].
     pub fn is_empty(&self) -> bool {
         self == &Self::none()
@@ -1,6 +1,9 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_network_peers::{AnyNode, NodeRecord};
-use reth_rpc_types::admin::{NodeInfo, PeerInfo};
+use reth_rpc_types::{
+    admin::{ExtendedPeerInfo, NodeInfo},
+    state::StateOverride,
+};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -30,9 +33,12 @@ pub trait AdminApi {
     /// The peers administrative property can be queried for all the information known about the
     /// connected remote nodes at the networking granularity. These include general information
     /// about the nodes themselves as participants of the devp2p P2P overlay protocol, as well as
-    /// specialized information added by each of the running application protocols
+    /// specialized information added by each of the running application protocols.
+    ///
+    /// In addition, the reputation and the request statistics tracked for each peer are
+    /// returned.
     #[method(name = "peers")]
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+    async fn peers(&self) -> RpcResult<Vec<ExtendedPeerInfo>>;
 
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
@@ -46,3 +52,23 @@ pub trait AdminApi {
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
 }
+
+/// Admin namespace rpc interface for managing the named state override presets that can be
+/// applied to `eth_call`, `eth_estimateGas`, `eth_callMany` and `debug_traceCall`.
+///
+/// These methods persist the presets to the node's datadir and are therefore only served by the
+/// authenticated auth server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminStateOverridePresetsApi {
+    /// Validates and stores the named state override preset, replacing an existing preset with
+    /// the same name.
+    #[method(name = "setStateOverridePreset")]
+    fn set_state_override_preset(&self, name: String, overrides: StateOverride) -> RpcResult<bool>;
+
+    /// Removes the named state override preset.
+    ///
+    /// Returns false if there is no such preset.
+    #[method(name = "removeStateOverridePreset")]
+    fn remove_state_override_preset(&self, name: String) -> RpcResult<bool>;
+}
@@ -1,11 +1,12 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256};
 use reth_rpc_types::{
+    debug::{BadBlock, StateDump, StdTraceConfig},
     trace::geth::{
         BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
         TraceResult,
     },
-    Bundle, RichBlock, StateContext, TransactionRequest,
+    Bundle, StateContext, TransactionRequest,
 };
 
 /// Debug rpc interface.
@@ -36,16 +37,24 @@ pub trait DebugApi {
 
     /// Returns an array of recent bad blocks that the client has seen on the network.
     #[method(name = "getBadBlocks")]
-    async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>>;
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock>>;
 
-    /// Returns the structured logs created during the execution of EVM between two blocks
-    /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
+    /// Creates an RPC subscription that replays all blocks between two blocks (excluding start)
+    /// and streams the structured logs created during the execution of EVM for each block.
+    ///
+    /// Blocks are traced one after another, the next block is only traced once the previous
+    /// result was accepted by the subscriber.
+    #[subscription(
+        name = "traceChain",
+        unsubscribe = "traceChain_unsubscribe",
+        item = BlockTraceResult
+    )]
     async fn debug_trace_chain(
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -101,12 +110,15 @@ pub trait DebugApi {
     /// The trace can be configured similar to `debug_traceTransaction`,
     /// see [GethDebugTracingOptions]. The method returns the same output as
     /// `debug_traceTransaction`.
+    /// A named state override preset can be given as the fourth argument, the state overrides of
+    /// the tracing options take precedence over it for the same account.
     #[method(name = "traceCall")]
     async fn debug_trace_call(
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
         opts: Option<GethDebugTracingCallOptions>,
+        preset: Option<String>,
     ) -> RpcResult<GethTrace>;
 
     /// The `debug_traceCallMany` method lets you run an `eth_callMany` within the context of the
@@ -142,7 +154,8 @@ pub trait DebugApi {
     /// in the page and the items have keys that come after the `start` key (hashed address).
     ///
     /// If incompletes is false, then accounts for which the key preimage (i.e: the address) doesn't
-    /// exist in db are skipped. NB: geth by default does not store preimages.
+    /// exist in db are skipped. NB: preimages are only recorded if the `Preimages` stage is
+    /// enabled, and only the latest state can be enumerated.
     #[method(name = "accountRange")]
     async fn debug_account_range(
         &self,
@@ -152,7 +165,7 @@ pub trait DebugApi {
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StateDump>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -165,9 +178,10 @@ pub trait DebugApi {
     #[method(name = "chaindbCompact")]
     async fn debug_chaindb_compact(&self) -> RpcResult<()>;
 
-    /// Returns leveldb properties of the key-value database.
+    /// Returns the statistics of the database tables (`mdbx.stats`), the static files
+    /// (`static_files.stats`) or both (`stats` or an empty string) as text.
     #[method(name = "chaindbProperty")]
-    async fn debug_chaindb_property(&self, property: String) -> RpcResult<()>;
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<String>;
 
     /// Turns on CPU profiling for the given duration and writes profile data to disk.
     #[method(name = "cpuProfile")]
@@ -183,14 +197,23 @@ pub trait DebugApi {
     #[method(name = "dbAncients")]
     async fn debug_db_ancients(&self) -> RpcResult<()>;
 
-    /// Returns the raw value of a key stored in the database.
+    /// Returns the raw value of a key stored in the named database table. For tables with
+    /// duplicate keys, the first value of the key is returned.
     #[method(name = "dbGet")]
-    async fn debug_db_get(&self, key: String) -> RpcResult<()>;
+    async fn debug_db_get(&self, table: String, key: Bytes) -> RpcResult<Option<Bytes>>;
 
     /// Retrieves the state that corresponds to the block number and returns a list of accounts
     /// (including storage and code).
+    ///
+    /// At most `maxResults` accounts are returned per call, ordered by address and starting at
+    /// `start`. If there are more accounts, `next` holds the address to continue from.
     #[method(name = "dumpBlock")]
-    async fn debug_dump_block(&self, number: BlockId) -> RpcResult<()>;
+    async fn debug_dump_block(
+        &self,
+        number: BlockId,
+        start: Option<Address>,
+        max_results: Option<u64>,
+    ) -> RpcResult<StateDump>;
 
     /// Forces garbage collection.
     #[method(name = "freeOSMemory")]
@@ -214,7 +237,7 @@ pub trait DebugApi {
         &self,
         from: BlockNumberOrTag,
         to: BlockNumberOrTag,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<u64>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
     /// as a difference in nonce, balance, code hash, or storage hash. With one parameter, returns
@@ -223,17 +246,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -241,12 +265,15 @@ pub trait DebugApi {
 
     /// Executes a block (bad- or canon- or side-), and returns a list of intermediate roots: the
     /// stateroot after each transaction.
+    ///
+    /// If the execution fails, only the roots of the transactions before the failing one are
+    /// returned. Nothing is traced, so only the default tracing options are accepted.
     #[method(name = "intermediateRoots")]
     async fn debug_intermediate_roots(
         &self,
         block_hash: B256,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -259,8 +286,11 @@ pub trait DebugApi {
     async fn debug_mutex_profile(&self, file: String, nsec: u64) -> RpcResult<()>;
 
     /// Returns the preimage for a sha3 hash, if known.
+    ///
+    /// Only the preimages of addresses and storage slots are known, and only if the `Preimages`
+    /// stage is enabled.
     #[method(name = "preimage")]
-    async fn debug_preimage(&self, hash: B256) -> RpcResult<()>;
+    async fn debug_preimage(&self, hash: B256) -> RpcResult<Bytes>;
 
     /// Retrieves a block and returns its pretty printed form.
     #[method(name = "printBlock")]
@@ -299,22 +329,25 @@ pub trait DebugApi {
     #[method(name = "stacks")]
     async fn debug_stacks(&self) -> RpcResult<()>;
 
-    /// Used to obtain info about a block.
+    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
+    /// about a block which has been rejected as invalid (for some reason).
     #[method(name = "standardTraceBadBlockToFile")]
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        config: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>>;
 
-    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
-    /// about a block which has been rejected as invalid (for some reason).
+    /// Replays a block and writes an EIP-3155 trace of each transaction to a separate file,
+    /// returning the paths of the written files.
+    ///
+    /// If `txHash` is set in the config, only that transaction is written out.
     #[method(name = "standardTraceBlockToFile")]
     async fn debug_standard_trace_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        config: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>>;
 
     /// Turns on CPU profiling indefinitely, writing to the given file.
     #[method(name = "startCPUProfile")]
@@ -352,8 +385,8 @@ pub trait DebugApi {
     async fn debug_trace_bad_block(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
 
     /// Sets the logging verbosity ceiling. Log messages with level up to and including the given
     /// level will be printed.
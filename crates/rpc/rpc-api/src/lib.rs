@@ -21,6 +21,7 @@ mod engine;
 mod ganache;
 mod hardhat;
 mod mev;
+mod miner;
 mod net;
 mod otterscan;
 mod reth;
@@ -36,10 +37,11 @@ pub use servers::*;
 /// Aggregates all server traits.
 pub mod servers {
     pub use crate::{
-        admin::AdminApiServer,
+        admin::{AdminApiServer, AdminStateOverridePresetsApiServer},
         debug::DebugApiServer,
         engine::{EngineApiServer, EngineEthApiServer},
         mev::MevApiServer,
+        miner::{EthMiningApiServer, MinerApiServer},
         net::NetApiServer,
         otterscan::OtterscanServer,
         reth::RethApiServer,
@@ -63,13 +65,14 @@ pub use clients::*;
 #[cfg(feature = "client")]
 pub mod clients {
     pub use crate::{
-        admin::AdminApiClient,
+        admin::{AdminApiClient, AdminStateOverridePresetsApiClient},
         anvil::AnvilApiClient,
         debug::DebugApiClient,
         engine::{EngineApiClient, EngineEthApiClient},
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
         mev::MevApiClient,
+        miner::{EthMiningApiClient, MinerApiClient},
         net::NetApiClient,
         otterscan::OtterscanClient,
         rpc::RpcApiServer,
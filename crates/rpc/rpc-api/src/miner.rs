@@ -0,0 +1,40 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::{Address, U256, U64};
+
+/// Miner rpc interface, controls local block production.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "miner"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "miner"))]
+pub trait MinerApi {
+    /// Starts sealing blocks.
+    #[method(name = "start")]
+    fn start(&self) -> RpcResult<()>;
+
+    /// Stops sealing blocks.
+    #[method(name = "stop")]
+    fn stop(&self) -> RpcResult<()>;
+
+    /// Sets the minimum priority fee a transaction must pay to be included in a sealed block.
+    #[method(name = "setGasPrice")]
+    fn set_gas_price(&self, gas_price: U256) -> RpcResult<bool>;
+
+    /// Sets the gas limit sealed blocks should target.
+    #[method(name = "setGasLimit")]
+    fn set_gas_limit(&self, gas_limit: U64) -> RpcResult<bool>;
+
+    /// Sets the extra data of sealed blocks.
+    #[method(name = "setExtra")]
+    fn set_extra(&self, extra: String) -> RpcResult<bool>;
+}
+
+/// The `eth_` mining status calls, served by nodes that seal blocks.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthMiningApi {
+    /// Returns the address that receives the rewards of sealed blocks.
+    #[method(name = "coinbase")]
+    fn coinbase(&self) -> RpcResult<Address>;
+
+    /// Returns whether the node is actively sealing blocks.
+    #[method(name = "mining")]
+    fn mining(&self) -> RpcResult<bool>;
+}
@@ -64,6 +64,10 @@ pub trait Otterscan {
     ) -> RpcResult<OtsBlockTransactions>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Transactions are found through the address transactions index, which covers senders,
+    /// recipients, created contracts and the addresses in logs, but not addresses that are only
+    /// reached through internal calls.
     #[method(name = "searchTransactionsBefore")]
     async fn search_transactions_before(
         &self,
@@ -73,6 +77,10 @@ pub trait Otterscan {
     ) -> RpcResult<TransactionsWithReceipts>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Transactions are found through the address transactions index, which covers senders,
+    /// recipients, created contracts and the addresses in logs, but not addresses that are only
+    /// reached through internal calls.
     #[method(name = "searchTransactionsAfter")]
     async fn search_transactions_after(
         &self,
@@ -1,9 +1,10 @@
 use reth_evm::ConfigureEvm;
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider, StateProviderFactory};
-use reth_rpc::{EthFilter, EthPubSub};
-use reth_rpc_eth_types::{
-    cache::cache_new_blocks_task, EthApiBuilderCtx, EthConfig, EthStateCache,
+use reth_provider::{
+    BlockReader, CanonStateSubscriptions, EvmEnvProvider, ForkChoiceSubscriptions,
+    StateProviderFactory,
 };
+use reth_rpc::{EthFilter, EthPubSub};
+use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthApiBuilderCtx, EthConfig, EthStateCache};
 use reth_tasks::TaskSpawner;
 
 /// Alias for `eth` namespace API builder.
@@ -78,7 +79,7 @@ where
     EvmConfig: ConfigureEvm,
     Network: Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
-    Events: CanonStateSubscriptions + Clone + 'static,
+    Events: CanonStateSubscriptions + ForkChoiceSubscriptions + Clone + 'static,
     EthApi: 'static,
 {
     /// Returns a new instance with handlers for `eth` namespace.
@@ -102,6 +103,8 @@ where
             }),
         );
 
+        let state_override_presets = config.state_override_presets.clone();
+
         let ctx = EthApiBuilderCtx {
             provider,
             pool,
@@ -111,6 +114,7 @@ where
             executor,
             events,
             cache,
+            state_override_presets,
         };
 
         let api = eth_api_builder(&ctx);
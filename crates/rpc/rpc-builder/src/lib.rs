@@ -18,7 +18,11 @@
 //! ```
 //! use reth_evm::ConfigureEvm;
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider};
+//! use reth_provider::{
+//!     AccountReader, AddressTransactionsReader, BadBlockReader, CanonStateSubscriptions,
+//!     ChainDbReader, ChangeSetReader, ForkChoiceSubscriptions, FullRpcProvider, LogIndexReader,
+//!     ParliaSnapshotReader, PreimageReader, PruneCheckpointReader, StateDumpReader,
+//! };
 //! use reth_rpc::EthApi;
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -33,10 +37,20 @@
 //!     events: Events,
 //!     evm_config: EvmConfig,
 //! ) where
-//!     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+//!     Provider: FullRpcProvider
+//!         + AccountReader
+//!         + ChangeSetReader
+//!         + PruneCheckpointReader
+//!         + BadBlockReader
+//!         + AddressTransactionsReader
+//!         + PreimageReader
+//!         + StateDumpReader
+//!         + ChainDbReader
+//!         + LogIndexReader
+//!         + ParliaSnapshotReader,
 //!     Pool: TransactionPool + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
-//!     Events: CanonStateSubscriptions + Clone + 'static,
+//!     Events: CanonStateSubscriptions + ForkChoiceSubscriptions + Clone + 'static,
 //!     EvmConfig: ConfigureEvm,
 //! {
 //!     // configure the rpc module per transport
@@ -69,7 +83,11 @@
 //! use reth_engine_primitives::EngineTypes;
 //! use reth_evm::ConfigureEvm;
 //! use reth_network_api::{NetworkInfo, Peers};
-//! use reth_provider::{AccountReader, CanonStateSubscriptions, ChangeSetReader, FullRpcProvider};
+//! use reth_provider::{
+//!     AccountReader, AddressTransactionsReader, BadBlockReader, CanonStateSubscriptions,
+//!     ChainDbReader, ChangeSetReader, ForkChoiceSubscriptions, FullRpcProvider, LogIndexReader,
+//!     ParliaSnapshotReader, PreimageReader, PruneCheckpointReader, StateDumpReader,
+//! };
 //! use reth_rpc::EthApi;
 //! use reth_rpc_api::EngineApiServer;
 //! use reth_rpc_builder::{
@@ -88,10 +106,20 @@
 //!     engine_api: EngineApi,
 //!     evm_config: EvmConfig,
 //! ) where
-//!     Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+//!     Provider: FullRpcProvider
+//!         + AccountReader
+//!         + ChangeSetReader
+//!         + PruneCheckpointReader
+//!         + BadBlockReader
+//!         + AddressTransactionsReader
+//!         + PreimageReader
+//!         + StateDumpReader
+//!         + ChainDbReader
+//!         + LogIndexReader
+//!         + ParliaSnapshotReader,
 //!     Pool: TransactionPool + 'static,
 //!     Network: NetworkInfo + Peers + Clone + 'static,
-//!     Events: CanonStateSubscriptions + Clone + 'static,
+//!     Events: CanonStateSubscriptions + ForkChoiceSubscriptions + Clone + 'static,
 //!     EngineApi: EngineApiServer<EngineT>,
 //!     EngineT: EngineTypes + 'static,
 //!     EvmConfig: ConfigureEvm,
@@ -136,6 +164,7 @@
 use std::{
     collections::HashMap,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
@@ -154,13 +183,17 @@ use jsonrpsee::{
 use reth_engine_primitives::EngineTypes;
 use reth_evm::ConfigureEvm;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
+use reth_payload_primitives::MinerHandle;
+use reth_primitives::parlia::ParliaConfig;
 use reth_provider::{
-    AccountReader, BlockReader, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, FullRpcProvider, StateProviderFactory,
+    AccountReader, AddressTransactionsReader, BadBlockReader, BlockReader, CanonStateSubscriptions,
+    ChainDbReader, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, ForkChoiceSubscriptions,
+    FullRpcProvider, LogIndexReader, ParliaSnapshotReader, PreimageReader, PruneCheckpointReader,
+    StateDumpReader, StateProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, Web3Api,
+    AdminApi, AdminStateOverridePresetsApi, DebugApi, EngineEthApi, EthBundle, MinerApi, NetApi,
+    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -225,11 +258,21 @@ pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig, EthApi>(
     eth: DynEthApiBuilder<Provider, Pool, EvmConfig, Network, Tasks, Events, EthApi>,
 ) -> Result<RpcServerHandle, RpcError>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+    Provider: FullRpcProvider
+        + AccountReader
+        + ChangeSetReader
+        + PruneCheckpointReader
+        + BadBlockReader
+        + AddressTransactionsReader
+        + PreimageReader
+        + StateDumpReader
+        + ChainDbReader
+        + LogIndexReader
+        + ParliaSnapshotReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
-    Events: CanonStateSubscriptions + Clone + 'static,
+    Events: CanonStateSubscriptions + ForkChoiceSubscriptions + Clone + 'static,
     EvmConfig: ConfigureEvm,
     EthApi: FullEthApiServer,
 {
@@ -260,6 +303,8 @@ pub struct RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig> {
     events: Events,
     /// Defines how the EVM should be configured before execution.
     evm_config: EvmConfig,
+    /// Controls local block production, if the node seals blocks.
+    miner: Option<MinerHandle>,
 }
 
 // === impl RpcBuilder ===
@@ -276,7 +321,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         events: Events,
         evm_config: EvmConfig,
     ) -> Self {
-        Self { provider, pool, network, executor, events, evm_config }
+        Self { provider, pool, network, executor, events, evm_config, miner: None }
     }
 
     /// Configure the provider instance.
@@ -287,8 +332,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         P: BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     {
-        let Self { pool, network, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self { pool, network, executor, events, evm_config, miner, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, miner }
     }
 
     /// Configure the transaction pool instance.
@@ -299,8 +344,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         P: TransactionPool + 'static,
     {
-        let Self { provider, network, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self { provider, network, executor, events, evm_config, miner, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, miner }
     }
 
     /// Configure a [`NoopTransactionPool`] instance.
@@ -311,7 +356,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     pub fn with_noop_pool(
         self,
     ) -> RpcModuleBuilder<Provider, NoopTransactionPool, Network, Tasks, Events, EvmConfig> {
-        let Self { provider, executor, events, network, evm_config, .. } = self;
+        let Self { provider, executor, events, network, evm_config, miner, .. } = self;
         RpcModuleBuilder {
             provider,
             executor,
@@ -319,6 +364,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             network,
             evm_config,
             pool: NoopTransactionPool::default(),
+            miner,
         }
     }
 
@@ -330,8 +376,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         N: NetworkInfo + Peers + 'static,
     {
-        let Self { provider, pool, executor, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self { provider, pool, executor, events, evm_config, miner, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, miner }
     }
 
     /// Configure a [`NoopNetwork`] instance.
@@ -342,7 +388,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     pub fn with_noop_network(
         self,
     ) -> RpcModuleBuilder<Provider, Pool, NoopNetwork, Tasks, Events, EvmConfig> {
-        let Self { provider, pool, executor, events, evm_config, .. } = self;
+        let Self { provider, pool, executor, events, evm_config, miner, .. } = self;
         RpcModuleBuilder {
             provider,
             pool,
@@ -350,6 +396,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             events,
             network: NoopNetwork::default(),
             evm_config,
+            miner,
         }
     }
 
@@ -361,8 +408,8 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         T: TaskSpawner + 'static,
     {
-        let Self { pool, network, provider, events, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self { pool, network, provider, events, evm_config, miner, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, miner }
     }
 
     /// Configure [`TokioTaskExecutor`] as the task executor to use for additional tasks.
@@ -372,7 +419,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     pub fn with_tokio_executor(
         self,
     ) -> RpcModuleBuilder<Provider, Pool, Network, TokioTaskExecutor, Events, EvmConfig> {
-        let Self { pool, network, provider, events, evm_config, .. } = self;
+        let Self { pool, network, provider, events, evm_config, miner, .. } = self;
         RpcModuleBuilder {
             provider,
             network,
@@ -380,6 +427,7 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             events,
             executor: TokioTaskExecutor::default(),
             evm_config,
+            miner,
         }
     }
 
@@ -389,10 +437,10 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         events: E,
     ) -> RpcModuleBuilder<Provider, Pool, Network, Tasks, E, EvmConfig>
     where
-        E: CanonStateSubscriptions + 'static,
+        E: CanonStateSubscriptions + ForkChoiceSubscriptions + 'static,
     {
-        let Self { provider, pool, executor, network, evm_config, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self { provider, pool, executor, network, evm_config, miner, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, miner }
     }
 
     /// Configure the evm configuration type
@@ -403,19 +451,38 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     where
         E: ConfigureEvm + 'static,
     {
-        let Self { provider, pool, executor, network, events, .. } = self;
-        RpcModuleBuilder { provider, network, pool, executor, events, evm_config }
+        let Self { provider, pool, executor, network, events, miner, .. } = self;
+        RpcModuleBuilder { provider, network, pool, executor, events, evm_config, miner }
+    }
+
+    /// Configure the [`MinerHandle`] shared with the payload builder.
+    ///
+    /// This enables the `miner_` namespace and serves `eth_coinbase` and `eth_mining` from the
+    /// handle.
+    pub fn with_miner(mut self, miner: impl Into<Option<MinerHandle>>) -> Self {
+        self.miner = miner.into();
+        self
     }
 }
 
 impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
     RpcModuleBuilder<Provider, Pool, Network, Tasks, Events, EvmConfig>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+    Provider: FullRpcProvider
+        + AccountReader
+        + ChangeSetReader
+        + PruneCheckpointReader
+        + BadBlockReader
+        + AddressTransactionsReader
+        + PreimageReader
+        + StateDumpReader
+        + ChainDbReader
+        + LogIndexReader
+        + ParliaSnapshotReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
-    Events: CanonStateSubscriptions + Clone + 'static,
+    Events: CanonStateSubscriptions + ForkChoiceSubscriptions + Clone + 'static,
     EvmConfig: ConfigureEvm,
 {
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -440,13 +507,14 @@ where
         EngineApi: EngineApiServer<EngineT>,
         EthApi: FullEthApiServer,
     {
-        let Self { provider, pool, network, executor, events, evm_config } = self;
+        let Self { provider, pool, network, executor, events, evm_config, miner } = self;
 
         let config = module_config.config.clone().unwrap_or_default();
 
         let mut registry = RpcRegistryInner::new(
             provider, pool, network, executor, events, config, evm_config, eth,
-        );
+        )
+        .with_miner(miner);
 
         let modules = registry.create_transport_rpc_modules(module_config);
 
@@ -492,8 +560,9 @@ where
     where
         EthApi: 'static,
     {
-        let Self { provider, pool, network, executor, events, evm_config } = self;
+        let Self { provider, pool, network, executor, events, evm_config, miner } = self;
         RpcRegistryInner::new(provider, pool, network, executor, events, config, evm_config, eth)
+            .with_miner(miner)
     }
 
     /// Configures all [`RpcModule`]s specific to the given [`TransportRpcModuleConfig`] which can
@@ -508,7 +577,7 @@ where
     {
         let mut modules = TransportRpcModules::default();
 
-        let Self { provider, pool, network, executor, events, evm_config } = self;
+        let Self { provider, pool, network, executor, events, evm_config, miner } = self;
 
         if !module_config.is_empty() {
             let TransportRpcModuleConfig { http, ws, ipc, config } = module_config.clone();
@@ -522,7 +591,8 @@ where
                 config.unwrap_or_default(),
                 evm_config,
                 eth,
-            );
+            )
+            .with_miner(miner);
 
             modules.config = module_config;
             modules.http = registry.maybe_module(http.as_ref());
@@ -545,6 +615,10 @@ impl Default for RpcModuleBuilder<(), (), (), (), (), ()> {
 pub struct RpcModuleConfig {
     /// `eth` namespace settings
     eth: EthConfig,
+    /// Directory `debug_standardTrace*ToFile` calls write their traces to.
+    trace_dir: Option<PathBuf>,
+    /// The parlia consensus config `debug` calls re-execute blocks with.
+    parlia: ParliaConfig,
 }
 
 // === impl RpcModuleConfig ===
@@ -556,8 +630,20 @@ impl RpcModuleConfig {
     }
 
     /// Returns a new RPC module config given the eth namespace config
-    pub const fn new(eth: EthConfig) -> Self {
-        Self { eth }
+    pub fn new(eth: EthConfig) -> Self {
+        Self { eth, trace_dir: None, parlia: ParliaConfig::default() }
+    }
+
+    /// Sets the directory `debug_standardTrace*ToFile` calls write their traces to.
+    pub fn with_trace_dir(mut self, trace_dir: impl Into<PathBuf>) -> Self {
+        self.trace_dir = Some(trace_dir.into());
+        self
+    }
+
+    /// Sets the parlia consensus config `debug` calls re-execute blocks with.
+    pub fn with_parlia(mut self, parlia: ParliaConfig) -> Self {
+        self.parlia = parlia;
+        self
     }
 
     /// Get a reference to the eth namespace config
@@ -569,12 +655,24 @@ impl RpcModuleConfig {
     pub fn eth_mut(&mut self) -> &mut EthConfig {
         &mut self.eth
     }
+
+    /// Get the directory `debug_standardTrace*ToFile` calls write their traces to, if any
+    pub fn trace_dir(&self) -> Option<&Path> {
+        self.trace_dir.as_deref()
+    }
+
+    /// Get the parlia consensus config `debug` calls re-execute blocks with
+    pub const fn parlia(&self) -> &ParliaConfig {
+        &self.parlia
+    }
 }
 
 /// Configures [`RpcModuleConfig`]
 #[derive(Clone, Debug, Default)]
 pub struct RpcModuleConfigBuilder {
     eth: Option<EthConfig>,
+    trace_dir: Option<PathBuf>,
+    parlia: Option<ParliaConfig>,
 }
 
 // === impl RpcModuleConfigBuilder ===
@@ -586,10 +684,26 @@ impl RpcModuleConfigBuilder {
         self
     }
 
+    /// Configures the directory `debug_standardTrace*ToFile` calls write their traces to
+    pub fn trace_dir(mut self, trace_dir: impl Into<PathBuf>) -> Self {
+        self.trace_dir = Some(trace_dir.into());
+        self
+    }
+
+    /// Configures the parlia consensus config `debug` calls re-execute blocks with
+    pub fn parlia(mut self, parlia: ParliaConfig) -> Self {
+        self.parlia = Some(parlia);
+        self
+    }
+
     /// Consumes the type and creates the [`RpcModuleConfig`]
     pub fn build(self) -> RpcModuleConfig {
-        let Self { eth } = self;
-        RpcModuleConfig { eth: eth.unwrap_or_default() }
+        let Self { eth, trace_dir, parlia } = self;
+        RpcModuleConfig {
+            eth: eth.unwrap_or_default(),
+            trace_dir,
+            parlia: parlia.unwrap_or_default(),
+        }
     }
 
     /// Get a reference to the eth namespace config, if any
@@ -620,6 +734,12 @@ pub struct RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi> {
     eth: EthHandlers<Provider, Pool, Network, Events, EthApi>,
     /// to put trace calls behind semaphore
     blocking_pool_guard: BlockingTaskGuard,
+    /// Directory `debug_standardTrace*ToFile` calls write their traces to
+    trace_dir: Option<PathBuf>,
+    /// The parlia consensus config `debug` calls re-execute blocks with
+    parlia: ParliaConfig,
+    /// Controls local block production, if the node seals blocks
+    miner: Option<MinerHandle>,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
 }
@@ -632,7 +752,7 @@ where
     Provider: StateProviderFactory + BlockReader + EvmEnvProvider + Clone + Unpin + 'static,
     Pool: Send + Sync + Clone + 'static,
     Network: Clone + 'static,
-    Events: CanonStateSubscriptions + Clone + 'static,
+    Events: CanonStateSubscriptions + ForkChoiceSubscriptions + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     EthApi: 'static,
 {
@@ -660,6 +780,8 @@ where
         EvmConfig: ConfigureEvm,
     {
         let blocking_pool_guard = BlockingTaskGuard::new(config.eth.max_tracing_requests);
+        let trace_dir = config.trace_dir;
+        let parlia = config.parlia;
 
         let eth = EthHandlers::builder(
             provider.clone(),
@@ -681,6 +803,9 @@ where
             executor,
             modules: Default::default(),
             blocking_pool_guard,
+            trace_dir,
+            parlia,
+            miner: None,
             events,
         }
     }
@@ -689,6 +814,18 @@ where
 impl<Provider, Pool, Network, Tasks, Events, EthApi>
     RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi>
 {
+    /// Sets the [`MinerHandle`] the `miner_` namespace and the mining status calls of the `eth_`
+    /// namespace are served from.
+    pub fn with_miner(mut self, miner: Option<MinerHandle>) -> Self {
+        self.miner = miner;
+        self
+    }
+
+    /// Returns the installed [`MinerHandle`], if any.
+    pub const fn miner(&self) -> Option<&MinerHandle> {
+        self.miner.as_ref()
+    }
+
     /// Returns a reference to the installed [`EthApi`](reth_rpc::eth::EthApi).
     pub const fn eth_api(&self) -> &EthApi {
         &self.eth.api
@@ -795,7 +932,17 @@ where
 impl<Provider, Pool, Network, Tasks, Events, EthApi>
     RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+    Provider: FullRpcProvider
+        + AccountReader
+        + ChangeSetReader
+        + PruneCheckpointReader
+        + BadBlockReader
+        + AddressTransactionsReader
+        + PreimageReader
+        + StateDumpReader
+        + ChainDbReader
+        + LogIndexReader
+        + ParliaSnapshotReader,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
     EthApi: Clone,
@@ -919,12 +1066,12 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
-    pub fn otterscan_api(&self) -> OtterscanApi<EthApi>
+    pub fn otterscan_api(&self) -> OtterscanApi<Provider, EthApi>
     where
         EthApi: EthApiServer,
     {
         let eth_api = self.eth_api().clone();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(self.provider.clone(), eth_api)
     }
 
     /// Instantiates `DebugApi`
@@ -937,7 +1084,13 @@ where
         EthApi: EthApiSpec + EthTransactions + TraceExt,
     {
         let eth_api = self.eth_api().clone();
-        DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
+        DebugApi::new(
+            self.provider.clone(),
+            eth_api,
+            self.blocking_pool_guard.clone(),
+            self.trace_dir.clone(),
+            self.parlia.clone(),
+        )
     }
 
     /// Instantiates `NetApi`
@@ -962,16 +1115,27 @@ where
 impl<Provider, Pool, Network, Tasks, Events, EthApi>
     RpcRegistryInner<Provider, Pool, Network, Tasks, Events, EthApi>
 where
-    Provider: FullRpcProvider + AccountReader + ChangeSetReader,
+    Provider: FullRpcProvider
+        + AccountReader
+        + ChangeSetReader
+        + PruneCheckpointReader
+        + BadBlockReader
+        + AddressTransactionsReader
+        + PreimageReader
+        + StateDumpReader
+        + ChainDbReader
+        + LogIndexReader
+        + ParliaSnapshotReader,
     Pool: TransactionPool + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     Tasks: TaskSpawner + Clone + 'static,
-    Events: CanonStateSubscriptions + Clone + 'static,
+    Events: CanonStateSubscriptions + ForkChoiceSubscriptions + Clone + 'static,
     EthApi: FullEthApiServer,
 {
     /// Configures the auth module that includes the
     ///   * `engine_` namespace
     ///   * `api_` namespace
+    ///   * `admin_` state override preset methods
     ///
     /// Note: This does _not_ register the `engine_` in this registry.
     pub fn create_auth_module<EngineApi, EngineT>(&self, engine_api: EngineApi) -> AuthRpcModule
@@ -989,6 +1153,12 @@ where
 
         module.merge(engine_eth.into_rpc()).expect("No conflicting methods");
 
+        // the state override presets can only be modified through the authenticated server
+        let presets = Call::state_override_presets(&eth_handlers.api).clone();
+        module
+            .merge(AdminStateOverridePresetsApi::new(presets).into_rpc())
+            .expect("No conflicting methods");
+
         AuthRpcModule { inner: module }
     }
 
@@ -1060,6 +1230,8 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.trace_dir.clone(),
+                            self.parlia.clone(),
                         )
                         .into_rpc()
                         .into(),
@@ -1068,6 +1240,16 @@ where
                             let mut module = eth_api.clone().into_rpc();
                             module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
+                            if let Some(miner) = &self.miner {
+                                // serve the mining status from the block production settings
+                                module.remove_method("eth_coinbase");
+                                module.remove_method("eth_mining");
+                                module
+                                    .merge(EthMiningApiServer::into_rpc(MinerApi::new(Some(
+                                        miner.clone(),
+                                    ))))
+                                    .expect("No conflicts");
+                            }
 
                             module.into()
                         }
@@ -1093,17 +1275,32 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth_api.clone())
                                 .into_rpc()
                                 .into()
                         }
-                        RethRpcModule::EthCallBundle => {
-                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
+                        RethRpcModule::Reth => {
+                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::EthCallBundle => EthCallBundleApiServer::into_rpc(
+                            EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone()),
+                        )
+                        .into(),
+                        RethRpcModule::EthBundle => {
+                            let mut methods: Methods = EthBundleApiServer::into_rpc(
+                                EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone()),
+                            )
+                            .into();
+                            // served by the `eth-call-bundle` module
+                            methods.remove_method("eth_callBundle");
+                            methods
+                        }
+                        RethRpcModule::Miner => {
+                            MinerApiServer::into_rpc(MinerApi::new(self.miner.clone())).into()
+                        }
                     })
                     .clone()
             })
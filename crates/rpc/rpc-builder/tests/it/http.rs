@@ -1,7 +1,7 @@
 #![allow(unreachable_pub)]
 //! Standalone http tests
 
-use crate::utils::{launch_http, launch_http_ws, launch_ws};
+use crate::utils::{launch_http, launch_http_ws, launch_ws, test_address, test_rpc_builder};
 use jsonrpsee::{
     core::{
         client::{ClientT, SubscriptionClientT},
@@ -12,18 +12,22 @@ use jsonrpsee::{
     types::error::ErrorCode,
 };
 use reth_network_peers::NodeRecord;
+use reth_payload_primitives::{MinerHandle, MinerSettings};
 use reth_primitives::{
     hex_literal::hex, Address, BlockId, BlockNumberOrTag, Bytes, TxHash, B256, B64, U256, U64,
 };
+use reth_rpc::EthApi;
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, EthFilterApiClient, NetApiClient, OtterscanClient, TraceApiClient,
-    Web3ApiClient,
+    DebugApiClient, EthFilterApiClient, MinerApiClient, NetApiClient, OtterscanClient,
+    TraceApiClient, Web3ApiClient,
 };
+use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
 use reth_rpc_server_types::RethRpcModule;
 use reth_rpc_types::{
-    trace::filter::TraceFilter, FeeHistory, Filter, Index, Log, PendingTransactionFilterKind,
-    RichBlock, SyncStatus, Transaction, TransactionReceipt, TransactionRequest,
+    simulate::SimulatePayload, trace::filter::TraceFilter, FeeHistory, Filter, Index, Log,
+    PendingTransactionFilterKind, RichBlock, SyncStatus, Transaction, TransactionReceipt,
+    TransactionRequest,
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
@@ -198,12 +202,26 @@ where
     EthApiClient::create_access_list(client, call_request.clone(), Some(block_number.into()))
         .await
         .unwrap();
-    EthApiClient::estimate_gas(client, call_request.clone(), Some(block_number.into()), None)
+    EthApiClient::estimate_gas(client, call_request.clone(), Some(block_number.into()), None, None)
         .await
         .unwrap();
-    EthApiClient::call(client, call_request.clone(), Some(block_number.into()), None, None)
+    EthApiClient::call(client, call_request.clone(), Some(block_number.into()), None, None, None)
         .await
         .unwrap();
+    EthApiClient::call(
+        client,
+        call_request.clone(),
+        Some(block_number.into()),
+        None,
+        None,
+        Some("unknown".to_string()),
+    )
+    .await
+    .unwrap_err();
+    assert!(EthApiClient::get_state_override_presets(client).await.unwrap().is_empty());
+    EthApiClient::simulate_v1(client, SimulatePayload::default(), Some(block_number.into()))
+        .await
+        .unwrap_err();
     EthApiClient::syncing(client).await.unwrap();
     EthApiClient::send_transaction(client, transaction_request).await.unwrap_err();
     EthApiClient::hashrate(client).await.unwrap();
@@ -211,17 +229,16 @@ where
     EthApiClient::gas_price(client).await.unwrap_err();
     EthApiClient::max_priority_fee_per_gas(client).await.unwrap_err();
     EthApiClient::get_proof(client, address, vec![], None).await.unwrap();
+    assert!(!EthApiClient::is_mining(client).await.unwrap());
+
+    // Not supported without block production or proof-of-work
+    EthApiClient::author(client).await.unwrap_err();
+    EthApiClient::get_work(client).await.unwrap_err();
+    EthApiClient::submit_work(client, B64::default(), B256::default(), B256::default())
+        .await
+        .unwrap_err();
 
     // Unimplemented
-    assert!(is_unimplemented(EthApiClient::author(client).await.err().unwrap()));
-    assert!(is_unimplemented(EthApiClient::is_mining(client).await.err().unwrap()));
-    assert!(is_unimplemented(EthApiClient::get_work(client).await.err().unwrap()));
-    assert!(is_unimplemented(
-        EthApiClient::submit_work(client, B64::default(), B256::default(), B256::default())
-            .await
-            .err()
-            .unwrap()
-    ));
     assert!(is_unimplemented(
         EthApiClient::sign_transaction(client, call_request.clone()).await.err().unwrap()
     ));
@@ -237,7 +254,10 @@ where
     DebugApiClient::raw_block(client, block_id).await.unwrap_err();
     DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
     DebugApiClient::raw_receipts(client, block_id).await.unwrap();
-    assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
+    assert!(DebugApiClient::bad_blocks(client).await.unwrap().is_empty());
+    DebugApiClient::debug_trace_bad_block(client, B256::default(), None).await.unwrap_err();
+    DebugApiClient::debug_trace_chain(client, 1.into(), 0.into(), None).await.unwrap_err();
+    DebugApiClient::debug_intermediate_roots(client, B256::default(), None).await.unwrap_err();
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -540,6 +560,50 @@ async fn test_call_otterscan_functions_http_and_ws() {
     test_basic_otterscan_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_call_miner_functions_http() {
+    reth_tracing::init_test_tracing();
+
+    // without block production all calls fail
+    let handle = launch_http(vec![RethRpcModule::Miner]).await;
+    let client = handle.http_client().unwrap();
+    MinerApiClient::start(&client).await.unwrap_err();
+    MinerApiClient::set_gas_price(&client, U256::from(1)).await.unwrap_err();
+
+    let coinbase = Address::with_last_byte(1);
+    let miner = MinerHandle::new(Some(coinbase), Default::default());
+    let modules = vec![RethRpcModule::Eth, RethRpcModule::Miner];
+    let server = test_rpc_builder()
+        .with_miner(miner.clone())
+        .build(TransportRpcModuleConfig::set_http(modules), Box::new(EthApi::with_spawner));
+    let handle = RpcServerConfig::http(Default::default())
+        .with_http_address(test_address())
+        .start(&server)
+        .await
+        .unwrap();
+    let client = handle.http_client().unwrap();
+
+    assert_eq!(EthApiClient::author(&client).await.unwrap(), coinbase);
+    assert!(!EthApiClient::is_mining(&client).await.unwrap());
+    MinerApiClient::start(&client).await.unwrap();
+    assert!(EthApiClient::is_mining(&client).await.unwrap());
+    MinerApiClient::stop(&client).await.unwrap();
+    assert!(!EthApiClient::is_mining(&client).await.unwrap());
+
+    assert!(MinerApiClient::set_gas_price(&client, U256::from(7)).await.unwrap());
+    assert!(MinerApiClient::set_gas_limit(&client, U64::from(40_000_000)).await.unwrap());
+    assert!(MinerApiClient::set_extra(&client, "bsc".to_string()).await.unwrap());
+    MinerApiClient::set_extra(&client, "x".repeat(33)).await.unwrap_err();
+    assert_eq!(
+        miner.settings(),
+        MinerSettings {
+            gas_price: 7,
+            gas_limit: Some(40_000_000),
+            extra_data: Some(Bytes::from_static(b"bsc")),
+        }
+    );
+}
+
 // <https://github.com/paradigmxyz/reth/issues/5830>
 #[tokio::test(flavor = "multi_thread")]
 async fn test_eth_logs_args() {
@@ -768,16 +832,8 @@ async fn test_eth_coinbase_rpc_call() {
     let handle = launch_http(vec![RethRpcModule::Eth]).await;
     let client = handle.http_client().unwrap();
 
-    // Requesting coinbase address without any parameter should return Unimplemented
-    match client.request::<Address, _>("eth_coinbase", rpc_params![]).await {
-        Ok(_) => {
-            // If there's a response, it's unexpected, panic
-            panic!("Expected Unimplemented error, got successful response");
-        }
-        Err(err) => {
-            assert!(is_unimplemented(err));
-        }
-    };
+    // Requesting coinbase address without a configured coinbase should return an error
+    test_rpc_call_err::<Address>(&client, "eth_coinbase", rpc_params![]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
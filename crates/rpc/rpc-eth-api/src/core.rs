@@ -7,19 +7,17 @@ use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, B64, U256
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_rpc_types::{
     serde_helpers::JsonStorageKey,
+    simulate::{SimulatePayload, SimulatedBlock},
     state::{EvmOverrides, StateOverride},
     AccessListWithGasUsed, AnyTransactionReceipt, BlockOverrides, BlockSidecar, Bundle,
     EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Header, Index, RichBlock,
     StateContext, SyncStatus, Transaction, TransactionRequest, Work,
 };
+use std::collections::BTreeMap;
 use tracing::trace;
-use serde::{Serialize, Deserialize};
-use serde_json::{Value, from_reader};
-use std::fs::File;
-use std::io::{self, prelude::*, BufReader, BufRead};
 
 use crate::helpers::{
-    transaction::UpdateRawTxForwarder, EthApiSpec, EthBlocks, EthCall, EthFees, EthState,
+    transaction::UpdateRawTxForwarder, Call, EthApiSpec, EthBlocks, EthCall, EthFees, EthState,
     EthTransactions, FullEthApi,
 };
 
@@ -195,6 +193,9 @@ pub trait EthApi {
     async fn header_by_hash(&self, hash: B256) -> RpcResult<Option<Header>>;
 
     /// Executes a new message call immediately without creating a transaction on the block chain.
+    ///
+    /// If a state override `preset` is given, its overrides are applied together with the
+    /// `state_overrides`, which take precedence for the same account.
     #[method(name = "call")]
     async fn call(
         &self,
@@ -202,37 +203,34 @@ pub trait EthApi {
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
+        preset: Option<String>,
     ) -> RpcResult<Bytes>;
 
-
-    /// Executes a new message call immediately without creating a transaction on the block chain.
-    #[method(name = "pfcall")]
-    async fn pfcall(
-        &self,
-        request: TransactionRequest,
-        block_number: Option<BlockId>,
-        state_overrides: Option<StateOverride>,
-        block_overrides: Option<Box<BlockOverrides>>,
-    ) -> RpcResult<Bytes>;
-
-    /// update PFC
-    #[method(name = "updatePFC")]
-    async fn update_pfc(&self, input_list: Vec<String>) -> RpcResult<bool>; 
-
-    /// update PFC
-    #[method(name = "getPFC")]
-    async fn get_pfc(&self) -> RpcResult<std::collections::HashMap<String, String>>; 
+    /// Returns the named state override presets that can be passed to `eth_call`,
+    /// `eth_estimateGas`, `eth_callMany` and `debug_traceCall`.
+    #[method(name = "getStateOverridePresets")]
+    async fn get_state_override_presets(&self) -> RpcResult<BTreeMap<String, StateOverride>>;
 
     /// Simulate arbitrary number of transactions at an arbitrary blockchain index, with the
-    /// optionality of state overrides
+    /// optionality of state overrides and a named state override preset
     #[method(name = "callMany")]
     async fn call_many(
         &self,
         bundle: Bundle,
         state_context: Option<StateContext>,
         state_override: Option<StateOverride>,
+        preset: Option<String>,
     ) -> RpcResult<Vec<EthCallResponse>>;
 
+    /// Simulates blocks of calls on top of the given block, with per block overrides, and returns
+    /// the simulated blocks along with the results of their calls.
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<Vec<SimulatedBlock>>;
+
     /// Generates an access list for a transaction.
     ///
     /// This method creates an [EIP2930](https://eips.ethereum.org/EIPS/eip-2930) type accessList based on a given Transaction.
@@ -262,6 +260,7 @@ pub trait EthApi {
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
+        preset: Option<String>,
     ) -> RpcResult<U256>;
 
     /// Returns the current price per gas in wei.
@@ -358,51 +357,6 @@ pub trait EthApi {
     async fn get_block_sidecar_by_tx_hash(&self, hash: B256) -> RpcResult<Option<BlockSidecar>>;
 }
 
-
-const PFC_FILE_PATH: &str = "/data01/full_node/PFC.json";
-
-// 定义 AccountState 结构体
-#[derive(Serialize, Deserialize)]
-struct AccountState {
-    nonce: Option<String>,
-    code: String,
-    balance: Option<String>,
-    state: Option<String>,
-    stateDiff: Option<String>,
-}
-
-async fn save_pfc_to_file(contracts: Vec<String>) -> Result<bool, io::Error> {
-    let accounts = contracts
-        .into_iter()
-        .map(|b| AccountState {
-            nonce: None,
-            code: b,
-            balance: None,
-            state: None,
-            stateDiff: None,
-        })
-        .collect::<Vec<_>>();
-
-    // 构建最终的 JSON 对象
-    let result = serde_json::to_string_pretty(&accounts.into_iter().enumerate().map(|(i, a)| if i<10 {(format!("0xceeb00000000000000000000000000000000000{}", i), a)} else {(format!("0xbeef00000000000000000000000000000000000{}", i-10), a)} ).collect::<std::collections::HashMap<_, _>>())
-        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("Failed to serialize data: {}", e)))?;
-
-    // 写入文件
-    let mut file = File::create(PFC_FILE_PATH)?;
-    file.write_all(result.as_bytes())?;
-
-    Ok(true)
-}
-
-/// 从文件中读取 StateOverride 数据
-fn read_state_override_from_file(file_path: &str) -> Result<Option<StateOverride>, io::Error>  {
-    let file = File::open(file_path)?;
-    let reader = BufReader::new(file);
-
-    let overrides: StateOverride = serde_json::from_reader(reader)?; // 假设使用 serde_json 来反序列化
-    Ok(Some(overrides))
-}
-
 #[async_trait::async_trait]
 impl<T> EthApiServer for T
 where
@@ -421,8 +375,11 @@ where
     }
 
     /// Handler for: `eth_coinbase`
+    ///
+    /// Nodes that seal blocks serve this from the `miner` handle instead.
     async fn author(&self) -> RpcResult<Address> {
-        Err(internal_rpc_err("unimplemented"))
+        trace!(target: "rpc::eth", "Serving eth_coinbase");
+        Err(internal_rpc_err("etherbase must be explicitly specified"))
     }
 
     /// Handler for: `eth_accounts`
@@ -639,28 +596,11 @@ where
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
+        preset: Option<String>,
     ) -> RpcResult<Bytes> {
-        trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, "Serving eth_call");
-        Ok(EthCall::call(
-            self,
-            request,
-            block_number,
-            EvmOverrides::new(state_overrides, block_overrides),
-        )
-        .await?)
-    }
-
-
-    /// Handler for: `eth_pfcall`
-    async fn pfcall(
-        &self,
-        request: TransactionRequest,
-        block_number: Option<BlockId>,
-        state_overrides: Option<StateOverride>,
-        block_overrides: Option<Box<BlockOverrides>>,
-    ) -> RpcResult<Bytes> {
-        let state_overrides = read_state_override_from_file(PFC_FILE_PATH).expect("Failed to Reading from PFC file");
-        trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, "Serving eth_pfcall");
+        trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, ?preset, "Serving eth_call");
+        let state_overrides =
+            Call::state_override_presets(self).resolve(preset.as_deref(), state_overrides)?;
         Ok(EthCall::call(
             self,
             request,
@@ -670,24 +610,10 @@ where
         .await?)
     }
 
-    /// Handler for: `eth_updatePFC`
-    async fn update_pfc(&self, input_list: Vec<String>) -> RpcResult<bool> {
-        if input_list.len() > 20 {
-            return Err(internal_rpc_err("The number of contracts is incorrect, the maximum number of contracts supported is 20.".to_string()));
-        }      
-        save_pfc_to_file(input_list).await.map_err(|e| internal_rpc_err(format!("Failed to save PFC: {}", e)))
-    }
-
-    /// 获取 PFC 映射
-    async fn get_pfc(&self) -> RpcResult<std::collections::HashMap<String, String>> {
-        let state_overrides = read_state_override_from_file(PFC_FILE_PATH).expect("Failed to Reading from PFC file").unwrap();
-    
-        let mut output_map = std::collections::HashMap::new();
-        for (key, value) in &state_overrides {
-            output_map.insert(key.clone().to_string(), value.code.clone().unwrap().to_string());
-        }
-    
-        Ok(output_map)
+    /// Handler for: `eth_getStateOverridePresets`
+    async fn get_state_override_presets(&self) -> RpcResult<BTreeMap<String, StateOverride>> {
+        trace!(target: "rpc::eth", "Serving eth_getStateOverridePresets");
+        Ok(Call::state_override_presets(self).all())
     }
 
     /// Handler for: `eth_callMany`
@@ -696,11 +622,24 @@ where
         bundle: Bundle,
         state_context: Option<StateContext>,
         state_override: Option<StateOverride>,
+        preset: Option<String>,
     ) -> RpcResult<Vec<EthCallResponse>> {
-        trace!(target: "rpc::eth", ?bundle, ?state_context, ?state_override, "Serving eth_callMany");
+        trace!(target: "rpc::eth", ?bundle, ?state_context, ?state_override, ?preset, "Serving eth_callMany");
+        let state_override =
+            Call::state_override_presets(self).resolve(preset.as_deref(), state_override)?;
         Ok(EthCall::call_many(self, bundle, state_context, state_override).await?)
     }
 
+    /// Handler for: `eth_simulateV1`
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        trace!(target: "rpc::eth", ?block_number, "Serving eth_simulateV1");
+        Ok(EthCall::simulate_v1(self, payload, block_number).await?)
+    }
+
     /// Handler for: `eth_createAccessList`
     async fn create_access_list(
         &self,
@@ -720,8 +659,11 @@ where
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
+        preset: Option<String>,
     ) -> RpcResult<U256> {
-        trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_estimateGas");
+        trace!(target: "rpc::eth", ?request, ?block_number, ?preset, "Serving eth_estimateGas");
+        let state_override =
+            Call::state_override_presets(self).resolve(preset.as_deref(), state_override)?;
         Ok(EthCall::estimate_gas_at(
             self,
             request,
@@ -771,8 +713,11 @@ where
     }
 
     /// Handler for: `eth_mining`
+    ///
+    /// Nodes that seal blocks serve this from the `miner` handle instead.
     async fn is_mining(&self) -> RpcResult<bool> {
-        Err(internal_rpc_err("unimplemented"))
+        trace!(target: "rpc::eth", "Serving eth_mining");
+        Ok(false)
     }
 
     /// Handler for: `eth_hashrate`
@@ -782,7 +727,7 @@ where
 
     /// Handler for: `eth_getWork`
     async fn get_work(&self) -> RpcResult<Work> {
-        Err(internal_rpc_err("unimplemented"))
+        Err(internal_rpc_err("eth_getWork is not supported: the chain is not proof-of-work"))
     }
 
     /// Handler for: `eth_submitHashrate`
@@ -797,7 +742,7 @@ where
         _pow_hash: B256,
         _mix_digest: B256,
     ) -> RpcResult<bool> {
-        Err(internal_rpc_err("unimplemented"))
+        Err(internal_rpc_err("eth_submitWork is not supported: the chain is not proof-of-work"))
     }
 
     /// Handler for: `eth_sendTransaction`
@@ -6,6 +6,8 @@ use cfg_if::cfg_if;
 use futures::Future;
 #[cfg(feature = "bsc")]
 use reth_chainspec::BscHardforks;
+#[cfg(feature = "bsc")]
+use reth_errors::RethError;
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
 #[cfg(feature = "bsc")]
 use reth_primitives::system_contracts::get_upgrade_system_contracts;
@@ -18,7 +20,7 @@ use reth_primitives::{
     },
     Bytes, TransactionSignedEcRecovered, TxKind, B256, U256,
 };
-use reth_provider::{ChainSpecProvider, StateProvider};
+use reth_provider::{ChainSpecProvider, HeaderProvider, StateProvider, StateRootProvider};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB, DatabaseRef};
 use reth_rpc_eth_types::{
     cache::db::{StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
@@ -27,10 +29,16 @@ use reth_rpc_eth_types::{
         apply_block_overrides, apply_state_overrides, caller_gas_allowance,
         cap_tx_gas_limit_with_caller_allowance, get_precompiles, CallFees,
     },
+    simulate::{
+        build_simulated_block, build_simulated_transaction, hashed_post_state, SimulatedCall,
+        TransferInspector, MAX_SIMULATE_BLOCKS, SIMULATE_TIMESTAMP_INCREMENT,
+    },
     EthApiError, EthResult, RevertError, RpcInvalidTransactionError, StateCacheDb,
+    StateOverridePresets,
 };
 use reth_rpc_server_types::constants::gas_oracle::{ESTIMATE_GAS_ERROR_RATIO, MIN_TRANSACTION_GAS};
 use reth_rpc_types::{
+    simulate::{SimBlock, SimulatePayload, SimulatedBlock},
     state::{EvmOverrides, StateOverride},
     AccessListWithGasUsed, BlockId, Bundle, EthCallResponse, StateContext, TransactionInfo,
     TransactionRequest,
@@ -181,6 +189,208 @@ pub trait EthCall: Call + LoadPendingBlock {
         }
     }
 
+    /// Simulates blocks of calls on top of the given block (`eth_simulateV1`).
+    ///
+    /// Each simulated block is numbered and timestamped after its parent unless overridden, the
+    /// state changes of a block are visible to all following blocks. The gas used by all calls of
+    /// all blocks is capped at the call gas limit.
+    fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockId>,
+    ) -> impl Future<Output = EthResult<Vec<SimulatedBlock>>> + Send
+    where
+        Self: LoadBlock + Trace,
+    {
+        async move {
+            let SimulatePayload {
+                block_state_calls,
+                trace_transfers,
+                validation,
+                return_full_transactions,
+            } = payload;
+            if block_state_calls.is_empty() {
+                return Err(EthApiError::InvalidParams(String::from("block state calls are empty.")))
+            }
+            if block_state_calls.len() > MAX_SIMULATE_BLOCKS {
+                return Err(EthApiError::InvalidParams(format!(
+                    "too many blocks, at most {MAX_SIMULATE_BLOCKS} blocks can be simulated"
+                )))
+            }
+
+            let block = block.unwrap_or_default();
+            let ((mut cfg, block_env, at), parent) =
+                futures::try_join!(self.evm_env_at(block), self.block(block))?;
+            let Some(parent) = parent else { return Err(EthApiError::UnknownBlockNumber) };
+            let parent_total_difficulty =
+                LoadBlock::provider(self).header_td_by_number(parent.number)?.unwrap_or_default();
+
+            let chain_id = LoadState::provider(self).chain_spec().chain.id();
+            let gas_limit = self.call_gas_limit();
+
+            // Disabled because simulated calls are sent from arbitrary senders
+            cfg.disable_eip3607 = true;
+            if !validation {
+                cfg.disable_base_fee = true;
+                cfg.disable_balance_check = true;
+                cfg.disable_block_gas_limit = true;
+            }
+
+            let this = self.clone();
+            self.spawn_with_state_at_block(at, move |state| {
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut blocks = Vec::with_capacity(block_state_calls.len());
+
+                let mut parent_hash = parent.hash();
+                let mut parent_number = parent.number;
+                let mut parent_timestamp = parent.timestamp;
+                let mut total_difficulty = parent_total_difficulty;
+                // shared by all blocks, so a simulation is as expensive as a single call at most
+                let mut remaining_call_gas = gas_limit;
+
+                for SimBlock { block_overrides, state_overrides, calls } in block_state_calls {
+                    let mut block_env = block_env.clone();
+                    block_env.number = U256::from(parent_number + 1);
+                    block_env.timestamp =
+                        U256::from(parent_timestamp + SIMULATE_TIMESTAMP_INCREMENT);
+                    if !validation {
+                        block_env.basefee = U256::ZERO;
+                    }
+                    if let Some(mut block_overrides) = block_overrides {
+                        if let Some(block_hashes) = block_overrides.block_hash.take() {
+                            db.block_hashes.extend(
+                                block_hashes.into_iter().map(|(num, hash)| (U256::from(num), hash)),
+                            )
+                        }
+                        apply_block_overrides(block_overrides, &mut block_env);
+                    }
+
+                    let number: u64 = block_env.number.saturating_to();
+                    let timestamp: u64 = block_env.timestamp.saturating_to();
+                    if number <= parent_number {
+                        return Err(EthApiError::InvalidParams(format!(
+                            "block number {number} is not after {parent_number}"
+                        )))
+                    }
+                    if timestamp <= parent_timestamp {
+                        return Err(EthApiError::InvalidParams(format!(
+                            "block timestamp {timestamp} is not after {parent_timestamp}"
+                        )))
+                    }
+
+                    if let Some(state_overrides) = state_overrides {
+                        apply_state_overrides(state_overrides, &mut db)?;
+                    }
+
+                    // the system contracts are upgraded before all txs if feynman is not active and
+                    // between normal and system txs otherwise, simulated blocks only have normal
+                    // txs
+                    #[cfg(feature = "bsc")]
+                    let is_feynman = LoadState::provider(&this)
+                        .chain_spec()
+                        .is_feynman_active_at_timestamp(timestamp);
+                    #[cfg(feature = "bsc")]
+                    if !is_feynman {
+                        this.upgrade_system_contracts(
+                            &mut db,
+                            number,
+                            timestamp,
+                            parent_timestamp,
+                        )?;
+                    }
+
+                    let mut simulated_calls = Vec::with_capacity(calls.len());
+                    let mut cumulative_gas_used = 0u64;
+                    for call in calls {
+                        let sender = call.from.unwrap_or_default();
+                        let nonce = match call.nonce {
+                            Some(nonce) => nonce,
+                            None => db.basic_ref(sender)?.unwrap_or_default().nonce,
+                        };
+                        let block_gas_limit: u64 = block_env.gas_limit.saturating_to();
+                        let remaining_gas = block_gas_limit.saturating_sub(cumulative_gas_used);
+                        if validation && call.gas.map_or(false, |gas| gas > remaining_gas) {
+                            return Err(EthApiError::InvalidParams(format!(
+                                "block gas limit exceeded in block {number}"
+                            )))
+                        }
+                        if call.gas.map_or(remaining_call_gas == 0, |gas| gas > remaining_call_gas)
+                        {
+                            return Err(EthApiError::InvalidParams(format!(
+                                "gas cap {gas_limit} exceeded in block {number}"
+                            )))
+                        }
+
+                        let request_gas = call.gas;
+                        let mut env =
+                            this.build_call_evm_env(cfg.clone(), block_env.clone(), call.clone())?;
+                        if request_gas.is_none() {
+                            // calls without a gas limit may use the rest of the block's gas
+                            env.tx.gas_limit = remaining_gas.min(remaining_call_gas);
+                        }
+                        env.tx.nonce = validation.then_some(nonce);
+
+                        let transaction =
+                            build_simulated_transaction(call, &env.tx, nonce, chain_id)?;
+                        let (res, logs) = if trace_transfers {
+                            let mut inspector = TransferInspector::default();
+                            let (res, _) = this.inspect(&mut db, env, &mut inspector)?;
+                            (res, inspector.into_logs())
+                        } else {
+                            let (res, _) = this.transact(&mut db, env)?;
+                            let logs = res.result.logs().to_vec();
+                            (res, logs)
+                        };
+
+                        let gas_used = res.result.gas_used();
+                        cumulative_gas_used += gas_used;
+                        remaining_call_gas = remaining_call_gas.saturating_sub(gas_used);
+                        db.commit(res.state);
+                        simulated_calls.push(SimulatedCall {
+                            transaction,
+                            sender,
+                            result: res.result,
+                            logs,
+                        });
+                    }
+
+                    #[cfg(feature = "bsc")]
+                    if is_feynman {
+                        this.upgrade_system_contracts(
+                            &mut db,
+                            number,
+                            timestamp,
+                            parent_timestamp,
+                        )?;
+                    }
+
+                    let state_root = db.db.hashed_state_root(&hashed_post_state(&db))?;
+                    total_difficulty += block_env.difficulty;
+                    let block = build_simulated_block(
+                        parent_hash,
+                        &block_env,
+                        state_root,
+                        total_difficulty,
+                        simulated_calls,
+                        return_full_transactions,
+                    )?;
+
+                    // later blocks can access the hashes of the simulated blocks
+                    let block_hash = block.inner.header.hash.unwrap_or_default();
+                    db.block_hashes.insert(U256::from(number), block_hash);
+
+                    parent_hash = block_hash;
+                    parent_number = number;
+                    parent_timestamp = timestamp;
+                    blocks.push(block);
+                }
+
+                Ok(blocks)
+            })
+            .await
+        }
+    }
+
     /// Creates [`AccessListWithGasUsed`] for the [`TransactionRequest`] at the given
     /// [`BlockId`], or latest block.
     fn create_access_list_at(
@@ -286,6 +496,9 @@ pub trait Call: LoadState + SpawnBlocking {
     /// Data access in default (L1) trait method implementations.
     fn evm_config(&self) -> &impl ConfigureEvm;
 
+    /// Returns a handle to the named state override presets that can be applied to calls.
+    fn state_override_presets(&self) -> &StateOverridePresets;
+
     /// Executes the closure with the state that corresponds to the given [`BlockId`].
     fn with_state_at_block<F, T>(&self, at: BlockId, f: F) -> EthResult<T>
     where
@@ -497,22 +710,12 @@ pub trait Call: LoadState + SpawnBlocking {
         // try to upgrade system contracts before all txs if feynman is not active
         #[cfg(feature = "bsc")]
         if !self.provider().chain_spec().is_feynman_active_at_timestamp(block_env.timestamp.to()) {
-            let contracts = get_upgrade_system_contracts(
-                self.provider().chain_spec().as_ref(),
+            self.upgrade_system_contracts(
+                evm.db_mut(),
                 block_env.number.to(),
                 block_env.timestamp.to(),
                 parent_timestamp,
-            )
-            .expect("get upgrade system contracts failed");
-
-            for (k, v) in contracts {
-                let account = evm.db_mut().load_account(k)?;
-                if account.account_state == NotExisting {
-                    account.account_state = Touched;
-                }
-                account.info.code_hash = v.clone().unwrap().hash_slow();
-                account.info.code = v;
-            }
+            )?;
         }
 
         for tx in transactions {
@@ -539,22 +742,12 @@ pub trait Call: LoadState + SpawnBlocking {
                     .chain_spec()
                     .is_feynman_active_at_timestamp(block_env.timestamp.to())
                 {
-                    let contracts = get_upgrade_system_contracts(
-                        self.provider().chain_spec().as_ref(),
+                    self.upgrade_system_contracts(
+                        evm.db_mut(),
                         block_env.number.to(),
                         block_env.timestamp.to(),
                         parent_timestamp,
-                    )
-                    .expect("get upgrade system contracts failed");
-
-                    for (k, v) in contracts {
-                        let account = evm.db_mut().load_account(k)?;
-                        if account.account_state == NotExisting {
-                            account.account_state = Touched;
-                        }
-                        account.info.code_hash = v.clone().unwrap().hash_slow();
-                        account.info.code = v;
-                    }
+                    )?;
                 }
 
                 before_system_tx = false;
@@ -579,6 +772,39 @@ pub trait Call: LoadState + SpawnBlocking {
         Ok(index)
     }
 
+    /// Deploys the system contracts that are upgraded by the block with the given number and
+    /// timestamp to the [`CacheDB`].
+    #[cfg(feature = "bsc")]
+    fn upgrade_system_contracts<DB>(
+        &self,
+        db: &mut CacheDB<DB>,
+        block_number: u64,
+        block_timestamp: u64,
+        parent_timestamp: u64,
+    ) -> EthResult<()>
+    where
+        DB: DatabaseRef,
+        EthApiError: From<<DB as DatabaseRef>::Error>,
+    {
+        let contracts = get_upgrade_system_contracts(
+            self.provider().chain_spec().as_ref(),
+            block_number,
+            block_timestamp,
+            parent_timestamp,
+        )
+        .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+
+        for (k, v) in contracts {
+            let account = db.load_account(k)?;
+            if account.account_state == NotExisting {
+                account.account_state = Touched;
+            }
+            account.info.code_hash = v.clone().unwrap().hash_slow();
+            account.info.code = v;
+        }
+        Ok(())
+    }
+
     /// Estimate gas needed for execution of the `request` at the [`BlockId`].
     fn estimate_gas_at(
         &self,
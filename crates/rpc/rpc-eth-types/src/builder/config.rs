@@ -3,7 +3,8 @@
 use std::time::Duration;
 
 use crate::{
-    EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
+    EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, StateOverridePresets,
+    RPC_DEFAULT_GAS_CAP,
 };
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
@@ -40,6 +41,11 @@ pub struct EthConfig {
     pub fee_history_cache: FeeHistoryCacheConfig,
     /// The maximum number of getproof calls that can be executed concurrently.
     pub proof_permits: usize,
+    /// The named state override presets.
+    ///
+    /// Defaults to presets that are only kept in memory.
+    #[serde(skip)]
+    pub state_override_presets: StateOverridePresets,
 }
 
 impl EthConfig {
@@ -65,6 +71,7 @@ impl Default for EthConfig {
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
+            state_override_presets: StateOverridePresets::default(),
         }
     }
 }
@@ -117,6 +124,12 @@ impl EthConfig {
         self.proof_permits = permits;
         self
     }
+
+    /// Configures the named state override presets
+    pub fn state_override_presets(mut self, presets: StateOverridePresets) -> Self {
+        self.state_override_presets = presets;
+        self
+    }
 }
 
 /// Config for the filter
@@ -5,7 +5,7 @@ use reth_tasks::TaskSpawner;
 
 use crate::{
     fee_history::fee_history_cache_new_blocks_task, EthConfig, EthStateCache, FeeHistoryCache,
-    GasPriceOracle,
+    GasPriceOracle, StateOverridePresets,
 };
 
 /// Context for building the `eth` namespace API.
@@ -27,6 +27,8 @@ pub struct EthApiBuilderCtx<Provider, Pool, EvmConfig, Network, Tasks, Events> {
     pub events: Events,
     /// RPC cache handle.
     pub cache: EthStateCache,
+    /// Named state override presets.
+    pub state_override_presets: StateOverridePresets,
 }
 
 impl<Provider, Pool, EvmConfig, Network, Tasks, Events>
@@ -18,6 +18,8 @@ pub mod logs_utils;
 pub mod pending_block;
 pub mod receipt;
 pub mod revm_utils;
+pub mod simulate;
+pub mod state_override;
 pub mod transaction;
 pub mod utils;
 
@@ -38,4 +40,5 @@ pub use id_provider::EthSubscriptionIdProvider;
 pub use logs_utils::EthFilterError;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
 pub use receipt::ReceiptBuilder;
+pub use state_override::{StateOverridePresetError, StateOverridePresets};
 pub use transaction::TransactionSource;
@@ -0,0 +1,445 @@
+//! Utilities for `eth_simulateV1`.
+
+use reth_primitives::{
+    address, b256, keccak256,
+    proofs::{calculate_receipt_root, calculate_transaction_root},
+    revm_primitives::BlockEnv,
+    Address, Block, BlockWithSenders, Bloom, Bytes, Header, Log, Receipt, Signature, Transaction,
+    TransactionSigned, TxEip1559, TxEip2930, TxEip4844, TxKind, TxLegacy, B256,
+    EMPTY_OMMER_ROOT_HASH, U256,
+};
+use reth_rpc_types::{
+    simulate::{SimCallResult, SimulateError, SimulatedBlock},
+    BlockTransactionsKind, TransactionRequest,
+};
+use reth_rpc_types_compat::block::from_block;
+use reth_trie::{HashedPostState, HashedStorage};
+use revm::{
+    db::{AccountState, CacheDB},
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    primitives::{ExecutionResult, TxEnv},
+    Database, EvmContext, Inspector,
+};
+
+use crate::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError};
+
+/// Maximum number of blocks that can be simulated in a single request.
+pub const MAX_SIMULATE_BLOCKS: usize = 256;
+
+/// Number of seconds a simulated block's timestamp is ahead of its parent's by default.
+pub const SIMULATE_TIMESTAMP_INCREMENT: u64 = 12;
+
+/// Error code of a simulated call that reverted.
+pub const SIMULATE_REVERT_CODE: i32 = 3;
+
+/// Error code of a simulated call that halted.
+pub const SIMULATE_VM_ERROR_CODE: i32 = -32015;
+
+/// The address that emits the transfer logs of native value transfers.
+pub const TRANSFER_LOG_EMITTER: Address = address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+
+/// Topic of the `Transfer(address,address,uint256)` event.
+pub const TRANSFER_EVENT_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// An [`Inspector`] that collects the logs of a call in order, with an `ERC20` like `Transfer`
+/// log emitted by [`TRANSFER_LOG_EMITTER`] for every native value transfer.
+///
+/// Logs of reverted call frames, including their transfer logs, are discarded.
+#[derive(Debug, Default)]
+pub struct TransferInspector {
+    /// The collected logs.
+    logs: Vec<Log>,
+    /// Number of collected logs when each active call frame was entered.
+    checkpoints: Vec<usize>,
+}
+
+impl TransferInspector {
+    /// Consumes the inspector and returns the collected logs.
+    pub fn into_logs(self) -> Vec<Log> {
+        self.logs
+    }
+
+    fn enter(&mut self, from: Address, to: Address, value: U256) {
+        self.checkpoints.push(self.logs.len());
+        self.transfer(from, to, value);
+    }
+
+    fn exit(&mut self, success: bool) {
+        if let Some(checkpoint) = self.checkpoints.pop() {
+            if !success {
+                self.logs.truncate(checkpoint);
+            }
+        }
+    }
+
+    fn transfer(&mut self, from: Address, to: Address, value: U256) {
+        if value.is_zero() {
+            return
+        }
+        self.logs.push(Log::new_unchecked(
+            TRANSFER_LOG_EMITTER,
+            vec![TRANSFER_EVENT_TOPIC, from.into_word(), to.into_word()],
+            Bytes::from(value.to_be_bytes::<32>()),
+        ));
+    }
+}
+
+impl<DB: Database> Inspector<DB> for TransferInspector {
+    fn log(&mut self, _interp: &mut Interpreter, _context: &mut EvmContext<DB>, log: &Log) {
+        self.logs.push(log.clone());
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.enter(
+            inputs.caller,
+            inputs.target_address,
+            inputs.transfer_value().unwrap_or_default(),
+        );
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit(outcome.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let nonce = context
+            .journaled_state
+            .state
+            .get(&inputs.caller)
+            .map(|account| account.info.nonce)
+            .unwrap_or_default();
+        self.enter(inputs.caller, inputs.created_address(nonce), inputs.value);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.exit(outcome.result.is_ok());
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        self.transfer(contract, target, value);
+    }
+}
+
+/// A call executed as part of a simulated block.
+#[derive(Debug)]
+pub struct SimulatedCall {
+    /// The unsigned transaction of the call.
+    pub transaction: TransactionSigned,
+    /// The sender of the call.
+    pub sender: Address,
+    /// The result of the call.
+    pub result: ExecutionResult,
+    /// The logs of the call, including transfer logs if they were traced.
+    pub logs: Vec<Log>,
+}
+
+/// Builds the transaction of a simulated call from the request and its configured [`TxEnv`].
+///
+/// The transaction carries an empty signature, its hash only identifies it within the simulated
+/// blocks.
+pub fn build_simulated_transaction(
+    request: TransactionRequest,
+    tx_env: &TxEnv,
+    nonce: u64,
+    chain_id: u64,
+) -> EthResult<TransactionSigned> {
+    let TransactionRequest {
+        to,
+        value,
+        input,
+        access_list,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        max_fee_per_blob_gas,
+        blob_versioned_hashes,
+        chain_id: request_chain_id,
+        ..
+    } = request;
+
+    let chain_id = request_chain_id.unwrap_or(chain_id);
+    let to = to.unwrap_or(TxKind::Create);
+    let value = value.unwrap_or_default();
+    let input = input.into_input().unwrap_or_default();
+    let gas_limit = tx_env.gas_limit;
+    let gas_price = tx_env.gas_price.saturating_to::<u128>();
+    let priority_fee = tx_env.gas_priority_fee.unwrap_or_default().saturating_to::<u128>();
+
+    let transaction = if let Some(blob_versioned_hashes) = blob_versioned_hashes {
+        let TxKind::Call(to) = to else {
+            return Err(RpcInvalidTransactionError::BlobTransactionIsCreate.into())
+        };
+        Transaction::Eip4844(TxEip4844 {
+            chain_id,
+            nonce,
+            gas_limit,
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: priority_fee,
+            placeholder: Some(()),
+            to,
+            value,
+            access_list: access_list.unwrap_or_default(),
+            blob_versioned_hashes,
+            max_fee_per_blob_gas: max_fee_per_blob_gas.unwrap_or_default(),
+            input,
+        })
+    } else if max_fee_per_gas.is_some() || max_priority_fee_per_gas.is_some() {
+        Transaction::Eip1559(TxEip1559 {
+            chain_id,
+            nonce,
+            gas_limit,
+            max_fee_per_gas: gas_price,
+            max_priority_fee_per_gas: priority_fee,
+            to,
+            value,
+            access_list: access_list.unwrap_or_default(),
+            input,
+        })
+    } else if let Some(access_list) = access_list {
+        Transaction::Eip2930(TxEip2930 {
+            chain_id,
+            nonce,
+            gas_price,
+            gas_limit,
+            to,
+            value,
+            access_list,
+            input,
+        })
+    } else {
+        Transaction::Legacy(TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price,
+            gas_limit,
+            to,
+            value,
+            input,
+        })
+    };
+
+    Ok(TransactionSigned::from_transaction_and_signature(transaction, Signature::default()))
+}
+
+/// Returns the changes of the [`CacheDB`] relative to its underlying database as a
+/// [`HashedPostState`].
+pub fn hashed_post_state<DB>(db: &CacheDB<DB>) -> HashedPostState {
+    let mut state = HashedPostState::default();
+    for (address, account) in &db.accounts {
+        let hashed_address = keccak256(address);
+        if account.account_state == AccountState::NotExisting {
+            state.accounts.insert(hashed_address, None);
+            state.storages.insert(hashed_address, HashedStorage::new(true));
+            continue
+        }
+
+        state.accounts.insert(hashed_address, Some(account.info.clone().into()));
+        state.storages.insert(
+            hashed_address,
+            HashedStorage::from_iter(
+                account.account_state == AccountState::StorageCleared,
+                account.storage.iter().map(|(slot, value)| (keccak256(B256::from(*slot)), *value)),
+            ),
+        );
+    }
+    state
+}
+
+/// Assembles the [`SimulatedBlock`] of the executed calls on top of the parent block.
+pub fn build_simulated_block(
+    parent_hash: B256,
+    block_env: &BlockEnv,
+    state_root: B256,
+    total_difficulty: U256,
+    calls: Vec<SimulatedCall>,
+    full_transactions: bool,
+) -> EthResult<SimulatedBlock> {
+    let mut cumulative_gas_used = 0;
+    let mut logs_bloom = Bloom::ZERO;
+    let mut receipts = Vec::with_capacity(calls.len());
+    let mut transactions = Vec::with_capacity(calls.len());
+    let mut senders = Vec::with_capacity(calls.len());
+    let mut results = Vec::with_capacity(calls.len());
+
+    for SimulatedCall { transaction, sender, result, logs } in calls {
+        cumulative_gas_used += result.gas_used();
+        #[allow(clippy::needless_update)]
+        let receipt = Receipt {
+            tx_type: transaction.tx_type(),
+            success: result.is_success(),
+            cumulative_gas_used,
+            logs: logs.clone(),
+            ..Default::default()
+        }
+        .with_bloom();
+        logs_bloom |= receipt.bloom;
+
+        receipts.push(receipt);
+        results.push((transaction.hash(), result, logs));
+        transactions.push(transaction);
+        senders.push(sender);
+    }
+
+    let header = Header {
+        parent_hash,
+        ommers_hash: EMPTY_OMMER_ROOT_HASH,
+        beneficiary: block_env.coinbase,
+        state_root,
+        transactions_root: calculate_transaction_root(&transactions),
+        receipts_root: calculate_receipt_root(&receipts),
+        withdrawals_root: None,
+        logs_bloom,
+        timestamp: block_env.timestamp.saturating_to(),
+        mix_hash: block_env.prevrandao.unwrap_or_default(),
+        nonce: 0,
+        base_fee_per_gas: Some(block_env.basefee.saturating_to()),
+        number: block_env.number.saturating_to(),
+        gas_limit: block_env.gas_limit.saturating_to(),
+        difficulty: block_env.difficulty,
+        gas_used: cumulative_gas_used,
+        blob_gas_used: None,
+        excess_blob_gas: block_env.get_blob_excess_gas(),
+        extra_data: Default::default(),
+        parent_beacon_block_root: None,
+        requests_root: None,
+    };
+    let block = Block {
+        header,
+        body: transactions,
+        ommers: vec![],
+        withdrawals: None,
+        sidecars: None,
+        requests: None,
+    }
+    .seal_slow();
+    let (block_hash, block_number, block_timestamp) = (block.hash(), block.number, block.timestamp);
+
+    let mut log_index = 0;
+    let calls = results
+        .into_iter()
+        .enumerate()
+        .map(|(index, (transaction_hash, result, logs))| {
+            let logs = logs
+                .into_iter()
+                .map(|inner| {
+                    let log = reth_rpc_types::Log {
+                        inner,
+                        block_hash: Some(block_hash),
+                        block_number: Some(block_number),
+                        block_timestamp: Some(block_timestamp),
+                        transaction_hash: Some(transaction_hash),
+                        transaction_index: Some(index as u64),
+                        log_index: Some(log_index),
+                        removed: false,
+                    };
+                    log_index += 1;
+                    log
+                })
+                .collect();
+
+            let gas_used = result.gas_used();
+            let status = result.is_success() as u64;
+            let (return_data, error) = match result {
+                ExecutionResult::Success { output, .. } => (output.into_data(), None),
+                ExecutionResult::Revert { output, .. } => {
+                    let message = RevertError::new(output.clone()).to_string();
+                    (output, Some(SimulateError { code: SIMULATE_REVERT_CODE, message }))
+                }
+                ExecutionResult::Halt { reason, gas_used } => {
+                    let message = RpcInvalidTransactionError::halt(reason, gas_used).to_string();
+                    (Bytes::new(), Some(SimulateError { code: SIMULATE_VM_ERROR_CODE, message }))
+                }
+            };
+
+            SimCallResult { return_data, logs, gas_used, status, error }
+        })
+        .collect();
+
+    let kind =
+        if full_transactions { BlockTransactionsKind::Full } else { BlockTransactionsKind::Hashes };
+    let block =
+        BlockWithSenders::new(block.unseal(), senders).ok_or(EthApiError::InternalEthError)?;
+    let inner = from_block(block, total_difficulty, kind, Some(block_hash))?;
+
+    Ok(SimulatedBlock { inner, calls })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::primitives::{Output, SuccessReason};
+
+    #[test]
+    fn transfer_logs_are_discarded_on_revert() {
+        let (alice, bob) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let mut inspector = TransferInspector::default();
+
+        inspector.enter(alice, bob, U256::from(1));
+        inspector.enter(bob, alice, U256::from(2));
+        inspector.exit(false);
+        inspector.enter(bob, alice, U256::ZERO);
+        inspector.exit(true);
+        inspector.exit(true);
+
+        let logs = inspector.into_logs();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address, TRANSFER_LOG_EMITTER);
+        assert_eq!(logs[0].topics(), &[TRANSFER_EVENT_TOPIC, alice.into_word(), bob.into_word()]);
+        assert_eq!(U256::from_be_slice(&logs[0].data.data), U256::from(1));
+    }
+
+    #[test]
+    fn builds_simulated_block() {
+        let request = TransactionRequest::default().to(Address::with_last_byte(2));
+        let tx_env = TxEnv { gas_limit: 21_000, ..Default::default() };
+        let transaction = build_simulated_transaction(request, &tx_env, 7, 56).unwrap();
+        assert_eq!(transaction.nonce(), 7);
+        assert_eq!(transaction.chain_id(), Some(56));
+
+        let block_env = BlockEnv { number: U256::from(10), ..Default::default() };
+        let calls = vec![SimulatedCall {
+            transaction,
+            sender: Address::with_last_byte(1),
+            result: ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                gas_used: 21_000,
+                gas_refunded: 0,
+                logs: vec![],
+                output: Output::Call(Bytes::from_static(&[1])),
+            },
+            logs: vec![Log::new_unchecked(Address::ZERO, vec![], Bytes::new())],
+        }];
+
+        let block =
+            build_simulated_block(B256::ZERO, &block_env, B256::ZERO, U256::ZERO, calls, false)
+                .unwrap();
+        assert_eq!(block.inner.header.number, Some(10));
+        assert_eq!(block.inner.header.gas_used, 21_000u128);
+        assert_eq!(block.calls.len(), 1);
+        assert_eq!(block.calls[0].status, 1);
+        assert_eq!(block.calls[0].return_data, Bytes::from_static(&[1]));
+        assert_eq!(block.calls[0].logs[0].block_hash, block.inner.header.hash);
+    }
+}
@@ -0,0 +1,300 @@
+//! Registry of named [`StateOverride`] presets.
+
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use parking_lot::RwLock;
+use reth_errors::RethError;
+use reth_primitives::Address;
+use reth_rpc_types::state::StateOverride;
+
+use crate::{EthApiError, EthResult};
+
+/// Maximum length of a preset name.
+pub const MAX_STATE_OVERRIDE_PRESET_NAME_LEN: usize = 64;
+
+/// Presets by name, as stored on disk.
+type Presets = BTreeMap<String, StateOverride>;
+
+/// Errors returned when loading or updating [`StateOverridePresets`].
+#[derive(Debug, thiserror::Error)]
+pub enum StateOverridePresetError {
+    /// The preset name is empty, too long or contains characters other than ASCII
+    /// alphanumerics, `-` and `_`.
+    #[error("invalid preset name {0:?}")]
+    InvalidName(String),
+    /// The preset does not override any account.
+    #[error("preset {0} is empty")]
+    Empty(String),
+    /// An account override sets both `state` and `stateDiff`.
+    #[error("preset {0} overrides both state and stateDiff of account {1}")]
+    StateAndStateDiff(String, Address),
+    /// The presets file could not be read or written.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// The presets file could not be decoded or encoded.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl From<StateOverridePresetError> for EthApiError {
+    fn from(error: StateOverridePresetError) -> Self {
+        match error {
+            err @ (StateOverridePresetError::Io(_) | StateOverridePresetError::Json(_)) => {
+                Self::Internal(RethError::other(err))
+            }
+            err => Self::InvalidParams(err.to_string()),
+        }
+    }
+}
+
+/// A registry of named [`StateOverride`] presets that can be applied to call requests by name.
+///
+/// The presets are loaded once and kept in memory, every update is persisted to the backing file
+/// before it becomes visible. Without a backing file the presets only live in memory.
+#[derive(Debug, Clone, Default)]
+pub struct StateOverridePresets {
+    inner: Arc<StateOverridePresetsInner>,
+}
+
+/// Two handles are equal if they share the same registry.
+impl PartialEq for StateOverridePresets {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for StateOverridePresets {}
+
+#[derive(Debug, Default)]
+struct StateOverridePresetsInner {
+    /// File the presets are persisted to.
+    path: Option<PathBuf>,
+    /// The current presets.
+    presets: RwLock<Presets>,
+}
+
+impl StateOverridePresets {
+    /// Loads the presets from the given file, which is created on the first update if it does not
+    /// exist yet.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, StateOverridePresetError> {
+        let path = path.into();
+        let presets = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice::<Presets>(&bytes)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Presets::default(),
+            Err(err) => return Err(err.into()),
+        };
+        for (name, overrides) in &presets {
+            validate_preset(name, overrides)?;
+        }
+
+        Ok(Self {
+            inner: Arc::new(StateOverridePresetsInner {
+                path: Some(path),
+                presets: RwLock::new(presets),
+            }),
+        })
+    }
+
+    /// Returns the file the presets are persisted to, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.inner.path.as_deref()
+    }
+
+    /// Returns the preset with the given name.
+    pub fn get(&self, name: &str) -> Option<StateOverride> {
+        self.inner.presets.read().get(name).cloned()
+    }
+
+    /// Returns all presets by name.
+    pub fn all(&self) -> BTreeMap<String, StateOverride> {
+        self.inner.presets.read().clone()
+    }
+
+    /// Validates and stores the preset, replacing an existing preset with the same name.
+    pub fn insert(
+        &self,
+        name: String,
+        overrides: StateOverride,
+    ) -> Result<(), StateOverridePresetError> {
+        validate_preset(&name, &overrides)?;
+
+        let mut presets = self.inner.presets.write();
+        let mut updated = presets.clone();
+        updated.insert(name, overrides);
+        self.persist(&updated)?;
+        *presets = updated;
+
+        Ok(())
+    }
+
+    /// Removes the preset with the given name.
+    ///
+    /// Returns `false` if there is no such preset.
+    pub fn remove(&self, name: &str) -> Result<bool, StateOverridePresetError> {
+        let mut presets = self.inner.presets.write();
+        if !presets.contains_key(name) {
+            return Ok(false)
+        }
+
+        let mut updated = presets.clone();
+        updated.remove(name);
+        self.persist(&updated)?;
+        *presets = updated;
+
+        Ok(true)
+    }
+
+    /// Returns the state overrides to apply to a call that requested the given preset.
+    ///
+    /// Account overrides passed with the request take precedence over the preset's overrides for
+    /// the same account.
+    pub fn resolve(
+        &self,
+        preset: Option<&str>,
+        overrides: Option<StateOverride>,
+    ) -> EthResult<Option<StateOverride>> {
+        let Some(name) = preset else { return Ok(overrides) };
+        let mut resolved = self.get(name).ok_or_else(|| {
+            EthApiError::InvalidParams(format!("state override preset {name} not found"))
+        })?;
+        resolved.extend(overrides.unwrap_or_default());
+
+        Ok(Some(resolved))
+    }
+
+    /// Writes the presets to the backing file, if any.
+    ///
+    /// The presets are written to a temporary file first which then replaces the backing file, so
+    /// a failed write never leaves a truncated file behind.
+    fn persist(&self, presets: &Presets) -> Result<(), StateOverridePresetError> {
+        let Some(path) = self.path() else { return Ok(()) };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(presets)?)?;
+        fs::rename(tmp, path)?;
+
+        Ok(())
+    }
+}
+
+/// Validates the name and overrides of a preset.
+fn validate_preset(name: &str, overrides: &StateOverride) -> Result<(), StateOverridePresetError> {
+    if name.is_empty() ||
+        name.len() > MAX_STATE_OVERRIDE_PRESET_NAME_LEN ||
+        !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(StateOverridePresetError::InvalidName(name.to_string()))
+    }
+    if overrides.is_empty() {
+        return Err(StateOverridePresetError::Empty(name.to_string()))
+    }
+    for (address, account) in overrides {
+        if account.state.is_some() && account.state_diff.is_some() {
+            return Err(StateOverridePresetError::StateAndStateDiff(name.to_string(), *address))
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Bytes, U256};
+    use reth_rpc_types::state::AccountOverride;
+
+    fn code_override(code: &'static [u8]) -> AccountOverride {
+        AccountOverride { code: Some(Bytes::from_static(code)), ..Default::default() }
+    }
+
+    #[test]
+    fn rejects_invalid_presets() {
+        let presets = StateOverridePresets::default();
+        let overrides = StateOverride::from([(Address::ZERO, code_override(&[0x00]))]);
+
+        assert!(matches!(
+            presets.insert("bad name".to_string(), overrides.clone()),
+            Err(StateOverridePresetError::InvalidName(_))
+        ));
+        assert!(matches!(
+            presets.insert("empty".to_string(), StateOverride::default()),
+            Err(StateOverridePresetError::Empty(_))
+        ));
+
+        let both = AccountOverride {
+            state: Some(Default::default()),
+            state_diff: Some(Default::default()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            presets.insert("both".to_string(), StateOverride::from([(Address::ZERO, both)])),
+            Err(StateOverridePresetError::StateAndStateDiff(_, _))
+        ));
+        assert!(presets.all().is_empty());
+    }
+
+    #[test]
+    fn resolve_prefers_request_overrides() {
+        let presets = StateOverridePresets::default();
+        let other = Address::with_last_byte(1);
+        presets
+            .insert(
+                "sim".to_string(),
+                StateOverride::from([
+                    (Address::ZERO, code_override(&[0x00])),
+                    (other, code_override(&[0x01])),
+                ]),
+            )
+            .unwrap();
+
+        let request = AccountOverride { balance: Some(U256::from(1)), ..Default::default() };
+        let resolved = presets
+            .resolve(Some("sim"), Some(StateOverride::from([(other, request)])))
+            .unwrap()
+            .unwrap();
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[&Address::ZERO].code, Some(Bytes::from_static(&[0x00])));
+        assert_eq!(resolved[&other].code, None);
+        assert_eq!(resolved[&other].balance, Some(U256::from(1)));
+
+        assert!(presets.resolve(Some("unknown"), None).is_err());
+        assert!(presets.resolve(None, None).unwrap().is_none());
+    }
+
+    #[test]
+    fn persists_updates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state_override_presets.json");
+
+        let presets = StateOverridePresets::load(&path).unwrap();
+        presets
+            .insert(
+                "sim".to_string(),
+                StateOverride::from([(Address::ZERO, code_override(&[0x00]))]),
+            )
+            .unwrap();
+        presets
+            .insert(
+                "tmp".to_string(),
+                StateOverride::from([(Address::ZERO, code_override(&[0x01]))]),
+            )
+            .unwrap();
+        assert!(presets.remove("tmp").unwrap());
+        assert!(!presets.remove("tmp").unwrap());
+
+        let reloaded = StateOverridePresets::load(&path).unwrap();
+        assert_eq!(reloaded.all().keys().collect::<Vec<_>>(), vec!["sim"]);
+        assert_eq!(
+            reloaded.get("sim").unwrap()[&Address::ZERO].code,
+            Some(Bytes::from_static(&[0x00]))
+        );
+    }
+}
@@ -260,6 +260,13 @@ pub enum RethRpcModule {
     /// This is separate from [`RethRpcModule::Eth`] because it is a non standardized call that
     /// should be opt-in.
     EthCallBundle,
+    /// Non-standard `eth_` bundle and private transaction submission calls, e.g.
+    /// `eth_sendBundle` and `eth_sendPrivateTransaction`.
+    ///
+    /// `eth_callBundle` is served by [`RethRpcModule::EthCallBundle`].
+    EthBundle,
+    /// `miner_` module
+    Miner,
 }
 
 // === impl RethRpcModule ===
@@ -309,6 +316,8 @@ impl FromStr for RethRpcModule {
             "reth" => Self::Reth,
             "ots" => Self::Ots,
             "eth-call-bundle" | "eth_callBundle" => Self::EthCallBundle,
+            "eth-bundle" => Self::EthBundle,
+            "miner" => Self::Miner,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -13,4 +13,5 @@
 pub mod block;
 pub mod engine;
 pub mod proof;
+pub mod state;
 pub mod transaction;
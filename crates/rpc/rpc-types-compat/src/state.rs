@@ -0,0 +1,34 @@
+//! Compatibility functions for rpc state dump types.
+
+use reth_primitives::{keccak256, Account, Address, Bytes, StorageEntry, B256, KECCAK_EMPTY};
+use reth_rpc_types::debug::DumpAccount;
+use std::collections::BTreeMap;
+
+/// Creates a new state dump account from a primitive account with the given storage root.
+///
+/// The code and storage are omitted if `None` or empty.
+pub fn from_primitive_dump_account(
+    address: Address,
+    account: Account,
+    storage_root: B256,
+    code: Option<Bytes>,
+    storage: Option<Vec<StorageEntry>>,
+) -> DumpAccount {
+    DumpAccount {
+        balance: account.balance.to_string(),
+        nonce: account.nonce,
+        root: storage_root,
+        code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+        code: code.filter(|code| !code.is_empty()),
+        storage: storage
+            .map(|storage| {
+                storage
+                    .into_iter()
+                    .map(|entry| (entry.key, entry.value))
+                    .collect::<BTreeMap<_, _>>()
+            })
+            .filter(|storage| !storage.is_empty()),
+        address: Some(address),
+        address_hash: Some(keccak256(address)),
+    }
+}
@@ -0,0 +1,60 @@
+//! Types for the `admin` namespace.
+
+pub use alloy_rpc_types_admin::*;
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A connected peer as returned by `admin_peers`, extended with the score the node tracks for
+/// the peer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtendedPeerInfo {
+    /// The standard peer information.
+    #[serde(flatten)]
+    pub info: PeerInfo,
+    /// The reputation of the peer, peers below the ban threshold are disconnected.
+    pub reputation: i32,
+    /// Statistics about the block requests sent to the peer.
+    pub requests: PeerRequestInfo,
+    /// The bytes exchanged with the peer in the current session.
+    pub bandwidth: PeerBandwidthInfo,
+}
+
+/// Statistics about the block requests sent to a peer, including previous sessions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerRequestInfo {
+    /// Number of requests answered with a valid response.
+    pub successful: u64,
+    /// Number of requests that failed, timed out or were answered with a bad response.
+    pub failed: u64,
+    /// Moving average of the response latency of successful requests, in milliseconds.
+    pub avg_latency_ms: u64,
+}
+
+/// The bytes exchanged with a peer in the current session, as RLP encoded `eth` messages.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerBandwidthInfo {
+    /// Total number of bytes received from the peer.
+    pub ingress_bytes: u64,
+    /// Total number of bytes sent to the peer.
+    pub egress_bytes: u64,
+    /// The usage per message type, keyed by message name.
+    pub messages: BTreeMap<String, MessageBandwidthInfo>,
+}
+
+/// The usage of a single message type in a session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageBandwidthInfo {
+    /// Number of messages received.
+    pub ingress_messages: u64,
+    /// Number of bytes received.
+    pub ingress_bytes: u64,
+    /// Number of messages sent.
+    pub egress_messages: u64,
+    /// Number of bytes sent.
+    pub egress_bytes: u64,
+}
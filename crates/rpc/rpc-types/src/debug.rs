@@ -0,0 +1,119 @@
+//! Types for the `debug` namespace that are not covered by alloy.
+
+use crate::RichBlock;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A block that was rejected by the node, as returned by `debug_getBadBlocks`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadBlock {
+    /// Hash of the rejected block.
+    pub hash: B256,
+    /// The rejected block.
+    pub block: RichBlock,
+    /// RLP encoding of the rejected block.
+    pub rlp: Bytes,
+    /// The reason the block was rejected.
+    pub validation_error: String,
+}
+
+/// Configuration for `debug_standardTrace*ToFile` calls.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StdTraceConfig {
+    /// Whether to include memory in each step of the trace.
+    pub enable_memory: bool,
+    /// Whether to omit the stack from each step of the trace.
+    pub disable_stack: bool,
+    /// Whether to omit storage from each step of the trace.
+    ///
+    /// EIP-3155 traces never include storage, so this has no effect and is only accepted for
+    /// compatibility with geth.
+    pub disable_storage: bool,
+    /// If set, only the transaction with this hash is traced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<B256>,
+}
+
+/// An account of a state dump, as returned by `debug_accountRange`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// The balance of the account, in decimal.
+    pub balance: String,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The storage root of the account.
+    pub root: B256,
+    /// The hash of the account's code.
+    pub code_hash: B256,
+    /// The code of the account, omitted if not requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The storage of the account, keyed by slot, or by hashed slot if the slot's preimage is not
+    /// known. Omitted if not requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<B256, U256>>,
+    /// The address of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// The hashed address of the account.
+    #[serde(default, rename = "key", skip_serializing_if = "Option::is_none")]
+    pub address_hash: Option<B256>,
+}
+
+/// A dump of (a range of) the accounts of a state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateDump {
+    /// The state root of the dumped state.
+    pub root: B256,
+    /// The dumped accounts, keyed by address, or by `pre(<hashed address>)` if the address is not
+    /// known.
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// The key to continue the iteration from, if there are more accounts: the hashed address
+    /// for `debug_accountRange` and the address for `debug_dumpBlock`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Bytes>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_std_trace_config() {
+        let config: StdTraceConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, StdTraceConfig::default());
+
+        let s = r#"{"enableMemory":true,"disableStack":true,"txHash":"0x0000000000000000000000000000000000000000000000000000000000000001"}"#;
+        let config: StdTraceConfig = serde_json::from_str(s).unwrap();
+        assert!(config.enable_memory);
+        assert!(config.disable_stack);
+        assert!(!config.disable_storage);
+        assert_eq!(config.tx_hash, Some(B256::with_last_byte(1)));
+    }
+
+    #[test]
+    fn serialize_state_dump() {
+        let account = DumpAccount {
+            balance: "1000".to_string(),
+            nonce: 1,
+            address: Some(Address::with_last_byte(1)),
+            ..Default::default()
+        };
+        let dump = StateDump {
+            accounts: BTreeMap::from([(format!("{:?}", Address::with_last_byte(1)), account)]),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&dump).unwrap();
+        assert!(json.get("next").is_none());
+        let account = &json["accounts"]["0x0000000000000000000000000000000000000001"];
+        assert_eq!(account["balance"], "1000");
+        assert!(account.get("codeHash").is_some());
+        assert!(account.get("code").is_none());
+        assert!(account.get("storage").is_none());
+        assert_eq!(serde_json::from_value::<StateDump>(json).unwrap(), dump);
+    }
+}
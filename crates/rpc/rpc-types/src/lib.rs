@@ -12,6 +12,10 @@
 #[allow(hidden_glob_reexports)]
 mod eth;
 
+pub mod debug;
+pub mod pubsub;
+pub mod simulate;
+
 /// Alias for a peer identifier
 pub type PeerId = B512;
 
@@ -30,8 +34,7 @@ pub mod trace {
     pub use alloy_rpc_types_trace::*;
 }
 
-// re-export admin
-pub use alloy_rpc_types_admin as admin;
+pub mod admin;
 
 // Anvil specific rpc types coming from alloy.
 pub use alloy_rpc_types_anvil as anvil;
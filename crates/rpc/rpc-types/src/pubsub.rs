@@ -0,0 +1,206 @@
+//! Types for `eth_subscribe`, extending the subscriptions supported by alloy.
+
+use crate::{Filter, Header, Log, Transaction, ValueOrArray};
+use alloy_primitives::{Address, B256};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+pub use alloy_rpc_types::pubsub::{PubSubSyncStatus, SyncStatusMetadata};
+
+/// Subscription kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+    /// New block headers subscription.
+    ///
+    /// Fires a notification each time a new header is appended to the chain, including chain
+    /// reorganizations.
+    NewHeads,
+    /// Logs subscription.
+    ///
+    /// Returns logs that are included in new imported blocks and match the given filter criteria.
+    Logs,
+    /// New Pending Transactions subscription.
+    ///
+    /// Returns the hash or the full body of all transactions that are added to the pending state
+    /// and are signed with a key that is available in the node, optionally filtered by sender and
+    /// recipient.
+    NewPendingTransactions,
+    /// Node syncing status subscription.
+    ///
+    /// Indicates when the node starts or stops synchronizing.
+    Syncing,
+    /// Finalized block headers subscription.
+    ///
+    /// Fires a notification each time the finalized block of the chain advances.
+    FinalizedHeads,
+    /// Chain reorganizations subscription.
+    ///
+    /// Fires a notification with the reverted and the new blocks each time the canonical chain
+    /// is reorganized.
+    Reorgs,
+}
+
+/// Any additional parameters for a subscription.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Params {
+    /// No parameters passed.
+    #[default]
+    None,
+    /// Log parameters.
+    Logs(Box<Filter>),
+    /// Boolean parameter for new pending transactions.
+    Bool(bool),
+    /// Filter parameter for new pending transactions.
+    PendingTransactions(PendingTransactionsFilter),
+}
+
+impl Serialize for Params {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::None => (&[] as &[serde_json::Value]).serialize(serializer),
+            Self::Logs(logs) => logs.serialize(serializer),
+            Self::Bool(full) => full.serialize(serializer),
+            Self::PendingTransactions(filter) => filter.serialize(serializer),
+        }
+    }
+}
+
+impl<'a> Deserialize<'a> for Params {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'a>,
+    {
+        let v = serde_json::Value::deserialize(deserializer)?;
+
+        if v.is_null() {
+            return Ok(Self::None)
+        }
+
+        if let Some(val) = v.as_bool() {
+            return Ok(Self::Bool(val))
+        }
+
+        // the pending transactions filter has its own keys, any other object is a log filter
+        let is_pending_transactions_filter = v.as_object().is_some_and(|object| {
+            PendingTransactionsFilter::KEYS.iter().any(|key| object.contains_key(*key))
+        });
+        if is_pending_transactions_filter {
+            return serde_json::from_value(v)
+                .map(Self::PendingTransactions)
+                .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {e}")))
+        }
+
+        serde_json::from_value(v)
+            .map(|f| Self::Logs(Box::new(f)))
+            .map_err(|e| D::Error::custom(format!("Invalid Pub-Sub parameters: {e}")))
+    }
+}
+
+/// Filter of the `newPendingTransactions` subscription.
+///
+/// A transaction matches if its sender is one of `from_address` and its recipient is one of
+/// `to_address`, an unset field matches any transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct PendingTransactionsFilter {
+    /// The senders to match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<ValueOrArray<Address>>,
+    /// The recipients to match, contract creations never match.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<ValueOrArray<Address>>,
+    /// Whether to only return the transaction hashes instead of the full transactions.
+    pub hashes_only: bool,
+}
+
+impl PendingTransactionsFilter {
+    /// The keys that identify the filter in the subscription parameters.
+    const KEYS: [&'static str; 3] = ["fromAddress", "toAddress", "hashesOnly"];
+
+    /// Returns whether the transaction with the given sender and recipient matches the filter.
+    pub fn matches(&self, from: Address, to: Option<Address>) -> bool {
+        fn contains(addresses: &Option<ValueOrArray<Address>>, address: Option<Address>) -> bool {
+            match addresses {
+                None => true,
+                Some(ValueOrArray::Value(value)) => address == Some(*value),
+                Some(ValueOrArray::Array(values)) => {
+                    address.is_some_and(|address| values.contains(&address))
+                }
+            }
+        }
+
+        contains(&self.from_address, Some(from)) && contains(&self.to_address, to)
+    }
+}
+
+/// A reorganization of the canonical chain, as returned by the `reorgs` subscription.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reorg {
+    /// The headers of the blocks removed from the canonical chain, lowest first.
+    pub reverted: Vec<Header>,
+    /// The headers of the blocks added to the canonical chain, lowest first.
+    pub committed: Vec<Header>,
+}
+
+/// Subscription result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SubscriptionResult {
+    /// New block header.
+    Header(Box<Header>),
+    /// Log
+    Log(Box<Log>),
+    /// Transaction hash
+    TransactionHash(B256),
+    /// Full Transaction
+    FullTransaction(Box<Transaction>),
+    /// `SyncStatus`
+    SyncState(PubSubSyncStatus),
+    /// Chain reorganization.
+    Reorg(Box<Reorg>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_subscription_kind() {
+        let kind: SubscriptionKind = serde_json::from_str(r#""finalizedHeads""#).unwrap();
+        assert_eq!(kind, SubscriptionKind::FinalizedHeads);
+        let kind: SubscriptionKind = serde_json::from_str(r#""reorgs""#).unwrap();
+        assert_eq!(kind, SubscriptionKind::Reorgs);
+        let kind: SubscriptionKind = serde_json::from_str(r#""newPendingTransactions""#).unwrap();
+        assert_eq!(kind, SubscriptionKind::NewPendingTransactions);
+    }
+
+    #[test]
+    fn deserialize_params() {
+        assert_eq!(serde_json::from_str::<Params>("null").unwrap(), Params::None);
+        assert_eq!(serde_json::from_str::<Params>("true").unwrap(), Params::Bool(true));
+
+        let params: Params =
+            serde_json::from_str(r#"{"address":"0x0000000000000000000000000000000000000001"}"#)
+                .unwrap();
+        assert!(matches!(params, Params::Logs(_)));
+
+        let params: Params = serde_json::from_str(
+            r#"{"fromAddress":"0x0000000000000000000000000000000000000001","toAddress":["0x0000000000000000000000000000000000000002"]}"#,
+        )
+        .unwrap();
+        let Params::PendingTransactions(filter) = params else { panic!("expected filter") };
+        assert!(!filter.hashes_only);
+
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        assert!(filter.matches(a, Some(b)));
+        assert!(!filter.matches(b, Some(b)));
+        assert!(!filter.matches(a, None));
+        assert!(PendingTransactionsFilter::default().matches(b, None));
+
+        assert!(serde_json::from_str::<Params>(r#"{"hashesOnly":true,"foo":1}"#).is_err());
+    }
+}
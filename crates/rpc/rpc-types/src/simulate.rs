@@ -0,0 +1,106 @@
+//! Types for `eth_simulateV1` that are not covered by alloy.
+
+use crate::{
+    state::{BlockOverrides, StateOverride},
+    Block, Log, TransactionRequest,
+};
+use alloy_primitives::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// The payload of an `eth_simulateV1` request.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate, in order.
+    pub block_state_calls: Vec<SimBlock>,
+    /// Whether to add an `ERC20` like `Transfer` log for every native value transfer.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether to execute the calls with the same checks as regular transactions, e.g. nonce,
+    /// balance and base fee checks.
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether to return full transaction objects instead of hashes.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+/// A block of calls to simulate.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBlock {
+    /// Overrides of the block environment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    /// State overrides applied before the calls of the block are executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The calls to execute.
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+/// A simulated block, as returned by `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    /// The simulated block.
+    #[serde(flatten)]
+    pub inner: Block,
+    /// The results of the calls of the block.
+    pub calls: Vec<SimCallResult>,
+}
+
+/// The result of a simulated call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimCallResult {
+    /// The data returned by the call.
+    pub return_data: Bytes,
+    /// The logs emitted by the call.
+    #[serde(default)]
+    pub logs: Vec<Log>,
+    /// The gas used by the call.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// `1` if the call succeeded, `0` otherwise.
+    #[serde(with = "alloy_serde::quantity")]
+    pub status: u64,
+    /// The error of a failed call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+/// The error of a failed simulated call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateError {
+    /// The error code.
+    pub code: i32,
+    /// The error message.
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, U256};
+
+    #[test]
+    fn deserialize_simulate_payload() {
+        let s = r#"{"blockStateCalls":[{"blockOverrides":{"number":"0x10"},"stateOverrides":{"0x0000000000000000000000000000000000000001":{"balance":"0x1"}},"calls":[{"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x1"}]},{}],"traceTransfers":true}"#;
+        let payload: SimulatePayload = serde_json::from_str(s).unwrap();
+        assert!(payload.trace_transfers);
+        assert!(!payload.validation);
+        assert!(!payload.return_full_transactions);
+        assert_eq!(payload.block_state_calls.len(), 2);
+
+        let block = &payload.block_state_calls[0];
+        assert_eq!(block.block_overrides.as_ref().unwrap().number, Some(U256::from(0x10)));
+        assert_eq!(
+            block.state_overrides.as_ref().unwrap()[&Address::with_last_byte(1)].balance,
+            Some(U256::from(1))
+        );
+        assert_eq!(block.calls[0].value, Some(U256::from(1)));
+        assert_eq!(payload.block_state_calls[1], SimBlock::default());
+    }
+}
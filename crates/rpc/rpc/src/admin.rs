@@ -4,14 +4,19 @@ use alloy_genesis::ChainConfig;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::ChainSpec;
-use reth_network_api::{NetworkInfo, PeerKind, Peers};
+use reth_network_api::{MessageBandwidth, NetworkInfo, PeerKind, Peers};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_primitives::EthereumHardfork;
-use reth_rpc_api::AdminApiServer;
+use reth_rpc_api::{AdminApiServer, AdminStateOverridePresetsApiServer};
+use reth_rpc_eth_types::{EthApiError, StateOverridePresets};
 use reth_rpc_server_types::ToRpcResult;
-use reth_rpc_types::admin::{
-    EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo,
-    Ports, ProtocolInfo,
+use reth_rpc_types::{
+    admin::{
+        EthInfo, EthPeerInfo, EthProtocolInfo, ExtendedPeerInfo, MessageBandwidthInfo, NodeInfo,
+        PeerBandwidthInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo, PeerRequestInfo, Ports,
+        ProtocolInfo,
+    },
+    state::StateOverride,
 };
 
 /// `admin` API implementation.
@@ -64,13 +69,13 @@ where
     }
 
     /// Handler for `admin_peers`
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>> {
+    async fn peers(&self) -> RpcResult<Vec<ExtendedPeerInfo>> {
         let peers = self.network.get_all_peers().await.to_rpc_result()?;
         let mut infos = Vec::with_capacity(peers.len());
 
         for peer in peers {
             if let Ok(pk) = id2pk(peer.remote_id) {
-                infos.push(PeerInfo {
+                let info = PeerInfo {
                     id: pk.to_string(),
                     name: peer.client_version.to_string(),
                     enode: peer.enode,
@@ -95,6 +100,16 @@ where
                         snap: None,
                         other: Default::default(),
                     },
+                };
+                infos.push(ExtendedPeerInfo {
+                    info,
+                    reputation: peer.reputation,
+                    requests: PeerRequestInfo {
+                        successful: peer.request_stats.successful_requests,
+                        failed: peer.request_stats.failed_requests,
+                        avg_latency_ms: peer.request_stats.avg_latency_ms,
+                    },
+                    bandwidth: bandwidth_info(&peer.bandwidth),
                 })
             }
         }
@@ -189,3 +204,51 @@ impl<N> std::fmt::Debug for AdminApi<N> {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
 }
+
+/// Converts the bandwidth usage of a session into its `admin_peers` representation.
+fn bandwidth_info(bandwidth: &[MessageBandwidth]) -> PeerBandwidthInfo {
+    let mut info = PeerBandwidthInfo::default();
+    for message in bandwidth {
+        info.ingress_bytes += message.ingress_bytes;
+        info.egress_bytes += message.egress_bytes;
+        info.messages.insert(
+            format!("{:?}", message.message_id),
+            MessageBandwidthInfo {
+                ingress_messages: message.ingress_messages,
+                ingress_bytes: message.ingress_bytes,
+                egress_messages: message.egress_messages,
+                egress_bytes: message.egress_bytes,
+            },
+        );
+    }
+    info
+}
+
+/// `admin` API implementation for managing the named state override presets.
+///
+/// This is only served by the authenticated auth server.
+#[derive(Debug, Clone)]
+pub struct AdminStateOverridePresetsApi {
+    /// The presets shared with the `eth` API.
+    presets: StateOverridePresets,
+}
+
+impl AdminStateOverridePresetsApi {
+    /// Creates a new instance of `AdminStateOverridePresetsApi`.
+    pub const fn new(presets: StateOverridePresets) -> Self {
+        Self { presets }
+    }
+}
+
+impl AdminStateOverridePresetsApiServer for AdminStateOverridePresetsApi {
+    /// Handler for `admin_setStateOverridePreset`
+    fn set_state_override_preset(&self, name: String, overrides: StateOverride) -> RpcResult<bool> {
+        self.presets.insert(name, overrides).map_err(EthApiError::from)?;
+        Ok(true)
+    }
+
+    /// Handler for `admin_removeStateOverridePreset`
+    fn remove_state_override_preset(&self, name: String) -> RpcResult<bool> {
+        Ok(self.presets.remove(&name).map_err(EthApiError::from)?)
+    }
+}
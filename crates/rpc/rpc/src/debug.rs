@@ -1,42 +1,77 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::Arc,
+};
 
 use alloy_rlp::{Decodable, Encodable};
 use async_trait::async_trait;
 use cfg_if::cfg_if;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    types::ErrorObject,
+    PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
+#[cfg(feature = "bsc")]
+use parking_lot::Mutex;
 #[cfg(feature = "bsc")]
 use reth_chainspec::BscHardforks;
 use reth_chainspec::EthereumHardforks;
+use reth_db::Tables;
+use reth_db_api::models::StoredBadBlock;
+use reth_db_common::stats;
+use reth_errors::RethError;
+#[cfg(feature = "bsc")]
+use reth_evm::execute::{BlockExecutionInput, Executor};
 use reth_evm::ConfigureEvmEnv;
 #[cfg(feature = "bsc")]
+use reth_evm_bsc::BscBlockExecutor;
+#[cfg(feature = "bsc")]
 use reth_primitives::system_contracts::{get_upgrade_system_contracts, is_system_transaction};
 use reth_primitives::{
-    Address, Block, BlockId, BlockNumberOrTag, Bytes, TransactionSignedEcRecovered, B256, U256,
+    parlia::ParliaConfig, Address, Block, BlockId, BlockNumber, BlockNumberOrTag, BlockWithSenders,
+    Bytes, TransactionSignedEcRecovered, B256, KECCAK_EMPTY, U256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, StateProviderFactory,
-    TransactionVariant,
+    BadBlockReader, BlockReaderIdExt, ChainDbReader, ChainSpecProvider, ChangeSetReader,
+    EvmEnvProvider, HeaderProvider, ParliaSnapshotReader, PreimageReader, PruneCheckpointReader,
+    StateDumpReader, StateProviderFactory, TransactionVariant,
 };
+#[cfg(feature = "bsc")]
+use reth_provider::{BlockSource, StateRootProvider};
+use reth_prune_types::PruneSegment;
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::DebugApiServer;
-use reth_rpc_eth_api::helpers::{Call, EthApiSpec, EthTransactions, TraceExt};
+use reth_rpc_eth_api::helpers::{Call, EthApiSpec, EthTransactions, SpawnBlocking, TraceExt};
 use reth_rpc_eth_types::{EthApiError, EthResult, StateCacheDb};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_rpc_types::{
+    debug::{BadBlock, DumpAccount, StateDump, StdTraceConfig},
     state::EvmOverrides,
     trace::geth::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
         GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
     },
-    BlockError, Bundle, RichBlock, StateContext, TransactionRequest,
+    BlockError, BlockTransactionsKind, Bundle, StateContext, TransactionRequest,
+};
+use reth_rpc_types_compat::{
+    block::{from_block, from_block_with_tx_hashes},
+    state::from_primitive_dump_account,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 #[cfg(feature = "bsc")]
 use revm::bsc::SYSTEM_ADDRESS;
 #[cfg(feature = "bsc")]
-use revm::db::AccountState::{NotExisting, Touched};
+use revm::db::{
+    AccountState::{NotExisting, Touched},
+    BundleState, State,
+};
 use revm::{
     db::CacheDB,
+    inspectors::TracerEip3155,
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
 };
 use revm_inspectors::tracing::{
@@ -44,6 +79,21 @@ use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig,
 };
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tracing::{debug, warn};
+
+/// The maximum number of accounts returned by a single `debug_accountRange` call.
+const ACCOUNT_RANGE_MAX_RESULTS: usize = 256;
+
+/// The maximum number of accounts returned by a single `debug_dumpBlock` call.
+const DUMP_BLOCK_MAX_RESULTS: usize = 256;
+
+/// The maximum number of blocks a single `debug_getModifiedAccountsBy*` call can cover.
+const MODIFIED_ACCOUNTS_MAX_BLOCKS: u64 = 1024;
+
+/// The parent block id, the recovered transactions, the evm environment and the parent's
+/// timestamp needed to replay a block on top of its parent.
+type BlockReplayEnv =
+    (BlockId, Vec<TransactionSignedEcRecovered>, CfgEnvWithHandlerCfg, BlockEnv, u64);
 
 /// `debug` API implementation.
 ///
@@ -56,8 +106,24 @@ pub struct DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> DebugApi<Provider, Eth> {
     /// Create a new instance of the [`DebugApi`]
-    pub fn new(provider: Provider, eth: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard });
+    ///
+    /// `trace_dir` is the directory `debug_standardTrace*ToFile` calls write their output to, these
+    /// calls are rejected if it is not set. `parlia` is the configured parlia consensus config the
+    /// blocks are re-executed with.
+    pub fn new(
+        provider: Provider,
+        eth: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        trace_dir: Option<PathBuf>,
+        parlia: ParliaConfig,
+    ) -> Self {
+        let inner = Arc::new(DebugApiInner {
+            provider,
+            eth_api: eth,
+            blocking_task_guard,
+            trace_dir,
+            parlia,
+        });
         Self { inner }
     }
 
@@ -74,6 +140,13 @@ where
     Provider: BlockReaderIdExt
         + HeaderProvider
         + ChainSpecProvider
+        + ChangeSetReader
+        + PruneCheckpointReader
+        + BadBlockReader
+        + PreimageReader
+        + StateDumpReader
+        + ChainDbReader
+        + ParliaSnapshotReader
         + StateProviderFactory
         + EvmEnvProvider
         + 'static,
@@ -85,7 +158,6 @@ where
     }
 
     /// Trace the entire block asynchronously
-    #[allow(unused_variables)]
     async fn trace_block(
         &self,
         at: BlockId,
@@ -95,6 +167,57 @@ where
         opts: GethDebugTracingOptions,
         parent_timestamp: u64,
     ) -> EthResult<Vec<TraceResult>> {
+        let block_hash = at.as_block_hash();
+        self.replay_block_with(
+            at,
+            transactions,
+            cfg,
+            block_env,
+            parent_timestamp,
+            move |this, index, tx_hash, env, db| {
+                let (result, state_changes) = this.trace_transaction(
+                    opts.clone(),
+                    env,
+                    db,
+                    Some(TransactionContext {
+                        block_hash,
+                        tx_hash: Some(tx_hash),
+                        tx_index: Some(index),
+                    }),
+                )?;
+                Ok((TraceResult::Success { result, tx_hash: Some(tx_hash) }, state_changes))
+            },
+        )
+        .await
+    }
+
+    /// Replays the given transactions on top of the state at `at`, handling BSC system
+    /// transactions and system contract upgrades, and calls `f` for every transaction.
+    ///
+    /// `f` executes the transaction and returns its output together with the resulting state
+    /// changes, which are applied before the next transaction is executed.
+    #[allow(unused_variables)]
+    async fn replay_block_with<F, R>(
+        &self,
+        at: BlockId,
+        transactions: Vec<TransactionSignedEcRecovered>,
+        cfg: CfgEnvWithHandlerCfg,
+        block_env: BlockEnv,
+        parent_timestamp: u64,
+        mut f: F,
+    ) -> EthResult<Vec<R>>
+    where
+        F: FnMut(
+                &Self,
+                usize,
+                B256,
+                EnvWithHandlerCfg,
+                &mut StateCacheDb<'_>,
+            ) -> EthResult<(R, revm_primitives::EvmState)>
+            + Send
+            + 'static,
+        R: Send + 'static,
+    {
         if transactions.is_empty() {
             // nothing to trace
             return Ok(Vec::new())
@@ -111,7 +234,6 @@ where
         let this = self.clone();
         self.eth_api()
             .spawn_with_state_at_block(at, move |state| {
-                let block_hash = at.as_block_hash();
                 let mut results = Vec::with_capacity(transactions.len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
                 let mut transactions = transactions.into_iter().enumerate().peekable();
@@ -200,18 +322,9 @@ where
                         env: Env::boxed(cfg.cfg_env.clone(), block_env.clone(), tx_env),
                         handler_cfg: cfg.handler_cfg,
                     };
-                    let (result, state_changes) = this.trace_transaction(
-                        opts.clone(),
-                        env,
-                        &mut db,
-                        Some(TransactionContext {
-                            block_hash,
-                            tx_hash: Some(tx_hash),
-                            tx_index: Some(index),
-                        }),
-                    )?;
+                    let (result, state_changes) = f(&this, index, tx_hash, env, &mut db)?;
 
-                    results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+                    results.push(result);
                     if transactions.peek().is_some() {
                         // need to apply the state changes of this transaction before executing the
                         // next transaction
@@ -237,6 +350,142 @@ where
         let block =
             Block::decode(&mut rlp_block.as_ref()).map_err(BlockError::RlpDecodeRawBlock)?;
 
+        let (at, transactions, cfg, block_env, parent_timestamp) =
+            self.replay_env_for_raw_block(block).await?;
+        self.trace_block(at, transactions, cfg, block_env, opts, parent_timestamp).await
+    }
+
+    /// Replays a block that was rejected as invalid and returns the trace of each transaction.
+    ///
+    /// Note, the parent of this block must be present, or it will fail.
+    pub async fn debug_trace_bad_block(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<Vec<TraceResult>> {
+        let bad_block = self.bad_block(block_hash)?;
+
+        let (at, transactions, cfg, block_env, parent_timestamp) =
+            self.replay_env_for_raw_block(bad_block.block.unseal()).await?;
+        self.trace_block(at, transactions, cfg, block_env, opts, parent_timestamp).await
+    }
+
+    /// Replays a block that was rejected as invalid and writes an EIP-3155 trace of each
+    /// transaction to a separate file in the trace directory.
+    ///
+    /// Returns the paths of the written files.
+    pub async fn debug_standard_trace_bad_block_to_file(
+        &self,
+        block_hash: B256,
+        config: StdTraceConfig,
+    ) -> EthResult<Vec<String>> {
+        let bad_block = self.bad_block(block_hash)?;
+
+        let (at, transactions, cfg, block_env, parent_timestamp) =
+            self.replay_env_for_raw_block(bad_block.block.unseal()).await?;
+        self.standard_trace_to_file(
+            "badblock",
+            block_hash,
+            at,
+            transactions,
+            cfg,
+            block_env,
+            parent_timestamp,
+            config,
+        )
+        .await
+    }
+
+    /// Re-executes the given bad, canonical or side chain block on top of its parent and returns
+    /// the state root after each transaction, including the system transactions.
+    ///
+    /// This is used to find the transaction at which the execution diverged when a block fails
+    /// with a state root mismatch. If the execution fails, the roots of the transactions executed
+    /// before the failure are returned.
+    ///
+    /// Nothing is traced, so only the default tracing options are accepted.
+    #[cfg(feature = "bsc")]
+    pub async fn debug_intermediate_roots(
+        &self,
+        block_hash: B256,
+        opts: Option<GethDebugTracingCallOptions>,
+    ) -> EthResult<Vec<B256>> {
+        if opts.unwrap_or_default() != GethDebugTracingCallOptions::default() {
+            return Err(EthApiError::InvalidParams(String::from(
+                "tracing options are not supported by debug_intermediateRoots",
+            )))
+        }
+
+        let block = match self.inner.provider.bad_block(block_hash)? {
+            Some(bad_block) => bad_block.block.unseal(),
+            None => self
+                .inner
+                .provider
+                .find_block_by_hash(block_hash, BlockSource::Any)?
+                .ok_or(EthApiError::UnknownBlockNumber)?,
+        };
+        let block =
+            block.with_recovered_senders().ok_or(EthApiError::InvalidTransactionSignature)?;
+        let parent_td = self
+            .inner
+            .provider
+            .header_td(&block.parent_hash)?
+            .ok_or(EthApiError::UnknownParentBlock)?;
+        let total_difficulty = parent_td + block.difficulty;
+        let evm_config = Call::evm_config(self.eth_api()).clone();
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let provider = &this.inner.provider;
+                // the roots are computed over the parent state with the block's changes so far
+                // as an in-memory overlay
+                let root_provider = provider.state_by_block_hash(block.parent_hash)?;
+                let roots = Arc::new(Mutex::new(Vec::with_capacity(block.body.len())));
+                let hook_roots = Arc::clone(&roots);
+                let hook = move |_: B256, bundle_state: &BundleState| {
+                    hook_roots.lock().push(root_provider.state_root(bundle_state));
+                };
+
+                let state = State::builder()
+                    .with_database(StateProviderDatabase::new(
+                        provider.state_by_block_hash(block.parent_hash)?,
+                    ))
+                    .with_bundle_update()
+                    .without_state_clear()
+                    .build();
+                if let Err(err) = BscBlockExecutor::new(
+                    provider.chain_spec(),
+                    evm_config,
+                    this.inner.parlia.clone(),
+                    state,
+                    provider,
+                )
+                .with_tx_state_hook(hook)
+                .execute(BlockExecutionInput::new(&block, total_difficulty, None))
+                {
+                    // the roots up to the failing transaction are what is needed to diagnose it
+                    debug!(target: "rpc::debug", %err, ?block_hash, "Block execution failed, returning intermediate roots so far");
+                }
+
+                let roots = std::mem::take(&mut *roots.lock());
+                Ok(roots.into_iter().collect::<Result<Vec<_>, _>>()?)
+            })
+            .await
+    }
+
+    /// Returns the stored bad block with the given hash.
+    fn bad_block(&self, block_hash: B256) -> EthResult<StoredBadBlock> {
+        self.inner
+            .provider
+            .bad_block(block_hash)?
+            .ok_or_else(|| EthApiError::InvalidParams(format!("bad block {block_hash} not found")))
+    }
+
+    /// Returns everything needed to replay the given block on top of its parent.
+    ///
+    /// Note, the parent of this block must be present, or it will fail.
+    async fn replay_env_for_raw_block(&self, block: Block) -> EthResult<BlockReplayEnv> {
         let (cfg, block_env) = self.eth_api().evm_env_for_raw_block(&block.header).await?;
         // we trace on top the block's parent block
         let parent = block.parent_hash;
@@ -273,7 +522,74 @@ where
                     .collect::<EthResult<Vec<_>>>()?
             };
 
-        self.trace_block(parent.into(), transactions, cfg, block_env, opts, parent_timestamp).await
+        Ok((parent.into(), transactions, cfg, block_env, parent_timestamp))
+    }
+
+    /// Replays the given transactions on top of `at` and writes an EIP-3155 trace of each
+    /// transaction to `<trace_dir>/<prefix>_<block_hash>-<tx_index>-<tx_hash>.jsonl`.
+    ///
+    /// Returns the paths of the written files.
+    #[allow(clippy::too_many_arguments)]
+    async fn standard_trace_to_file(
+        &self,
+        prefix: &'static str,
+        block_hash: B256,
+        at: BlockId,
+        transactions: Vec<TransactionSignedEcRecovered>,
+        cfg: CfgEnvWithHandlerCfg,
+        block_env: BlockEnv,
+        parent_timestamp: u64,
+        config: StdTraceConfig,
+    ) -> EthResult<Vec<String>> {
+        let trace_dir = self
+            .inner
+            .trace_dir
+            .clone()
+            .ok_or(EthApiError::Unsupported("trace directory is not configured"))?;
+        if let Some(tx_hash) = config.tx_hash {
+            if !transactions.iter().any(|tx| tx.hash == tx_hash) {
+                return Err(EthApiError::InvalidParams(format!(
+                    "transaction {tx_hash} not found in block {block_hash}"
+                )))
+            }
+        }
+        std::fs::create_dir_all(&trace_dir)
+            .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+
+        let paths = self
+            .replay_block_with(
+                at,
+                transactions,
+                cfg,
+                block_env,
+                parent_timestamp,
+                move |this, index, tx_hash, env, db| {
+                    if config.tx_hash.is_some_and(|hash| hash != tx_hash) {
+                        // still needs to be executed so that subsequent transactions see its
+                        // state changes
+                        let (res, _) = this.eth_api().transact(&mut *db, env)?;
+                        return Ok((None, res.state))
+                    }
+
+                    let path = trace_dir
+                        .join(format!("{prefix}_{block_hash:#x}-{index}-{tx_hash:#x}.jsonl"));
+                    let file = File::create(&path)
+                        .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+                    let mut inspector = TracerEip3155::new(Box::new(StdTraceWriter::new(
+                        BufWriter::new(file),
+                        config.disable_stack,
+                    )));
+                    if config.enable_memory {
+                        inspector = inspector.with_memory();
+                    }
+                    let (res, _) = this.eth_api().inspect(&mut *db, env, &mut inspector)?;
+
+                    Ok((Some(path.display().to_string()), res.state))
+                },
+            )
+            .await?;
+
+        Ok(paths.into_iter().flatten().collect())
     }
 
     /// Replays a block and returns the trace of each transaction.
@@ -282,6 +598,38 @@ where
         block_id: BlockId,
         opts: GethDebugTracingOptions,
     ) -> EthResult<Vec<TraceResult>> {
+        let (_, (at, transactions, cfg, block_env, parent_timestamp)) =
+            self.replay_env_for_block(block_id).await?;
+        self.trace_block(at, transactions, cfg, block_env, opts, parent_timestamp).await
+    }
+
+    /// Replays a block and writes an EIP-3155 trace of each transaction to a separate file in the
+    /// trace directory.
+    ///
+    /// Returns the paths of the written files.
+    pub async fn debug_standard_trace_block_to_file(
+        &self,
+        block_id: BlockId,
+        config: StdTraceConfig,
+    ) -> EthResult<Vec<String>> {
+        let (block_hash, (at, transactions, cfg, block_env, parent_timestamp)) =
+            self.replay_env_for_block(block_id).await?;
+        self.standard_trace_to_file(
+            "block",
+            block_hash,
+            at,
+            transactions,
+            cfg,
+            block_env,
+            parent_timestamp,
+            config,
+        )
+        .await
+    }
+
+    /// Returns the hash of the given block together with everything needed to replay it on top
+    /// of its parent.
+    async fn replay_env_for_block(&self, block_id: BlockId) -> EthResult<(B256, BlockReplayEnv)> {
         let block_hash = self
             .inner
             .provider
@@ -308,15 +656,403 @@ where
             }
         }
 
-        self.trace_block(
-            state_at.into(),
-            block.into_transactions_ecrecovered().collect(),
-            cfg,
-            block_env,
-            opts,
-            parent_timestamp,
-        )
-        .await
+        Ok((
+            block_hash,
+            (
+                state_at.into(),
+                block.into_transactions_ecrecovered().collect(),
+                cfg,
+                block_env,
+                parent_timestamp,
+            ),
+        ))
+    }
+
+    /// Resolves the blocks traced by `debug_traceChain` for the range
+    /// `(start_exclusive, end_inclusive]`.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> EthResult<RangeInclusive<BlockNumber>> {
+        let start = self
+            .inner
+            .provider
+            .convert_block_number(start_exclusive)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let end = self
+            .inner
+            .provider
+            .convert_block_number(end_inclusive)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        if start >= end {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block ({start}) must be less than end block ({end})"
+            )))
+        }
+        Ok(start + 1..=end)
+    }
+
+    /// Traces all blocks in the given range and sends the result of each block to the
+    /// subscription.
+    ///
+    /// A trace permit is acquired for every block, and the next block is only traced once the
+    /// previous result was accepted by the subscription, so a slow subscriber throttles tracing.
+    ///
+    /// Returns the error that ends the subscription early, if a block can't be traced.
+    async fn trace_chain_to_sink(
+        self,
+        range: RangeInclusive<BlockNumber>,
+        opts: GethDebugTracingOptions,
+        sink: SubscriptionSink,
+    ) -> Result<(), ErrorObject<'static>> {
+        for number in range {
+            if sink.is_closed() {
+                return Ok(())
+            }
+
+            let result = {
+                let _permit = self.acquire_trace_permit().await;
+                self.trace_chain_block(number, opts.clone()).await
+            };
+            let result = result.inspect_err(|err| {
+                warn!(target: "rpc::debug", %err, number, "Failed to trace block for debug_traceChain");
+            })?;
+            let msg = SubscriptionMessage::from_json(&result)
+                .map_err(|err| internal_rpc_err(err.to_string()))?;
+
+            if sink.send(msg).await.is_err() {
+                // subscription was closed
+                return Ok(())
+            }
+        }
+        Ok(())
+    }
+
+    /// Traces a single block for `debug_traceChain`.
+    async fn trace_chain_block(
+        &self,
+        number: BlockNumber,
+        opts: GethDebugTracingOptions,
+    ) -> EthResult<BlockTraceResult> {
+        let hash =
+            self.inner.provider.block_hash(number)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let traces = self.debug_trace_block(hash.into(), opts).await?;
+        Ok(BlockTraceResult { block: U256::from(number), hash, traces })
+    }
+
+    /// Returns an error if the preimages of hashed state keys are not recorded.
+    fn ensure_preimages_recorded(&self) -> EthResult<()> {
+        if self.inner.provider.preimages_recorded_block()?.is_none() {
+            return Err(EthApiError::Unsupported(
+                "preimages are not recorded, enable the `Preimages` stage",
+            ))
+        }
+        Ok(())
+    }
+
+    /// Returns the preimage of the given hash, if it is the hash of a recorded address or storage
+    /// slot.
+    pub fn debug_preimage(&self, hash: B256) -> EthResult<Bytes> {
+        self.ensure_preimages_recorded()?;
+        self.inner
+            .provider
+            .preimage(hash)?
+            .ok_or_else(|| EthApiError::InvalidParams("unknown preimage".to_string()))
+    }
+
+    /// Returns up to `max_results` accounts of the latest state, ordered by hashed address and
+    /// starting at the given hashed address prefix.
+    ///
+    /// Accounts are keyed by their address, which requires the recorded preimages. If
+    /// `incompletes` is set, accounts with an unknown address are keyed by their hashed address
+    /// instead of being skipped.
+    pub async fn debug_account_range(
+        &self,
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> EthResult<StateDump> {
+        let provider = &self.inner.provider;
+        let number =
+            provider.convert_block_number(block_number)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        if number != provider.best_block_number()? {
+            return Err(EthApiError::Unsupported(
+                "account ranges are only available for the latest block",
+            ))
+        }
+        if !incompletes {
+            self.ensure_preimages_recorded()?;
+        }
+        if start.len() > B256::len_bytes() {
+            return Err(EthApiError::InvalidParams("start key is longer than 32 bytes".to_string()))
+        }
+        let mut start_key = B256::ZERO;
+        start_key[..start.len()].copy_from_slice(&start);
+        let max_results = match max_results as usize {
+            0 => ACCOUNT_RANGE_MAX_RESULTS,
+            max_results => max_results.min(ACCOUNT_RANGE_MAX_RESULTS),
+        };
+        let root =
+            provider.header_by_number(number)?.ok_or(EthApiError::UnknownBlockNumber)?.state_root;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let provider = &this.inner.provider;
+                let state = provider.latest()?;
+
+                // fetch one more account to know where to continue from
+                let mut accounts = provider.hashed_accounts_from(start_key, max_results + 1)?;
+                let next = if accounts.len() > max_results {
+                    accounts.pop().map(|(hashed_address, _)| {
+                        Bytes::copy_from_slice(hashed_address.as_slice())
+                    })
+                } else {
+                    None
+                };
+
+                let mut dump = StateDump { root, accounts: BTreeMap::new(), next };
+                for (hashed_address, account) in accounts {
+                    let address = provider
+                        .preimage(hashed_address)?
+                        .and_then(|preimage| Address::try_from(preimage.as_ref()).ok());
+                    let key = match address {
+                        Some(address) => format!("{address:?}"),
+                        None if incompletes => format!("pre({hashed_address:?})"),
+                        None => continue,
+                    };
+
+                    let code = match account.bytecode_hash {
+                        Some(code_hash) if !nocode => state
+                            .bytecode_by_hash(code_hash)?
+                            .map(|bytecode| bytecode.original_bytes())
+                            .filter(|code| !code.is_empty()),
+                        _ => None,
+                    };
+                    let storage = if nostorage {
+                        None
+                    } else {
+                        let mut storage = BTreeMap::new();
+                        for (hashed_slot, value) in provider.hashed_storage(hashed_address)? {
+                            // fall back to the hashed slot if the slot is unknown
+                            let slot = provider
+                                .preimage(hashed_slot)?
+                                .and_then(|preimage| B256::try_from(preimage.as_ref()).ok())
+                                .unwrap_or(hashed_slot);
+                            storage.insert(slot, value);
+                        }
+                        Some(storage).filter(|storage| !storage.is_empty())
+                    };
+
+                    dump.accounts.insert(
+                        key,
+                        DumpAccount {
+                            balance: account.balance.to_string(),
+                            nonce: account.nonce,
+                            root: provider.hashed_storage_root(hashed_address)?,
+                            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+                            code,
+                            storage,
+                            address,
+                            address_hash: Some(hashed_address),
+                        },
+                    );
+                }
+
+                Ok(dump)
+            })
+            .await
+    }
+
+    /// Returns up to `max_results` accounts of the state at the end of the given block, keyed by
+    /// address and starting at `start`.
+    ///
+    /// The state of historical blocks is only available if their history is not pruned.
+    pub async fn debug_dump_block(
+        &self,
+        block_id: BlockId,
+        start: Option<Address>,
+        max_results: Option<u64>,
+    ) -> EthResult<StateDump> {
+        let header =
+            self.inner.provider.header_by_id(block_id)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let max_results = match max_results.unwrap_or_default() as usize {
+            0 => DUMP_BLOCK_MAX_RESULTS,
+            max_results => max_results.min(DUMP_BLOCK_MAX_RESULTS),
+        };
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let mut dump = StateDump { root: header.state_root, ..Default::default() };
+                this.inner.provider.dump_state(
+                    header.number,
+                    start.unwrap_or_default(),
+                    true,
+                    &mut |dumped| {
+                        // the first account of the next page
+                        if dump.accounts.len() == max_results {
+                            dump.next = Some(Bytes::copy_from_slice(dumped.address.as_slice()));
+                            return false
+                        }
+                        dump.accounts.insert(
+                            format!("{:?}", dumped.address),
+                            from_primitive_dump_account(
+                                dumped.address,
+                                dumped.account,
+                                dumped.storage_root,
+                                dumped.code.map(|code| code.original_bytes()),
+                                Some(dumped.storage),
+                            ),
+                        );
+                        true
+                    },
+                )?;
+                Ok(dump)
+            })
+            .await
+    }
+
+    /// Returns the first block of the `from..=to` sequence, which may go backwards, whose
+    /// post-state is available.
+    pub fn debug_get_accessible_state(
+        &self,
+        from: BlockNumberOrTag,
+        to: BlockNumberOrTag,
+    ) -> EthResult<u64> {
+        let block_number = |block: BlockNumberOrTag| -> EthResult<u64> {
+            self.inner.provider.convert_block_number(block)?.ok_or(EthApiError::UnknownBlockNumber)
+        };
+        let (from, to) = (block_number(from)?, block_number(to)?);
+        let available = self.inner.provider.available_state_range()?;
+
+        let first =
+            if from <= to { from.max(*available.start()) } else { from.min(*available.end()) };
+        if available.contains(&first) && (from.min(to)..=from.max(to)).contains(&first) {
+            Ok(first)
+        } else {
+            Err(EthApiError::InvalidParams("no accessible state found in range".to_string()))
+        }
+    }
+
+    /// Returns the raw value stored for the raw key in the named table.
+    pub fn debug_db_get(&self, table: String, key: Bytes) -> EthResult<Option<Bytes>> {
+        let table = table.parse::<Tables>().map_err(EthApiError::InvalidParams)?;
+        Ok(self.inner.provider.raw_table_value(table, &key)?.map(Into::into))
+    }
+
+    /// Returns the statistics of the database tables and static files as text, as printed by
+    /// `reth db stats`.
+    ///
+    /// The `property` selects the statistics: `mdbx.stats` for the database tables,
+    /// `static_files.stats` for the static files, or `stats` or an empty string for both.
+    pub async fn debug_chaindb_property(&self, property: String) -> EthResult<String> {
+        let (tables, static_files) = match property.as_str() {
+            "" | "stats" => (true, true),
+            "mdbx.stats" => (true, false),
+            "static_files.stats" => (false, true),
+            _ => return Err(EthApiError::InvalidParams(format!("unknown property: {property}"))),
+        };
+
+        let this = self.clone();
+        let stats = self
+            .eth_api()
+            .spawn_blocking_io(move |_| Ok(this.inner.provider.chain_db_stats()?))
+            .await?;
+        let mut out = Vec::new();
+        if static_files {
+            out.push(
+                stats::static_files_stats_table(&stats.static_files, false, false).to_string(),
+            );
+        }
+        if tables {
+            out.push(stats::db_stats_table(&stats).to_string());
+        }
+        Ok(out.join("\n\n"))
+    }
+
+    /// Returns the blocks that were rejected as invalid, most recently rejected first.
+    pub fn debug_bad_blocks(&self) -> EthResult<Vec<BadBlock>> {
+        self.inner
+            .provider
+            .bad_blocks()?
+            .into_iter()
+            .map(|StoredBadBlock { block, error, .. }| {
+                let hash = block.hash();
+                // the block was never inserted, so its total difficulty is derived from the parent
+                let total_difficulty = self
+                    .inner
+                    .provider
+                    .header_td(&block.parent_hash)?
+                    .map(|td| td + block.difficulty)
+                    .unwrap_or_default();
+
+                let block = block.unseal();
+                let mut rlp = Vec::new();
+                block.encode(&mut rlp);
+
+                let rpc_block = match block.clone().with_recovered_senders() {
+                    Some(block) => from_block(
+                        block,
+                        total_difficulty,
+                        BlockTransactionsKind::Full,
+                        Some(hash),
+                    )?,
+                    // the senders of a bad block can't always be recovered, in which case only the
+                    // transaction hashes are returned
+                    None => from_block_with_tx_hashes(
+                        BlockWithSenders { block, senders: Vec::new() },
+                        total_difficulty,
+                        Some(hash),
+                    ),
+                };
+
+                Ok(BadBlock {
+                    hash,
+                    block: rpc_block.into(),
+                    rlp: rlp.into(),
+                    validation_error: error,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns all accounts that were modified by the blocks in the range
+    /// `(start_exclusive, end_inclusive]`.
+    ///
+    /// The accounts are collected from the account and storage changesets, so the range must not
+    /// reach below the blocks that were pruned and it can span at most
+    /// [`MODIFIED_ACCOUNTS_MAX_BLOCKS`] blocks.
+    ///
+    /// Note: an account that is modified and then reverted to its original state within the range
+    /// is still reported.
+    pub async fn debug_get_modified_accounts(
+        &self,
+        start_exclusive: BlockNumber,
+        end_inclusive: BlockNumber,
+    ) -> EthResult<Vec<Address>> {
+        if start_exclusive >= end_inclusive {
+            return Err(EthApiError::InvalidParams(format!(
+                "start block height ({start_exclusive}) must be less than end block height ({end_inclusive})"
+            )))
+        }
+        if end_inclusive - start_exclusive > MODIFIED_ACCOUNTS_MAX_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "requested range of blocks ({}) exceeds the maximum of {MODIFIED_ACCOUNTS_MAX_BLOCKS}",
+                end_inclusive - start_exclusive
+            )))
+        }
+        if end_inclusive > self.inner.provider.best_block_number()? {
+            return Err(EthApiError::UnknownBlockNumber)
+        }
+
+        let modified =
+            modified_accounts(&self.inner.provider, start_exclusive + 1..=end_inclusive)?;
+        Ok(modified.into_iter().collect())
     }
 
     /// Trace the transaction according to the provided options.
@@ -776,6 +1512,13 @@ where
     Provider: BlockReaderIdExt
         + HeaderProvider
         + ChainSpecProvider
+        + ChangeSetReader
+        + PruneCheckpointReader
+        + BadBlockReader
+        + PreimageReader
+        + StateDumpReader
+        + ChainDbReader
+        + ParliaSnapshotReader
         + StateProviderFactory
         + EvmEnvProvider
         + 'static,
@@ -852,17 +1595,25 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock>> {
+        Ok(Self::debug_bad_blocks(self)?)
     }
 
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> SubscriptionResult {
+        let range = self.trace_chain_range(start_exclusive, end_inclusive)?;
+
+        // the subscription is closed with the error if tracing fails
+        let sink = pending.accept().await?;
+        self.clone().trace_chain_to_sink(range, opts.unwrap_or_default(), sink).await?;
+
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -911,9 +1662,13 @@ where
         request: TransactionRequest,
         block_number: Option<BlockId>,
         opts: Option<GethDebugTracingCallOptions>,
+        preset: Option<String>,
     ) -> RpcResult<GethTrace> {
+        let mut opts = opts.unwrap_or_default();
+        opts.state_overrides = Call::state_override_presets(self.eth_api())
+            .resolve(preset.as_deref(), opts.state_overrides.take())?;
         let _permit = self.acquire_trace_permit().await;
-        Ok(Self::debug_trace_call(self, request, block_number, opts.unwrap_or_default()).await?)
+        Ok(Self::debug_trace_call(self, request, block_number, opts).await?)
     }
 
     async fn debug_trace_call_many(
@@ -930,16 +1685,26 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<StateDump> {
+        Ok(Self::debug_account_range(
+            self,
+            block_number,
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await?)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -950,8 +1715,9 @@ where
         Ok(())
     }
 
-    async fn debug_chaindb_property(&self, _property: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_chaindbProperty`
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<String> {
+        Ok(Self::debug_chaindb_property(self, property).await?)
     }
 
     async fn debug_cpu_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
@@ -966,12 +1732,19 @@ where
         Ok(())
     }
 
-    async fn debug_db_get(&self, _key: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_dbGet`
+    async fn debug_db_get(&self, table: String, key: Bytes) -> RpcResult<Option<Bytes>> {
+        Ok(Self::debug_db_get(self, table, key)?)
     }
 
-    async fn debug_dump_block(&self, _number: BlockId) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_dumpBlock`
+    async fn debug_dump_block(
+        &self,
+        number: BlockId,
+        start: Option<Address>,
+        max_results: Option<u64>,
+    ) -> RpcResult<StateDump> {
+        Ok(Self::debug_dump_block(self, number, start, max_results).await?)
     }
 
     async fn debug_free_os_memory(&self) -> RpcResult<()> {
@@ -986,40 +1759,72 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getAccessibleState`
     async fn debug_get_accessible_state(
         &self,
-        _from: BlockNumberOrTag,
-        _to: BlockNumberOrTag,
-    ) -> RpcResult<()> {
-        Ok(())
+        from: BlockNumberOrTag,
+        to: BlockNumberOrTag,
+    ) -> RpcResult<u64> {
+        Ok(Self::debug_get_accessible_state(self, from, to)?)
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        let block_number = |hash: B256| -> RpcResult<u64> {
+            self.inner
+                .provider
+                .block_number(hash)
+                .to_rpc_result()?
+                .ok_or_else(|| EthApiError::UnknownBlockNumber.into())
+        };
+        let start_number = block_number(start_hash)?;
+        let end_number = end_hash.map(block_number).transpose()?;
+        self.debug_get_modified_accounts_by_number(start_number, end_number).await
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        // with a single block, the accounts modified by that block are returned
+        let (start_exclusive, end_inclusive) = match end_number {
+            Some(end_number) => (start_number, end_number),
+            None => (
+                start_number.checked_sub(1).ok_or_else(|| {
+                    EthApiError::InvalidParams("genesis block has no parent".to_string())
+                })?,
+                start_number,
+            ),
+        };
+        Ok(Self::debug_get_modified_accounts(self, start_exclusive, end_inclusive).await?)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingCallOptions>,
+    ) -> RpcResult<Vec<B256>> {
+        cfg_if! {
+            if #[cfg(feature = "bsc")] {
+                let _permit = self.acquire_trace_permit().await;
+                Ok(Self::debug_intermediate_roots(self, block_hash, opts).await?)
+            } else {
+                let _ = (block_hash, opts);
+                Err(EthApiError::Unsupported("debug_intermediateRoots is only supported on bsc")
+                    .into())
+            }
+        }
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1030,8 +1835,9 @@ where
         Ok(())
     }
 
-    async fn debug_preimage(&self, _hash: B256) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_preimage`
+    async fn debug_preimage(&self, hash: B256) -> RpcResult<Bytes> {
+        Ok(Self::debug_preimage(self, hash)?)
     }
 
     async fn debug_print_block(&self, _number: u64) -> RpcResult<()> {
@@ -1066,20 +1872,34 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_standardTraceBadBlockToFile`
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        config: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(Self::debug_standard_trace_bad_block_to_file(
+            self,
+            block_hash,
+            config.unwrap_or_default(),
+        )
+        .await?)
     }
 
+    /// Handler for `debug_standardTraceBlockToFile`
     async fn debug_standard_trace_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        config: Option<StdTraceConfig>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(Self::debug_standard_trace_block_to_file(
+            self,
+            block_hash.into(),
+            config.unwrap_or_default(),
+        )
+        .await?)
     }
 
     async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
@@ -1109,12 +1929,14 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(Self::debug_trace_bad_block(self, block_hash, opts.unwrap_or_default()).await?)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -1138,6 +1960,99 @@ where
     }
 }
 
+/// Writes the lines of an EIP-3155 trace, removing the stack from every step if it is disabled.
+struct StdTraceWriter<W> {
+    inner: W,
+    disable_stack: bool,
+    /// The incomplete line that was written so far.
+    line: Vec<u8>,
+}
+
+impl<W: Write> StdTraceWriter<W> {
+    const fn new(inner: W, disable_stack: bool) -> Self {
+        Self { inner, disable_stack, line: Vec::new() }
+    }
+
+    fn write_line(&mut self, line: &[u8]) -> io::Result<()> {
+        match serde_json::from_slice::<serde_json::Value>(line) {
+            Ok(serde_json::Value::Object(mut step)) => {
+                step.remove("stack");
+                serde_json::to_writer(&mut self.inner, &step)?;
+                self.inner.write_all(b"\n")
+            }
+            // anything that is not a step is written as is
+            _ => self.inner.write_all(line),
+        }
+    }
+}
+
+impl<W: Write> Write for StdTraceWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !self.disable_stack {
+            return self.inner.write(buf)
+        }
+        self.line.extend_from_slice(buf);
+        while let Some(end) = self.line.iter().position(|byte| *byte == b'\n') {
+            let line = self.line.drain(..=end).collect::<Vec<_>>();
+            self.write_line(&line)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.line.is_empty() {
+            let line = std::mem::take(&mut self.line);
+            self.write_line(&line)?;
+        }
+        self.inner.flush()
+    }
+}
+
+/// Returns the lowest block for which both the account and the storage changesets are available,
+/// according to the prune checkpoints.
+fn lowest_changeset_block<P: PruneCheckpointReader>(provider: &P) -> EthResult<BlockNumber> {
+    let mut lowest = 0;
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        if let Some(pruned) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        {
+            lowest = lowest.max(pruned + 1);
+        }
+    }
+    Ok(lowest)
+}
+
+/// Returns all accounts that were modified by the blocks in the given range, according to the
+/// account and storage changesets.
+///
+/// Fails if the changesets of a block in the range have already been pruned.
+fn modified_accounts<P>(
+    provider: &P,
+    blocks: RangeInclusive<BlockNumber>,
+) -> EthResult<BTreeSet<Address>>
+where
+    P: ChangeSetReader + PruneCheckpointReader,
+{
+    let lowest = lowest_changeset_block(provider)?;
+    if *blocks.start() < lowest {
+        return Err(EthApiError::InvalidParams(format!("history pruned below block {lowest}")))
+    }
+
+    let mut modified = BTreeSet::new();
+    for block_number in blocks {
+        modified.extend(
+            provider.account_block_changeset(block_number)?.into_iter().map(|acc| acc.address),
+        );
+        modified.extend(
+            provider
+                .storage_block_changeset(block_number)?
+                .into_iter()
+                .map(|(block_address, _)| block_address.address()),
+        );
+    }
+    Ok(modified)
+}
+
 impl<Provider, Eth> std::fmt::Debug for DebugApi<Provider, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1157,4 +2072,415 @@ struct DebugApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
+    /// Directory the `debug_standardTrace*ToFile` calls write to.
+    trace_dir: Option<PathBuf>,
+    /// The parlia consensus config blocks are re-executed with.
+    #[cfg_attr(not(feature = "bsc"), allow(dead_code))]
+    parlia: ParliaConfig,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use reth_blockchain_tree::noop::NoopBlockchainTree;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_db::tables;
+    use reth_db_api::{
+        models::{AccountBeforeTx, BlockNumberAddress},
+        transaction::DbTxMut,
+    };
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{
+        constants::ETH_TO_WEI, public_key_to_address, Genesis, GenesisAccount, Header,
+        StorageEntry, Transaction, TxKind, TxLegacy,
+    };
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{create_test_provider_factory, create_test_provider_factory_with_chain_spec},
+        BlockWriter, PruneCheckpointWriter, StageCheckpointWriter,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    #[test]
+    fn modified_accounts_from_changesets() {
+        let factory = create_test_provider_factory();
+        let (a, b, c) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+
+        let provider = factory.provider_rw().unwrap().0;
+        let tx = provider.tx_ref();
+        tx.put::<tables::AccountChangeSets>(1, AccountBeforeTx { address: a, info: None }).unwrap();
+        tx.put::<tables::AccountChangeSets>(2, AccountBeforeTx { address: b, info: None }).unwrap();
+        tx.put::<tables::StorageChangeSets>(
+            BlockNumberAddress((2, c)),
+            StorageEntry { key: B256::with_last_byte(1), value: U256::from(1) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(3, AccountBeforeTx { address: a, info: None }).unwrap();
+
+        assert_eq!(modified_accounts(&provider, 1..=1).unwrap(), BTreeSet::from([a]));
+        assert_eq!(modified_accounts(&provider, 2..=2).unwrap(), BTreeSet::from([b, c]));
+        assert_eq!(modified_accounts(&provider, 1..=3).unwrap(), BTreeSet::from([a, b, c]));
+        assert!(modified_accounts(&provider, 4..=4).unwrap().is_empty());
+
+        // blocks with pruned changesets are rejected
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::StorageHistory,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Full,
+                },
+            )
+            .unwrap();
+        assert_eq!(lowest_changeset_block(&provider).unwrap(), 2);
+        assert!(modified_accounts(&provider, 1..=3).is_err());
+        assert_eq!(modified_accounts(&provider, 2..=3).unwrap(), BTreeSet::from([a, b, c]));
+    }
+
+    #[tokio::test]
+    async fn modified_accounts_below_pruned_history() {
+        let factory = create_test_provider_factory();
+        init_genesis(factory.clone()).unwrap();
+        let provider_rw = factory.provider_rw().unwrap();
+        let mut parent_hash = MAINNET.genesis_hash();
+        for number in 1..=3 {
+            let block = Block {
+                header: Header { parent_hash, number, gas_limit: 1_000_000, ..Default::default() },
+                ..Default::default()
+            }
+            .with_recovered_senders()
+            .unwrap()
+            .seal_slow();
+            parent_hash = block.hash();
+            provider_rw.insert_block(block).unwrap();
+        }
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(3)).unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider =
+            BlockchainProvider::new(factory, Arc::new(NoopBlockchainTree::default())).unwrap();
+        let debug_api = DebugApi::new(
+            provider.clone(),
+            build_test_eth_api(provider),
+            BlockingTaskGuard::new(1),
+            None,
+            ParliaConfig::default(),
+        );
+
+        // the range starts at the lowest block with changesets
+        assert!(debug_api.debug_get_modified_accounts(1, 3).await.unwrap().is_empty());
+
+        // the range reaches below the pruned history
+        let err = debug_api.debug_get_modified_accounts(0, 3).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EthApiError::InvalidParams(msg) if msg == "history pruned below block 2"
+        ));
+    }
+
+    #[cfg(feature = "bsc")]
+    #[tokio::test]
+    async fn intermediate_roots_reject_tracing_options() {
+        let factory = create_test_provider_factory();
+        init_genesis(factory.clone()).unwrap();
+        let provider =
+            BlockchainProvider::new(factory, Arc::new(NoopBlockchainTree::default())).unwrap();
+        let debug_api = DebugApi::new(
+            provider.clone(),
+            build_test_eth_api(provider),
+            BlockingTaskGuard::new(1),
+            None,
+            ParliaConfig::default(),
+        );
+
+        let opts = GethDebugTracingCallOptions {
+            tracing_options: GethDebugTracingOptions {
+                tracer: Some(GethDebugBuiltInTracerType::CallTracer.into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let err = debug_api
+            .debug_intermediate_roots(MAINNET.genesis_hash(), Some(opts))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+
+        // the default options are accepted, the genesis block has no parent to execute it on
+        let err = debug_api
+            .debug_intermediate_roots(MAINNET.genesis_hash(), Some(Default::default()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::UnknownParentBlock));
+    }
+
+    #[test]
+    fn std_trace_writer_removes_stack() {
+        let trace = concat!(
+            r#"{"pc":0,"op":96,"stack":["0x1"],"depth":1}"#,
+            "\n",
+            r#"{"output":"","gasUsed":"0x5"}"#,
+            "\n"
+        );
+
+        let mut writer = StdTraceWriter::new(Vec::new(), true);
+        // lines can be split across writes
+        writer.write_all(&trace.as_bytes()[..10]).unwrap();
+        writer.write_all(&trace.as_bytes()[10..]).unwrap();
+        writer.flush().unwrap();
+        let lines = String::from_utf8(writer.inner)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            vec![
+                serde_json::json!({"pc": 0, "op": 96, "depth": 1}),
+                serde_json::json!({"output": "", "gasUsed": "0x5"}),
+            ]
+        );
+
+        let mut writer = StdTraceWriter::new(Vec::new(), false);
+        writer.write_all(trace.as_bytes()).unwrap();
+        assert_eq!(String::from_utf8(writer.inner).unwrap(), trace);
+    }
+
+    fn build_test_eth_api<P>(provider: P) -> EthApi<P, TestPool, NoopNetwork, EthEvmConfig>
+    where
+        P: BlockReaderIdExt
+            + ChainSpecProvider
+            + EvmEnvProvider
+            + StateProviderFactory
+            + Unpin
+            + Clone
+            + 'static,
+    {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+
+        let gas_cap = provider.chain_spec().max_gas_limit;
+        EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            gas_cap,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+            None,
+            DEFAULT_PROOF_PERMITS,
+        )
+    }
+
+    #[tokio::test]
+    async fn dump_block_pages() {
+        let addresses = (1..=3).map(Address::with_last_byte).collect::<Vec<_>>();
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(Genesis {
+                    alloc: addresses
+                        .iter()
+                        .map(|address| {
+                            (
+                                *address,
+                                GenesisAccount { balance: U256::from(1), ..Default::default() },
+                            )
+                        })
+                        .collect(),
+                    ..MAINNET.genesis.clone()
+                })
+                .paris_activated()
+                .build(),
+        );
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(factory.clone()).unwrap();
+        let provider =
+            BlockchainProvider::new(factory, Arc::new(NoopBlockchainTree::default())).unwrap();
+        let debug_api = DebugApi::new(
+            provider.clone(),
+            build_test_eth_api(provider),
+            BlockingTaskGuard::new(1),
+            None,
+            ParliaConfig::default(),
+        );
+        let keys = |dump: &StateDump| dump.accounts.keys().cloned().collect::<Vec<_>>();
+
+        let first = debug_api.debug_dump_block(0u64.into(), None, Some(2)).await.unwrap();
+        assert_eq!(
+            keys(&first),
+            vec![format!("{:?}", addresses[0]), format!("{:?}", addresses[1])]
+        );
+        assert_eq!(first.next, Some(Bytes::copy_from_slice(addresses[2].as_slice())));
+
+        let start = Address::from_slice(&first.next.unwrap());
+        let second = debug_api.debug_dump_block(0u64.into(), Some(start), Some(2)).await.unwrap();
+        assert_eq!(keys(&second), vec![format!("{:?}", addresses[2])]);
+        assert_eq!(second.next, None);
+    }
+
+    /// Reads the steps of an EIP-3155 trace file.
+    fn read_trace(path: &str) -> Vec<serde_json::Value> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn standard_trace_block_to_file() {
+        let key_pair = generators::generate_keys(&mut generators::rng(), 1)[0];
+        let sender = public_key_to_address(key_pair.public_key());
+        // PUSH1 1 PUSH1 1 ADD
+        let contract = Address::with_last_byte(0xc0);
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(Genesis {
+                    alloc: [
+                        (
+                            sender,
+                            GenesisAccount {
+                                balance: U256::from(ETH_TO_WEI),
+                                ..Default::default()
+                            },
+                        ),
+                        (
+                            contract,
+                            GenesisAccount {
+                                code: Some(Bytes::from_static(&[0x60, 0x01, 0x60, 0x01, 0x01])),
+                                ..Default::default()
+                            },
+                        ),
+                    ]
+                    .into(),
+                    ..MAINNET.genesis.clone()
+                })
+                .paris_activated()
+                .build(),
+        );
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(factory.clone()).unwrap();
+
+        // the second transaction can only be executed after the first one
+        let transactions = (0..2)
+            .map(|nonce| {
+                sign_tx_with_key_pair(
+                    key_pair,
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(chain_spec.chain.id()),
+                        nonce,
+                        gas_price: 1_000_000_000,
+                        gas_limit: 50_000,
+                        to: TxKind::Call(contract),
+                        ..Default::default()
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        let block = Block {
+            header: Header {
+                parent_hash: chain_spec.genesis_hash(),
+                number: 1,
+                gas_limit: 1_000_000,
+                ..Default::default()
+            },
+            body: transactions.clone(),
+            ..Default::default()
+        }
+        .with_recovered_senders()
+        .unwrap()
+        .seal_slow();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.insert_block(block.clone()).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider =
+            BlockchainProvider::new(factory, Arc::new(NoopBlockchainTree::default())).unwrap();
+        let trace_dir = tempfile::tempdir().unwrap();
+        let debug_api = DebugApi::new(
+            provider.clone(),
+            build_test_eth_api(provider),
+            BlockingTaskGuard::new(1),
+            Some(trace_dir.path().to_path_buf()),
+            ParliaConfig::default(),
+        );
+        let expected_paths = transactions
+            .iter()
+            .enumerate()
+            .map(|(index, tx)| {
+                trace_dir
+                    .path()
+                    .join(format!("block_{:#x}-{index}-{:#x}.jsonl", block.hash(), tx.hash()))
+                    .display()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+
+        // every transaction is written to its own file
+        let paths = debug_api
+            .debug_standard_trace_block_to_file(block.hash().into(), StdTraceConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(paths, expected_paths);
+        for path in &paths {
+            let steps = read_trace(path);
+            assert_eq!(steps[0]["op"], 0x60);
+            assert!(steps[0].get("stack").is_some());
+        }
+
+        // only the selected transaction is written
+        std::fs::remove_dir_all(trace_dir.path()).unwrap();
+        let config = StdTraceConfig {
+            tx_hash: Some(transactions[1].hash()),
+            disable_stack: true,
+            ..Default::default()
+        };
+        let paths = debug_api
+            .debug_standard_trace_block_to_file(block.hash().into(), config)
+            .await
+            .unwrap();
+        assert_eq!(paths, expected_paths[1..]);
+        assert!(!std::path::Path::new(&expected_paths[0]).exists());
+        let steps = read_trace(&paths[0]);
+        assert_eq!(steps[0]["op"], 0x60);
+        assert!(steps.iter().all(|step| step.get("stack").is_none()));
+
+        // the selected transaction must be in the block
+        let config = StdTraceConfig { tx_hash: Some(B256::ZERO), ..Default::default() };
+        assert!(debug_api
+            .debug_standard_trace_block_to_file(block.hash().into(), config)
+            .await
+            .is_err());
+    }
 }
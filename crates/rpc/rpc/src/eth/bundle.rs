@@ -1,27 +1,35 @@
 //! `Eth` bundle implementation and helpers.
 
-use std::sync::Arc;
+use std::{ops::RangeInclusive, sync::Arc};
 
 use jsonrpsee::core::RpcResult;
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
 use reth_primitives::{
     keccak256,
     revm_primitives::db::{DatabaseCommit, DatabaseRef},
-    PooledTransactionsElement, U256,
+    Bytes, FromRecoveredPooledTransaction, PooledTransactionsElement, B256, U256,
 };
 use reth_revm::database::StateProviderDatabase;
-use reth_rpc_types::mev::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use reth_rpc_types::mev::{
+    CancelBundleRequest, CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle,
+    EthCallBundleResponse, EthCallBundleTransactionResult, EthSendBundle,
+    PrivateTransactionRequest,
+};
 use reth_tasks::pool::BlockingTaskGuard;
+use reth_transaction_pool::{
+    bundle::MAX_BUNDLE_BLOCK_RANGE, Bundle, BundlePool, PoolTransaction, TransactionOrigin,
+    TransactionPool,
+};
 use revm::{
     db::CacheDB,
     primitives::{ResultAndState, TxEnv},
 };
 use revm_primitives::{EnvKzgSettings, EnvWithHandlerCfg, SpecId, MAX_BLOB_GAS_PER_BLOCK};
 
-use reth_provider::{ChainSpecProvider, HeaderProvider};
+use reth_provider::{BlockNumReader, ChainSpecProvider, HeaderProvider};
 use reth_rpc_eth_api::{
-    helpers::{Call, EthTransactions, LoadPendingBlock},
-    EthCallBundleApiServer,
+    helpers::{Call, EthTransactions, LoadPendingBlock, LoadTransaction},
+    EthBundleApiServer, EthCallBundleApiServer,
 };
 use reth_rpc_eth_types::{
     utils::recover_raw_transaction, EthApiError, EthResult, RpcInvalidTransactionError,
@@ -248,6 +256,130 @@ where
     }
 }
 
+impl<Eth> EthBundle<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    /// Returns the [`BundlePool`] of the node's transaction pool.
+    fn bundle_pool(&self) -> EthResult<&BundlePool<<Eth::Pool as TransactionPool>::Transaction>> {
+        LoadTransaction::pool(&self.inner.eth_api).bundle_pool().ok_or_else(|| {
+            EthApiError::InvalidParams(EthBundleError::BundlesNotSupported.to_string())
+        })
+    }
+
+    /// Returns the blocks a bundle or private transaction with the given maximum block number
+    /// can be included in.
+    ///
+    /// Like on BSC, a maximum block number of `0` targets the next [`MAX_BUNDLE_BLOCK_RANGE`]
+    /// blocks.
+    fn inclusion_range(&self, max_block_number: u64) -> EthResult<RangeInclusive<u64>> {
+        let current = EthTransactions::provider(&self.inner.eth_api).best_block_number()?;
+        let limit = current + MAX_BUNDLE_BLOCK_RANGE;
+        let max_block_number = if max_block_number == 0 { limit } else { max_block_number };
+        if max_block_number <= current || max_block_number > limit {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::InvalidBlockRange(max_block_number).to_string(),
+            ))
+        }
+        Ok(current + 1..=max_block_number)
+    }
+
+    /// Decodes the raw transaction into a transaction of the pool.
+    fn pool_transaction(tx: Bytes) -> EthResult<<Eth::Pool as TransactionPool>::Transaction> {
+        let recovered = recover_raw_transaction(tx)?;
+        Ok(<Eth::Pool as TransactionPool>::Transaction::from_recovered_pooled_transaction(
+            recovered,
+        ))
+    }
+
+    /// Adds a bundle to the bundle pool, the bundle is offered to the payload builder until its
+    /// block range is exceeded or it is cancelled.
+    pub async fn send_bundle(&self, bundle: EthSendBundle) -> EthResult<EthBundleHash> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+        } = bundle;
+        if txs.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::EmptyBundleTransactions.to_string(),
+            ))
+        }
+        let block_range = self.inclusion_range(block_number)?;
+
+        let transactions =
+            txs.into_iter().map(Self::pool_transaction).collect::<EthResult<Vec<_>>>()?;
+        if transactions.iter().any(|tx| tx.is_eip4844()) {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BlobTransactionsNotSupported.to_string(),
+            ))
+        }
+
+        let bundle = Bundle::new(transactions, block_range)
+            .with_timestamps(min_timestamp, max_timestamp)
+            .with_reverting_tx_hashes(reverting_tx_hashes)
+            .with_replacement_uuid(replacement_uuid);
+        let bundle_hash = self
+            .bundle_pool()?
+            .insert(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Removes a bundle from the bundle pool.
+    ///
+    /// The bundle is identified by its replacement UUID or by its hash.
+    pub async fn cancel_bundle(&self, request: CancelBundleRequest) -> EthResult<()> {
+        let pool = self.bundle_pool()?;
+        let id = request.bundle_hash;
+        let removed = pool.cancel(&id) || id.parse::<B256>().is_ok_and(|hash| pool.remove(&hash));
+        if !removed {
+            return Err(EthApiError::InvalidParams(EthBundleError::UnknownBundle(id).to_string()))
+        }
+        Ok(())
+    }
+
+    /// Adds a transaction to the pool that is not propagated to peers and dropped once
+    /// `max_block_number` is exceeded.
+    pub async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> EthResult<B256> {
+        let PrivateTransactionRequest { tx, max_block_number, .. } = request;
+        let block_range = self.inclusion_range(max_block_number.unwrap_or_default())?;
+        let transaction = Self::pool_transaction(tx)?;
+
+        // the pool does not propagate transactions with a `Private` origin
+        let hash = LoadTransaction::pool(&self.inner.eth_api)
+            .add_transaction(TransactionOrigin::Private, transaction)
+            .await?;
+        self.bundle_pool()?.track_private_transaction(hash, *block_range.end());
+
+        Ok(hash)
+    }
+
+    /// Removes a private transaction from the pool.
+    ///
+    /// Returns `false` if there is no such transaction or it was not submitted privately.
+    pub async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> EthResult<bool> {
+        let pool = LoadTransaction::pool(&self.inner.eth_api);
+        let hash = request.tx_hash;
+        if !pool.get(&hash).is_some_and(|tx| tx.origin.is_private()) {
+            return Ok(false)
+        }
+        pool.remove_transactions(vec![hash]);
+        self.bundle_pool()?.untrack_private_transaction(&hash);
+        Ok(true)
+    }
+}
+
 #[async_trait::async_trait]
 impl<Eth> EthCallBundleApiServer for EthBundle<Eth>
 where
@@ -258,6 +390,47 @@ where
     }
 }
 
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Ok(Self::send_bundle(self, bundle).await?)
+    }
+
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        Ok(Self::call_bundle(self, request).await?)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        Ok(Self::cancel_bundle(self, request).await?)
+    }
+
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        Ok(Self::send_private_transaction(self, request).await?)
+    }
+
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        let request = PrivateTransactionRequest {
+            tx: bytes,
+            max_block_number: None,
+            preferences: Default::default(),
+        };
+        Ok(Self::send_private_transaction(self, request).await?)
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        Ok(Self::cancel_private_transaction(self, request).await?)
+    }
+}
+
 /// Container type for  `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
@@ -293,4 +466,19 @@ pub enum EthBundleError {
     /// [`MAX_BLOB_GAS_PER_BLOCK`].
     #[error("blob gas usage exceeds the limit of {MAX_BLOB_GAS_PER_BLOCK} gas per block.")]
     Eip4844BlobGasExceeded,
+    /// Thrown if the max block number of a bundle or private transaction is not within the next
+    /// [`MAX_BUNDLE_BLOCK_RANGE`] blocks.
+    #[error(
+        "invalid max block number {0}, must be within the next {MAX_BUNDLE_BLOCK_RANGE} blocks"
+    )]
+    InvalidBlockRange(u64),
+    /// Thrown if a bundle sent with `eth_sendBundle` contains blob transactions.
+    #[error("blob transactions are not supported in bundles")]
+    BlobTransactionsNotSupported,
+    /// Thrown if there is no bundle with the given hash or replacement UUID.
+    #[error("unknown bundle {0}")]
+    UnknownBundle(String),
+    /// Thrown if the transaction pool does not support bundles.
+    #[error("bundles are not supported")]
+    BundlesNotSupported,
 }
@@ -14,6 +14,7 @@ use reth_rpc_eth_api::{
 };
 use reth_rpc_eth_types::{
     EthApiBuilderCtx, EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle, PendingBlock,
+    StateOverridePresets,
 };
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
@@ -70,6 +71,7 @@ where
             TokioTaskExecutor::default(),
             raw_transaction_forwarder,
             proof_permits,
+            StateOverridePresets::default(),
         );
 
         Self { inner: Arc::new(inner) }
@@ -108,6 +110,7 @@ where
             ctx.executor.clone(),
             None,
             ctx.config.proof_permits,
+            ctx.state_override_presets.clone(),
         );
 
         Self { inner: Arc::new(inner) }
@@ -205,6 +208,8 @@ pub struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     raw_transaction_forwarder: parking_lot::RwLock<Option<Arc<dyn RawTransactionForwarder>>>,
     /// Guard for getproof calls
     blocking_task_guard: BlockingTaskGuard,
+    /// Named state override presets
+    state_override_presets: StateOverridePresets,
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, EvmConfig>
@@ -227,6 +232,7 @@ where
         task_spawner: impl TaskSpawner + 'static,
         raw_transaction_forwarder: Option<Arc<dyn RawTransactionForwarder>>,
         proof_permits: usize,
+        state_override_presets: StateOverridePresets,
     ) -> Self {
         let signers = parking_lot::RwLock::new(Default::default());
         // get the block number of the latest block
@@ -256,6 +262,7 @@ where
             evm_config,
             raw_transaction_forwarder: parking_lot::RwLock::new(raw_transaction_forwarder),
             blocking_task_guard: BlockingTaskGuard::new(proof_permits),
+            state_override_presets,
         }
     }
 }
@@ -327,6 +334,12 @@ impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, Ev
         &self.fee_history_cache
     }
 
+    /// Returns a handle to the named state override presets.
+    #[inline]
+    pub const fn state_override_presets(&self) -> &StateOverridePresets {
+        &self.state_override_presets
+    }
+
     /// Returns a handle to the signers.
     #[inline]
     pub const fn signers(&self) -> &parking_lot::RwLock<Vec<Box<dyn EthSigner>>> {
@@ -1,7 +1,7 @@
 //! `eth_` `Filter` RPC handler implementation
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt,
     iter::StepBy,
     ops::RangeInclusive,
@@ -13,7 +13,7 @@ use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_chainspec::ChainInfo;
 use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, LogIndexReader, ProviderError};
 use reth_rpc_eth_api::EthFilterApiServer;
 use reth_rpc_eth_types::{
     logs_utils::{self, append_matching_block_logs},
@@ -124,7 +124,7 @@ where
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
 {
@@ -221,7 +221,7 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -340,7 +340,7 @@ struct EthFilterInner<Provider, Pool> {
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -461,15 +461,126 @@ where
             return Ok(all_logs)
         }
 
+        // use the log index for the part of the range it covers and check the bloom filters of
+        // the headers for the rest, the matching logs are the same either way
+        let is_multi_block_range = from_block != to_block;
+        match self.indexed_log_blocks(filter, from_block..=to_block)? {
+            Some((indexed_range, block_numbers)) => {
+                if from_block < *indexed_range.start() {
+                    self.append_bloom_matching_logs(
+                        &mut all_logs,
+                        filter,
+                        &filter_params,
+                        from_block..=*indexed_range.start() - 1,
+                        is_multi_block_range,
+                    )
+                    .await?;
+                }
+                for block_number in block_numbers {
+                    let header = self
+                        .provider
+                        .sealed_header(block_number)?
+                        .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+                    self.append_block_logs(
+                        &mut all_logs,
+                        &filter_params,
+                        header.num_hash(),
+                        header.timestamp,
+                        is_multi_block_range,
+                    )
+                    .await?;
+                }
+                if *indexed_range.end() < to_block {
+                    self.append_bloom_matching_logs(
+                        &mut all_logs,
+                        filter,
+                        &filter_params,
+                        *indexed_range.end() + 1..=to_block,
+                        is_multi_block_range,
+                    )
+                    .await?;
+                }
+            }
+            None => {
+                self.append_bloom_matching_logs(
+                    &mut all_logs,
+                    filter,
+                    &filter_params,
+                    from_block..=to_block,
+                    is_multi_block_range,
+                )
+                .await?
+            }
+        }
+
+        Ok(all_logs)
+    }
+
+    /// Returns the part of the given range covered by the log index, together with the numbers of
+    /// the blocks in it that contain logs with the filtered addresses and topics, lowest first.
+    ///
+    /// Returns `None` if the index is not enabled or doesn't cover the range, or if the filter
+    /// doesn't restrict the addresses or topics of the logs.
+    fn indexed_log_blocks(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<u64>,
+    ) -> Result<Option<(RangeInclusive<u64>, Vec<u64>)>, EthFilterError> {
+        if filter.address.is_empty() && filter.topics.iter().all(|topic| topic.is_empty()) {
+            return Ok(None)
+        }
+        let Some(index_range) = self.provider.log_index_range()? else { return Ok(None) };
+        let range =
+            (*range.start()).max(*index_range.start())..=(*range.end()).min(*index_range.end());
+        if range.is_empty() {
+            return Ok(None)
+        }
+
+        // a matching log is emitted by one of the addresses and has one of the topics at every
+        // restricted position, the index doesn't store the positions so this may include blocks
+        // without matching logs, which are filtered out by their receipts
+        let mut block_numbers: Option<BTreeSet<u64>> = None;
+        let mut intersect = |candidates: BTreeSet<u64>| {
+            block_numbers = Some(match block_numbers.take() {
+                Some(block_numbers) => block_numbers.intersection(&candidates).copied().collect(),
+                None => candidates,
+            })
+        };
+        if !filter.address.is_empty() {
+            let mut candidates = BTreeSet::new();
+            for address in filter.address.iter() {
+                candidates.extend(self.provider.log_address_blocks(*address, range.clone())?);
+            }
+            intersect(candidates);
+        }
+        for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut candidates = BTreeSet::new();
+            for topic in topics.iter() {
+                candidates.extend(self.provider.log_topic_blocks(*topic, range.clone())?);
+            }
+            intersect(candidates);
+        }
+
+        Ok(Some((range, block_numbers.unwrap_or_default().into_iter().collect())))
+    }
+
+    /// Appends the logs matching the filter of all blocks in the given range whose header bloom
+    /// filter matches.
+    async fn append_bloom_matching_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter: &Filter,
+        filter_params: &FilteredParams,
+        range: RangeInclusive<u64>,
+        is_multi_block_range: bool,
+    ) -> Result<(), EthFilterError> {
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
 
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
+        for (from, to) in BlockRangeInclusiveIter::new(range, self.max_headers_range) {
             let headers = self.provider.headers_range(from..=to)?;
 
             for (idx, header) in headers.iter().enumerate() {
@@ -487,31 +598,52 @@ where
                             .ok_or(ProviderError::HeaderNotFound(header.number.into()))?,
                     };
 
-                    if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            &self.provider,
-                            &filter_params,
-                            BlockNumHash::new(header.number, block_hash),
-                            &receipts,
-                            false,
-                            header.timestamp,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(EthFilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
-                    }
+                    self.append_block_logs(
+                        all_logs,
+                        filter_params,
+                        BlockNumHash::new(header.number, block_hash),
+                        header.timestamp,
+                        is_multi_block_range,
+                    )
+                    .await?;
                 }
             }
         }
 
-        Ok(all_logs)
+        Ok(())
+    }
+
+    /// Appends the logs of the block that match the filter, if its receipts are available.
+    ///
+    /// Returns an error if the range has multiple blocks and the amount of logs exceeds the
+    /// configured limit.
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        block: BlockNumHash,
+        timestamp: u64,
+        is_multi_block_range: bool,
+    ) -> Result<(), EthFilterError> {
+        if let Some(receipts) = self.eth_cache.get_receipts(block.hash).await? {
+            append_matching_block_logs(
+                all_logs,
+                &self.provider,
+                filter_params,
+                block,
+                &receipts,
+                false,
+                timestamp,
+            )?;
+
+            // size check but only if range is multiple blocks, so we always return all
+            // logs of a single block
+            if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                return Err(EthFilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+            }
+        }
+
+        Ok(())
     }
 }
 
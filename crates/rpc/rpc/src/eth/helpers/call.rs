@@ -2,6 +2,7 @@
 
 use reth_evm::ConfigureEvm;
 use reth_rpc_eth_api::helpers::{Call, EthCall, LoadPendingBlock, LoadState, SpawnBlocking};
+use reth_rpc_eth_types::StateOverridePresets;
 
 use crate::EthApi;
 
@@ -24,4 +25,196 @@ where
     fn evm_config(&self) -> &impl ConfigureEvm {
         self.inner.evm_config()
     }
+
+    #[inline]
+    fn state_override_presets(&self) -> &StateOverridePresets {
+        self.inner.state_override_presets()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_blockchain_tree::noop::NoopBlockchainTree;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{Address, Genesis, TxKind};
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+        BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
+    };
+    use reth_rpc_eth_types::{
+        EthApiError, EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
+    use reth_rpc_types::{
+        simulate::{SimBlock, SimulatePayload},
+        state::BlockOverrides,
+        TransactionRequest,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use std::sync::Arc;
+
+    fn build_test_eth_api<P>(provider: P, gas_cap: u64) -> EthApi<P, TestPool, (), EthEvmConfig>
+    where
+        P: BlockReaderIdExt
+            + ChainSpecProvider
+            + EvmEnvProvider
+            + StateProviderFactory
+            + Unpin
+            + Clone
+            + 'static,
+    {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            gas_cap,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+            DEFAULT_PROOF_PERMITS,
+        )
+    }
+
+    /// A block with a single call to the given address.
+    fn sim_block(to: Address, gas: Option<u64>, overrides: Option<BlockOverrides>) -> SimBlock {
+        SimBlock {
+            block_overrides: overrides,
+            state_overrides: None,
+            calls: vec![TransactionRequest {
+                to: Some(TxKind::Call(to)),
+                gas,
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[tokio::test]
+    async fn simulate_caps_gas_across_blocks() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(Genesis { gas_limit: 30_000_000, ..MAINNET.genesis.clone() })
+                .paris_activated()
+                .build(),
+        );
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(factory.clone()).unwrap();
+        let provider =
+            BlockchainProvider::new(factory, Arc::new(NoopBlockchainTree::default())).unwrap();
+        let eth_api = build_test_eth_api(provider, 50_000);
+        let to = Address::with_last_byte(1);
+
+        // two transfers fit into the cap
+        let payload = SimulatePayload {
+            block_state_calls: vec![sim_block(to, None, None), sim_block(to, None, None)],
+            ..Default::default()
+        };
+        let blocks = eth_api.simulate_v1(payload, None).await.unwrap();
+        assert_eq!(blocks.len(), 2);
+        for block in &blocks {
+            assert_eq!(block.calls[0].status, 1);
+            assert_eq!(block.calls[0].gas_used, 21_000);
+        }
+
+        // the third one doesn't, although every block has enough gas
+        let payload = SimulatePayload {
+            block_state_calls: vec![
+                sim_block(to, None, None),
+                sim_block(to, None, None),
+                sim_block(to, Some(21_000), None),
+            ],
+            ..Default::default()
+        };
+        let err = eth_api.simulate_v1(payload, None).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EthApiError::InvalidParams(msg) if msg == "gas cap 50000 exceeded in block 3"
+        ));
+    }
+
+    #[cfg(feature = "bsc")]
+    #[tokio::test]
+    async fn simulate_upgrades_system_contracts() {
+        use reth_chainspec::{BscHardfork, ChainSpec, BSC_MAINNET};
+        use reth_primitives::{
+            system_contracts::{SLASH_CONTRACT, STAKE_HUB_CONTRACT},
+            U256,
+        };
+
+        let planck_block = BscHardfork::bsc_mainnet_activation_block(BscHardfork::Planck).unwrap();
+        let feynman_time =
+            BscHardfork::bsc_mainnet_activation_timestamp(BscHardfork::Feynman).unwrap();
+        // the bsc mainnet forks, on top of an empty genesis shortly before feynman
+        let chain_spec = Arc::new(ChainSpec {
+            chain: BSC_MAINNET.chain,
+            genesis: Genesis {
+                gas_limit: 30_000_000,
+                timestamp: feynman_time - 100,
+                ..Default::default()
+            },
+            hardforks: BSC_MAINNET.hardforks.clone(),
+            paris_block_and_final_difficulty: Some((0, U256::ZERO)),
+            ..Default::default()
+        });
+        let genesis_state_root = chain_spec.genesis_header().state_root;
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec);
+        init_genesis(factory.clone()).unwrap();
+        let provider =
+            BlockchainProvider::new(factory, Arc::new(NoopBlockchainTree::default())).unwrap();
+        let eth_api = build_test_eth_api(provider, 30_000_000);
+        let slash: Address = SLASH_CONTRACT.parse().unwrap();
+        let stake_hub: Address = STAKE_HUB_CONTRACT.parse().unwrap();
+
+        // planck is activated before feynman, its contracts are deployed before the calls
+        let planck = sim_block(
+            slash,
+            None,
+            Some(BlockOverrides {
+                number: Some(U256::from(planck_block)),
+                time: Some(feynman_time - 50),
+                ..Default::default()
+            }),
+        );
+        // the feynman contracts are deployed after the calls of the feynman block
+        let feynman = sim_block(
+            stake_hub,
+            None,
+            Some(BlockOverrides { time: Some(feynman_time), ..Default::default() }),
+        );
+        let after_feynman = sim_block(stake_hub, None, None);
+        let payload = SimulatePayload {
+            block_state_calls: vec![planck.clone(), feynman, after_feynman],
+            ..Default::default()
+        };
+        let blocks = eth_api.simulate_v1(payload, None).await.unwrap();
+
+        assert!(blocks[0].calls[0].gas_used > 21_000);
+        assert_eq!(blocks[1].calls[0].gas_used, 21_000);
+        assert!(blocks[2].calls[0].gas_used > 21_000);
+
+        // every block commits to its own post state, including the upgraded contracts
+        let state_roots =
+            blocks.iter().map(|block| block.inner.header.state_root).collect::<Vec<_>>();
+        assert_ne!(state_roots[0], genesis_state_root);
+        assert_ne!(state_roots[1], state_roots[0]);
+        assert_ne!(state_roots[2], state_roots[1]);
+        for pair in blocks.windows(2) {
+            assert_eq!(Some(pair[1].inner.header.parent_hash), pair[0].inner.header.hash);
+        }
+
+        // the root of a block doesn't depend on the blocks after it
+        let payload = SimulatePayload { block_state_calls: vec![planck], ..Default::default() };
+        let blocks = eth_api.simulate_v1(payload, None).await.unwrap();
+        assert_eq!(blocks[0].inner.header.state_root, state_roots[0]);
+    }
 }
@@ -8,14 +8,17 @@ use jsonrpsee::{
 };
 use reth_network_api::NetworkInfo;
 use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockReader, CanonStateSubscriptions, EvmEnvProvider};
+use reth_provider::{
+    BlockReader, CanonStateNotification, CanonStateSubscriptions, EvmEnvProvider,
+    ForkChoiceSubscriptions,
+};
 use reth_rpc_eth_api::pubsub::EthPubSubApiServer;
 use reth_rpc_eth_types::logs_utils;
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_rpc_types::{
     pubsub::{
-        Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
-        SyncStatusMetadata,
+        Params, PubSubSyncStatus, Reorg, SubscriptionKind,
+        SubscriptionResult as EthSubscriptionResult, SyncStatusMetadata,
     },
     FilteredParams, Header, Log,
 };
@@ -73,7 +76,7 @@ impl<Provider, Pool, Events, Network> EthPubSubApiServer
 where
     Provider: BlockReader + EvmEnvProvider + Clone + 'static,
     Pool: TransactionPool + 'static,
-    Events: CanonStateSubscriptions + Clone + 'static,
+    Events: CanonStateSubscriptions + ForkChoiceSubscriptions + Clone + 'static,
     Network: NetworkInfo + Clone + 'static,
 {
     /// Handler for `eth_subscribe`
@@ -103,7 +106,7 @@ async fn handle_accepted<Provider, Pool, Events, Network>(
 where
     Provider: BlockReader + EvmEnvProvider + Clone + 'static,
     Pool: TransactionPool + 'static,
-    Events: CanonStateSubscriptions + Clone + 'static,
+    Events: CanonStateSubscriptions + ForkChoiceSubscriptions + Clone + 'static,
     Network: NetworkInfo + Clone + 'static,
 {
     match kind {
@@ -117,7 +120,7 @@ where
             // if no params are provided, used default filter params
             let filter = match params {
                 Some(Params::Logs(filter)) => FilteredParams::new(Some(*filter)),
-                Some(Params::Bool(_)) => {
+                Some(Params::Bool(_) | Params::PendingTransactions(_)) => {
                     return Err(invalid_params_rpc_err("Invalid params for logs"))
                 }
                 _ => FilteredParams::default(),
@@ -140,6 +143,29 @@ where
                         });
                         return pipe_from_stream(accepted_sink, stream).await
                     }
+                    Params::PendingTransactions(filter) => {
+                        // transactions of the given senders and recipients requested
+                        let hashes_only = filter.hashes_only;
+                        let stream = pubsub
+                            .full_pending_transaction_stream()
+                            .filter(move |tx| {
+                                futures::future::ready(
+                                    filter.matches(tx.transaction.sender(), tx.transaction.to()),
+                                )
+                            })
+                            .map(move |tx| {
+                                if hashes_only {
+                                    EthSubscriptionResult::TransactionHash(*tx.transaction.hash())
+                                } else {
+                                    EthSubscriptionResult::FullTransaction(Box::new(
+                                        reth_rpc_types_compat::transaction::from_recovered(
+                                            tx.transaction.to_recovered_transaction(),
+                                        ),
+                                    ))
+                                }
+                            });
+                        return pipe_from_stream(accepted_sink, stream).await
+                    }
                     Params::Bool(false) | Params::None => {
                         // only hashes requested
                     }
@@ -156,6 +182,25 @@ where
                 .map(EthSubscriptionResult::TransactionHash);
             pipe_from_stream(accepted_sink, stream).await
         }
+        SubscriptionKind::FinalizedHeads => {
+            if params.is_some_and(|params| params != Params::None) {
+                return Err(invalid_params_rpc_err("Invalid params for finalizedHeads"))
+            }
+            let stream = pubsub.chain_events.finalized_block_stream().map(|header| {
+                EthSubscriptionResult::Header(Box::new(
+                    reth_rpc_types_compat::block::from_primitive_with_hash(header),
+                ))
+            });
+            pipe_from_stream(accepted_sink, stream).await
+        }
+        SubscriptionKind::Reorgs => {
+            if params.is_some_and(|params| params != Params::None) {
+                return Err(invalid_params_rpc_err("Invalid params for reorgs"))
+            }
+            let stream =
+                pubsub.reorg_stream().map(|reorg| EthSubscriptionResult::Reorg(Box::new(reorg)));
+            pipe_from_stream(accepted_sink, stream).await
+        }
         SubscriptionKind::Syncing => {
             // get new block subscription
             let mut canon_state =
@@ -320,6 +365,27 @@ where
         })
     }
 
+    /// Returns a stream that yields the reverted and the new headers of all reorgs of the canonical
+    /// chain.
+    fn reorg_stream(&self) -> impl Stream<Item = Reorg> {
+        self.chain_events.canonical_state_stream().filter_map(|notification| {
+            let reorg = match notification {
+                CanonStateNotification::Reorg { old, new } => Some(Reorg {
+                    reverted: old
+                        .headers()
+                        .map(reth_rpc_types_compat::block::from_primitive_with_hash)
+                        .collect(),
+                    committed: new
+                        .headers()
+                        .map(reth_rpc_types_compat::block::from_primitive_with_hash)
+                        .collect(),
+                }),
+                CanonStateNotification::Commit { .. } => None,
+            };
+            futures::future::ready(reorg)
+        })
+    }
+
     /// Returns a stream that yields all logs that match the given filter.
     fn log_stream(&self, filter: FilteredParams) -> impl Stream<Item = Log> {
         BroadcastStream::new(self.chain_events.subscribe_to_canonical_state())
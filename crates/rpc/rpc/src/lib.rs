@@ -36,6 +36,7 @@ mod admin;
 mod debug;
 mod engine;
 pub mod eth;
+mod miner;
 mod net;
 mod otterscan;
 mod reth;
@@ -43,10 +44,11 @@ mod rpc;
 mod trace;
 mod txpool;
 mod web3;
-pub use admin::AdminApi;
+pub use admin::{AdminApi, AdminStateOverridePresetsApi};
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
+pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
@@ -0,0 +1,76 @@
+use jsonrpsee::core::RpcResult;
+use reth_payload_primitives::MinerHandle;
+use reth_primitives::{Address, U256, U64};
+use reth_rpc_api::{EthMiningApiServer, MinerApiServer};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+
+/// `miner` API implementation.
+///
+/// This type controls local block production through the [`MinerHandle`] shared with the payload
+/// job generator. It also serves `eth_coinbase` and `eth_mining` for nodes that seal blocks.
+#[derive(Debug, Clone)]
+pub struct MinerApi {
+    /// The shared settings of local block production, if the node seals blocks.
+    miner: Option<MinerHandle>,
+}
+
+impl MinerApi {
+    /// Creates a new instance of `MinerApi`.
+    pub const fn new(miner: Option<MinerHandle>) -> Self {
+        Self { miner }
+    }
+
+    /// Returns the [`MinerHandle`] or an error if the node does not seal blocks.
+    fn miner(&self) -> RpcResult<&MinerHandle> {
+        self.miner.as_ref().ok_or_else(|| internal_rpc_err("block production is not enabled"))
+    }
+}
+
+impl MinerApiServer for MinerApi {
+    /// Handler for `miner_start`
+    fn start(&self) -> RpcResult<()> {
+        self.miner()?.start().map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `miner_stop`
+    fn stop(&self) -> RpcResult<()> {
+        self.miner()?.stop();
+        Ok(())
+    }
+
+    /// Handler for `miner_setGasPrice`
+    fn set_gas_price(&self, gas_price: U256) -> RpcResult<bool> {
+        self.miner()?.set_gas_price(gas_price.saturating_to());
+        Ok(true)
+    }
+
+    /// Handler for `miner_setGasLimit`
+    fn set_gas_limit(&self, gas_limit: U64) -> RpcResult<bool> {
+        self.miner()?
+            .set_gas_limit(gas_limit.to())
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        Ok(true)
+    }
+
+    /// Handler for `miner_setExtra`
+    fn set_extra(&self, extra: String) -> RpcResult<bool> {
+        self.miner()?
+            .set_extra_data(extra.into_bytes().into())
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))?;
+        Ok(true)
+    }
+}
+
+impl EthMiningApiServer for MinerApi {
+    /// Handler for `eth_coinbase`
+    fn coinbase(&self) -> RpcResult<Address> {
+        self.miner()?
+            .coinbase()
+            .ok_or_else(|| internal_rpc_err("etherbase must be explicitly specified"))
+    }
+
+    /// Handler for `eth_mining`
+    fn mining(&self) -> RpcResult<bool> {
+        Ok(self.miner.as_ref().is_some_and(MinerHandle::is_mining))
+    }
+}
@@ -1,7 +1,9 @@
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_primitives::{Address, BlockNumberOrTag, TxHash, B256, U256};
+use reth_db_api::models::StoredBlockBodyIndices;
+use reth_primitives::{Address, BlockNumber, BlockNumberOrTag, TxHash, TxNumber, B256, U256};
+use reth_provider::{AddressTransactionsReader, BlockReader};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::helpers::TraceExt;
 use reth_rpc_eth_types::EthApiError;
@@ -14,7 +16,7 @@ use reth_rpc_types::{
         },
         parity::{Action, CreateAction, CreateOutput, TraceOutput},
     },
-    AnyTransactionReceipt, BlockTransactions, Header, RichBlock,
+    AnyTransactionReceipt, BlockTransactions, Header, RichBlock, Transaction,
 };
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
@@ -27,14 +29,15 @@ const API_LEVEL: u64 = 8;
 
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    provider: Provider,
     eth: Eth,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub const fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
     }
 
     /// Constructs a `BlockDetails` from a block and its receipts.
@@ -56,9 +59,83 @@ impl<Eth> OtterscanApi<Eth> {
     }
 }
 
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: BlockReader + AddressTransactionsReader,
+    Eth: EthApiServer,
+{
+    /// Returns an error if the address transactions index is not enabled.
+    fn ensure_address_index(&self) -> RpcResult<()> {
+        self.provider
+            .address_transactions_indexed_block()
+            .map_err(EthApiError::from)?
+            .ok_or_else(|| internal_rpc_err("address transactions index is not enabled"))?;
+        Ok(())
+    }
+
+    /// Returns the body indices of the given block.
+    fn block_body_indices(&self, block_number: BlockNumber) -> RpcResult<StoredBlockBodyIndices> {
+        Ok(self
+            .provider
+            .block_body_indices(block_number)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::UnknownBlockNumber)?)
+    }
+
+    /// Returns the body indices of the block of the given transaction.
+    fn transaction_block_body_indices(
+        &self,
+        tx_number: TxNumber,
+    ) -> RpcResult<StoredBlockBodyIndices> {
+        let block_number = self
+            .provider
+            .transaction_block(tx_number)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::TransactionNotFound)?;
+        self.block_body_indices(block_number)
+    }
+
+    /// Fetches the given transactions with their receipts.
+    async fn transactions_with_receipts(
+        &self,
+        tx_numbers: Vec<TxNumber>,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>)> {
+        let mut txs = Vec::with_capacity(tx_numbers.len());
+        let mut receipts = Vec::with_capacity(tx_numbers.len());
+        for tx_number in tx_numbers {
+            let hash = self
+                .provider
+                .transaction_by_id(tx_number)
+                .map_err(EthApiError::from)?
+                .ok_or(EthApiError::TransactionNotFound)?
+                .hash();
+            let (tx, receipt) = futures::try_join!(
+                self.eth.transaction_by_hash(hash),
+                self.eth.transaction_receipt(hash)
+            )?;
+            let tx = tx.ok_or(EthApiError::TransactionNotFound)?;
+            let receipt = receipt.ok_or_else(|| internal_rpc_err("receipt not found"))?;
+
+            let timestamp = match receipt.block_number {
+                Some(block_number) => self
+                    .provider
+                    .header_by_number(block_number)
+                    .map_err(EthApiError::from)?
+                    .map(|header| header.timestamp),
+                None => None,
+            };
+
+            txs.push(tx);
+            receipts.push(ots_receipt(receipt, timestamp));
+        }
+        Ok((txs, receipts))
+    }
+}
+
 #[async_trait]
-impl<Eth> OtterscanServer for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer for OtterscanApi<Provider, Eth>
 where
+    Provider: BlockReader + AddressTransactionsReader + 'static,
     Eth: EthApiServer + TraceExt + 'static,
 {
     /// Handler for `{ots,erigon}_getHeaderByNumber`
@@ -213,22 +290,7 @@ where
         let timestamp = Some(block.header.timestamp);
         let receipts = receipts
             .drain(page_start..page_end)
-            .map(|receipt| {
-                let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
-                    status: receipt
-                        .inner
-                        .receipt
-                        .status
-                        .as_eip658()
-                        .expect("ETH API returned pre-EIP-658 status"),
-                    cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: receipt.r#type,
-                });
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|receipt| ots_receipt(receipt, timestamp))
             .collect();
         Ok(OtsBlockTransactions { fullblock: block.inner.into(), receipts })
     }
@@ -236,21 +298,100 @@ where
     /// Handler for `searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
+        address: Address,
+        block_number: u64,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        self.ensure_address_index()?;
+
+        // Block number 0 means searching from the latest block.
+        let before = if block_number == 0 {
+            TxNumber::MAX
+        } else {
+            self.block_body_indices(block_number)?.first_tx_num()
+        };
+
+        let mut tx_numbers = self
+            .provider
+            .address_transactions_before(address, before, page_size)
+            .map_err(EthApiError::from)?;
+
+        // The next page continues before the block of the last transaction, so the page has to
+        // contain all transactions of that block.
+        let mut has_more = false;
+        if tx_numbers.len() == page_size {
+            if let Some(&last) = tx_numbers.last() {
+                let first = self.transaction_block_body_indices(last)?.first_tx_num();
+                if first < last {
+                    let remaining = self
+                        .provider
+                        .address_transactions_in_range(address, first..=last - 1)
+                        .map_err(EthApiError::from)?;
+                    tx_numbers.extend(remaining.into_iter().rev());
+                }
+                has_more = !self
+                    .provider
+                    .address_transactions_before(address, first, 1)
+                    .map_err(EthApiError::from)?
+                    .is_empty();
+            }
+        }
+
+        let (txs, receipts) = self.transactions_with_receipts(tx_numbers).await?;
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: block_number == 0,
+            last_page: !has_more,
+        })
     }
 
     /// Handler for `searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
+        address: Address,
+        block_number: u64,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        self.ensure_address_index()?;
+
+        let from = self.block_body_indices(block_number)?.next_tx_num();
+        let mut tx_numbers = self
+            .provider
+            .address_transactions_from(address, from, page_size)
+            .map_err(EthApiError::from)?;
+
+        // The next page continues after the block of the last transaction, so the page has to
+        // contain all transactions of that block.
+        let mut has_more = false;
+        if tx_numbers.len() == page_size {
+            if let Some(&last) = tx_numbers.last() {
+                let body = self.transaction_block_body_indices(last)?;
+                if last < body.last_tx_num() {
+                    tx_numbers.extend(
+                        self.provider
+                            .address_transactions_in_range(address, last + 1..=body.last_tx_num())
+                            .map_err(EthApiError::from)?,
+                    );
+                }
+                has_more = !self
+                    .provider
+                    .address_transactions_from(address, body.next_tx_num(), 1)
+                    .map_err(EthApiError::from)?
+                    .is_empty();
+            }
+        }
+
+        // Otterscan expects the transactions in descending order.
+        tx_numbers.reverse();
+
+        let (txs, receipts) = self.transactions_with_receipts(tx_numbers).await?;
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: !has_more,
+            last_page: block_number == 0,
+        })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
@@ -360,6 +501,24 @@ where
     }
 }
 
+/// Converts a receipt into the trimmed down receipt format used by Otterscan.
+fn ots_receipt(receipt: AnyTransactionReceipt, timestamp: Option<u64>) -> OtsTransactionReceipt {
+    let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
+        status: receipt
+            .inner
+            .receipt
+            .status
+            .as_eip658()
+            .expect("ETH API returned pre-EIP-658 status"),
+        cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
+        logs: None,
+        logs_bloom: None,
+        r#type: receipt.r#type,
+    });
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
+
 /// Performs a binary search within a given block range to find the desired block number.
 ///
 /// The binary search is performed by calling the provided asynchronous `check` closure on the
@@ -1,4 +1,7 @@
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{BTreeSet, HashSet},
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult as Result;
@@ -7,8 +10,10 @@ use reth_consensus_common::calc::{
     base_block_reward, base_block_reward_pre_merge, block_reward, ommer_reward,
 };
 use reth_evm::ConfigureEvmEnv;
-use reth_primitives::{BlockId, Bytes, Header, B256, U256};
-use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
+use reth_primitives::{Address, BlockId, BlockNumber, Bytes, Header, B256, U256};
+use reth_provider::{
+    AddressTransactionsReader, BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
+};
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::TraceApiServer;
 use reth_rpc_eth_api::helpers::{Call, TraceExt};
@@ -19,12 +24,12 @@ use reth_rpc_eth_types::{
 use reth_rpc_types::{
     state::{EvmOverrides, StateOverride},
     trace::{
-        filter::TraceFilter,
+        filter::{TraceFilter, TraceFilterMatcher},
         opcode::{BlockOpcodeGas, TransactionOpcodeGas},
         parity::*,
         tracerequest::TraceCallRequest,
     },
-    BlockError, BlockOverrides, Index, TransactionRequest,
+    BlockOverrides, Index, TransactionRequest,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
@@ -75,7 +80,12 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
 
 impl<Provider, Eth> TraceApi<Provider, Eth>
 where
-    Provider: BlockReader + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
+    Provider: BlockReader
+        + AddressTransactionsReader
+        + StateProviderFactory
+        + EvmEnvProvider
+        + ChainSpecProvider
+        + 'static,
     Eth: TraceExt + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
@@ -244,13 +254,15 @@ where
 
     /// Returns all transaction traces that match the given filter.
     ///
-    /// This is similar to [`Self::trace_block`] but only returns traces for transactions that match
-    /// the filter.
+    /// This is similar to [`Self::trace_block`] but only returns the traces whose addresses match
+    /// the filter, including the traces of internal calls.
     pub async fn trace_filter(
         &self,
         filter: TraceFilter,
     ) -> EthResult<Vec<LocalizedTransactionTrace>> {
         let matcher = filter.matcher();
+        let addresses =
+            filter.from_address.iter().chain(filter.to_address.iter()).copied().collect::<Vec<_>>();
         let TraceFilter { from_block, to_block, .. } = filter;
         let start = from_block.unwrap_or(0);
         let end = if let Some(to_block) = to_block {
@@ -273,47 +285,20 @@ where
             ))
         }
 
-        // fetch all blocks in that range
-        let blocks = self.provider().block_range(start..=end)?;
-
-        // find relevant blocks to trace
-        let mut target_blocks = Vec::new();
-        for block in &blocks {
-            let mut transaction_indices = HashSet::new();
-            let mut highest_matching_index = 0;
-            for (tx_idx, tx) in block.body.iter().enumerate() {
-                let from = tx.recover_signer_unchecked().ok_or(BlockError::InvalidSignature)?;
-                let to = tx.to();
-                if matcher.matches(from, to) {
-                    let idx = tx_idx as u64;
-                    transaction_indices.insert(idx);
-                    highest_matching_index = idx;
-                }
-            }
-            if !transaction_indices.is_empty() {
-                target_blocks.push((block.number, transaction_indices, highest_matching_index));
-            }
-        }
-
-        // trace all relevant blocks
-        let mut block_traces = Vec::with_capacity(target_blocks.len());
-        for (num, indices, highest_idx) in target_blocks {
-            let traces = self.inner.eth_api.trace_block_until(
-                num.into(),
-                Some(highest_idx),
+        // trace all blocks in that range, skipping the blocks whose calls are indexed and that the
+        // filtered addresses don't appear in
+        let block_numbers = self.address_block_numbers(&addresses, start, end)?;
+        let mut block_traces = Vec::with_capacity(block_numbers.len());
+        for number in block_numbers {
+            let traces = self.inner.eth_api.trace_block_with(
+                number.into(),
                 TracingInspectorConfig::default_parity(),
-                move |tx_info, inspector, res, _, _| {
-                    if let Some(idx) = tx_info.index {
-                        if !indices.contains(&idx) {
-                            // only record traces for relevant transactions
-                            return Ok(None)
-                        }
-                    }
+                |tx_info, inspector, res, _, _| {
                     let traces = inspector
                         .with_transaction_gas_used(res.gas_used())
                         .into_parity_builder()
                         .into_localized_transaction_traces(tx_info);
-                    Ok(Some(traces))
+                    Ok(traces)
                 },
             );
             block_traces.push(traces);
@@ -323,17 +308,21 @@ where
         let mut all_traces = block_traces
             .into_iter()
             .flatten()
-            .flat_map(|traces| traces.into_iter().flatten().flat_map(|traces| traces.into_iter()))
+            .flat_map(|traces| traces.into_iter().flatten())
+            .filter(|trace| trace_matches(&matcher, &trace.trace))
             .collect::<Vec<_>>();
 
         // add reward traces for all blocks
-        for block in &blocks {
-            if let Some(base_block_reward) = self.calculate_base_block_reward(&block.header)? {
-                all_traces.extend(self.extract_reward_traces(
-                    &block.header,
-                    &block.ommers,
-                    base_block_reward,
-                ));
+        for number in start..=end {
+            let header =
+                self.provider().header_by_number(number)?.ok_or(EthApiError::UnknownBlockNumber)?;
+            if let Some(base_block_reward) = self.calculate_base_block_reward(&header)? {
+                let ommers = self.provider().ommers(number.into())?.unwrap_or_default();
+                all_traces.extend(
+                    self.extract_reward_traces(&header, &ommers, base_block_reward)
+                        .into_iter()
+                        .filter(|trace| trace_matches(&matcher, &trace.trace)),
+                );
             } else {
                 // no block reward, means we're past the Paris hardfork and don't expect any rewards
                 // because the blocks in ascending order
@@ -344,6 +333,48 @@ where
         Ok(all_traces)
     }
 
+    /// Returns the numbers of the blocks in the given range that the addresses may appear in.
+    ///
+    /// Blocks whose internal calls are indexed by the address transactions index are only returned
+    /// if one of the addresses appears in them, all other blocks of the range are returned. Without
+    /// addresses, all blocks of the range are returned.
+    fn address_block_numbers(
+        &self,
+        addresses: &[Address],
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> EthResult<Vec<BlockNumber>> {
+        let indexed = self
+            .provider()
+            .address_calls_indexed_range()?
+            .map(|indexed| start.max(*indexed.start())..=end.min(*indexed.end()))
+            .filter(|indexed| !addresses.is_empty() && !indexed.is_empty());
+        let Some(indexed) = indexed else { return Ok((start..=end).collect()) };
+        let (Some(first), Some(last)) = (
+            self.provider().block_body_indices(*indexed.start())?,
+            self.provider().block_body_indices(*indexed.end())?,
+        ) else {
+            return Ok((start..=end).collect())
+        };
+
+        let mut block_numbers = (start..*indexed.start()).collect::<BTreeSet<_>>();
+        if first.first_tx_num() < last.next_tx_num() {
+            let tx_range = first.first_tx_num()..=last.next_tx_num() - 1;
+            for address in addresses {
+                for tx_number in
+                    self.provider().address_transactions_in_range(*address, tx_range.clone())?
+                {
+                    if let Some(block_number) = self.provider().transaction_block(tx_number)? {
+                        block_numbers.insert(block_number);
+                    }
+                }
+            }
+        }
+        block_numbers.extend(*indexed.end() + 1..=end);
+
+        Ok(block_numbers.into_iter().collect())
+    }
+
     /// Returns all traces for the given transaction hash
     pub async fn trace_transaction(
         &self,
@@ -557,7 +588,12 @@ where
 #[async_trait]
 impl<Provider, Eth> TraceApiServer for TraceApi<Provider, Eth>
 where
-    Provider: BlockReader + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
+    Provider: BlockReader
+        + AddressTransactionsReader
+        + StateProviderFactory
+        + EvmEnvProvider
+        + ChainSpecProvider
+        + 'static,
     Eth: TraceExt + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
@@ -710,3 +746,190 @@ fn reward_trace(header: &Header, reward: RewardAction) -> LocalizedTransactionTr
         },
     }
 }
+
+/// Returns whether the addresses of the trace match the filter.
+///
+/// Calls match on their caller and callee, creations on their creator and the created contract,
+/// and self-destructs on the destroyed contract and the refund address.
+fn trace_matches(matcher: &TraceFilterMatcher, trace: &TransactionTrace) -> bool {
+    match &trace.action {
+        Action::Call(call) => matcher.matches(call.from, Some(call.to)),
+        Action::Create(create) => {
+            let created = match &trace.result {
+                Some(TraceOutput::Create(output)) => Some(output.address),
+                _ => None,
+            };
+            matcher.matches(create.from, created)
+        }
+        Action::Selfdestruct(selfdestruct) => {
+            matcher.matches(selfdestruct.address, Some(selfdestruct.refund_address))
+        }
+        Action::Reward(reward) => matcher.matches(reward.author, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use reth_blockchain_tree::noop::NoopBlockchainTree;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{
+        constants::ETH_TO_WEI, public_key_to_address, Block, Genesis, GenesisAccount, Transaction,
+        TxKind, TxLegacy,
+    };
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+        BlockReaderIdExt, BlockWriter, HistoryWriter, StageCheckpointWriter,
+    };
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
+    use reth_stages_types::{
+        CheckpointBlockRange, IndexHistoryCheckpoint, StageCheckpoint, StageId,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    fn build_test_eth_api<P>(provider: P) -> EthApi<P, TestPool, NoopNetwork, EthEvmConfig>
+    where
+        P: BlockReaderIdExt
+            + ChainSpecProvider
+            + EvmEnvProvider
+            + StateProviderFactory
+            + Unpin
+            + Clone
+            + 'static,
+    {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+
+        let gas_cap = provider.chain_spec().max_gas_limit;
+        EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            gas_cap,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+            None,
+            DEFAULT_PROOF_PERMITS,
+        )
+    }
+
+    #[tokio::test]
+    async fn trace_filter_traces_internal_calls_of_blocks_indexed_without_calls() {
+        let key_pair = generators::generate_keys(&mut generators::rng(), 1)[0];
+        let sender = public_key_to_address(key_pair.public_key());
+        // a contract that calls another account
+        let contract = Address::with_last_byte(0xc0);
+        let callee = Address::with_last_byte(0xca);
+        let code: Bytes = [
+            &[0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73][..],
+            &callee[..],
+            &[0x5a, 0xf1, 0x00][..],
+        ]
+        .concat()
+        .into();
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(Genesis {
+                    alloc: [
+                        (
+                            sender,
+                            GenesisAccount {
+                                balance: U256::from(ETH_TO_WEI),
+                                ..Default::default()
+                            },
+                        ),
+                        (contract, GenesisAccount { code: Some(code), ..Default::default() }),
+                    ]
+                    .into(),
+                    ..MAINNET.genesis.clone()
+                })
+                .paris_activated()
+                .build(),
+        );
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(factory.clone()).unwrap();
+
+        let transaction = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 0,
+                gas_price: 1_000_000_000,
+                gas_limit: 100_000,
+                to: TxKind::Call(contract),
+                ..Default::default()
+            }),
+        );
+        let block = Block {
+            header: Header {
+                parent_hash: chain_spec.genesis_hash(),
+                number: 1,
+                gas_limit: 1_000_000,
+                ..Default::default()
+            },
+            body: vec![transaction],
+            ..Default::default()
+        }
+        .with_recovered_senders()
+        .unwrap()
+        .seal_slow();
+
+        // the stage traced the calls of the genesis block, the block is indexed by the live path
+        // without its internal calls
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(
+                StageId::IndexAddressTransactions,
+                StageCheckpoint::new(0).with_index_history_stage_checkpoint(
+                    IndexHistoryCheckpoint {
+                        block_range: CheckpointBlockRange { from: 0, to: 0 },
+                        ..Default::default()
+                    },
+                ),
+            )
+            .unwrap();
+        provider_rw.insert_block(block).unwrap();
+        provider_rw.update_history_indices(1..=1).unwrap();
+        provider_rw.update_pipeline_stages(1, false).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider =
+            BlockchainProvider::new(factory, Arc::new(NoopBlockchainTree::default())).unwrap();
+        assert_eq!(provider.address_transactions_indexed_block().unwrap(), Some(1));
+        assert_eq!(provider.address_calls_indexed_range().unwrap(), Some(0..=0));
+        assert!(provider.address_transactions_from(callee, 0, 1).unwrap().is_empty());
+
+        let trace_api = TraceApi::new(
+            provider.clone(),
+            build_test_eth_api(provider),
+            BlockingTaskGuard::new(1),
+        );
+        let filter = TraceFilter {
+            from_block: Some(1),
+            to_block: Some(1),
+            to_address: vec![callee],
+            ..Default::default()
+        };
+        let traces = trace_api.trace_filter(filter).await.unwrap();
+        assert!(traces.iter().any(|trace| matches!(
+            &trace.trace.action,
+            Action::Call(call) if call.from == contract && call.to == callee
+        )));
+    }
+}
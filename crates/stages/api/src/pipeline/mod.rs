@@ -6,10 +6,12 @@ use alloy_primitives::{BlockNumber, B256};
 pub use event::*;
 use futures_util::Future;
 use reth_db_api::database::Database;
-use reth_primitives_traits::constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH;
+use reth_primitives::SealedBlock;
+use reth_primitives_traits::{constants::BEACON_CONSENSUS_REORG_UNWIND_DEPTH, SealedHeader};
 use reth_provider::{
-    providers::StaticFileWriter, FinalizedBlockReader, FinalizedBlockWriter, ProviderFactory,
-    StageCheckpointReader, StageCheckpointWriter, StaticFileProviderFactory,
+    providers::StaticFileWriter, BadBlockWriter, BlockReader, FinalizedBlockReader,
+    FinalizedBlockWriter, ProviderFactory, StageCheckpointReader, StageCheckpointWriter,
+    StaticFileProviderFactory,
 };
 use reth_prune::PrunerBuilder;
 use reth_static_file::StaticFileProducer;
@@ -488,6 +490,25 @@ where
     }
 }
 
+/// Persists the block that failed validation or execution, so it can later be inspected via
+/// `debug_getBadBlocks`.
+///
+/// The body is stored along with the header if it was already downloaded. Failing to record the
+/// block is not fatal for the pipeline and is only logged.
+fn record_bad_block<DB: Database>(
+    factory: &ProviderFactory<DB>,
+    header: &SealedHeader,
+    error: String,
+) {
+    let block = match factory.block_by_hash(header.hash()) {
+        Ok(Some(block)) => block.seal(header.hash()),
+        _ => SealedBlock::new(header.clone(), Default::default()),
+    };
+    if let Err(err) = factory.insert_bad_block(block, error) {
+        warn!(target: "sync::pipeline", %err, hash = ?header.hash(), "Failed to record bad block");
+    }
+}
+
 fn on_stage_error<DB: Database>(
     factory: &ProviderFactory<DB>,
     stage_id: StageId,
@@ -510,6 +531,7 @@ fn on_stage_error<DB: Database>(
                     bad_block = %block.number,
                     "Stage encountered a validation error: {validation_error}"
                 );
+                record_bad_block(factory, &block, validation_error.to_string());
 
                 // FIXME: When handling errors, we do not commit the database transaction. This
                 // leads to the Merkle stage not clearing its checkpoint, and restarting from an
@@ -539,6 +561,7 @@ fn on_stage_error<DB: Database>(
                     bad_block = %block.number,
                     "Stage encountered an execution error: {execution_error}"
                 );
+                record_bad_block(factory, &block, execution_error.to_string());
 
                 // We unwind because of an execution error. If the unwind itself
                 // fails, we bail entirely,
@@ -36,7 +36,8 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, ExecutionStage, FinishStage, HeaderStage,
-        IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage, PruneSenderRecoveryStage,
+        IndexAccountHistoryStage, IndexAddressTransactionsStage, IndexLogsStage,
+        IndexStorageHistoryStage, MerkleStage, PreimagesStage, PruneSenderRecoveryStage,
         PruneStage, SenderRecoveryStage, StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
@@ -73,6 +74,9 @@ use tokio::sync::watch;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`IndexAddressTransactionsStage`] (if enabled)
+/// - [`PreimagesStage`] (if enabled)
+/// - [`IndexLogsStage`] (if enabled)
 /// - [`PruneStage`] (execute)
 /// - [`FinishStage`]
 #[derive(Debug)]
@@ -282,7 +286,7 @@ where
 {
     fn builder(self) -> StageSetBuilder<DB> {
         ExecutionStages::new(
-            self.executor_factory,
+            self.executor_factory.clone(),
             self.stages_config.clone(),
             self.prune_modes.clone(),
         )
@@ -293,6 +297,7 @@ where
         }))
         .add_set(HashingStages { stages_config: self.stages_config.clone() })
         .add_set(HistoryIndexingStages {
+            executor_factory: self.executor_factory,
             stages_config: self.stages_config.clone(),
             prune_modes: self.prune_modes.clone(),
         })
@@ -372,14 +377,20 @@ impl<DB: Database> StageSet<DB> for HashingStages {
 /// A set containing all stages that do additional indexing for historical state.
 #[derive(Debug, Default)]
 #[non_exhaustive]
-pub struct HistoryIndexingStages {
+pub struct HistoryIndexingStages<E> {
+    /// Executor factory used to trace the internal calls of transactions.
+    executor_factory: E,
     /// Configuration for each stage in the pipeline
     stages_config: StageConfig,
     /// Prune configuration for every segment that can be pruned
     prune_modes: PruneModes,
 }
 
-impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
+impl<E, DB> StageSet<DB> for HistoryIndexingStages<E>
+where
+    E: BlockExecutorProvider,
+    DB: Database,
+{
     fn builder(self) -> StageSetBuilder<DB> {
         StageSetBuilder::default()
             .add_stage(TransactionLookupStage::new(
@@ -397,5 +408,23 @@ impl<DB: Database> StageSet<DB> for HistoryIndexingStages {
                 self.stages_config.etl.clone(),
                 self.prune_modes.storage_history,
             ))
+            .add_stage_opt(self.stages_config.index_address_transactions.enabled.then(|| {
+                IndexAddressTransactionsStage::new(
+                    self.executor_factory,
+                    self.stages_config.index_address_transactions,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.address_transactions,
+                )
+            }))
+            .add_stage_opt(self.stages_config.preimages.enabled.then(|| {
+                PreimagesStage::new(self.stages_config.preimages, self.stages_config.etl.clone())
+            }))
+            .add_stage_opt(self.stages_config.index_logs.enabled.then(|| {
+                IndexLogsStage::new(
+                    self.stages_config.index_logs,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.log_index(),
+                )
+            }))
     }
 }
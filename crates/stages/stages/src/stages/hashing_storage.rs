@@ -85,7 +85,11 @@ impl<DB: Database> Stage<DB> for StorageHashingStage {
         // account otherwise take changesets aggregate the sets and apply hashing to
         // AccountHashing table. Also, if we start from genesis, we need to hash from scratch, as
         // genesis accounts are not in changeset, along with their storages.
-        if to_block - from_block > self.clean_threshold || from_block == 1 {
+        // The plain state of a snap synced database lacks the state of the pivot block, so its
+        // hashed state is only ever updated incrementally.
+        if (to_block - from_block > self.clean_threshold || from_block == 1) &&
+            !provider.is_snap_synced()?
+        {
             // clear table, load all accounts and hash it
             tx.clear::<tables::HashedStorages>()?;
 
@@ -0,0 +1,571 @@
+use super::load_history_indices;
+use reth_config::config::{EtlConfig, IndexAddressTransactionsConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{
+    cursor::DbCursorRW, database::Database, models::ShardedKey, table::Decode,
+    transaction::DbTxMut,
+};
+use reth_etl::Collector;
+use reth_evm::{execute::BlockExecutorProvider, noop::NoopBlockExecutorProvider};
+use reth_primitives::{Address, BlockNumber, TxNumber};
+use reth_provider::{
+    BlockReader, DatabaseProviderRW, HeaderProvider, HistoricalStateProviderRef, HistoryWriter,
+    PruneCheckpointReader, PruneCheckpointWriter, TransactionVariant,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_revm::database::StateProviderDatabase;
+use reth_stages_api::{
+    BlockErrorKind, CheckpointBlockRange, EntitiesCheckpoint, ExecInput, ExecOutput,
+    IndexHistoryCheckpoint, Stage, StageCheckpoint, StageError, StageId, UnwindInput,
+    UnwindOutput,
+};
+use reth_storage_errors::provider::ProviderError;
+use std::{collections::HashMap, ops::RangeInclusive};
+use tracing::info;
+
+/// The address transactions index stage.
+///
+/// This stage walks over existing transactions and indexes the number of each transaction under
+/// every address it touches: the sender, the recipient or created contract, the emitters and
+/// address-like topics of its logs, and the participants of its internal calls. The indices are
+/// written to [`tables::AddressTransactions`] and are used to search the transactions of an
+/// address, e.g. by the `ots_` namespace and `trace_filter`. The addresses of every transaction
+/// are also written to [`tables::TransactionAddresses`] to unwind and prune the indices.
+///
+/// The internal calls are found by re-executing the transactions on top of their historical
+/// state, so they are only indexed if the executor can trace calls and the account and storage
+/// history of the block hasn't been pruned. The range of blocks with indexed calls is kept in the
+/// [`IndexHistoryCheckpoint`] of the stage. System transactions that are executed outside the
+/// inspected EVM, such as the BSC system transactions, have no indexed internal calls.
+///
+/// The stage is optional and only part of the pipeline if enabled in the
+/// [`IndexAddressTransactionsConfig`].
+#[derive(Debug)]
+pub struct IndexAddressTransactionsStage<E = NoopBlockExecutorProvider> {
+    /// The maximum number of transactions to process before flushing the collected indices to
+    /// [`reth_etl::Collector`].
+    pub chunk_size: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+    /// The executor provider used to trace the internal calls of the transactions.
+    pub executor_provider: E,
+}
+
+impl<E> IndexAddressTransactionsStage<E> {
+    /// Create new instance of [`IndexAddressTransactionsStage`].
+    pub const fn new(
+        executor_provider: E,
+        config: IndexAddressTransactionsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { chunk_size: config.chunk_size, etl_config, prune_mode, executor_provider }
+    }
+}
+
+impl Default for IndexAddressTransactionsStage {
+    fn default() -> Self {
+        Self {
+            chunk_size: 500_000,
+            prune_mode: None,
+            etl_config: EtlConfig::default(),
+            executor_provider: NoopBlockExecutorProvider::default(),
+        }
+    }
+}
+
+impl<E: BlockExecutorProvider> IndexAddressTransactionsStage<E> {
+    /// Re-executes the transactions of the given blocks and returns the addresses reached by
+    /// their calls, together with the number of the transaction.
+    ///
+    /// Returns `None` if the executor can't trace calls.
+    fn call_addresses<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<Option<Vec<(Address, TxNumber)>>, StageError> {
+        let mut addresses = Vec::new();
+        for block_number in range {
+            let body = provider
+                .block_body_indices(block_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+            if body.tx_count == 0 {
+                continue
+            }
+
+            let td = provider
+                .header_td_by_number(block_number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+            let block = provider
+                .block_with_senders(block_number.into(), TransactionVariant::NoHash)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+
+            let db = StateProviderDatabase::new(HistoricalStateProviderRef::new(
+                provider.tx_ref(),
+                block_number,
+                provider.static_file_provider().clone(),
+            ));
+            let Some(tx_addresses) = self
+                .executor_provider
+                .call_addresses(db, (&block, td, None).into())
+                .map_err(|error| StageError::Block {
+                    block: Box::new(block.header.clone().seal_slow()),
+                    error: BlockErrorKind::Execution(error),
+                })?
+            else {
+                return Ok(None)
+            };
+
+            for (tx_number, tx_addresses) in body.tx_num_range().zip(tx_addresses) {
+                addresses.extend(tx_addresses.into_iter().map(|address| (address, tx_number)));
+            }
+        }
+
+        Ok(Some(addresses))
+    }
+}
+
+/// Returns the lowest block whose transactions can be re-executed, i.e. whose state is still
+/// available in the account and storage history.
+fn lowest_traceable_block<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+) -> Result<BlockNumber, StageError> {
+    let mut lowest = 0;
+    for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+        if let Some(pruned) =
+            provider.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+        {
+            lowest = lowest.max(pruned + 1);
+        }
+    }
+    Ok(lowest)
+}
+
+/// Returns the checkpoint of the stage at the given block with the range of blocks whose calls
+/// are indexed, if any.
+fn calls_checkpoint(
+    block_number: BlockNumber,
+    calls: Option<CheckpointBlockRange>,
+) -> StageCheckpoint {
+    let checkpoint = StageCheckpoint::new(block_number);
+    match calls {
+        Some(block_range) => {
+            let blocks = block_range.to - block_range.from + 1;
+            checkpoint.with_index_history_stage_checkpoint(IndexHistoryCheckpoint {
+                block_range,
+                progress: EntitiesCheckpoint { processed: blocks, total: blocks },
+            })
+        }
+        None => checkpoint,
+    }
+}
+
+impl<E, DB> Stage<DB> for IndexAddressTransactionsStage<E>
+where
+    E: BlockExecutorProvider,
+    DB: Database,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        // the range of blocks whose calls are indexed already
+        let mut previous_calls = input
+            .checkpoint()
+            .index_history_stage_checkpoint()
+            .map(|checkpoint| checkpoint.block_range);
+
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AddressTransactions,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+                previous_calls = None;
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::AddressTransactions)?.is_none() {
+                    let target_prunable_tx_number = provider
+                        .block_body_indices(target_prunable_block)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(target_prunable_block))?
+                        .last_tx_num();
+
+                    provider.save_prune_checkpoint(
+                        PruneSegment::AddressTransactions,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: Some(target_prunable_tx_number),
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(calls_checkpoint(
+                input.checkpoint().block_number,
+                previous_calls,
+            )))
+        }
+
+        // On first sync we clear the tables since it's faster to rebuild from scratch.
+        let first_sync = input.checkpoint().block_number == 0;
+        if first_sync {
+            provider.tx_ref().clear::<tables::AddressTransactions>()?;
+            provider.tx_ref().clear::<tables::TransactionAddresses>()?;
+            previous_calls = None;
+        }
+
+        info!(
+            target: "sync::stages::index_address_transactions",
+            block_range = ?input.checkpoint().block_number..=input.target(),
+            ?first_sync,
+            "Collecting indices"
+        );
+
+        let mut collector: Collector<ShardedKey<Address>, BlockNumberList> =
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        let mut addresses_cursor =
+            provider.tx_ref().cursor_dup_write::<tables::TransactionAddresses>()?;
+
+        let lowest_traceable_block = lowest_traceable_block(provider)?;
+        let mut calls: Option<CheckpointBlockRange> = None;
+        let mut trace_calls = true;
+
+        loop {
+            let (tx_range, block_range, is_final_range) =
+                input.next_block_range_with_transaction_threshold(provider, self.chunk_size)?;
+
+            info!(target: "sync::stages::index_address_transactions", ?tx_range, "Collecting transaction addresses");
+
+            let mut addresses = provider.transaction_addresses(tx_range)?;
+
+            let traceable_range =
+                (*block_range.start()).max(lowest_traceable_block)..=*block_range.end();
+            if trace_calls && !traceable_range.is_empty() {
+                match self.call_addresses(provider, traceable_range.clone())? {
+                    Some(call_addresses) => {
+                        addresses.extend(call_addresses);
+                        calls = Some(CheckpointBlockRange {
+                            from: calls.map_or(*traceable_range.start(), |calls| calls.from),
+                            to: *traceable_range.end(),
+                        });
+                    }
+                    None => {
+                        calls = None;
+                        trace_calls = false;
+                    }
+                }
+            }
+
+            addresses.sort_unstable_by_key(|(address, tx_number)| (*tx_number, *address));
+            addresses.dedup();
+
+            let mut cache: HashMap<Address, Vec<u64>> = HashMap::new();
+            for (address, tx_number) in addresses {
+                addresses_cursor.upsert(tx_number, address)?;
+                cache.entry(address).or_default().push(tx_number);
+            }
+            for (address, tx_numbers) in cache {
+                let last = *tx_numbers.last().expect("qed");
+                collector.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(tx_numbers),
+                )?;
+            }
+
+            input.checkpoint = Some(StageCheckpoint::new(*block_range.end()));
+
+            if is_final_range {
+                break
+            }
+        }
+
+        info!(target: "sync::stages::index_address_transactions", "Loading indices into database");
+        load_history_indices::<_, tables::AddressTransactions, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode,
+            |key| key.key,
+        )?;
+
+        // extend the range of blocks with indexed calls if this run continues it
+        let calls = match (previous_calls, calls) {
+            (Some(previous), Some(calls)) if previous.to + 1 == calls.from => {
+                Some(CheckpointBlockRange { from: previous.from, to: calls.to })
+            }
+            (_, Some(calls)) => Some(calls),
+            (previous, None) => previous,
+        };
+
+        Ok(ExecOutput { checkpoint: calls_checkpoint(input.target(), calls), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) = input.unwind_block_range_with_threshold(self.chunk_size);
+
+        provider.unwind_address_transaction_indices(range)?;
+
+        let calls = input
+            .checkpoint
+            .index_history_stage_checkpoint()
+            .map(|checkpoint| checkpoint.block_range)
+            .filter(|calls| calls.from <= unwind_progress)
+            .map(|calls| CheckpointBlockRange {
+                from: calls.from,
+                to: calls.to.min(unwind_progress),
+            });
+
+        Ok(UnwindOutput { checkpoint: calls_checkpoint(unwind_progress, calls) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use reth_evm_ethereum::execute::EthExecutorProvider;
+    use reth_primitives::{
+        keccak256, Account, Bytecode, Bytes, Header, Log, SealedBlock, StorageEntry, Transaction,
+        TxKind, TxLegacy, B256, U256,
+    };
+    use reth_testing_utils::{
+        generators,
+        generators::{random_block_range, random_receipt, sign_tx_with_random_key_pair},
+    };
+    use std::collections::BTreeMap;
+
+    fn expected_index(
+        blocks: &[reth_primitives::SealedBlock],
+        up_to: u64,
+    ) -> BTreeMap<Address, Vec<u64>> {
+        let mut index = BTreeMap::<Address, Vec<u64>>::new();
+        let mut tx_number = 0;
+        for block in blocks.iter().take_while(|block| block.number <= up_to) {
+            for transaction in &block.body {
+                let sender = transaction.recover_signer().unwrap();
+                let mut addresses = vec![sender];
+                if transaction.kind().is_create() {
+                    addresses.push(sender.create(transaction.nonce()));
+                }
+                addresses.extend(transaction.to());
+                addresses.sort_unstable();
+                addresses.dedup();
+                for address in addresses {
+                    index.entry(address).or_default().push(tx_number);
+                }
+                tx_number += 1;
+            }
+        }
+        index
+    }
+
+    fn table(db: &TestStageDB) -> BTreeMap<Address, Vec<u64>> {
+        db.table::<tables::AddressTransactions>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| {
+                assert_eq!(key.highest_block_number, u64::MAX);
+                (key.key, list.iter().collect())
+            })
+            .collect()
+    }
+
+    /// Returns the index as recorded in [`tables::TransactionAddresses`].
+    fn transaction_addresses_table(db: &TestStageDB) -> BTreeMap<Address, Vec<u64>> {
+        let mut index = BTreeMap::<Address, Vec<u64>>::new();
+        for (tx_number, address) in db.table::<tables::TransactionAddresses>().unwrap() {
+            index.entry(address).or_default().push(tx_number);
+        }
+        index
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=20, B256::ZERO, 0..4);
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("insert blocks");
+
+        // execute in two runs to exercise merging with the existing shards
+        let mut stage = IndexAddressTransactionsStage { chunk_size: 5, ..Default::default() };
+        for (checkpoint, target) in [(None, 10), (Some(10), 20)] {
+            let provider = db.factory.provider_rw().unwrap();
+            let input = ExecInput {
+                target: Some(target),
+                checkpoint: checkpoint.map(StageCheckpoint::new),
+            };
+            let out = stage.execute(&provider, input).unwrap();
+            assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(target), done: true });
+            provider.commit().unwrap();
+        }
+        assert_eq!(table(&db), expected_index(&blocks, 20));
+        assert_eq!(transaction_addresses_table(&db), expected_index(&blocks, 20));
+
+        // unwind
+        let provider = db.factory.provider_rw().unwrap();
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(20),
+            unwind_to: 12,
+            ..Default::default()
+        };
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(12) });
+        provider.commit().unwrap();
+        assert_eq!(table(&db), expected_index(&blocks, 12));
+        assert_eq!(transaction_addresses_table(&db), expected_index(&blocks, 12));
+    }
+
+    #[test]
+    fn execute_indexes_log_addresses() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=5, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // every transaction emits a log of the same contract that mentions the same account in a
+        // topic, neither of them is the sender or recipient of any transaction
+        let emitter = Address::random();
+        let account = Address::random();
+        let mut receipts = Vec::new();
+        for transaction in blocks.iter().flat_map(|block| &block.body) {
+            let mut receipt = random_receipt(&mut rng, transaction, Some(0));
+            receipt.logs.push(Log::new_unchecked(
+                emitter,
+                vec![B256::random(), account.into_word()],
+                Default::default(),
+            ));
+            receipts.push((receipts.len() as u64, receipt));
+        }
+        let tx_numbers = (0..receipts.len() as u64).collect::<Vec<_>>();
+        db.insert_receipts(receipts).expect("insert receipts");
+
+        let mut stage = IndexAddressTransactionsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(5), checkpoint: None };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(5), done: true });
+        provider.commit().unwrap();
+
+        let index = table(&db);
+        assert_eq!(index.get(&emitter), Some(&tx_numbers));
+        assert_eq!(index.get(&account), Some(&tx_numbers));
+    }
+
+    #[test]
+    fn execute_indexes_call_addresses() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        // a contract that calls another account
+        let contract = Address::with_last_byte(0xc0);
+        let callee = Address::with_last_byte(0xca);
+        let code: Bytes = [
+            &[0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x73][..],
+            &callee[..],
+            &[0x5a, 0xf1, 0x00][..],
+        ]
+        .concat()
+        .into();
+
+        let transaction = sign_tx_with_random_key_pair(
+            &mut rng,
+            Transaction::Legacy(TxLegacy {
+                chain_id: None,
+                nonce: 0,
+                gas_price: 0,
+                gas_limit: 100_000,
+                to: TxKind::Call(contract),
+                value: U256::ZERO,
+                input: Bytes::new(),
+            }),
+        );
+        let sender = transaction.recover_signer().unwrap();
+
+        let genesis = SealedBlock { header: Header::default().seal_slow(), ..Default::default() };
+        let block = SealedBlock {
+            header: Header {
+                parent_hash: genesis.hash(),
+                number: 1,
+                gas_limit: 1_000_000,
+                ..Default::default()
+            }
+            .seal_slow(),
+            body: vec![transaction],
+            ..Default::default()
+        };
+        db.insert_blocks([&genesis, &block], StorageKind::Database(None)).expect("insert blocks");
+        db.insert_transaction_senders([(0, sender)]).expect("insert senders");
+
+        // the accounts exist since genesis
+        let contract_account =
+            Account { nonce: 1, balance: U256::ZERO, bytecode_hash: Some(keccak256(&code)) };
+        db.insert_accounts_and_storages([
+            (sender, (Account::default(), Vec::<StorageEntry>::new())),
+            (contract, (contract_account, Vec::new())),
+        ])
+        .expect("insert accounts");
+        db.commit(|tx| {
+            tx.put::<tables::Bytecodes>(keccak256(&code), Bytecode::new_raw(code.clone()))?;
+            for address in [sender, contract] {
+                tx.put::<tables::AccountsHistory>(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted([0]),
+                )?;
+            }
+            Ok(())
+        })
+        .expect("insert history");
+
+        let mut stage = IndexAddressTransactionsStage::new(
+            EthExecutorProvider::mainnet(),
+            IndexAddressTransactionsConfig::default(),
+            EtlConfig::default(),
+            None,
+        );
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(
+            out.checkpoint
+                .index_history_stage_checkpoint()
+                .map(|checkpoint| checkpoint.block_range),
+            Some(CheckpointBlockRange { from: 1, to: 1 })
+        );
+        provider.commit().unwrap();
+
+        let index = table(&db);
+        for address in [sender, contract, callee] {
+            assert_eq!(index.get(&address), Some(&vec![0]));
+        }
+        assert_eq!(transaction_addresses_table(&db), index);
+    }
+}
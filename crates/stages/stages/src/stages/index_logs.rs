@@ -0,0 +1,306 @@
+use super::load_history_indices;
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{database::Database, models::ShardedKey, table::Decode, transaction::DbTxMut};
+use reth_etl::Collector;
+use reth_primitives::{Address, B256};
+use reth_provider::{
+    DatabaseProviderRW, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use tracing::info;
+
+/// The log index stage.
+///
+/// This stage walks over the receipts of existing blocks and indexes the number of each block
+/// under the emitters and the topics of its logs. The indices are written to
+/// [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`] and are used to find the blocks with
+/// matching logs in `eth_getLogs`, instead of checking the bloom filter of every header.
+///
+/// The stage is optional and only part of the pipeline if enabled in the [`IndexLogsConfig`].
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// The maximum number of transactions to process before flushing the collected indices to
+    /// [`reth_etl::Collector`].
+    pub chunk_size: u64,
+    /// Pruning configuration, see [`PruneModes::log_index`](reth_prune_types::PruneModes).
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(
+        config: IndexLogsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { chunk_size: config.chunk_size, etl_config, prune_mode }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { chunk_size: 500_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexLogsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(input.target(), PruneSegment::LogIndex, PrunePurpose::User)
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::LogIndex)?.is_none() {
+                    provider.save_prune_checkpoint(
+                        PruneSegment::LogIndex,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: None,
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        // On first sync we clear the tables since it's faster to rebuild from scratch.
+        let first_sync = input.checkpoint().block_number == 0;
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            provider.tx_ref().clear::<tables::LogTopicIndex>()?;
+        }
+
+        info!(
+            target: "sync::stages::index_logs",
+            block_range = ?input.checkpoint().block_number..=input.target(),
+            ?first_sync,
+            "Collecting indices"
+        );
+
+        let mut address_collector: Collector<ShardedKey<Address>, BlockNumberList> =
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+        let mut topic_collector: Collector<ShardedKey<B256>, BlockNumberList> =
+            Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+
+        loop {
+            let (_, block_range, is_final_range) =
+                input.next_block_range_with_transaction_threshold(provider, self.chunk_size)?;
+
+            info!(target: "sync::stages::index_logs", ?block_range, "Collecting log addresses and topics");
+
+            let (addresses, topics) = provider.block_log_keys(block_range.clone())?;
+            for (address, block_numbers) in addresses {
+                let last = *block_numbers.last().expect("qed");
+                address_collector.insert(
+                    ShardedKey::new(address, last),
+                    BlockNumberList::new_pre_sorted(block_numbers),
+                )?;
+            }
+            for (topic, block_numbers) in topics {
+                let last = *block_numbers.last().expect("qed");
+                topic_collector.insert(
+                    ShardedKey::new(topic, last),
+                    BlockNumberList::new_pre_sorted(block_numbers),
+                )?;
+            }
+
+            input.checkpoint = Some(StageCheckpoint::new(*block_range.end()));
+
+            if is_final_range {
+                break
+            }
+        }
+
+        info!(target: "sync::stages::index_logs", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider,
+            address_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicIndex, _>(
+            provider,
+            topic_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(input.target()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) = input.unwind_block_range_with_threshold(self.chunk_size);
+
+        provider.unwind_log_indices(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use reth_primitives::{Receipt, SealedBlock};
+    use reth_provider::{LogIndexReader, StageCheckpointWriter};
+    use reth_testing_utils::{
+        generators,
+        generators::{random_block_range, random_receipt},
+    };
+    use std::collections::BTreeMap;
+
+    fn expected_index(
+        blocks: &[SealedBlock],
+        receipts: &[Vec<Receipt>],
+        up_to: u64,
+    ) -> (BTreeMap<Address, Vec<u64>>, BTreeMap<B256, Vec<u64>>) {
+        let mut addresses = BTreeMap::<Address, Vec<u64>>::new();
+        let mut topics = BTreeMap::<B256, Vec<u64>>::new();
+        for (block, receipts) in blocks.iter().zip(receipts) {
+            if block.number > up_to {
+                break
+            }
+            for log in receipts.iter().flat_map(|receipt| &receipt.logs) {
+                let list = addresses.entry(log.address).or_default();
+                if list.last() != Some(&block.number) {
+                    list.push(block.number);
+                }
+                for topic in log.topics() {
+                    let list = topics.entry(*topic).or_default();
+                    if list.last() != Some(&block.number) {
+                        list.push(block.number);
+                    }
+                }
+            }
+        }
+        (addresses, topics)
+    }
+
+    fn tables(db: &TestStageDB) -> (BTreeMap<Address, Vec<u64>>, BTreeMap<B256, Vec<u64>>) {
+        let addresses = db
+            .table::<tables::LogAddressIndex>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| {
+                assert_eq!(key.highest_block_number, u64::MAX);
+                (key.key, list.iter().collect())
+            })
+            .collect();
+        let topics = db
+            .table::<tables::LogTopicIndex>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| {
+                assert_eq!(key.highest_block_number, u64::MAX);
+                (key.key, list.iter().collect())
+            })
+            .collect();
+        (addresses, topics)
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=20, B256::ZERO, 0..4);
+        db.insert_blocks(blocks.iter(), StorageKind::Static).expect("insert blocks");
+
+        let receipts = blocks
+            .iter()
+            .map(|block| {
+                block
+                    .body
+                    .iter()
+                    .map(|transaction| random_receipt(&mut rng, transaction, Some(3)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        db.insert_receipts(
+            receipts
+                .iter()
+                .flatten()
+                .cloned()
+                .enumerate()
+                .map(|(tx, receipt)| (tx as u64, receipt)),
+        )
+        .expect("insert receipts");
+
+        // execute in two runs to exercise merging with the existing shards
+        let mut stage = IndexLogsStage { chunk_size: 5, ..Default::default() };
+        for (checkpoint, target) in [(None, 10), (Some(10), 20)] {
+            let provider = db.factory.provider_rw().unwrap();
+            let input = ExecInput {
+                target: Some(target),
+                checkpoint: checkpoint.map(StageCheckpoint::new),
+            };
+            let out = stage.execute(&provider, input).unwrap();
+            assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(target), done: true });
+            provider.save_stage_checkpoint(StageId::IndexLogs, out.checkpoint).unwrap();
+            provider.commit().unwrap();
+        }
+        let expected = expected_index(&blocks, &receipts, 20);
+        assert_eq!(tables(&db), expected);
+
+        // the reader returns the blocks of a key within the requested range
+        let provider = db.factory.provider().unwrap();
+        assert_eq!(provider.log_index_range().unwrap(), Some(0..=20));
+        if let Some((address, block_numbers)) = expected.0.iter().next() {
+            let range = 5..=15;
+            assert_eq!(
+                provider.log_address_blocks(*address, range.clone()).unwrap(),
+                block_numbers.iter().copied().filter(|n| range.contains(n)).collect::<Vec<_>>()
+            );
+        }
+        drop(provider);
+
+        // unwind
+        let provider = db.factory.provider_rw().unwrap();
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(20),
+            unwind_to: 12,
+            ..Default::default()
+        };
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(12) });
+        provider.commit().unwrap();
+        assert_eq!(tables(&db), expected_index(&blocks, &receipts, 12));
+    }
+}
@@ -12,13 +12,21 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index transactions by address
+mod index_address_transactions;
+/// Index blocks by log address and topic
+mod index_logs;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
 mod merkle;
+/// Record the preimages of hashed state keys
+mod preimages;
 mod prune;
 /// The sender recovery stage.
 mod sender_recovery;
+/// The snap sync stage.
+mod snap_sync;
 /// The transaction lookup stage
 mod tx_lookup;
 
@@ -29,10 +37,14 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_address_transactions::*;
+pub use index_logs::*;
 pub use index_storage_history::*;
 pub use merkle::*;
+pub use preimages::*;
 pub use prune::*;
 pub use sender_recovery::*;
+pub use snap_sync::*;
 pub use tx_lookup::*;
 
 mod utils;
@@ -0,0 +1,196 @@
+use reth_config::config::{EtlConfig, PreimagesConfig};
+use reth_db::{tables, RawKey, RawTable, RawValue};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO},
+    database::Database,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_etl::Collector;
+use reth_primitives::{keccak256, Bytes, B256};
+use reth_provider::{DatabaseProviderRW, PreimageWriter};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use tracing::info;
+
+/// The preimages stage.
+///
+/// This stage records the preimages of the keys of the hashed state tables, i.e. the addresses of
+/// accounts and the storage slots, in [`tables::Preimages`]. On first sync the preimages are
+/// collected from the plain state, afterwards from the changesets of the executed blocks. The
+/// preimages are used to serve `debug_preimage` and to key state dumps by plain address.
+///
+/// The stage is optional and only part of the pipeline if enabled in the [`PreimagesConfig`].
+#[derive(Debug)]
+pub struct PreimagesStage {
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl PreimagesStage {
+    /// Create new instance of [`PreimagesStage`].
+    pub const fn new(config: PreimagesConfig, etl_config: EtlConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config }
+    }
+}
+
+impl Default for PreimagesStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<DB: Database> Stage<DB> for PreimagesStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::Preimages
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        // On first sync the plain state holds all keys up to the target, and the changesets of
+        // the genesis state do not exist, so the preimages are collected from the plain state.
+        if input.checkpoint().block_number == 0 {
+            let tx = provider.tx_ref();
+            tx.clear::<tables::Preimages>()?;
+
+            info!(target: "sync::stages::preimages", target = input.target(), "Collecting preimages from plain state");
+
+            let mut collector: Collector<B256, Bytes> =
+                Collector::new(self.etl_config.file_size, self.etl_config.dir.clone());
+            for entry in tx.cursor_read::<tables::PlainAccountState>()?.walk(None)? {
+                let (address, _) = entry?;
+                collector.insert(keccak256(address), Bytes::copy_from_slice(address.as_slice()))?;
+            }
+            for entry in tx.cursor_dup_read::<tables::PlainStorageState>()?.walk(None)? {
+                let (_, storage_entry) = entry?;
+                collector.insert(
+                    keccak256(storage_entry.key),
+                    Bytes::copy_from_slice(storage_entry.key.as_slice()),
+                )?;
+            }
+
+            info!(target: "sync::stages::preimages", preimages = collector.len(), "Loading preimages into database");
+
+            let mut cursor = tx.cursor_write::<RawTable<tables::Preimages>>()?;
+            let mut last_hash = None;
+            for item in collector.iter()? {
+                let (hash, preimage) = item?;
+                // the same storage slot is used by many accounts
+                if last_hash.as_ref() == Some(&hash) {
+                    continue
+                }
+                cursor.append(
+                    RawKey::<B256>::from_vec(hash.clone()),
+                    RawValue::<Bytes>::from_vec(preimage),
+                )?;
+                last_hash = Some(hash);
+            }
+
+            return Ok(ExecOutput::done(StageCheckpoint::new(input.target())))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+        let recorded = provider.insert_changed_preimages(range.clone())?;
+        info!(target: "sync::stages::preimages", ?range, recorded, "Recorded preimages");
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    ///
+    /// Preimages are content-addressed and stay valid, so they are kept.
+    fn unwind(
+        &mut self,
+        _provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use reth_primitives::{Account, Address, StorageEntry, U256};
+    use std::collections::BTreeMap;
+
+    fn expected(preimages: &[Bytes]) -> BTreeMap<B256, Bytes> {
+        preimages.iter().map(|preimage| (keccak256(preimage), preimage.clone())).collect()
+    }
+
+    fn table(db: &TestStageDB) -> BTreeMap<B256, Bytes> {
+        db.table::<tables::Preimages>().unwrap().into_iter().collect()
+    }
+
+    #[test]
+    fn execute_and_unwind() {
+        let db = TestStageDB::default();
+
+        // two accounts sharing a storage slot
+        let slot = B256::with_last_byte(1);
+        let storage = vec![StorageEntry { key: slot, value: U256::from(1) }];
+        let (first, second) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        db.insert_accounts_and_storages([
+            (first, (Account::default(), storage.clone())),
+            (second, (Account::default(), storage)),
+        ])
+        .unwrap();
+
+        let mut stage = PreimagesStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(5), checkpoint: None };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(5), done: true });
+        provider.commit().unwrap();
+
+        let mut preimages = vec![
+            Bytes::copy_from_slice(first.as_slice()),
+            Bytes::copy_from_slice(second.as_slice()),
+            Bytes::copy_from_slice(slot.as_slice()),
+        ];
+        assert_eq!(table(&db), expected(&preimages));
+
+        // incrementally record the keys changed by the next block
+        let (third, other_slot) = (Address::with_last_byte(3), B256::with_last_byte(2));
+        db.insert_changesets(
+            [vec![(
+                third,
+                Account::default(),
+                vec![StorageEntry { key: other_slot, value: U256::ZERO }],
+            )]],
+            Some(6),
+        )
+        .unwrap();
+
+        let provider = db.factory.provider_rw().unwrap();
+        let input = ExecInput { target: Some(6), checkpoint: Some(StageCheckpoint::new(5)) };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(6), done: true });
+        provider.commit().unwrap();
+
+        preimages.push(Bytes::copy_from_slice(third.as_slice()));
+        preimages.push(Bytes::copy_from_slice(other_slot.as_slice()));
+        assert_eq!(table(&db), expected(&preimages));
+
+        // unwinding keeps the preimages
+        let provider = db.factory.provider_rw().unwrap();
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(6), unwind_to: 5, ..Default::default() };
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(5) });
+        provider.commit().unwrap();
+        assert_eq!(table(&db), expected(&preimages));
+    }
+}
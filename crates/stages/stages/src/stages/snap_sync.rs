@@ -0,0 +1,1675 @@
+use alloy_rlp::{Decodable, Header};
+use futures_util::{stream::FuturesUnordered, FutureExt, StreamExt};
+use reth_codecs::Compact;
+use reth_config::config::SnapSyncConfig;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
+    database::Database,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_eth_wire_types::{AccountRange, ByteCodes, StorageRanges, TrieNodes};
+use reth_network_p2p::{
+    error::{RequestError, RequestResult},
+    snap::client::{SnapClient, SnapFut},
+};
+use reth_network_peers::PeerId;
+use reth_primitives::{
+    constants::EMPTY_ROOT_HASH, keccak256, Account, BlockNumber, Bytecode, Bytes, StorageEntry,
+    B256, KECCAK_EMPTY, U256,
+};
+use reth_provider::{
+    BlockReader, DatabaseProviderRW, HeaderProvider, ProviderError, PruneCheckpointWriter,
+    StageCheckpointReader, StageCheckpointWriter,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PruneModes, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, SnapSyncCheckpoint, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
+};
+use reth_trie::{prefix_set::TriePrefixSetsMut, proof::Proof, Nibbles, StateRoot, TrieAccount};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+use tokio::time::Sleep;
+use tracing::*;
+
+/// The maximum size of a response requested from a peer.
+const RESPONSE_BYTES: u64 = 512 * 1024;
+
+/// The maximum number of accounts to request the storage of at once.
+const STORAGE_BATCH_SIZE: usize = 64;
+
+/// The maximum number of bytecodes to request at once.
+const BYTECODE_BATCH_SIZE: usize = 64;
+
+/// The maximum number of trie nodes to request at once.
+const TRIE_NODE_BATCH_SIZE: usize = 128;
+
+/// The number of queued storage downloads above which no further accounts are requested.
+const MAX_QUEUED_STORAGE_TASKS: usize = 10_000;
+
+/// The interval to wait for idle peers at, if there are requests to send.
+const PEER_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The stages that are skipped up to the pivot block once the state was downloaded.
+const HANDOFF_STAGES: [StageId; 7] = [
+    StageId::SenderRecovery,
+    StageId::Execution,
+    StageId::AccountHashing,
+    StageId::StorageHashing,
+    StageId::MerkleExecute,
+    StageId::IndexAccountHistory,
+    StageId::IndexStorageHistory,
+];
+
+/// The errors of the [`SnapSyncStage`].
+#[derive(Debug, thiserror::Error)]
+pub enum SnapSyncError {
+    /// The state below the pivot block was never executed, so it can't be unwound to.
+    #[error("cannot unwind to block {unwind_to}, the state was snap synced at block {pivot}")]
+    UnwindBelowPivot {
+        /// The block to unwind to.
+        unwind_to: BlockNumber,
+        /// The pivot block of the download.
+        pivot: BlockNumber,
+    },
+}
+
+/// The snap sync stage downloads the state of a recent pivot block over the `snap` protocol,
+/// instead of executing all blocks from genesis.
+///
+/// The stage runs after the bodies stage, if enabled, and picks the pivot block at
+/// [`SnapSyncConfig::pivot_distance`] blocks below the sync target. It then:
+///
+/// - downloads the accounts of the pivot state in ranges, along with the storage and bytecode of
+///   the contracts, into the hashed state tables,
+/// - rebuilds the trie and compares its root with the state root of the pivot block,
+/// - heals the differences by downloading the trie nodes that don't match the local trie, until the
+///   roots match,
+/// - and hands off to the pipeline by moving the checkpoints of the execution related stages to the
+///   pivot block, so they only process the blocks after it.
+///
+/// The range proofs of the responses are not verified. A peer serving a wrong range only causes
+/// the affected part of the trie to be healed, since healing verifies every node against its hash
+/// and the download is only complete once the root matches the pivot block.
+///
+/// If all peers stop serving the pivot state and a newer pivot block is available, the stage ends
+/// the pipeline run without progress, so the next run continues the download at a new pivot. The
+/// downloaded state is kept and healed to the new pivot.
+///
+/// Once the download completes, the state below the pivot block is not available: the history is
+/// marked as pruned up to the pivot block, and the plain state only contains the state changed
+/// after it, see [`DatabaseProvider::is_snap_synced`](reth_provider::DatabaseProvider).
+#[derive(Debug)]
+pub struct SnapSyncStage<C> {
+    /// The client sending the `snap` requests.
+    client: C,
+    /// The configuration of the stage.
+    config: SnapSyncConfig,
+    /// The configured prune modes, used for the history that is missing below the pivot block.
+    prune_modes: PruneModes,
+    /// The enabled optional stages, that are skipped up to the pivot block along with
+    /// [`HANDOFF_STAGES`].
+    optional_stages: Vec<StageId>,
+    /// The state of the ongoing download, `None` until the stage executes.
+    sync: Option<SnapSyncState>,
+    /// The requests sent to the peers.
+    in_flight: FuturesUnordered<SnapRequestFut>,
+    /// The responses to apply on the next execution.
+    responses: Vec<(PeerId, SnapRequest, RequestResult<SnapResponse>)>,
+    /// The timer to check for idle peers again.
+    retry: Option<Pin<Box<Sleep>>>,
+}
+
+impl<C> SnapSyncStage<C> {
+    /// Creates the stage sending the requests with the given client.
+    pub fn new(client: C, config: SnapSyncConfig, prune_modes: PruneModes) -> Self {
+        Self {
+            client,
+            config,
+            prune_modes,
+            optional_stages: Vec::new(),
+            sync: None,
+            in_flight: FuturesUnordered::new(),
+            responses: Vec::new(),
+            retry: None,
+        }
+    }
+
+    /// Sets the enabled optional stages, which are skipped up to the pivot block as well.
+    pub fn with_optional_stages(mut self, stages: impl IntoIterator<Item = StageId>) -> Self {
+        self.optional_stages = stages.into_iter().collect();
+        self
+    }
+
+    /// Gets the progress of the download.
+    pub fn get_checkpoint<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+    ) -> Result<Option<SnapSyncCheckpoint>, StageError> {
+        let buf = provider.get_stage_checkpoint_progress(StageId::SnapSync)?.unwrap_or_default();
+
+        if buf.is_empty() {
+            return Ok(None)
+        }
+
+        let (checkpoint, _) = SnapSyncCheckpoint::from_compact(&buf, buf.len());
+        Ok(Some(checkpoint))
+    }
+
+    /// Saves the progress of the download.
+    pub fn save_checkpoint<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        checkpoint: SnapSyncCheckpoint,
+    ) -> Result<(), StageError> {
+        let mut buf = vec![];
+        checkpoint.to_compact(&mut buf);
+        Ok(provider.save_stage_checkpoint_progress(StageId::SnapSync, buf)?)
+    }
+
+    /// Drops the state of the download, along with the requests in flight.
+    fn reset(&mut self) {
+        self.sync = None;
+        self.in_flight = FuturesUnordered::new();
+        self.responses.clear();
+        self.retry = None;
+    }
+
+    /// Moves the checkpoints of the execution related stages to the pivot block and marks the
+    /// history below it as pruned.
+    fn hand_off<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        mut checkpoint: SnapSyncCheckpoint,
+    ) -> Result<(), StageError> {
+        let pivot = checkpoint.pivot;
+        for stage_id in HANDOFF_STAGES.iter().chain(&self.optional_stages) {
+            provider.save_stage_checkpoint(*stage_id, StageCheckpoint::new(pivot))?;
+        }
+
+        let last_tx = provider
+            .block_body_indices(pivot)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(pivot))?
+            .last_tx_num();
+        for (segment, prune_mode, tx_number) in [
+            (PruneSegment::AccountHistory, self.prune_modes.account_history, None),
+            (PruneSegment::StorageHistory, self.prune_modes.storage_history, None),
+            (PruneSegment::Receipts, self.prune_modes.receipts, Some(last_tx)),
+        ] {
+            provider.save_prune_checkpoint(
+                segment,
+                PruneCheckpoint {
+                    block_number: Some(pivot),
+                    tx_number,
+                    prune_mode: prune_mode.unwrap_or(PruneMode::Before(pivot + 1)),
+                },
+            )?;
+        }
+
+        checkpoint.complete = true;
+        self.save_checkpoint(provider, checkpoint)
+    }
+}
+
+impl<C: SnapClient> SnapSyncStage<C> {
+    /// Sends the queued requests to the idle peers that serve the pivot state.
+    fn dispatch(&mut self) {
+        let Some(sync) = &mut self.sync else { return };
+        for peer_id in self.client.snap_peers() {
+            if sync.busy_peers.contains(&peer_id) || sync.stale_peers.contains(&peer_id) {
+                continue
+            }
+            let Some(request) = sync.next_request() else { break };
+            sync.busy_peers.insert(peer_id);
+            self.in_flight.push(send_request(&self.client, peer_id, sync.state_root, request));
+        }
+    }
+}
+
+impl<DB, C> Stage<DB> for SnapSyncStage<C>
+where
+    DB: Database,
+    C: SnapClient + 'static,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::SnapSync
+    }
+
+    fn poll_execute_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+        input: ExecInput,
+    ) -> Poll<Result<(), StageError>> {
+        if input.target_reached() {
+            return Poll::Ready(Ok(()))
+        }
+
+        loop {
+            // the state of the download is only set up on execution
+            let Some(sync) = &self.sync else { return Poll::Ready(Ok(())) };
+            if sync.pivot_stale || !self.responses.is_empty() {
+                return Poll::Ready(Ok(()))
+            }
+
+            self.dispatch();
+            while let Poll::Ready(Some((peer_id, request, result))) =
+                self.in_flight.poll_next_unpin(cx)
+            {
+                if let Some(sync) = &mut self.sync {
+                    sync.busy_peers.remove(&peer_id);
+                }
+                self.responses.push((peer_id, request, result));
+            }
+            if !self.responses.is_empty() {
+                return Poll::Ready(Ok(()))
+            }
+
+            let Some(sync) = &mut self.sync else { return Poll::Ready(Ok(())) };
+            if !sync.has_queued_requests() {
+                // the state is either complete, or the requests in flight are awaited
+                return if self.in_flight.is_empty() { Poll::Ready(Ok(())) } else { Poll::Pending }
+            }
+
+            // there are requests to send, but no idle peer to send them to
+            let retry =
+                self.retry.get_or_insert_with(|| Box::pin(tokio::time::sleep(PEER_RETRY_INTERVAL)));
+            ready!(retry.as_mut().poll(cx));
+            self.retry = None;
+
+            let peers = self.client.snap_peers();
+            if self.in_flight.is_empty() &&
+                !peers.is_empty() &&
+                peers.iter().all(|peer_id| sync.stale_peers.contains(peer_id))
+            {
+                debug!(
+                    target: "sync::stages::snap_sync",
+                    pivot = sync.checkpoint.pivot,
+                    "No peer serves the pivot state"
+                );
+                sync.pivot_stale = true;
+            }
+        }
+    }
+
+    /// Download the state of the pivot block, and apply the received responses.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+        let target = input.target();
+
+        let checkpoint = self.get_checkpoint(provider)?;
+        if checkpoint.is_some_and(|checkpoint| checkpoint.complete) {
+            return Ok(ExecOutput::done(StageCheckpoint::new(target)))
+        }
+
+        // Snap sync only starts on a node that hasn't executed any block yet, and if there is a
+        // pivot block far enough from genesis.
+        let executed =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number > 0;
+        if checkpoint.is_none() && (executed || target <= self.config.pivot_distance) {
+            return Ok(ExecOutput::done(StageCheckpoint::new(target)))
+        }
+
+        if self.sync.is_none() {
+            let pivot = target
+                .saturating_sub(self.config.pivot_distance)
+                .max(checkpoint.map(|checkpoint| checkpoint.pivot).unwrap_or_default());
+            if checkpoint.is_none() {
+                // The plain state only holds the genesis state. It is not updated by the download,
+                // so it's cleared to not shadow the downloaded hashed state.
+                provider.tx_ref().clear::<tables::PlainAccountState>()?;
+                provider.tx_ref().clear::<tables::PlainStorageState>()?;
+            }
+            let checkpoint = SnapSyncCheckpoint {
+                pivot,
+                ..checkpoint.unwrap_or_else(|| SnapSyncCheckpoint::new(pivot))
+            };
+            let state_root = provider
+                .header_by_number(pivot)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(pivot.into()))?
+                .state_root;
+
+            info!(
+                target: "sync::stages::snap_sync",
+                pivot,
+                ?state_root,
+                next_account = ?checkpoint.next_account,
+                "Downloading the state of the pivot block"
+            );
+            self.sync = Some(SnapSyncState::new(checkpoint, state_root));
+        }
+        let Some(sync) = &mut self.sync else { unreachable!("the state is set above") };
+
+        let tx = provider.tx_ref();
+        for (peer_id, request, result) in std::mem::take(&mut self.responses) {
+            sync.on_response(tx, peer_id, request, result)?;
+        }
+
+        if sync.pivot_stale {
+            if target.saturating_sub(self.config.pivot_distance) > sync.checkpoint.pivot {
+                info!(
+                    target: "sync::stages::snap_sync",
+                    pivot = sync.checkpoint.pivot,
+                    "Pivot state no longer served, moving the pivot on the next run"
+                );
+                let checkpoint = sync.checkpoint;
+                self.reset();
+                self.save_checkpoint(provider, checkpoint)?;
+                return Ok(ExecOutput::done(input.checkpoint()))
+            }
+            // the peers may not have synced up to the pivot block yet
+            sync.pivot_stale = false;
+            sync.stale_peers.clear();
+        }
+
+        let idle = self.in_flight.is_empty() && !sync.has_queued_requests();
+        if !sync.verified {
+            let state_root = match &mut sync.changes {
+                None if idle && sync.checkpoint.next_account.is_none() => {
+                    info!(
+                        target: "sync::stages::snap_sync",
+                        "Downloaded the state ranges, rebuilding the trie"
+                    );
+                    tx.clear::<tables::AccountsTrie>()?;
+                    tx.clear::<tables::StoragesTrie>()?;
+                    Some(update_trie(tx, TriePrefixSetsMut::default())?)
+                }
+                Some(changes) if idle || !is_empty(changes) => {
+                    Some(update_trie(tx, std::mem::take(changes))?)
+                }
+                _ => None,
+            };
+
+            match state_root {
+                Some(state_root) if state_root == sync.state_root => {
+                    info!(
+                        target: "sync::stages::snap_sync",
+                        ?state_root,
+                        "State root matches the pivot block"
+                    );
+                    sync.verified = true;
+                    sync.storage_tasks.clear();
+                    sync.heal_tasks.clear();
+                    sync.busy_peers.clear();
+                    self.in_flight = FuturesUnordered::new();
+
+                    // the bytecodes of the accounts healed into the state may still be missing
+                    let mut cursor = tx.cursor_read::<tables::HashedAccounts>()?;
+                    for entry in cursor.walk(None)? {
+                        let (_, account) = entry?;
+                        if let Some(code_hash) = account.bytecode_hash {
+                            sync.queue_code(tx, code_hash)?;
+                        }
+                    }
+                }
+                Some(state_root) if idle => {
+                    info!(
+                        target: "sync::stages::snap_sync",
+                        ?state_root,
+                        expected = ?sync.state_root,
+                        "Healing the state"
+                    );
+                    sync.changes.get_or_insert_with(Default::default);
+                    sync.heal_tasks.push_back(HealTask {
+                        account: None,
+                        path: Nibbles::default(),
+                        hash: sync.state_root,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        let checkpoint = sync.checkpoint;
+        if sync.verified && self.in_flight.is_empty() && !sync.has_queued_requests() {
+            info!(
+                target: "sync::stages::snap_sync",
+                pivot = checkpoint.pivot,
+                "Downloaded the state of the pivot block"
+            );
+            self.reset();
+            self.hand_off(provider, checkpoint)?;
+            return Ok(ExecOutput::done(StageCheckpoint::new(target)))
+        }
+
+        self.save_checkpoint(provider, checkpoint)?;
+        Ok(ExecOutput { checkpoint: input.checkpoint(), done: false })
+    }
+
+    /// Unwinding is only possible down to the pivot block of a completed download.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        if let Some(mut checkpoint) = self.get_checkpoint(provider)? {
+            if input.unwind_to < checkpoint.pivot {
+                if checkpoint.complete {
+                    return Err(StageError::Fatal(Box::new(SnapSyncError::UnwindBelowPivot {
+                        unwind_to: input.unwind_to,
+                        pivot: checkpoint.pivot,
+                    })))
+                }
+                // the pivot block is gone, the download continues at a new pivot
+                self.reset();
+                checkpoint.pivot = input.unwind_to;
+                self.save_checkpoint(provider, checkpoint)?;
+            }
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(input.unwind_to) })
+    }
+}
+
+/// The state of an ongoing download.
+#[derive(Debug)]
+struct SnapSyncState {
+    /// The persisted progress of the download.
+    checkpoint: SnapSyncCheckpoint,
+    /// The state root of the pivot block.
+    state_root: B256,
+    /// Whether an account range request is in flight.
+    account_range_in_flight: bool,
+    /// The accounts to download the storage of.
+    storage_tasks: VecDeque<StorageTask>,
+    /// The bytecodes to download.
+    code_tasks: HashSet<B256>,
+    /// The trie nodes to heal.
+    heal_tasks: VecDeque<HealTask>,
+    /// The keys changed since the trie was last updated, `None` until the state is healed.
+    changes: Option<TriePrefixSetsMut>,
+    /// Whether the root of the downloaded state matches the state root of the pivot block.
+    verified: bool,
+    /// The peers with a request in flight.
+    busy_peers: HashSet<PeerId>,
+    /// The peers that don't serve the pivot state.
+    stale_peers: HashSet<PeerId>,
+    /// Whether none of the peers serves the pivot state.
+    pivot_stale: bool,
+}
+
+impl SnapSyncState {
+    fn new(checkpoint: SnapSyncCheckpoint, state_root: B256) -> Self {
+        Self {
+            checkpoint,
+            state_root,
+            account_range_in_flight: false,
+            storage_tasks: VecDeque::new(),
+            code_tasks: HashSet::new(),
+            heal_tasks: VecDeque::new(),
+            changes: None,
+            verified: false,
+            busy_peers: HashSet::new(),
+            stale_peers: HashSet::new(),
+            pivot_stale: false,
+        }
+    }
+
+    /// Returns whether there are requests to send.
+    fn has_queued_requests(&self) -> bool {
+        !self.code_tasks.is_empty() ||
+            !self.storage_tasks.is_empty() ||
+            !self.heal_tasks.is_empty() ||
+            self.next_account_range().is_some()
+    }
+
+    /// Returns the origin of the next account range to request, if any.
+    fn next_account_range(&self) -> Option<B256> {
+        if self.account_range_in_flight ||
+            self.changes.is_some() ||
+            self.storage_tasks.len() >= MAX_QUEUED_STORAGE_TASKS
+        {
+            return None
+        }
+        self.checkpoint.next_account
+    }
+
+    /// Takes the next request to send from the queues.
+    fn next_request(&mut self) -> Option<SnapRequest> {
+        if !self.code_tasks.is_empty() {
+            let hashes = self.code_tasks.iter().take(BYTECODE_BATCH_SIZE).copied().collect();
+            for hash in &hashes {
+                self.code_tasks.remove(hash);
+            }
+            return Some(SnapRequest::ByteCodes(hashes))
+        }
+
+        if let Some(task) = self.storage_tasks.pop_front() {
+            // the origin of a request only applies to the first account, so a partially
+            // downloaded storage is requested alone
+            let mut tasks = vec![task];
+            while task.origin.is_zero() && tasks.len() < STORAGE_BATCH_SIZE {
+                match self.storage_tasks.front() {
+                    Some(next) if next.origin.is_zero() => {
+                        tasks.extend(self.storage_tasks.pop_front())
+                    }
+                    _ => break,
+                }
+            }
+            return Some(SnapRequest::StorageRanges(tasks))
+        }
+
+        if !self.heal_tasks.is_empty() {
+            let len = self.heal_tasks.len().min(TRIE_NODE_BATCH_SIZE);
+            return Some(SnapRequest::TrieNodes(self.heal_tasks.drain(..len).collect()))
+        }
+
+        let origin = self.next_account_range()?;
+        self.account_range_in_flight = true;
+        Some(SnapRequest::AccountRange { origin })
+    }
+
+    /// Queues the bytecode with the given hash for download, if it's missing.
+    fn queue_code<TX: DbTx>(&mut self, tx: &TX, code_hash: B256) -> Result<(), DatabaseError> {
+        if code_hash != KECCAK_EMPTY && tx.get::<tables::Bytecodes>(code_hash)?.is_none() {
+            self.code_tasks.insert(code_hash);
+        }
+        Ok(())
+    }
+
+    /// Puts the request back into the queues, to be sent to another peer.
+    fn requeue(&mut self, request: SnapRequest) {
+        match request {
+            SnapRequest::AccountRange { .. } => self.account_range_in_flight = false,
+            SnapRequest::StorageRanges(tasks) => {
+                for task in tasks.into_iter().rev() {
+                    self.storage_tasks.push_front(task);
+                }
+            }
+            SnapRequest::ByteCodes(hashes) => self.code_tasks.extend(hashes),
+            SnapRequest::TrieNodes(tasks) => {
+                for task in tasks.into_iter().rev() {
+                    self.heal_tasks.push_front(task);
+                }
+            }
+        }
+    }
+
+    /// Applies the response of a peer to the state.
+    fn on_response<TX: DbTx + DbTxMut>(
+        &mut self,
+        tx: &TX,
+        peer_id: PeerId,
+        request: SnapRequest,
+        result: RequestResult<SnapResponse>,
+    ) -> Result<(), StageError> {
+        // once the state root matches, only the missing bytecodes are downloaded
+        if self.verified && !matches!(request, SnapRequest::ByteCodes(_)) {
+            return Ok(())
+        }
+
+        let response = match result {
+            Ok(response) => response,
+            Err(err) => {
+                debug!(target: "sync::stages::snap_sync", %peer_id, %err, "Request failed");
+                if !matches!(err, RequestError::Timeout | RequestError::ConnectionDropped) {
+                    self.stale_peers.insert(peer_id);
+                }
+                self.requeue(request);
+                return Ok(())
+            }
+        };
+
+        let applied = match (request, response) {
+            (SnapRequest::AccountRange { origin }, SnapResponse::AccountRange(range)) => {
+                self.account_range_in_flight = false;
+                self.on_account_range(tx, origin, range)?
+            }
+            (SnapRequest::StorageRanges(tasks), SnapResponse::StorageRanges(ranges)) => {
+                self.on_storage_ranges(tx, tasks, ranges)?
+            }
+            (SnapRequest::ByteCodes(hashes), SnapResponse::ByteCodes(codes)) => {
+                self.on_byte_codes(tx, hashes, codes.codes)?
+            }
+            (SnapRequest::TrieNodes(tasks), SnapResponse::TrieNodes(nodes)) => {
+                self.on_trie_nodes(tx, tasks, nodes.nodes)?
+            }
+            (request, _) => {
+                self.requeue(request);
+                false
+            }
+        };
+
+        // an empty or invalid response means the peer doesn't serve the pivot state
+        if !applied {
+            debug!(
+                target: "sync::stages::snap_sync",
+                %peer_id,
+                "Peer does not serve the pivot state"
+            );
+            self.stale_peers.insert(peer_id);
+        }
+        Ok(())
+    }
+
+    /// Writes the downloaded accounts, replacing the local accounts of the range.
+    ///
+    /// Returns `false` if the response is empty or invalid.
+    fn on_account_range<TX: DbTx + DbTxMut>(
+        &mut self,
+        tx: &TX,
+        origin: B256,
+        range: AccountRange,
+    ) -> Result<bool, StageError> {
+        let mut accounts = Vec::with_capacity(range.accounts.len());
+        let mut previous = None;
+        for data in range.accounts {
+            let (Some(storage_root), Some(code_hash)) =
+                (data.body.storage_root(), data.body.code_hash())
+            else {
+                return Ok(false)
+            };
+            if data.hash < origin || previous.is_some_and(|previous| data.hash <= previous) {
+                return Ok(false)
+            }
+            previous = Some(data.hash);
+            accounts.push((data.hash, data.body.nonce, data.body.balance, storage_root, code_hash));
+        }
+
+        let Some(&(last, ..)) = accounts.last() else {
+            // without a proof, the peer doesn't serve the state root
+            if range.proof.is_empty() {
+                return Ok(false)
+            }
+            // there are no accounts after the origin
+            HashedStateWriter::new(tx, None).delete_accounts(origin, B256::repeat_byte(0xff))?;
+            self.checkpoint.next_account = None;
+            return Ok(true)
+        };
+
+        let mut writer = HashedStateWriter::new(tx, None);
+        writer.delete_accounts(origin, last)?;
+        for (hashed_address, nonce, balance, storage_root, code_hash) in accounts {
+            let bytecode_hash = (code_hash != KECCAK_EMPTY).then_some(code_hash);
+            writer.put_account(hashed_address, Account { nonce, balance, bytecode_hash })?;
+            if storage_root != EMPTY_ROOT_HASH {
+                self.storage_tasks.push_back(StorageTask::new(hashed_address));
+            }
+            self.queue_code(tx, code_hash)?;
+        }
+        self.checkpoint.next_account = next_key(last);
+
+        trace!(target: "sync::stages::snap_sync", ?origin, ?last, "Downloaded account range");
+        Ok(true)
+    }
+
+    /// Writes the downloaded storage slots, replacing the local slots of the ranges.
+    ///
+    /// Returns `false` if the response is empty or invalid.
+    fn on_storage_ranges<TX: DbTx + DbTxMut>(
+        &mut self,
+        tx: &TX,
+        tasks: Vec<StorageTask>,
+        ranges: StorageRanges,
+    ) -> Result<bool, StageError> {
+        if ranges.slots.is_empty() {
+            self.requeue(SnapRequest::StorageRanges(tasks));
+            return Ok(false)
+        }
+
+        let mut writer = HashedStateWriter::new(tx, self.changes.as_mut());
+        let last_range = ranges.slots.len() - 1;
+        let mut tasks = tasks.into_iter();
+        let mut continuation = None;
+        for (index, slots) in ranges.slots.into_iter().enumerate() {
+            let Some(task) = tasks.next() else { break };
+
+            let mut decoded = Vec::with_capacity(slots.len());
+            for slot in slots {
+                let value = U256::decode(&mut slot.data.as_ref()).ok();
+                let in_order =
+                    decoded.last().map_or(slot.hash >= task.origin, |(last, _)| slot.hash > *last);
+                let Some(value) = value.filter(|_| in_order) else {
+                    drop(writer);
+                    self.storage_tasks.push_front(task);
+                    self.requeue(SnapRequest::StorageRanges(tasks.collect()));
+                    return Ok(false)
+                };
+                decoded.push((slot.hash, value));
+            }
+
+            // The last range is incomplete if it has a proof. Since the proof is not verified,
+            // the storage is requested until a range is empty.
+            let next = (index == last_range && !ranges.proof.is_empty())
+                .then(|| decoded.last().and_then(|(last, _)| next_key(*last)))
+                .flatten();
+            let end =
+                next.map_or(B256::repeat_byte(0xff), |_| decoded.last().expect("not empty").0);
+
+            if task.origin.is_zero() {
+                writer.wipe_storage(task.account)?;
+            } else {
+                writer.delete_storage(task.account, task.origin, end)?;
+            }
+            for (hashed_slot, value) in decoded {
+                writer.put_storage(task.account, hashed_slot, value)?;
+            }
+            continuation = next.map(|origin| StorageTask { account: task.account, origin });
+        }
+
+        // the accounts without a range are requested again
+        let remaining = continuation.into_iter().chain(tasks).collect::<Vec<_>>();
+        self.requeue(SnapRequest::StorageRanges(remaining));
+        Ok(true)
+    }
+
+    /// Writes the downloaded bytecodes.
+    ///
+    /// Returns `false` if the response is empty or invalid.
+    fn on_byte_codes<TX: DbTx + DbTxMut>(
+        &mut self,
+        tx: &TX,
+        hashes: Vec<B256>,
+        codes: Vec<Bytes>,
+    ) -> Result<bool, StageError> {
+        let mut missing = hashes.into_iter().collect::<HashSet<_>>();
+        let mut valid = !codes.is_empty();
+        for code in codes {
+            let code_hash = keccak256(&code);
+            if !missing.remove(&code_hash) {
+                valid = false;
+                break
+            }
+            tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code))?;
+        }
+        self.code_tasks.extend(missing);
+        Ok(valid)
+    }
+
+    /// Heals the local trie with the downloaded trie nodes.
+    ///
+    /// Each node that differs from the local node at its path replaces the local leaves below it,
+    /// and queues its children that differ from the local children.
+    ///
+    /// Returns `false` if the response is empty or invalid.
+    fn on_trie_nodes<TX: DbTx + DbTxMut>(
+        &mut self,
+        tx: &TX,
+        tasks: Vec<HealTask>,
+        nodes: Vec<Bytes>,
+    ) -> Result<bool, StageError> {
+        let mut valid = !nodes.is_empty();
+        let mut tasks = tasks.into_iter();
+        let mut healed = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            let Some(task) = tasks.next() else { break };
+            let decoded =
+                (keccak256(&node) == task.hash).then(|| TrieNode::decode(&node).ok()).flatten();
+            let Some(decoded) = decoded else {
+                valid = false;
+                self.heal_tasks.push_front(task);
+                break
+            };
+            healed.push((task, node, decoded));
+        }
+        self.requeue(SnapRequest::TrieNodes(tasks.collect()));
+
+        // The local nodes are read before changing the state. The keys changed since the trie
+        // was last updated are recomputed from the hashed state.
+        let proof =
+            Proof::from_tx(tx).with_prefix_sets_mut(self.changes.clone().unwrap_or_default());
+        let mut paths = HashMap::<Option<B256>, Vec<Nibbles>>::new();
+        for (task, ..) in &healed {
+            paths.entry(task.account).or_default().push(task.path.clone());
+        }
+        let mut local_nodes = HashMap::with_capacity(paths.len());
+        for (account, paths) in paths {
+            let nodes = match account {
+                None => proof
+                    .account_trie_nodes(&paths)
+                    .map_err(|err| StageError::Fatal(Box::new(err)))?,
+                Some(hashed_address) => proof
+                    .storage_trie_nodes(hashed_address, &paths)
+                    .map_err(|err| StageError::Fatal(Box::new(err)))?,
+            };
+            local_nodes.insert(account, nodes);
+        }
+
+        let mut local = Vec::with_capacity(healed.len());
+        for (task, _, node) in &healed {
+            let local_node =
+                local_nodes.get_mut(&task.account).and_then(|nodes| nodes.remove(&task.path));
+
+            // the storage root of a healed account is compared with the remote one
+            let storage_root = match (task.account, node) {
+                (None, TrieNode::Leaf { key, .. }) => Some(
+                    proof
+                        .storage_root(key_bound(&join(&task.path, key), 0))
+                        .map_err(|err| StageError::Fatal(Box::new(err)))?,
+                ),
+                _ => None,
+            };
+            local.push((local_node, storage_root));
+        }
+
+        let mut changes = self.changes.take();
+        let mut writer = HashedStateWriter::new(tx, changes.as_mut());
+        for ((task, node, decoded), (local_node, storage_root)) in healed.into_iter().zip(local) {
+            if local_node.as_ref() == Some(&node) {
+                continue
+            }
+            let local_children = local_node.and_then(|node| match TrieNode::decode(&node) {
+                Ok(TrieNode::Branch(children)) => Some(children),
+                _ => None,
+            });
+            self.heal_node(
+                &mut writer,
+                task.account,
+                task.path.to_vec(),
+                decoded,
+                local_children,
+                storage_root,
+            )?;
+        }
+        drop(writer);
+        self.changes = changes;
+
+        Ok(valid)
+    }
+
+    /// Replaces the local leaves below the path with the given node, and queues the children
+    /// that differ from the local children.
+    fn heal_node<TX: DbTx + DbTxMut>(
+        &mut self,
+        writer: &mut HashedStateWriter<'_, TX>,
+        account: Option<B256>,
+        path: Vec<u8>,
+        node: TrieNode,
+        local_children: Option<Vec<ChildRef>>,
+        storage_root: Option<B256>,
+    ) -> Result<(), StageError> {
+        match node {
+            TrieNode::Branch(children) => {
+                for (nibble, child) in children.into_iter().enumerate() {
+                    if local_children.as_ref().is_some_and(|local| local[nibble] == child) {
+                        continue
+                    }
+                    let mut child_path = path.clone();
+                    child_path.push(nibble as u8);
+                    self.heal_child(writer, account, child_path, child)?;
+                }
+            }
+            TrieNode::Extension { key, child } => {
+                let child_path = join(&path, &key);
+                writer.delete_leaves_except(account, &path, &child_path)?;
+                self.heal_child(writer, account, child_path, child)?;
+            }
+            TrieNode::Leaf { key, value } => {
+                let key_path = join(&path, &key);
+                if key_path.len() != 64 {
+                    return Ok(())
+                }
+                writer.delete_leaves_except(account, &path, &key_path)?;
+                let hashed_key = key_bound(&key_path, 0);
+                match account {
+                    None => {
+                        let Ok(remote) = TrieAccount::decode(&mut value.as_slice()) else {
+                            return Ok(())
+                        };
+                        let bytecode_hash =
+                            (remote.code_hash != KECCAK_EMPTY).then_some(remote.code_hash);
+                        writer.put_account(
+                            hashed_key,
+                            Account { nonce: remote.nonce, balance: remote.balance, bytecode_hash },
+                        )?;
+                        self.queue_code(writer.tx, remote.code_hash)?;
+
+                        let local_root = storage_root.unwrap_or(EMPTY_ROOT_HASH);
+                        if remote.storage_root == local_root {
+                            // the storage is complete
+                        } else if remote.storage_root == EMPTY_ROOT_HASH {
+                            writer.wipe_storage(hashed_key)?;
+                        } else if local_root == EMPTY_ROOT_HASH {
+                            // a missing storage is downloaded in ranges
+                            self.storage_tasks.push_back(StorageTask::new(hashed_key));
+                        } else {
+                            self.heal_tasks.push_back(HealTask {
+                                account: Some(hashed_key),
+                                path: Nibbles::default(),
+                                hash: remote.storage_root,
+                            });
+                        }
+                    }
+                    Some(hashed_address) => {
+                        let Ok(value) = U256::decode(&mut value.as_slice()) else { return Ok(()) };
+                        writer.put_storage(hashed_address, hashed_key, value)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Heals the child of a healed node at the given path.
+    fn heal_child<TX: DbTx + DbTxMut>(
+        &mut self,
+        writer: &mut HashedStateWriter<'_, TX>,
+        account: Option<B256>,
+        path: Vec<u8>,
+        child: ChildRef,
+    ) -> Result<(), StageError> {
+        match child {
+            ChildRef::Empty => writer.delete_leaves_except(account, &path, &[])?,
+            ChildRef::Hash(hash) => self.heal_tasks.push_back(HealTask {
+                account,
+                path: Nibbles::from_nibbles_unchecked(path),
+                hash,
+            }),
+            // nodes shorter than a hash are embedded in their parent
+            ChildRef::Inline(node) => {
+                if let Ok(node) = TrieNode::decode(&node) {
+                    self.heal_node(writer, account, path, node, None, None)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The download of the storage of an account.
+#[derive(Debug, Clone, Copy)]
+struct StorageTask {
+    /// The hashed address of the account.
+    account: B256,
+    /// The hashed slot to continue the download at, zero if the whole storage is downloaded.
+    origin: B256,
+}
+
+impl StorageTask {
+    const fn new(account: B256) -> Self {
+        Self { account, origin: B256::ZERO }
+    }
+}
+
+/// The download of a trie node, to heal the local trie with.
+#[derive(Debug, Clone)]
+struct HealTask {
+    /// The hashed address of the account for a node of its storage trie, `None` for a node of the
+    /// account trie.
+    account: Option<B256>,
+    /// The path of the node in its trie.
+    path: Nibbles,
+    /// The hash of the node.
+    hash: B256,
+}
+
+/// A request sent to a peer.
+#[derive(Debug)]
+enum SnapRequest {
+    AccountRange { origin: B256 },
+    StorageRanges(Vec<StorageTask>),
+    ByteCodes(Vec<B256>),
+    TrieNodes(Vec<HealTask>),
+}
+
+/// The response of a peer to a [`SnapRequest`].
+#[derive(Debug)]
+enum SnapResponse {
+    AccountRange(AccountRange),
+    StorageRanges(StorageRanges),
+    ByteCodes(ByteCodes),
+    TrieNodes(TrieNodes),
+}
+
+/// The future of a request sent to a peer, resolving to the request and its response.
+type SnapRequestFut =
+    Pin<Box<dyn Future<Output = (PeerId, SnapRequest, RequestResult<SnapResponse>)> + Send + Sync>>;
+
+/// Sends the request for the state with the given root to the peer.
+fn send_request<C: SnapClient>(
+    client: &C,
+    peer_id: PeerId,
+    state_root: B256,
+    request: SnapRequest,
+) -> SnapRequestFut {
+    let response: SnapFut<SnapResponse> = match &request {
+        SnapRequest::AccountRange { origin } => Box::pin(
+            client
+                .get_account_range(
+                    peer_id,
+                    state_root,
+                    *origin,
+                    B256::repeat_byte(0xff),
+                    RESPONSE_BYTES,
+                )
+                .map(|response| response.map(SnapResponse::AccountRange)),
+        ),
+        SnapRequest::StorageRanges(tasks) => {
+            let origin = tasks[0].origin;
+            let starting_hash =
+                if origin.is_zero() { Bytes::new() } else { Bytes::copy_from_slice(&origin[..]) };
+            Box::pin(
+                client
+                    .get_storage_ranges(
+                        peer_id,
+                        state_root,
+                        tasks.iter().map(|task| task.account).collect(),
+                        starting_hash,
+                        Bytes::new(),
+                        RESPONSE_BYTES,
+                    )
+                    .map(|response| response.map(SnapResponse::StorageRanges)),
+            )
+        }
+        SnapRequest::ByteCodes(hashes) => Box::pin(
+            client
+                .get_byte_codes(peer_id, hashes.clone(), RESPONSE_BYTES)
+                .map(|response| response.map(SnapResponse::ByteCodes)),
+        ),
+        SnapRequest::TrieNodes(tasks) => {
+            let paths = tasks
+                .iter()
+                .map(|task| match task.account {
+                    None => vec![encode_compact_path(&task.path)],
+                    Some(account) => {
+                        vec![Bytes::copy_from_slice(&account[..]), encode_compact_path(&task.path)]
+                    }
+                })
+                .collect();
+            Box::pin(
+                client
+                    .get_trie_nodes(peer_id, state_root, paths, RESPONSE_BYTES)
+                    .map(|response| response.map(SnapResponse::TrieNodes)),
+            )
+        }
+    };
+    Box::pin(response.map(move |response| (peer_id, request, response)))
+}
+
+/// Updates the trie with the changed keys, and returns the new state root.
+fn update_trie<TX: DbTx + DbTxMut>(
+    tx: &TX,
+    changes: TriePrefixSetsMut,
+) -> Result<B256, StageError> {
+    let (state_root, updates) = StateRoot::from_tx(tx)
+        .with_prefix_sets(changes.freeze())
+        .root_with_updates()
+        .map_err(|err| StageError::Fatal(Box::new(err)))?;
+    updates.write_to_database(tx)?;
+    Ok(state_root)
+}
+
+/// Returns whether no keys changed.
+fn is_empty(changes: &TriePrefixSetsMut) -> bool {
+    changes.account_prefix_set.is_empty() && changes.storage_prefix_sets.is_empty()
+}
+
+/// Writes the downloaded state to the hashed state tables.
+///
+/// While healing, the changed keys are collected to update the trie incrementally. The storage
+/// trie of an account is dropped when its storage is replaced as a whole, and is rebuilt on the
+/// next update.
+struct HashedStateWriter<'a, TX> {
+    tx: &'a TX,
+    changes: Option<&'a mut TriePrefixSetsMut>,
+}
+
+impl<'a, TX: DbTx + DbTxMut> HashedStateWriter<'a, TX> {
+    fn new(tx: &'a TX, changes: Option<&'a mut TriePrefixSetsMut>) -> Self {
+        Self { tx, changes }
+    }
+
+    fn put_account(&mut self, hashed_address: B256, account: Account) -> Result<(), DatabaseError> {
+        self.tx.put::<tables::HashedAccounts>(hashed_address, account)?;
+        self.account_changed(hashed_address);
+        Ok(())
+    }
+
+    /// Deletes the accounts in the given range of hashed addresses, along with their storage.
+    fn delete_accounts(&mut self, from: B256, to: B256) -> Result<(), DatabaseError> {
+        let mut cursor = self.tx.cursor_write::<tables::HashedAccounts>()?;
+        while let Some((hashed_address, _)) = cursor.seek(from)?.filter(|(key, _)| *key <= to) {
+            cursor.delete_current()?;
+            self.wipe_storage(hashed_address)?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the storage of the account.
+    fn wipe_storage(&mut self, hashed_address: B256) -> Result<(), DatabaseError> {
+        let mut cursor = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+        if cursor.seek_exact(hashed_address)?.is_some() {
+            cursor.delete_current_duplicates()?;
+        }
+        let mut cursor = self.tx.cursor_dup_write::<tables::StoragesTrie>()?;
+        if cursor.seek_exact(hashed_address)?.is_some() {
+            cursor.delete_current_duplicates()?;
+        }
+        self.account_changed(hashed_address);
+        Ok(())
+    }
+
+    fn put_storage(
+        &mut self,
+        hashed_address: B256,
+        hashed_slot: B256,
+        value: U256,
+    ) -> Result<(), DatabaseError> {
+        let mut cursor = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+        if cursor
+            .seek_by_key_subkey(hashed_address, hashed_slot)?
+            .filter(|entry| entry.key == hashed_slot)
+            .is_some()
+        {
+            cursor.delete_current()?;
+        }
+        if !value.is_zero() {
+            cursor.upsert(hashed_address, StorageEntry { key: hashed_slot, value })?;
+        }
+        self.storage_changed(hashed_address, hashed_slot);
+        Ok(())
+    }
+
+    /// Deletes the storage slots of the account in the given range of hashed slots.
+    fn delete_storage(
+        &mut self,
+        hashed_address: B256,
+        from: B256,
+        to: B256,
+    ) -> Result<(), DatabaseError> {
+        let mut cursor = self.tx.cursor_dup_write::<tables::HashedStorages>()?;
+        while let Some(entry) =
+            cursor.seek_by_key_subkey(hashed_address, from)?.filter(|entry| entry.key <= to)
+        {
+            cursor.delete_current()?;
+            self.storage_changed(hashed_address, entry.key);
+        }
+        Ok(())
+    }
+
+    /// Deletes the leaves of the account trie, or the storage trie of the account, below the
+    /// path, except for the leaves below the kept path.
+    fn delete_leaves_except(
+        &mut self,
+        account: Option<B256>,
+        path: &[u8],
+        keep: &[u8],
+    ) -> Result<(), DatabaseError> {
+        let (from, to) = (key_bound(path, 0), key_bound(path, 0xf));
+        let mut ranges = vec![(from, to)];
+        if !keep.is_empty() {
+            let (keep_from, keep_to) = (key_bound(keep, 0), key_bound(keep, 0xf));
+            ranges = [(Some(from), prev_key(keep_from)), (next_key(keep_to), Some(to))]
+                .into_iter()
+                .filter_map(|(from, to)| from.zip(to))
+                .filter(|(from, to)| from <= to)
+                .collect();
+        }
+
+        for (from, to) in ranges {
+            match account {
+                None => self.delete_accounts(from, to)?,
+                Some(hashed_address) => self.delete_storage(hashed_address, from, to)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn account_changed(&mut self, hashed_address: B256) {
+        if let Some(changes) = &mut self.changes {
+            changes.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+        }
+    }
+
+    fn storage_changed(&mut self, hashed_address: B256, hashed_slot: B256) {
+        if let Some(changes) = &mut self.changes {
+            changes.account_prefix_set.insert(Nibbles::unpack(hashed_address));
+            changes
+                .storage_prefix_sets
+                .entry(hashed_address)
+                .or_default()
+                .insert(Nibbles::unpack(hashed_slot));
+        }
+    }
+}
+
+/// A reference to the child of a trie node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ChildRef {
+    /// No child.
+    Empty,
+    /// A child referenced by its hash.
+    Hash(B256),
+    /// A child shorter than a hash, embedded in its parent.
+    Inline(Vec<u8>),
+}
+
+/// A decoded node of the Merkle Patricia Trie.
+#[derive(Debug)]
+enum TrieNode {
+    Branch(Vec<ChildRef>),
+    Extension { key: Vec<u8>, child: ChildRef },
+    Leaf { key: Vec<u8>, value: Vec<u8> },
+}
+
+impl TrieNode {
+    /// Decodes the RLP encoded node.
+    fn decode(mut buf: &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(&mut buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let mut payload =
+            buf.get(..header.payload_length).ok_or(alloy_rlp::Error::InputTooShort)?;
+
+        let mut items = Vec::with_capacity(17);
+        while !payload.is_empty() {
+            items.push(RlpItem::decode(&mut payload)?);
+        }
+
+        match items.as_slice() {
+            // the value of a branch is always empty in the state and storage tries
+            [children @ .., _] if items.len() == 17 => Ok(Self::Branch(
+                children.iter().map(ChildRef::from_item).collect::<Result<_, _>>()?,
+            )),
+            [RlpItem::String(path), item] => {
+                let (key, is_leaf) = decode_compact_path(path)?;
+                match (is_leaf, item) {
+                    (true, RlpItem::String(value)) => Ok(Self::Leaf { key, value: value.to_vec() }),
+                    (false, item) => Ok(Self::Extension { key, child: ChildRef::from_item(item)? }),
+                    _ => Err(alloy_rlp::Error::UnexpectedList),
+                }
+            }
+            _ => Err(alloy_rlp::Error::Custom("invalid trie node")),
+        }
+    }
+}
+
+impl ChildRef {
+    fn from_item(item: &RlpItem<'_>) -> alloy_rlp::Result<Self> {
+        match item {
+            RlpItem::String([]) => Ok(Self::Empty),
+            RlpItem::String(hash) if hash.len() == 32 => Ok(Self::Hash(B256::from_slice(hash))),
+            RlpItem::List(node) => Ok(Self::Inline(node.to_vec())),
+            RlpItem::String(_) => Err(alloy_rlp::Error::UnexpectedLength),
+        }
+    }
+}
+
+/// An item of an RLP list.
+enum RlpItem<'a> {
+    /// The payload of a string.
+    String(&'a [u8]),
+    /// The whole encoding of a list.
+    List(&'a [u8]),
+}
+
+impl<'a> RlpItem<'a> {
+    fn decode(buf: &mut &'a [u8]) -> alloy_rlp::Result<Self> {
+        let encoded: &'a [u8] = buf;
+        let header = Header::decode(buf)?;
+        let remaining: &'a [u8] = buf;
+        if remaining.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let (payload, rest) = remaining.split_at(header.payload_length);
+        *buf = rest;
+        Ok(if header.list {
+            Self::List(&encoded[..encoded.len() - rest.len()])
+        } else {
+            Self::String(payload)
+        })
+    }
+}
+
+/// Decodes the nibbles of a path in the compact encoding, and whether it's the path of a leaf.
+fn decode_compact_path(path: &[u8]) -> alloy_rlp::Result<(Vec<u8>, bool)> {
+    let Some((&first, rest)) = path.split_first() else {
+        return Err(alloy_rlp::Error::InputTooShort)
+    };
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(alloy_rlp::Error::Custom("invalid path flag"))
+    }
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.extend([byte >> 4, byte & 0x0f]);
+    }
+    Ok((nibbles, flag & 2 == 2))
+}
+
+/// Encodes the path of a node in the compact encoding used by the `snap` requests.
+fn encode_compact_path(path: &[u8]) -> Bytes {
+    let mut encoded = Vec::with_capacity(path.len() / 2 + 1);
+    let mut nibbles = path;
+    if path.len() % 2 == 1 {
+        encoded.push(0x10 | path[0]);
+        nibbles = &path[1..];
+    } else {
+        encoded.push(0x00);
+    }
+    encoded.extend(nibbles.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+    encoded.into()
+}
+
+/// Returns the concatenation of the paths.
+fn join(path: &[u8], key: &[u8]) -> Vec<u8> {
+    let mut joined = Vec::with_capacity(path.len() + key.len());
+    joined.extend_from_slice(path);
+    joined.extend_from_slice(key);
+    joined
+}
+
+/// Returns the key below the path, with the remaining nibbles set to the given nibble.
+fn key_bound(path: &[u8], fill: u8) -> B256 {
+    let mut key = B256::repeat_byte(fill << 4 | fill);
+    for (index, nibble) in path.iter().take(64).enumerate() {
+        let byte = &mut key[index / 2];
+        *byte = if index % 2 == 0 { nibble << 4 | *byte & 0x0f } else { *byte & 0xf0 | nibble };
+    }
+    key
+}
+
+/// Returns the key following the given key, `None` if it's the last key.
+fn next_key(key: B256) -> Option<B256> {
+    U256::from_be_bytes(key.0).checked_add(U256::from(1)).map(B256::from)
+}
+
+/// Returns the key preceding the given key, `None` if it's the first key.
+fn prev_key(key: B256) -> Option<B256> {
+    U256::from_be_bytes(key.0).checked_sub(U256::from(1)).map(B256::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use reth_network::{
+        snap::{SnapHandle, SnapProtocolHandler},
+        test_utils::Testnet,
+    };
+    use reth_primitives::{Address, SealedBlock};
+    use reth_provider::{
+        test_utils::{MockEthProvider, NoopProvider},
+        AccountReader, PruneCheckpointReader, StateProvider, StateProviderFactory,
+    };
+    use reth_stages_api::StageExt;
+    use reth_testing_utils::generators::{self, random_block, random_eoa_accounts};
+    use std::time::Duration;
+
+    const TIP: BlockNumber = 10;
+    const PIVOT_DISTANCE: u64 = 4;
+
+    /// The state served by the source node.
+    struct SourceState {
+        accounts: Vec<(Address, (Account, Vec<StorageEntry>))>,
+        codes: Vec<Bytes>,
+    }
+
+    impl SourceState {
+        fn random() -> Self {
+            let mut rng = generators::rng();
+            let mut accounts = random_eoa_accounts(&mut rng, 100)
+                .into_iter()
+                .map(|(address, account)| (address, (account, Vec::new())))
+                .collect::<Vec<_>>();
+
+            let mut codes = Vec::new();
+            for i in 0..5u8 {
+                let code = Bytes::from(vec![0x60, i, 0x60, 0x00, 0x55, 0x00]);
+                let storage = (1..=50u64)
+                    .map(|slot| StorageEntry {
+                        key: B256::from(U256::from(slot)),
+                        value: U256::from(slot * (i as u64 + 1)),
+                    })
+                    .collect();
+                let account = Account {
+                    nonce: 1,
+                    balance: U256::ZERO,
+                    bytecode_hash: Some(keccak256(&code)),
+                };
+                accounts.push((Address::with_last_byte(i), (account, storage)));
+                codes.push(code);
+            }
+
+            Self { accounts, codes }
+        }
+    }
+
+    /// Returns a chain of empty blocks up to [`TIP`], all with the given state root.
+    fn blocks(state_root: B256) -> Vec<SealedBlock> {
+        let mut rng = generators::rng();
+        let mut parent = B256::ZERO;
+        (0..=TIP)
+            .map(|number| {
+                let block = random_block(&mut rng, number, Some(parent), Some(0), Some(0));
+                let mut header = block.header.clone().unseal();
+                header.state_root = state_root;
+                let block = SealedBlock { header: header.seal_slow(), ..block };
+                parent = block.hash();
+                block
+            })
+            .collect()
+    }
+
+    /// Writes the state and its trie to the source node, and returns the state root.
+    fn insert_source_state(db: &TestStageDB, state: &SourceState) -> B256 {
+        db.insert_accounts_and_storages(state.accounts.clone()).unwrap();
+        let mut state_root = B256::ZERO;
+        db.commit(|tx| {
+            for code in &state.codes {
+                tx.put::<tables::Bytecodes>(keccak256(code), Bytecode::new_raw(code.clone()))?;
+            }
+            let (root, updates) = StateRoot::from_tx(tx).root_with_updates().unwrap();
+            updates.write_to_database(tx)?;
+            state_root = root;
+            Ok(())
+        })
+        .unwrap();
+        state_root
+    }
+
+    /// Connects the source node serving its state to a node with a [`SnapHandle`]. The network
+    /// runs until the returned guard is dropped.
+    async fn connect(source: &TestStageDB) -> (impl Sized, SnapHandle) {
+        let mut net = Testnet::create_with(2, MockEthProvider::default()).await;
+        net.peers_mut()[0].add_rlpx_sub_protocol(SnapProtocolHandler::new(source.factory.clone()));
+        let client = SnapProtocolHandler::new(NoopProvider::default());
+        let snap = client.handle();
+        net.peers_mut()[1].add_rlpx_sub_protocol(client);
+
+        let handle = net.spawn();
+        handle.connect_peers().await;
+
+        let server_id = *handle.peers()[0].peer_id();
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !snap.snap_peers().contains(&server_id) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("snap connection not established");
+
+        (handle, snap)
+    }
+
+    /// Runs the stage to completion, the way the pipeline does.
+    async fn run(db: &TestStageDB, mut stage: SnapSyncStage<SnapHandle>) {
+        let mut checkpoint = None;
+        tokio::time::timeout(Duration::from_secs(60), async {
+            loop {
+                let input = ExecInput { target: Some(TIP), checkpoint };
+                stage.execute_ready(input).await.unwrap();
+                let provider = db.factory.provider_rw().unwrap();
+                let output = stage.execute(&provider, input).unwrap();
+                provider.save_stage_checkpoint(StageId::SnapSync, output.checkpoint).unwrap();
+                provider.commit().unwrap();
+                checkpoint = Some(output.checkpoint);
+                if output.done {
+                    break
+                }
+            }
+        })
+        .await
+        .expect("snap sync did not complete");
+    }
+
+    /// Asserts that the target node holds the state of the source node at the pivot block.
+    fn assert_synced(source: &TestStageDB, target: &TestStageDB, state: &SourceState, root: B256) {
+        assert_eq!(
+            target.table::<tables::HashedAccounts>().unwrap(),
+            source.table::<tables::HashedAccounts>().unwrap()
+        );
+        assert_eq!(
+            target.table::<tables::HashedStorages>().unwrap(),
+            source.table::<tables::HashedStorages>().unwrap()
+        );
+        assert_eq!(
+            target.table::<tables::Bytecodes>().unwrap(),
+            source.table::<tables::Bytecodes>().unwrap()
+        );
+        assert_eq!(target.query(|tx| Ok(StateRoot::from_tx(tx).root().unwrap())).unwrap(), root);
+
+        let pivot = TIP - PIVOT_DISTANCE;
+        let provider = target.factory.provider().unwrap();
+        for stage_id in HANDOFF_STAGES {
+            assert_eq!(
+                provider.get_stage_checkpoint(stage_id).unwrap(),
+                Some(StageCheckpoint::new(pivot))
+            );
+        }
+        for segment in
+            [PruneSegment::AccountHistory, PruneSegment::StorageHistory, PruneSegment::Receipts]
+        {
+            let checkpoint = provider.get_prune_checkpoint(segment).unwrap().unwrap();
+            assert_eq!(checkpoint.block_number, Some(pivot));
+        }
+
+        // the downloaded state is read through the hashed state
+        let latest = target.factory.latest().unwrap();
+        for (address, (account, storage)) in &state.accounts {
+            assert_eq!(latest.basic_account(*address).unwrap(), Some(*account));
+            for entry in storage {
+                assert_eq!(latest.storage(*address, entry.key).unwrap(), Some(entry.value));
+            }
+        }
+    }
+
+    fn stage(client: SnapHandle) -> SnapSyncStage<SnapHandle> {
+        let config = SnapSyncConfig { enabled: true, pivot_distance: PIVOT_DISTANCE };
+        SnapSyncStage::new(client, config, PruneModes::none())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn sync_state_from_peer() {
+        let state = SourceState::random();
+        let source = TestStageDB::default();
+        let root = insert_source_state(&source, &state);
+        let blocks = blocks(root);
+        source.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+        let provider = source.factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(TIP)).unwrap();
+        provider.commit().unwrap();
+
+        // the local genesis state is replaced by the downloaded state
+        let target = TestStageDB::default();
+        target.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+        let stale = random_eoa_accounts(&mut generators::rng(), 10);
+        target
+            .insert_accounts_and_storages(
+                stale.iter().map(|(address, account)| (*address, (*account, Vec::new()))),
+            )
+            .unwrap();
+
+        let (_net, snap) = connect(&source).await;
+        run(&target, stage(snap)).await;
+
+        assert_synced(&source, &target, &state, root);
+        for (address, _) in stale {
+            assert_eq!(target.factory.latest().unwrap().basic_account(address).unwrap(), None);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn heal_state_from_peer() {
+        let state = SourceState::random();
+        let source = TestStageDB::default();
+        let root = insert_source_state(&source, &state);
+        let blocks = blocks(root);
+        source.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+        let provider = source.factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(TIP)).unwrap();
+        provider.commit().unwrap();
+
+        // the ranges were downloaded, but the local state differs from the pivot state
+        let mut local = state.accounts.clone();
+        local[0].1 .0.balance += U256::from(1);
+        local.remove(1);
+        let (_, (_, storage)) = local.last_mut().expect("contract exists");
+        storage[0].value += U256::from(1);
+        storage.remove(1);
+        local.extend(
+            random_eoa_accounts(&mut generators::rng(), 3)
+                .into_iter()
+                .map(|(address, account)| (address, (account, Vec::new()))),
+        );
+
+        let target = TestStageDB::default();
+        target.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+        target.insert_accounts_and_storages(local).unwrap();
+        let (_net, snap) = connect(&source).await;
+        let stage = stage(snap);
+        let provider = target.factory.provider_rw().unwrap();
+        // the plain state was cleared when the download started
+        provider.tx_ref().clear::<tables::PlainAccountState>().unwrap();
+        provider.tx_ref().clear::<tables::PlainStorageState>().unwrap();
+        let pivot = TIP - PIVOT_DISTANCE;
+        let checkpoint = SnapSyncCheckpoint { pivot, next_account: None, complete: false };
+        stage.save_checkpoint(&provider, checkpoint).unwrap();
+        provider.commit().unwrap();
+
+        run(&target, stage).await;
+
+        assert_synced(&source, &target, &state, root);
+    }
+
+    #[test]
+    fn unwind_below_completed_pivot() {
+        let db = TestStageDB::default();
+        let mut stage = stage(SnapProtocolHandler::new(NoopProvider::default()).handle());
+        let provider = db.factory.provider_rw().unwrap();
+        let checkpoint = SnapSyncCheckpoint { pivot: 6, next_account: None, complete: true };
+        stage.save_checkpoint(&provider, checkpoint).unwrap();
+
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(TIP), unwind_to: 5, bad_block: None };
+        assert!(matches!(
+            stage.unwind(&provider, input),
+            Err(StageError::Fatal(err)) if err.to_string().contains("snap synced at block 6")
+        ));
+
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(TIP), unwind_to: 6, bad_block: None };
+        assert_eq!(stage.unwind(&provider, input).unwrap().checkpoint, StageCheckpoint::new(6));
+    }
+
+    #[test]
+    fn trie_node_roundtrip() {
+        let path = [0x1, 0x2, 0x3];
+        assert_eq!(encode_compact_path(&path), Bytes::from_static(&[0x11, 0x23]));
+        assert_eq!(decode_compact_path(&[0x11, 0x23]).unwrap(), (path.to_vec(), false));
+        assert_eq!(decode_compact_path(&[0x20, 0x12]).unwrap(), (vec![0x1, 0x2], true));
+        assert_eq!(encode_compact_path(&[]), Bytes::from_static(&[0x00]));
+
+        assert_eq!(key_bound(&[0xa], 0), B256::from(U256::from(0xau8) << 252));
+        assert_eq!(next_key(B256::repeat_byte(0xff)), None);
+        assert_eq!(prev_key(B256::ZERO), None);
+        assert_eq!(next_key(B256::ZERO), Some(B256::with_last_byte(1)));
+    }
+}
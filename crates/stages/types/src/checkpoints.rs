@@ -74,6 +74,62 @@ impl Compact for MerkleCheckpoint {
     }
 }
 
+/// Saves the progress of the SnapSync stage.
+///
+/// Once `complete` is set, the hashed state of the node was downloaded at the `pivot` block and
+/// the plain state below the pivot is missing.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapSyncCheckpoint {
+    /// The block the state is downloaded for.
+    pub pivot: BlockNumber,
+    /// The next account hash to request an account range from, `None` if all accounts were
+    /// downloaded.
+    pub next_account: Option<B256>,
+    /// Whether the downloaded state was healed and handed off to the pipeline.
+    pub complete: bool,
+}
+
+impl SnapSyncCheckpoint {
+    /// Creates the checkpoint of a download that starts at the given pivot.
+    pub const fn new(pivot: BlockNumber) -> Self {
+        Self { pivot, next_account: Some(B256::ZERO), complete: false }
+    }
+}
+
+impl Compact for SnapSyncCheckpoint {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let mut len = 0;
+
+        buf.put_u64(self.pivot);
+        len += 8;
+
+        buf.put_u8(self.complete as u8 | (self.next_account.is_some() as u8) << 1);
+        len += 1;
+
+        if let Some(next_account) = self.next_account {
+            buf.put_slice(next_account.as_slice());
+            len += next_account.len();
+        }
+        len
+    }
+
+    fn from_compact(mut buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let pivot = buf.get_u64();
+
+        let flags = buf.get_u8();
+        let next_account = (flags & 0b10 != 0).then(|| {
+            let next_account = B256::from_slice(&buf[..32]);
+            buf.advance(32);
+            next_account
+        });
+
+        (Self { pivot, next_account, complete: flags & 0b1 != 0 }, buf)
+    }
+}
+
 /// Saves the progress of AccountHashing stage.
 #[reth_codec]
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -397,4 +453,20 @@ mod tests {
         let (decoded, _) = MerkleCheckpoint::from_compact(&buf, encoded);
         assert_eq!(decoded, checkpoint);
     }
+
+    #[test]
+    fn snap_sync_checkpoint_roundtrip() {
+        let mut rng = rand::thread_rng();
+        for checkpoint in [
+            SnapSyncCheckpoint::new(rng.gen()),
+            SnapSyncCheckpoint { pivot: rng.gen(), next_account: Some(rng.gen()), complete: false },
+            SnapSyncCheckpoint { pivot: rng.gen(), next_account: None, complete: true },
+        ] {
+            let mut buf = Vec::new();
+            let encoded = checkpoint.to_compact(&mut buf);
+            let (decoded, rest) = SnapSyncCheckpoint::from_compact(&buf, encoded);
+            assert_eq!(decoded, checkpoint);
+            assert!(rest.is_empty());
+        }
+    }
 }
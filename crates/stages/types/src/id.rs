@@ -20,6 +20,22 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Optional stage indexing the transactions each address appears in.
+    ///
+    /// Not part of [`StageId::ALL`], as it only runs if enabled.
+    IndexAddressTransactions,
+    /// Optional stage recording the keccak256 preimages of hashed state keys.
+    ///
+    /// Not part of [`StageId::ALL`], as it only runs if enabled.
+    Preimages,
+    /// Optional stage indexing the blocks with logs of each address and topic.
+    ///
+    /// Not part of [`StageId::ALL`], as it only runs if enabled.
+    IndexLogs,
+    /// Optional stage downloading the state of a recent pivot block over the `snap` protocol.
+    ///
+    /// Not part of [`StageId::ALL`], as it only runs if enabled.
+    SnapSync,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -75,6 +91,10 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexAddressTransactions => "IndexAddressTransactions",
+            Self::Preimages => "Preimages",
+            Self::IndexLogs => "IndexLogs",
+            Self::SnapSync => "SnapSync",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -118,6 +138,10 @@ mod tests {
         assert_eq!(StageId::StorageHashing.to_string(), "StorageHashing");
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
+        assert_eq!(StageId::IndexAddressTransactions.to_string(), "IndexAddressTransactions");
+        assert_eq!(StageId::Preimages.to_string(), "Preimages");
+        assert_eq!(StageId::IndexLogs.to_string(), "IndexLogs");
+        assert_eq!(StageId::SnapSync.to_string(), "SnapSync");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
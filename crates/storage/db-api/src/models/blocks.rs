@@ -1,7 +1,7 @@
 //! Block related models and types.
 
 use reth_codecs::{reth_codec, Compact};
-use reth_primitives::{Header, TxNumber, Withdrawals, B256};
+use reth_primitives::{Header, SealedBlock, TxNumber, Withdrawals, B256};
 use serde::{Deserialize, Serialize};
 use std::ops::Range;
 
@@ -87,6 +87,20 @@ pub struct StoredBlockWithdrawals {
 /// Hash of the block header.
 pub type HeaderHash = B256;
 
+/// A block that failed validation, stored together with the reason it was rejected.
+///
+/// Unlike other block models this is not encoded with [`Compact`], since it is only used for
+/// debugging purposes and its layout is expected to change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredBadBlock {
+    /// The rejected block.
+    pub block: SealedBlock,
+    /// The validation error that caused the block to be rejected.
+    pub error: String,
+    /// Unix timestamp in seconds at which the block was rejected.
+    pub rejected_at: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
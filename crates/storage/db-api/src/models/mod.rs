@@ -197,6 +197,25 @@ impl Decompress for Snapshot {
     }
 }
 
+impl Compress for StoredBadBlock {
+    type Compressed = Vec<u8>;
+
+    fn compress(self) -> Self::Compressed {
+        serde_cbor::to_vec(&self).expect("Failed to serialize StoredBadBlock")
+    }
+
+    fn compress_to_buf<B: BufMut + AsMut<[u8]>>(self, buf: &mut B) {
+        let compressed = self.compress();
+        buf.put_slice(&compressed);
+    }
+}
+
+impl Decompress for StoredBadBlock {
+    fn decompress<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        serde_cbor::from_slice(value.as_ref()).map_err(|_| DatabaseError::Decode)
+    }
+}
+
 /// Implements compression for Compact type.
 macro_rules! impl_compression_for_compact {
     ($($name:tt),+) => {
@@ -245,6 +264,7 @@ impl_compression_for_compact!(
     Requests,
     BlobSidecar,
     BlobSidecars,
+    Bytes,
     // Non-DB
     GenesisAccount
 );
@@ -440,4 +460,20 @@ mod tests {
         println!("decompressed snapshot: {:?}", decompressed);
         assert_eq!(snap, decompressed);
     }
+
+    #[test]
+    fn compress_bad_block() {
+        let mut block = SealedBlock::default();
+        block.header = Header { number: 100, ..Default::default() }.seal_slow();
+        block.body.push(TransactionSigned::default());
+        let bad_block = StoredBadBlock {
+            block,
+            error: "invalid state root".to_string(),
+            rejected_at: 1_700_000_000,
+        };
+
+        let compressed = bad_block.clone().compress();
+        let decompressed = StoredBadBlock::decompress(&compressed).unwrap();
+        assert_eq!(bad_block, decompressed);
+    }
 }
@@ -0,0 +1,218 @@
+//! Formatting of the database and static file statistics, as printed by `reth db stats`.
+
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_provider::{ChainDbStats, StaticFileSegmentStats};
+use reth_static_file_types::SegmentRangeInclusive;
+
+/// Returns a table with the entries, pages and size of each database table, followed by the
+/// total size of the tables and the freelist.
+pub fn db_stats_table(stats: &ChainDbStats) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Table Name",
+        "# Entries",
+        "Branch Pages",
+        "Leaf Pages",
+        "Overflow Pages",
+        "Total Size",
+    ]);
+
+    let mut total_size = 0;
+    for table_stats in &stats.tables {
+        total_size += table_stats.size;
+        let mut row = Row::new();
+        row.add_cell(Cell::new(table_stats.table.name()))
+            .add_cell(Cell::new(table_stats.entries))
+            .add_cell(Cell::new(table_stats.branch_pages))
+            .add_cell(Cell::new(table_stats.leaf_pages))
+            .add_cell(Cell::new(table_stats.overflow_pages))
+            .add_cell(Cell::new(human_bytes(table_stats.size as f64)));
+        table.add_row(row);
+    }
+
+    add_separator(&mut table);
+
+    let mut row = Row::new();
+    row.add_cell(Cell::new("Tables"))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(human_bytes(total_size as f64)));
+    table.add_row(row);
+
+    if let (Some(freelist), Some(freelist_size)) = (stats.freelist, stats.freelist_size) {
+        let mut row = Row::new();
+        row.add_cell(Cell::new("Freelist"))
+            .add_cell(Cell::new(freelist))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(""))
+            .add_cell(Cell::new(human_bytes(freelist_size as f64)));
+        table.add_row(row);
+    }
+
+    table
+}
+
+/// Returns a table with the ranges, shape and size of the static files, followed by their total
+/// size.
+///
+/// With `detailed_segments`, every static file gets its own row instead of one row per segment.
+/// With `detailed_sizes`, the sizes of the data, index, offsets and config files are shown
+/// separately.
+pub fn static_files_stats_table(
+    stats: &[StaticFileSegmentStats],
+    detailed_segments: bool,
+    detailed_sizes: bool,
+) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+
+    if detailed_sizes {
+        table.set_header([
+            "Segment",
+            "Block Range",
+            "Transaction Range",
+            "Shape (columns x rows)",
+            "Data Size",
+            "Index Size",
+            "Offsets Size",
+            "Config Size",
+            "Total Size",
+        ]);
+    } else {
+        table.set_header([
+            "Segment",
+            "Block Range",
+            "Transaction Range",
+            "Shape (columns x rows)",
+            "Size",
+        ]);
+    }
+
+    // data, index, offsets and config sizes
+    let mut total_sizes = [0; 4];
+
+    for segment_stats in stats {
+        let mut segment_sizes = [0; 4];
+
+        for file in &segment_stats.files {
+            let sizes = [file.data_size, file.index_size, file.offsets_size, file.config_size];
+            for (total, size) in segment_sizes.iter_mut().zip(sizes) {
+                *total += size;
+            }
+
+            if detailed_segments {
+                let mut row = range_row(segment_stats, file.block_range, file.tx_range);
+                row.add_cell(Cell::new(format!("{} x {}", file.columns, file.rows)));
+                add_size_cells(&mut row, sizes, detailed_sizes);
+                table.add_row(row);
+            }
+        }
+
+        if !detailed_segments {
+            let columns = segment_stats.files.first().map(|file| file.columns).unwrap_or_default();
+            let mut row =
+                range_row(segment_stats, segment_stats.block_range, segment_stats.tx_range);
+            row.add_cell(Cell::new(format!("{columns} x {}", segment_stats.rows)));
+            add_size_cells(&mut row, segment_sizes, detailed_sizes);
+            table.add_row(row);
+        }
+
+        for (total, size) in total_sizes.iter_mut().zip(segment_sizes) {
+            *total += size;
+        }
+    }
+
+    add_separator(&mut table);
+
+    let mut row = Row::new();
+    row.add_cell(Cell::new("Total"))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(""));
+    add_size_cells(&mut row, total_sizes, detailed_sizes);
+    table.add_row(row);
+
+    table
+}
+
+/// Returns a row with the segment and the given block and transaction ranges.
+fn range_row(
+    segment_stats: &StaticFileSegmentStats,
+    block_range: SegmentRangeInclusive,
+    tx_range: Option<SegmentRangeInclusive>,
+) -> Row {
+    let mut row = Row::new();
+    row.add_cell(Cell::new(segment_stats.segment))
+        .add_cell(Cell::new(format!("{block_range}")))
+        .add_cell(Cell::new(tx_range.map_or("N/A".to_string(), |tx_range| format!("{tx_range}"))));
+    row
+}
+
+/// Adds the data, index, offsets and config sizes if `detailed_sizes` is set, and their total.
+fn add_size_cells(row: &mut Row, sizes: [u64; 4], detailed_sizes: bool) {
+    if detailed_sizes {
+        for size in sizes {
+            row.add_cell(Cell::new(human_bytes(size as f64)));
+        }
+    }
+    row.add_cell(Cell::new(human_bytes(sizes.iter().sum::<u64>() as f64)));
+}
+
+/// Adds a row of dashes as wide as the widest cell of each column.
+fn add_separator(table: &mut ComfyTable) {
+    let mut separator = Row::new();
+    for width in table.column_max_content_widths() {
+        separator.add_cell(Cell::new("-".repeat(width as usize)));
+    }
+    table.add_row(separator);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::StaticFileStats;
+    use reth_static_file_types::StaticFileSegment;
+
+    #[test]
+    fn static_files_stats_rows() {
+        let file = |start, end| StaticFileStats {
+            block_range: SegmentRangeInclusive::new(start, end),
+            tx_range: None,
+            columns: 2,
+            rows: 10,
+            data_size: 1000,
+            index_size: 100,
+            offsets_size: 10,
+            config_size: 1,
+        };
+        let stats = [StaticFileSegmentStats {
+            segment: StaticFileSegment::Headers,
+            files: vec![file(0, 9), file(10, 19)],
+            block_range: SegmentRangeInclusive::new(0, 19),
+            tx_range: None,
+            rows: 20,
+            size: 2222,
+        }];
+
+        // a row per segment, the separator and the total
+        let table = static_files_stats_table(&stats, false, false);
+        assert_eq!(table.row_iter().count(), 3);
+        let segment =
+            table.row(0).unwrap().cell_iter().map(|cell| cell.content()).collect::<Vec<_>>();
+        assert_eq!(segment[3], "2 x 20");
+        assert_eq!(segment[4], human_bytes(2222.0));
+
+        // a row per static file, with the separate sizes
+        let table = static_files_stats_table(&stats, true, true);
+        assert_eq!(table.row_iter().count(), 4);
+        let total =
+            table.row(3).unwrap().cell_iter().map(|cell| cell.content()).collect::<Vec<_>>();
+        assert_eq!(total[4], human_bytes(2000.0));
+        assert_eq!(total[8], human_bytes(2222.0));
+    }
+}
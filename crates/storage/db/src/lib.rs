@@ -116,6 +116,10 @@ pub mod test_utils {
         fn report_metrics(&self) {
             self.db().report_metrics()
         }
+
+        fn gauge_metrics(&self) -> Vec<(&'static str, f64, Vec<metrics::Label>)> {
+            self.db().gauge_metrics()
+        }
     }
 
     impl<DB: DatabaseMetadata> DatabaseMetadata for TempDatabase<DB> {
@@ -25,12 +25,12 @@ use reth_db_api::{
         blocks::{HeaderHash, StoredBlockOmmers},
         client_version::ClientVersion,
         storage_sharded_key::StorageShardedKey,
-        CompactU256, ShardedKey, StoredBlockBodyIndices, StoredBlockWithdrawals,
+        CompactU256, ShardedKey, StoredBadBlock, StoredBlockBodyIndices, StoredBlockWithdrawals,
     },
     table::{Decode, DupSort, Encode, Table},
 };
 use reth_primitives::{
-    parlia::Snapshot, Account, Address, BlockHash, BlockNumber, Bytecode, Header, Receipt,
+    parlia::Snapshot, Account, Address, BlockHash, BlockNumber, Bytecode, Bytes, Header, Receipt,
     Requests, StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
 };
 use reth_primitives_traits::{BlobSidecars, IntegerList};
@@ -415,6 +415,45 @@ tables! {
 
     /// Stores the parlia snapshot data by block hash.
     table ParliaSnapshot<Key = BlockHash, Value = Snapshot>;
+
+    /// Stores the most recent blocks that failed validation, keyed by a sequence number that
+    /// increases with every inserted block.
+    table BadBlocks<Key = u64, Value = StoredBadBlock>;
+
+    /// Stores pointers to the transactions each address appears in, as sender, recipient, log
+    /// emitter, log topic or participant of an internal call.
+    ///
+    /// Sharded like [`AccountsHistory`], but the lists contain transaction numbers instead of
+    /// block numbers. The last shard key of an address contains `u64::MAX`.
+    ///
+    /// Only populated if the `IndexAddressTransactions` stage is enabled.
+    table AddressTransactions<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores the addresses each transaction is indexed under in [`AddressTransactions`], so the
+    /// index can be unwound and pruned without re-executing the transactions.
+    ///
+    /// Only populated if the `IndexAddressTransactions` stage is enabled.
+    table TransactionAddresses<Key = TxNumber, Value = Address, SubKey = Address>;
+
+    /// Stores the keccak256 preimages of hashed state keys, i.e. addresses and storage slots, by
+    /// their hash.
+    ///
+    /// Only populated if the `Preimages` stage is enabled.
+    table Preimages<Key = B256, Value = Bytes>;
+
+    /// Stores pointers to the blocks with logs emitted by an address.
+    ///
+    /// Sharded like [`AccountsHistory`]. The last shard key of an address contains `u64::MAX`.
+    ///
+    /// Only populated if the `IndexLogs` stage is enabled.
+    table LogAddressIndex<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores pointers to the blocks with logs that contain a topic, at any position.
+    ///
+    /// Sharded like [`AccountsHistory`]. The last shard key of a topic contains `u64::MAX`.
+    ///
+    /// Only populated if the `IndexLogs` stage is enabled.
+    table LogTopicIndex<Key = ShardedKey<B256>, Value = BlockNumberList>;
 }
 
 /// Keys for the `ChainState` table.
@@ -6,7 +6,7 @@ use reth_db_api::{
     transaction::DbTxMut,
 };
 use reth_primitives::{Bytecode, StorageEntry, U256};
-use reth_storage_errors::db::DatabaseError;
+use reth_storage_errors::{db::DatabaseError, provider::ProviderError};
 use revm::db::states::{PlainStorageChangeset, StateChangeset};
 
 /// A change to the state of the world.
@@ -21,10 +21,19 @@ impl From<StateChangeset> for StateChanges {
 
 impl StateChanges {
     /// Write the bundle state to the database.
+    ///
+    /// If the state was downloaded by the snap sync stage, cleared storage slots are kept as zero
+    /// values in the plain state, so that the state providers don't fall back to the value of the
+    /// slot in the hashed state.
     pub fn write_to_db<DB>(mut self, provider: &DatabaseProviderRW<DB>) -> Result<(), DatabaseError>
     where
         DB: Database,
     {
+        let snap_synced = provider.is_snap_synced().map_err(|err| match err {
+            ProviderError::Database(err) => err,
+            err => DatabaseError::Other(err.to_string()),
+        })?;
+
         // sort all entries so they can be written to database in more performant way.
         // and take smaller memory footprint.
         self.0.accounts.par_sort_by_key(|a| a.0);
@@ -77,7 +86,7 @@ impl StateChanges {
                     }
                 }
 
-                if entry.value != U256::ZERO {
+                if entry.value != U256::ZERO || snap_synced {
                     storages_cursor.upsert(address, entry)?;
                 }
             }
@@ -8,7 +8,10 @@ use std::{
     },
     time::Instant,
 };
-use tokio::sync::watch;
+use tokio::sync::{broadcast, watch};
+
+/// The capacity of the channel notifying about new canonical heads.
+const FORK_CHOICE_CHANNEL_LEN: usize = 256;
 
 /// Tracks the chain info: canonical head, safe block, finalized block.
 #[derive(Debug, Clone)]
@@ -21,6 +24,7 @@ impl ChainInfoTracker {
     pub fn new(head: SealedHeader) -> Self {
         let (finalized_block, _) = watch::channel(None);
         let (safe_block, _) = watch::channel(None);
+        let (fork_choice, _) = broadcast::channel(FORK_CHOICE_CHANNEL_LEN);
         Self {
             inner: Arc::new(ChainInfoInner {
                 last_forkchoice_update: RwLock::new(None),
@@ -29,6 +33,7 @@ impl ChainInfoTracker {
                 canonical_head: RwLock::new(head),
                 safe_block,
                 finalized_block,
+                fork_choice,
             }),
         }
     }
@@ -74,6 +79,16 @@ impl ChainInfoTracker {
         self.inner.finalized_block.borrow().clone()
     }
 
+    /// Returns a receiver that is notified when a new canonical head of the chain is selected.
+    pub fn subscribe_fork_choice(&self) -> broadcast::Receiver<SealedHeader> {
+        self.inner.fork_choice.subscribe()
+    }
+
+    /// Returns a receiver that is notified when the finalized header of the chain changes.
+    pub fn subscribe_finalized_header(&self) -> watch::Receiver<Option<SealedHeader>> {
+        self.inner.finalized_block.subscribe()
+    }
+
     /// Returns the canonical head of the chain.
     #[allow(dead_code)]
     pub fn get_canonical_num_hash(&self) -> BlockNumHash {
@@ -102,10 +117,13 @@ impl ChainInfoTracker {
     /// Sets the canonical head of the chain.
     pub fn set_canonical_head(&self, header: SealedHeader) {
         let number = header.number;
-        *self.inner.canonical_head.write() = header;
+        *self.inner.canonical_head.write() = header.clone();
 
         // also update the atomic number.
         self.inner.canonical_head_number.store(number, Ordering::Relaxed);
+
+        // sending only fails if there are no subscribers
+        let _ = self.inner.fork_choice.send(header);
     }
 
     /// Sets the safe header of the chain.
@@ -116,9 +134,15 @@ impl ChainInfoTracker {
     }
 
     /// Sets the finalized header of the chain.
+    ///
+    /// Subscribers are only notified if the finalized block changed.
     pub fn set_finalized(&self, header: SealedHeader) {
-        self.inner.finalized_block.send_modify(|h| {
-            let _ = h.replace(header);
+        self.inner.finalized_block.send_if_modified(|h| {
+            if h.as_ref().map(|h| h.hash()) == Some(header.hash()) {
+                return false
+            }
+            h.replace(header);
+            true
         });
     }
 }
@@ -142,4 +166,6 @@ struct ChainInfoInner {
     safe_block: watch::Sender<Option<SealedHeader>>,
     /// The block that the beacon node considers finalized.
     finalized_block: watch::Sender<Option<SealedHeader>>,
+    /// Notifies the subscribers about new canonical heads.
+    fork_choice: broadcast::Sender<SealedHeader>,
 }
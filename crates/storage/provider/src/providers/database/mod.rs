@@ -1,23 +1,29 @@
 use crate::{
     providers::{state::latest::LatestStateProvider, StaticFileProvider},
     to_range,
-    traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ParliaSnapshotReader,
-    ProviderError, PruneCheckpointReader, RequestsProvider, StageCheckpointReader,
-    StateProviderBox, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
-    WithdrawalsProvider,
+    traits::{BlockSource, DumpedAccount, ReceiptProvider},
+    AddressTransactionsReader, BadBlockReader, BadBlockWriter, BlockHashReader, BlockNumReader,
+    BlockReader, ChainDbReader, ChainDbStats, ChainSpecProvider, DatabaseProviderFactory,
+    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, LogIndexReader,
+    ParliaSnapshotReader, PreimageReader, ProviderError, PruneCheckpointReader, RequestsProvider,
+    SnapAccountRange, SnapStateReader, SnapStorageRange, StageCheckpointReader, StateDumpReader,
+    StateProviderBox, StaticFileProviderFactory, TableStats, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use reth_chainspec::{ChainInfo, ChainSpec};
-use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
-use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
+use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv, Tables};
+use reth_db_api::{
+    database::Database,
+    database_metrics::DatabaseMetrics,
+    models::{StoredBadBlock, StoredBlockBodyIndices},
+};
 use reth_errors::{RethError, RethResult};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    parlia::Snapshot, Address, BlobSidecars, Block, BlockHash, BlockHashOrNumber, BlockNumber,
-    BlockWithSenders, Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader,
-    StaticFileSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash,
-    TxNumber, Withdrawal, Withdrawals, B256, U256,
+    parlia::Snapshot, Account, Address, BlobSidecars, Block, BlockHash, BlockHashOrNumber,
+    BlockNumber, BlockWithSenders, Bytes, Header, Receipt, SealedBlock, SealedBlockWithSenders,
+    SealedHeader, StaticFileSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
+    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -25,6 +31,7 @@ use reth_storage_api::SidecarsProvider;
 use reth_storage_errors::provider::ProviderResult;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
+    collections::BTreeMap,
     ops::{RangeBounds, RangeInclusive},
     path::Path,
     sync::Arc,
@@ -304,6 +311,10 @@ impl<DB: Database> BlockNumReader for ProviderFactory<DB> {
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.provider()?.block_number(hash)
     }
+
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.provider()?.earliest_block_number()
+    }
 }
 
 impl<DB: Database> BlockReader for ProviderFactory<DB> {
@@ -625,13 +636,227 @@ impl<DB: Database> ParliaSnapshotReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> AddressTransactionsReader for ProviderFactory<DB> {
+    fn address_transactions_indexed_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.address_transactions_indexed_block()
+    }
+
+    fn address_calls_indexed_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.address_calls_indexed_range()
+    }
+
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        before: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_transactions_before(address, before, limit)
+    }
+
+    fn address_transactions_from(
+        &self,
+        address: Address,
+        from: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_transactions_from(address, from, limit)
+    }
+
+    fn address_transactions_in_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_transactions_in_range(address, range)
+    }
+}
+
+impl<DB: Database> PreimageReader for ProviderFactory<DB> {
+    fn preimages_recorded_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.preimages_recorded_block()
+    }
+
+    fn preimage(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        self.provider()?.preimage(hash)
+    }
+
+    fn hashed_accounts_from(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        self.provider()?.hashed_accounts_from(start, limit)
+    }
+
+    fn hashed_storage(&self, hashed_address: B256) -> ProviderResult<Vec<(B256, U256)>> {
+        self.provider()?.hashed_storage(hashed_address)
+    }
+
+    fn hashed_storage_root(&self, hashed_address: B256) -> ProviderResult<B256> {
+        self.provider()?.hashed_storage_root(hashed_address)
+    }
+}
+
+impl<DB: Database> SnapStateReader for ProviderFactory<DB> {
+    fn snap_state_root(&self) -> ProviderResult<B256> {
+        self.provider()?.snap_state_root()
+    }
+
+    fn snap_account_range(
+        &self,
+        state_root: B256,
+        origin: B256,
+        limit: B256,
+        max_accounts: usize,
+    ) -> ProviderResult<Option<SnapAccountRange>> {
+        self.provider()?.snap_account_range(state_root, origin, limit, max_accounts)
+    }
+
+    fn snap_storage_range(
+        &self,
+        state_root: B256,
+        hashed_address: B256,
+        origin: B256,
+        limit: B256,
+        max_slots: usize,
+    ) -> ProviderResult<Option<SnapStorageRange>> {
+        self.provider()?.snap_storage_range(state_root, hashed_address, origin, limit, max_slots)
+    }
+
+    fn snap_trie_nodes(
+        &self,
+        state_root: B256,
+        paths: &[Vec<Bytes>],
+        max_bytes: usize,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        self.provider()?.snap_trie_nodes(state_root, paths, max_bytes)
+    }
+
+    fn snap_bytecodes(&self, code_hashes: &[B256], max_bytes: usize) -> ProviderResult<Vec<Bytes>> {
+        self.provider()?.snap_bytecodes(code_hashes, max_bytes)
+    }
+}
+
+impl<DB: Database> StateDumpReader for ProviderFactory<DB> {
+    fn dump_state(
+        &self,
+        block_number: BlockNumber,
+        start: Address,
+        include_storage: bool,
+        f: &mut dyn FnMut(DumpedAccount) -> bool,
+    ) -> ProviderResult<()> {
+        self.provider()?.dump_state(block_number, start, include_storage, f)
+    }
+}
+
+impl<DB: Database + DatabaseMetrics> ChainDbReader for ProviderFactory<DB> {
+    fn available_state_range(&self) -> ProviderResult<RangeInclusive<BlockNumber>> {
+        self.provider()?.available_state_range()
+    }
+
+    fn raw_table_value(&self, table: Tables, key: &[u8]) -> ProviderResult<Option<Vec<u8>>> {
+        self.provider()?.raw_table_value(table, key)
+    }
+
+    fn chain_db_stats(&self) -> ProviderResult<ChainDbStats> {
+        let mut tables = BTreeMap::<&str, TableStats>::new();
+        let (mut freelist, mut page_size) = (None, None);
+        // the table statistics are reported as labeled database metrics
+        for (name, value, labels) in self.db.gauge_metrics() {
+            let label = |key| {
+                labels
+                    .iter()
+                    .find(|label| label.key() == key)
+                    .map(|label| label.value().to_string())
+            };
+            let table = label("table").and_then(|table| table.parse::<Tables>().ok());
+            match (name, table) {
+                ("db.table_entries" | "db.table_size" | "db.table_pages", Some(table)) => {
+                    let stats = tables.entry(table.name()).or_insert(TableStats {
+                        table,
+                        entries: 0,
+                        branch_pages: 0,
+                        leaf_pages: 0,
+                        overflow_pages: 0,
+                        size: 0,
+                    });
+                    let value = value as usize;
+                    match (name, label("type").as_deref()) {
+                        ("db.table_entries", _) => stats.entries = value,
+                        ("db.table_size", _) => stats.size = value,
+                        (_, Some("branch")) => stats.branch_pages = value,
+                        (_, Some("leaf")) => stats.leaf_pages = value,
+                        (_, Some("overflow")) => stats.overflow_pages = value,
+                        _ => {}
+                    }
+                }
+                ("db.freelist", None) => freelist = Some(value as usize),
+                ("db.page_size", None) => page_size = Some(value as usize),
+                _ => {}
+            }
+        }
+
+        Ok(ChainDbStats {
+            tables: tables.into_values().collect(),
+            freelist,
+            freelist_size: freelist
+                .zip(page_size)
+                .map(|(freelist, page_size)| freelist * page_size),
+            static_files: self.static_file_provider.segment_stats()?,
+        })
+    }
+}
+
+impl<DB: Database> LogIndexReader for ProviderFactory<DB> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.log_topic_blocks(topic, range)
+    }
+}
+
+impl<DB: Database> BadBlockReader for ProviderFactory<DB> {
+    fn bad_blocks(&self) -> ProviderResult<Vec<StoredBadBlock>> {
+        self.provider()?.bad_blocks()
+    }
+
+    fn bad_block(&self, hash: B256) -> ProviderResult<Option<StoredBadBlock>> {
+        self.provider()?.bad_block(hash)
+    }
+}
+
+impl<DB: Database> BadBlockWriter for ProviderFactory<DB> {
+    fn insert_bad_block(&self, block: SealedBlock, error: String) -> ProviderResult<()> {
+        let provider = self.provider_rw()?;
+        provider.insert_bad_block(block, error)?;
+        provider.commit()?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::create_test_provider_factory,
-        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, PruneCheckpointWriter,
+        StageCheckpointWriter, TransactionsProvider, MAX_BAD_BLOCKS,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -641,14 +866,24 @@ mod tests {
         mdbx::DatabaseArguments,
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
+        BlockNumberList,
+    };
+    use reth_db_api::{
+        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        table::Compress,
+        transaction::DbTxMut,
+    };
+    use reth_primitives::{
+        constants::EMPTY_ROOT_HASH, hex_literal::hex, keccak256, Account, SealedBlock,
+        StaticFileSegment, StorageEntry, TxNumber, B256, U256,
     };
-    use reth_primitives::{hex_literal::hex, SealedBlock, StaticFileSegment, TxNumber, B256, U256};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::{
         generators,
         generators::{random_block, random_header},
     };
+    use reth_trie::root::storage_root_unhashed;
     use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
 
@@ -806,4 +1041,215 @@ mod tests {
         assert_eq!(gap.local_head, head);
         assert_eq!(gap.target.tip(), consensus_tip.into());
     }
+
+    #[test]
+    fn dump_historical_state() {
+        let factory = create_test_provider_factory();
+        let account = |balance: u64| Account { balance: U256::from(balance), ..Default::default() };
+        let slot = |key: u8, value: u64| StorageEntry {
+            key: B256::with_last_byte(key),
+            value: U256::from(value),
+        };
+        let (first, second, third) =
+            (Address::with_last_byte(1), Address::with_last_byte(2), Address::with_last_byte(3));
+
+        // block 2 changed the first account and its storage, created the second account and
+        // destroyed the third one
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+        tx.put::<tables::PlainAccountState>(first, account(2)).unwrap();
+        tx.put::<tables::PlainAccountState>(second, account(5)).unwrap();
+        tx.put::<tables::PlainStorageState>(first, slot(1, 7)).unwrap();
+        tx.put::<tables::HashedStorages>(
+            keccak256(first),
+            StorageEntry { key: keccak256(slot(1, 7).key), value: U256::from(7) },
+        )
+        .unwrap();
+        for (address, info) in
+            [(first, Some(account(1))), (second, None), (third, Some(account(9)))]
+        {
+            tx.put::<tables::AccountChangeSets>(2, AccountBeforeTx { address, info }).unwrap();
+        }
+        for entry in [slot(1, 0), slot(2, 3)] {
+            tx.put::<tables::StorageChangeSets>((2, first).into(), entry).unwrap();
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::last(first, entry.key),
+                BlockNumberList::new_pre_sorted([2]),
+            )
+            .unwrap();
+        }
+        for address in [first, second, third] {
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::last(address),
+                BlockNumberList::new_pre_sorted([2]),
+            )
+            .unwrap();
+        }
+        for stage_id in [StageId::Execution, StageId::IndexAccountHistory] {
+            provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(2)).unwrap();
+        }
+        provider.commit().unwrap();
+
+        // the history of block 2 is not indexed yet
+        assert_matches!(
+            factory.dump_state(1, Address::ZERO, true, &mut |_| true),
+            Err(ProviderError::StateAtBlockPruned(1))
+        );
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .save_stage_checkpoint(StageId::IndexStorageHistory, StageCheckpoint::new(2))
+            .unwrap();
+        provider.commit().unwrap();
+
+        let dump = |block_number, include_storage| {
+            let mut accounts = Vec::new();
+            factory
+                .dump_state(block_number, Address::ZERO, include_storage, &mut |dumped| {
+                    accounts.push((
+                        dumped.address,
+                        dumped.account.balance,
+                        dumped.storage_root,
+                        dumped.storage,
+                    ));
+                    true
+                })
+                .unwrap();
+            accounts
+        };
+        let storage_root = |entry: StorageEntry| storage_root_unhashed([(entry.key, entry.value)]);
+        assert_eq!(
+            dump(2, true),
+            vec![
+                (first, U256::from(2), storage_root(slot(1, 7)), vec![slot(1, 7)]),
+                (second, U256::from(5), EMPTY_ROOT_HASH, vec![])
+            ]
+        );
+        assert_eq!(
+            dump(1, true),
+            vec![
+                (first, U256::from(1), storage_root(slot(2, 3)), vec![slot(2, 3)]),
+                (third, U256::from(9), EMPTY_ROOT_HASH, vec![])
+            ]
+        );
+        // the storage roots don't depend on the storage being dumped
+        assert_eq!(
+            dump(1, false),
+            vec![
+                (first, U256::from(1), storage_root(slot(2, 3)), vec![]),
+                (third, U256::from(9), EMPTY_ROOT_HASH, vec![])
+            ]
+        );
+        assert_matches!(
+            factory.dump_state(3, Address::ZERO, true, &mut |_| true),
+            Err(ProviderError::HeaderNotFound(_))
+        );
+    }
+
+    #[test]
+    fn chain_db_introspection() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, ..Default::default() };
+
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::PlainAccountState>(address, account).unwrap();
+        provider.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(20_000)).unwrap();
+        provider.commit().unwrap();
+
+        // without history indices only the latest state is available
+        assert_eq!(factory.available_state_range().unwrap(), 20_000..=20_000);
+
+        let provider = factory.provider_rw().unwrap();
+        for stage_id in [StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+            provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(20_000)).unwrap();
+        }
+        provider.commit().unwrap();
+        assert_eq!(factory.available_state_range().unwrap(), 0..=20_000);
+
+        // pruned history and history that is due to be pruned are not available
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: Some(30),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(31),
+                },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+        assert_eq!(factory.available_state_range().unwrap(), 30..=20_000);
+        let factory = factory.with_prune_modes(PruneModes {
+            storage_history: Some(PruneMode::Distance(10_064)),
+            ..PruneModes::none()
+        });
+        assert_eq!(factory.available_state_range().unwrap(), 9_936..=20_000);
+
+        let compressed: Vec<u8> = account.compress().into();
+        assert_eq!(
+            factory.raw_table_value(Tables::PlainAccountState, address.as_slice()).unwrap(),
+            Some(compressed)
+        );
+        assert_eq!(
+            factory.raw_table_value(Tables::PlainAccountState, Address::ZERO.as_slice()).unwrap(),
+            None
+        );
+
+        let stats = factory.chain_db_stats().unwrap();
+        assert_eq!(stats.tables.len(), Tables::ALL.len());
+        assert_matches!(
+            stats.tables.iter().find(|stats| stats.table == Tables::PlainAccountState),
+            Some(TableStats { entries: 1, .. })
+        );
+    }
+
+    #[test]
+    fn earliest_block_follows_receipts_pruning() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::Receipts,
+                PruneCheckpoint {
+                    block_number: Some(100),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(101),
+                },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        // the checkpoint of the receipts moved to static files
+        assert_eq!(factory.earliest_block_number().unwrap(), 0);
+
+        let factory = factory.with_prune_modes(PruneModes {
+            receipts: Some(PruneMode::Distance(10_064)),
+            ..PruneModes::none()
+        });
+        assert_eq!(factory.earliest_block_number().unwrap(), 101);
+    }
+
+    #[test]
+    fn bad_blocks_are_ordered_and_evicted() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = (0..MAX_BAD_BLOCKS as u64 + 2)
+            .map(|number| random_block(&mut rng, number, None, Some(0), None))
+            .collect::<Vec<_>>();
+
+        for block in &blocks {
+            factory.insert_bad_block(block.clone(), "invalid".to_string()).unwrap();
+        }
+        // rejecting a stored block again does not change the order
+        factory.insert_bad_block(blocks[5].clone(), "invalid".to_string()).unwrap();
+
+        let stored = factory.bad_blocks().unwrap();
+        assert_eq!(
+            stored.iter().map(|bad_block| bad_block.block.hash()).collect::<Vec<_>>(),
+            blocks.iter().rev().take(MAX_BAD_BLOCKS).map(|block| block.hash()).collect::<Vec<_>>()
+        );
+        assert_eq!(factory.bad_block(blocks[5].hash()).unwrap().unwrap().block, blocks[5]);
+        assert!(factory.bad_block(blocks[0].hash()).unwrap().is_none());
+    }
 }
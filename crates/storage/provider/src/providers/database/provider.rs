@@ -3,49 +3,63 @@ use crate::{
     providers::{database::metrics, static_file::StaticFileWriter, StaticFileProvider},
     to_range,
     traits::{
-        AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
+        AccountExtReader, BlockSource, ChangeSetReader, DumpedAccount, ReceiptProvider,
+        StageCheckpointWriter,
     },
     writer::StorageWriter,
-    AccountReader, BlockExecutionReader, BlockExecutionWriter, BlockHashReader, BlockNumReader,
-    BlockReader, BlockWriter, Chain, EvmEnvProvider, FinalizedBlockReader, FinalizedBlockWriter,
-    HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HistoricalStateProvider,
-    HistoryWriter, LatestStateProvider, OriginalValuesKnown, ParliaSnapshotReader, ProviderError,
-    PruneCheckpointReader, PruneCheckpointWriter, RequestsProvider, SidecarsProvider,
-    StageCheckpointReader, StateProviderBox, StateWriter, StatsReader, StorageReader,
-    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BadBlockReader, BadBlockWriter, BlockExecutionReader,
+    BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter, Chain,
+    EvmEnvProvider, FinalizedBlockReader, FinalizedBlockWriter, HashingWriter, HeaderProvider,
+    HeaderSyncGap, HeaderSyncGapProvider, HistoricalStateProvider, HistoricalStateProviderRef,
+    HistoryWriter, LatestStateProvider, LogIndexReader, OriginalValuesKnown, ParliaSnapshotReader,
+    PreimageReader, PreimageWriter, ProviderError, PruneCheckpointReader, PruneCheckpointWriter,
+    RequestsProvider, SidecarsProvider, SnapAccount, SnapAccountRange, SnapStateReader,
+    SnapStorageRange, StageCheckpointReader, StateDumpReader, StateProvider, StateProviderBox,
+    StateWriter, StatsReader, StorageReader, TransactionVariant, TransactionsProvider,
+    TransactionsProviderExt, WithdrawalsProvider, MAX_BAD_BLOCKS,
 };
 use itertools::{izip, Itertools};
 use reth_chainspec::{ChainInfo, ChainSpec, EthereumHardforks};
-use reth_db::{tables, BlockNumberList, PlainAccountState, PlainStorageState};
+use reth_db::{
+    tables, BlockNumberList, PlainAccountState, PlainStorageState, RawKey, RawTable, RawValue,
+    TableViewer, Tables,
+};
 use reth_db_api::{
     common::KeyValue,
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, RangeWalker},
     database::Database,
     models::{
         sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
+        ShardedKey, StoredBadBlock, StoredBlockBodyIndices, StoredBlockOmmers,
+        StoredBlockWithdrawals,
     },
     table::{Table, TableRow},
     transaction::{DbTx, DbTxMut},
     DatabaseError,
 };
 use reth_evm::ConfigureEvmEnv;
+use reth_execution_errors::StorageRootError;
 use reth_execution_types::ExecutionOutcome;
 use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_primitives::{
     keccak256, parlia::Snapshot, Account, Address, BlobSidecars, Block, BlockHash,
-    BlockHashOrNumber, BlockNumber, BlockWithSenders, GotExpected, Header, Receipt, Requests,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry,
+    BlockHashOrNumber, BlockNumber, BlockWithSenders, Bytes, GotExpected, Header, Receipt,
+    Requests, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry,
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash,
     TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
-use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PruneSegment};
-use reth_stages_types::{StageCheckpoint, StageId};
+use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PrunePurpose, PruneSegment};
+use reth_stages_types::{
+    CheckpointBlockRange, EntitiesCheckpoint, IndexHistoryCheckpoint, StageCheckpoint, StageId,
+    StageUnitCheckpoint,
+};
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
+    proof::Proof,
     updates::TrieUpdates,
-    HashedPostStateSorted, Nibbles, StateRoot,
+    HashedPostState, HashedPostStateSorted, HashedStorage, Nibbles, StateRoot, StorageRoot,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
@@ -54,11 +68,14 @@ use std::{
     fmt::Debug,
     ops::{Bound, Deref, DerefMut, Range, RangeBounds, RangeInclusive},
     sync::{mpsc, Arc},
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::watch;
 use tracing::{debug, error, warn};
 
+/// The number of recent canonical blocks whose state is served over the `snap` protocol.
+const SNAP_SERVED_BLOCKS: u64 = 128;
+
 /// A [`DatabaseProvider`] that holds a read-only database transaction.
 pub type DatabaseProviderRO<DB> = DatabaseProvider<<DB as Database>::TX>;
 
@@ -290,6 +307,14 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         &self.chain_spec
     }
 
+    /// Returns whether the state was downloaded by the snap sync stage.
+    ///
+    /// The plain state then only contains the accounts and storage slots written after the pivot
+    /// block of the download, the state of the pivot block is only kept in the hashed state.
+    pub fn is_snap_synced(&self) -> ProviderResult<bool> {
+        crate::providers::state::is_snap_synced(&self.tx)
+    }
+
     /// Disables long-lived read transaction safety guarantees for leaks prevention and
     /// observability improvements.
     ///
@@ -1726,6 +1751,31 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
         Ok(())
     }
+
+    /// Truncates the range of blocks with indexed internal calls, kept in the checkpoint of the
+    /// [`StageId::IndexAddressTransactions`] stage, to the blocks before the given block.
+    fn truncate_address_calls_range(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        let Some(mut checkpoint) = self.get_stage_checkpoint(StageId::IndexAddressTransactions)?
+        else {
+            return Ok(())
+        };
+        let Some(calls) = checkpoint.index_history_stage_checkpoint().map(|c| c.block_range) else {
+            return Ok(())
+        };
+        if calls.to < block_number {
+            return Ok(())
+        }
+
+        checkpoint.stage_checkpoint = (calls.from < block_number).then(|| {
+            let block_range = CheckpointBlockRange { from: calls.from, to: block_number - 1 };
+            let blocks = block_range.to - block_range.from + 1;
+            StageUnitCheckpoint::IndexHistory(IndexHistoryCheckpoint {
+                block_range,
+                progress: EntitiesCheckpoint { processed: blocks, total: blocks },
+            })
+        });
+        self.save_stage_checkpoint(StageId::IndexAddressTransactions, checkpoint)
+    }
 }
 
 impl<TX: DbTx> AccountReader for DatabaseProvider<TX> {
@@ -1793,6 +1843,18 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
             })
             .collect()
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let range = BlockNumberAddress::range(block_number..=block_number);
+        self.tx
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(range)?
+            .map(|result| -> ProviderResult<_> { Ok(result?) })
+            .collect()
+    }
 }
 
 impl<TX: DbTx> HeaderSyncGapProvider for DatabaseProvider<TX> {
@@ -1993,6 +2055,18 @@ impl<TX: DbTx> BlockNumReader for DatabaseProvider<TX> {
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         Ok(self.tx.get::<tables::HeaderNumbers>(hash)?)
     }
+
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        // bodies are only moved to static files, while receipts are deleted if they are pruned.
+        // Without a receipts prune mode, the receipts checkpoint tracks the static files.
+        if self.prune_modes.receipts.is_none() {
+            return Ok(0)
+        }
+        Ok(self
+            .get_prune_checkpoint(PruneSegment::Receipts)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |pruned| pruned + 1))
+    }
 }
 
 impl<TX: DbTx> BlockReader for DatabaseProvider<TX> {
@@ -2628,6 +2702,25 @@ impl<TX: DbTxMut> StageCheckpointWriter for DatabaseProvider<TX> {
             )?;
         }
 
+        // optional stages are only updated if they are enabled, i.e. have a checkpoint
+        for stage_id in [
+            StageId::IndexAddressTransactions,
+            StageId::Preimages,
+            StageId::IndexLogs,
+            StageId::SnapSync,
+        ] {
+            let stage_id = stage_id.to_string();
+            if let Some((_, checkpoint)) = cursor.seek_exact(stage_id.clone())? {
+                cursor.upsert(
+                    stage_id,
+                    StageCheckpoint {
+                        block_number,
+                        ..if drop_stage_checkpoint { Default::default() } else { checkpoint }
+                    },
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -2999,6 +3092,109 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         )
     }
 
+    fn unwind_address_transaction_indices(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let Some(first_tx_number) =
+            self.block_body_indices(*range.start())?.map(|indices| indices.first_tx_num())
+        else {
+            return Ok(0)
+        };
+        let mut addresses = self
+            .take::<tables::TransactionAddresses>(first_tx_number..)?
+            .into_iter()
+            .map(|(_, address)| address)
+            .collect::<Vec<_>>();
+        addresses.sort_unstable();
+        addresses.dedup();
+
+        let mut cursor = self.tx.cursor_write::<tables::AddressTransactions>()?;
+        for &address in &addresses {
+            let partial_shard = unwind_history_shards::<_, tables::AddressTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                first_tx_number,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(addresses.len())
+    }
+
+    fn insert_address_transaction_index(
+        &self,
+        address_transactions: BTreeMap<Address, Vec<TxNumber>>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::AddressTransactions>(
+            address_transactions,
+            ShardedKey::new,
+        )
+    }
+
+    fn unwind_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let first_block = *range.start();
+        let (addresses, topics) = self.block_log_keys(range)?;
+
+        let mut cursor = self.tx.cursor_write::<tables::LogAddressIndex>()?;
+        for &address in addresses.keys() {
+            let partial_shard = unwind_history_shards::<_, tables::LogAddressIndex, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                first_block,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::LogTopicIndex>()?;
+        for &topic in topics.keys() {
+            let partial_shard = unwind_history_shards::<_, tables::LogTopicIndex, _>(
+                &mut cursor,
+                ShardedKey::last(topic),
+                first_block,
+                |sharded_key| sharded_key.key == topic,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(topic),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(addresses.len() + topics.len())
+    }
+
+    fn insert_log_index(
+        &self,
+        addresses: BTreeMap<Address, Vec<BlockNumber>>,
+        topics: BTreeMap<B256, Vec<BlockNumber>>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)
+    }
+
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
         // account history stage
         {
@@ -3008,10 +3204,36 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
 
         // storage history stage
         {
-            let indices = self.changed_storages_and_blocks_with_range(range)?;
+            let indices = self.changed_storages_and_blocks_with_range(range.clone())?;
             self.insert_storage_history_index(indices)?;
         }
 
+        // preimages stage, only if enabled
+        if self.preimages_recorded_block()?.is_some() {
+            self.insert_changed_preimages(range.clone())?;
+        }
+
+        // address transactions stage, only if enabled
+        if self.address_transactions_indexed_block()?.is_some() {
+            // internal calls aren't traced here, the blocks must not be reported as having
+            // indexed calls
+            self.truncate_address_calls_range(*range.start())?;
+
+            let mut cursor = self.tx.cursor_dup_write::<tables::TransactionAddresses>()?;
+            let mut indices = BTreeMap::<Address, Vec<TxNumber>>::new();
+            for (address, tx_number) in self.address_transactions_by_block_range(range.clone())? {
+                cursor.upsert(tx_number, address)?;
+                indices.entry(address).or_default().push(tx_number);
+            }
+            self.insert_address_transaction_index(indices)?;
+        }
+
+        // log index stage, only if enabled
+        if self.log_index_range()?.is_some() {
+            let (addresses, topics) = self.block_log_keys(range)?;
+            self.insert_log_index(addresses, topics)?;
+        }
+
         Ok(())
     }
 }
@@ -3052,6 +3274,16 @@ impl<DB: Database> BlockExecutionWriter for DatabaseProviderRW<DB> {
         // Unwind account history indices.
         self.unwind_account_history_indices(range.clone())?;
 
+        // Unwind address transaction indices, if enabled.
+        if self.address_transactions_indexed_block()?.is_some() {
+            self.unwind_address_transaction_indices(range.clone())?;
+        }
+
+        // Unwind log indices, if enabled.
+        if self.log_index_range()?.is_some() {
+            self.unwind_log_indices(range.clone())?;
+        }
+
         // Unwind storage hashes. Add changed account and storage keys to corresponding prefix
         // sets.
         let mut storage_prefix_sets = HashMap::<B256, PrefixSet>::default();
@@ -3140,6 +3372,16 @@ impl<DB: Database> BlockExecutionWriter for DatabaseProviderRW<DB> {
         // Unwind account history indices.
         self.unwind_account_history_indices(range.clone())?;
 
+        // Unwind address transaction indices, if enabled.
+        if self.address_transactions_indexed_block()?.is_some() {
+            self.unwind_address_transaction_indices(range.clone())?;
+        }
+
+        // Unwind log indices, if enabled.
+        if self.log_index_range()?.is_some() {
+            self.unwind_log_indices(range.clone())?;
+        }
+
         // Unwind storage hashes. Add changed account and storage keys to corresponding prefix
         // sets.
         let mut storage_prefix_sets = HashMap::<B256, PrefixSet>::default();
@@ -3478,6 +3720,804 @@ impl<TX: DbTx> ParliaSnapshotReader for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> BadBlockReader for DatabaseProvider<TX> {
+    fn bad_blocks(&self) -> ProviderResult<Vec<StoredBadBlock>> {
+        // entries are keyed in the order they were inserted
+        Ok(self
+            .tx
+            .cursor_read::<tables::BadBlocks>()?
+            .walk_back(None)?
+            .map(|entry| entry.map(|(_, bad_block)| bad_block))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn bad_block(&self, hash: B256) -> ProviderResult<Option<StoredBadBlock>> {
+        // there are at most `MAX_BAD_BLOCKS` entries
+        for entry in self.tx.cursor_read::<tables::BadBlocks>()?.walk(None)? {
+            let (_, bad_block) = entry?;
+            if bad_block.block.hash() == hash {
+                return Ok(Some(bad_block))
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns the addresses the transactions in the given range are indexed under in
+    /// [`tables::AddressTransactions`], together with the number of the transaction.
+    ///
+    /// These are the sender, the recipient or created contract, and the emitters and
+    /// address-like topics of the logs. Addresses are unique per transaction.
+    pub fn transaction_addresses(
+        &self,
+        range: Range<TxNumber>,
+    ) -> ProviderResult<Vec<(Address, TxNumber)>> {
+        let transactions = self.transactions_by_tx_range(range.clone())?;
+        if transactions.len() != range.clone().count() {
+            return Err(ProviderError::TransactionNotFound(
+                (range.start + transactions.len() as u64).into(),
+            ))
+        }
+
+        // senders and receipts may have been pruned, fall back to per transaction lookups
+        let senders = self.senders_by_tx_range(range.clone())?;
+        let senders = if senders.len() == transactions.len() {
+            senders.into_iter().map(Some).collect::<Vec<_>>()
+        } else {
+            transactions.iter().map(|transaction| transaction.recover_signer()).collect()
+        };
+        let receipts = self.receipts_by_tx_range(range.clone())?;
+        let receipts = if receipts.len() == transactions.len() {
+            receipts.into_iter().map(Some).collect::<Vec<_>>()
+        } else {
+            range.clone().map(|tx_number| self.receipt(tx_number)).collect::<ProviderResult<_>>()?
+        };
+
+        let mut addresses = Vec::new();
+        let mut tx_addresses = Vec::new();
+        for (tx_number, transaction, sender, receipt) in
+            izip!(range, transactions, senders, receipts)
+        {
+            if let Some(sender) = sender {
+                tx_addresses.push(sender);
+                if transaction.kind().is_create() {
+                    tx_addresses.push(sender.create(transaction.nonce()));
+                }
+            }
+            tx_addresses.extend(transaction.to());
+            for log in receipt.iter().flat_map(|receipt| &receipt.logs) {
+                tx_addresses.push(log.address);
+                tx_addresses.extend(log.topics().iter().filter_map(topic_address));
+            }
+
+            tx_addresses.sort_unstable();
+            tx_addresses.dedup();
+            addresses.extend(tx_addresses.drain(..).map(|address| (address, tx_number)));
+        }
+
+        Ok(addresses)
+    }
+
+    /// Returns the addresses the transactions of the given blocks are indexed under in
+    /// [`tables::AddressTransactions`], see [`Self::transaction_addresses`].
+    fn address_transactions_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(Address, TxNumber)>> {
+        let first = self
+            .block_body_indices(*range.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.start()))?;
+        let last = self
+            .block_body_indices(*range.end())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.end()))?;
+        self.transaction_addresses(first.first_tx_num()..last.next_tx_num())
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns the addresses and topics of the logs of the given blocks as indexed in
+    /// [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`], each with the numbers of the
+    /// blocks they appear in.
+    pub fn block_log_keys(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>)>
+    {
+        let mut addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+
+        let bodies = self
+            .tx
+            .cursor_read::<tables::BlockBodyIndices>()?
+            .walk_range(range)?
+            .collect::<Result<Vec<_>, _>>()?;
+        let (Some((_, first)), Some((_, last))) = (bodies.first(), bodies.last()) else {
+            return Ok((addresses, topics))
+        };
+        let tx_range = first.first_tx_num()..last.next_tx_num();
+
+        // receipts may have been pruned, fall back to per transaction lookups
+        let receipts = self.receipts_by_tx_range(tx_range.clone())?;
+        let receipts = if receipts.len() == tx_range.clone().count() {
+            receipts.into_iter().map(Some).collect::<Vec<_>>()
+        } else {
+            tx_range.map(|tx_number| self.receipt(tx_number)).collect::<ProviderResult<_>>()?
+        };
+
+        let mut receipts = receipts.into_iter();
+        for (block_number, body) in bodies {
+            for receipt in receipts.by_ref().take(body.tx_count() as usize).flatten() {
+                for log in receipt.logs {
+                    push_block(addresses.entry(log.address).or_default(), block_number);
+                    for topic in log.topics() {
+                        push_block(topics.entry(*topic).or_default(), block_number);
+                    }
+                }
+            }
+        }
+
+        Ok((addresses, topics))
+    }
+
+    /// Returns the numbers of the blocks in the given range from the shards of the key in the log
+    /// index table `T`.
+    fn log_index_blocks<K, T>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>
+    where
+        K: PartialEq + Clone,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut block_numbers = Vec::new();
+
+        let mut entry = cursor.seek(ShardedKey::new(key.clone(), *range.start()))?;
+        while let Some((sharded_key, list)) = entry {
+            if sharded_key.key != key {
+                break
+            }
+            block_numbers.extend(list.iter().filter(|block_number| range.contains(block_number)));
+            if sharded_key.highest_block_number >= *range.end() {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        Ok(block_numbers)
+    }
+}
+
+/// Appends the block number to the sorted list of blocks, unless it is already the last one.
+fn push_block(block_numbers: &mut Vec<BlockNumber>, block_number: BlockNumber) {
+    if block_numbers.last() != Some(&block_number) {
+        block_numbers.push(block_number);
+    }
+}
+
+/// Returns the address a log topic holds, if it looks like a left-padded address.
+fn topic_address(topic: &B256) -> Option<Address> {
+    (topic[..12] == [0u8; 12] && topic[12..] != [0u8; 20]).then(|| Address::from_word(*topic))
+}
+
+impl<TX: DbTx> AddressTransactionsReader for DatabaseProvider<TX> {
+    fn address_transactions_indexed_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self
+            .get_stage_checkpoint(StageId::IndexAddressTransactions)?
+            .map(|checkpoint| checkpoint.block_number))
+    }
+
+    fn address_calls_indexed_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(calls) = self
+            .get_stage_checkpoint(StageId::IndexAddressTransactions)?
+            .and_then(|checkpoint| checkpoint.index_history_stage_checkpoint())
+            .map(|checkpoint| checkpoint.block_range)
+        else {
+            return Ok(None)
+        };
+        let first_block = self
+            .get_prune_checkpoint(PruneSegment::AddressTransactions)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+        Ok(Some(calls.from.max(first_block)..=calls.to))
+    }
+
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        before: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut tx_numbers = Vec::new();
+
+        // the last shard of an address has the highest possible key, so this always finds a shard
+        // of the address if it is indexed
+        let mut entry = cursor.seek(ShardedKey::new(address, before))?;
+        while let Some((key, list)) = entry {
+            if key.key != address || tx_numbers.len() >= limit {
+                break
+            }
+            let shard = list.iter().take_while(|tx_number| *tx_number < before).collect::<Vec<_>>();
+            tx_numbers.extend(shard.into_iter().rev().take(limit - tx_numbers.len()));
+            entry = cursor.prev()?;
+        }
+
+        Ok(tx_numbers)
+    }
+
+    fn address_transactions_from(
+        &self,
+        address: Address,
+        from: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut tx_numbers = Vec::new();
+
+        let mut entry = cursor.seek(ShardedKey::new(address, from))?;
+        while let Some((key, list)) = entry {
+            if key.key != address || tx_numbers.len() >= limit {
+                break
+            }
+            tx_numbers.extend(
+                list.iter().filter(|tx_number| *tx_number >= from).take(limit - tx_numbers.len()),
+            );
+            entry = cursor.next()?;
+        }
+
+        Ok(tx_numbers)
+    }
+
+    fn address_transactions_in_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::AddressTransactions>()?;
+        let mut tx_numbers = Vec::new();
+
+        let mut entry = cursor.seek(ShardedKey::new(address, *range.start()))?;
+        while let Some((key, list)) = entry {
+            if key.key != address {
+                break
+            }
+            tx_numbers.extend(list.iter().filter(|tx_number| range.contains(tx_number)));
+            if key.highest_block_number >= *range.end() {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        Ok(tx_numbers)
+    }
+}
+
+impl<TX: DbTx> LogIndexReader for DatabaseProvider<TX> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(checkpoint) = self.get_stage_checkpoint(StageId::IndexLogs)? else {
+            return Ok(None)
+        };
+        let first_block = self
+            .get_prune_checkpoint(PruneSegment::LogIndex)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+        Ok(Some(first_block..=checkpoint.block_number))
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.log_index_blocks::<_, tables::LogAddressIndex>(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.log_index_blocks::<_, tables::LogTopicIndex>(topic, range)
+    }
+}
+
+impl<TX: DbTx> PreimageReader for DatabaseProvider<TX> {
+    fn preimages_recorded_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.get_stage_checkpoint(StageId::Preimages)?.map(|checkpoint| checkpoint.block_number))
+    }
+
+    fn preimage(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        Ok(self.tx.get::<tables::Preimages>(hash)?)
+    }
+
+    fn hashed_accounts_from(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        Ok(self
+            .tx
+            .cursor_read::<tables::HashedAccounts>()?
+            .walk(Some(start))?
+            .take(limit)
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn hashed_storage(&self, hashed_address: B256) -> ProviderResult<Vec<(B256, U256)>> {
+        Ok(self
+            .tx
+            .cursor_dup_read::<tables::HashedStorages>()?
+            .walk_dup(Some(hashed_address), None)?
+            .map(|entry| entry.map(|(_, entry)| (entry.key, entry.value)))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    fn hashed_storage_root(&self, hashed_address: B256) -> ProviderResult<B256> {
+        Ok(StorageRoot::from_tx_hashed(&self.tx, hashed_address)
+            .root()
+            .map_err(|StorageRootError::DB(err)| err)?)
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns the hashed state to overlay on the hashed state tables to get the state with the
+    /// given root, if it's the state of one of the last [`SNAP_SERVED_BLOCKS`] canonical blocks.
+    ///
+    /// The overlay of an older block reverts the changesets of the later blocks, so the state is
+    /// not available if they are pruned.
+    fn snap_state(&self, state_root: B256) -> ProviderResult<Option<HashedPostState>> {
+        let tip = self.best_block_number()?;
+        for block_number in (tip.saturating_sub(SNAP_SERVED_BLOCKS - 1)..=tip).rev() {
+            let Some(header) = self.header_by_number(block_number)? else { break };
+            if header.state_root != state_root {
+                continue
+            }
+            if block_number == tip {
+                return Ok(Some(HashedPostState::default()))
+            }
+            for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+                if self
+                    .get_prune_checkpoint(segment)?
+                    .and_then(|checkpoint| checkpoint.block_number)
+                    .is_some_and(|pruned| pruned > block_number)
+                {
+                    return Ok(None)
+                }
+            }
+            return Ok(Some(HashedPostState::from_revert_range(&self.tx, block_number + 1..=tip)?))
+        }
+        Ok(None)
+    }
+}
+
+impl<TX: DbTx> SnapStateReader for DatabaseProvider<TX> {
+    fn snap_state_root(&self) -> ProviderResult<B256> {
+        let best_number = self.best_block_number()?;
+        Ok(self
+            .header_by_number(best_number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(best_number.into()))?
+            .state_root)
+    }
+
+    fn snap_account_range(
+        &self,
+        state_root: B256,
+        origin: B256,
+        limit: B256,
+        max_accounts: usize,
+    ) -> ProviderResult<Option<SnapAccountRange>> {
+        let Some(state) = self.snap_state(state_root)? else { return Ok(None) };
+        let sorted = state.clone().into_sorted();
+        let cursor_factory = HashedPostStateCursorFactory::new(&self.tx, &sorted);
+        let proof = Proof::from_tx(&self.tx)
+            .with_hashed_cursor_factory(cursor_factory.clone())
+            .with_prefix_sets_mut(state.construct_prefix_sets());
+
+        let mut accounts = Vec::new();
+        let mut cursor = cursor_factory.hashed_account_cursor()?;
+        let mut entry = cursor.seek(origin)?;
+        while let Some((hashed_address, account)) = entry {
+            let storage_root =
+                proof.storage_root(hashed_address).map_err(|StorageRootError::DB(err)| err)?;
+            accounts.push(SnapAccount { hashed_address, account, storage_root });
+            if hashed_address >= limit || accounts.len() >= max_accounts {
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        let mut targets = vec![origin];
+        targets.extend(accounts.last().map(|account| account.hashed_address));
+        let proof =
+            proof.account_range_proof(&targets).map_err(Into::<reth_db::DatabaseError>::into)?;
+
+        Ok(Some(SnapAccountRange { accounts, proof }))
+    }
+
+    fn snap_storage_range(
+        &self,
+        state_root: B256,
+        hashed_address: B256,
+        origin: B256,
+        limit: B256,
+        max_slots: usize,
+    ) -> ProviderResult<Option<SnapStorageRange>> {
+        let Some(state) = self.snap_state(state_root)? else { return Ok(None) };
+        let sorted = state.clone().into_sorted();
+        let cursor_factory = HashedPostStateCursorFactory::new(&self.tx, &sorted);
+
+        let mut slots = Vec::new();
+        let mut truncated = false;
+        let mut cursor = cursor_factory.hashed_storage_cursor(hashed_address)?;
+        let mut entry = cursor.seek(origin)?;
+        while let Some((hashed_slot, value)) = entry {
+            if slots.len() >= max_slots.max(1) {
+                truncated = true;
+                break
+            }
+            slots.push((hashed_slot, value));
+            if hashed_slot >= limit {
+                truncated = true;
+                break
+            }
+            entry = cursor.next()?;
+        }
+
+        // no proof is needed if the whole storage is returned
+        if origin.is_zero() && !truncated {
+            return Ok(Some(SnapStorageRange { slots, proof: Vec::new() }))
+        }
+
+        let mut targets = vec![origin];
+        targets.extend(slots.last().map(|(hashed_slot, _)| *hashed_slot));
+        let proof = Proof::from_tx(&self.tx)
+            .with_hashed_cursor_factory(cursor_factory)
+            .with_prefix_sets_mut(state.construct_prefix_sets())
+            .storage_range_proof(hashed_address, &targets)
+            .map_err(|StorageRootError::DB(err)| err)?;
+
+        Ok(Some(SnapStorageRange { slots, proof }))
+    }
+
+    fn snap_trie_nodes(
+        &self,
+        state_root: B256,
+        paths: &[Vec<Bytes>],
+        max_bytes: usize,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        let Some(state) = self.snap_state(state_root)? else { return Ok(None) };
+        let sorted = state.clone().into_sorted();
+        let proof = Proof::from_tx(&self.tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(&self.tx, &sorted))
+            .with_prefix_sets_mut(state.construct_prefix_sets());
+
+        let mut nodes = Vec::new();
+        let mut size = 0;
+        for path_set in paths {
+            // the nodes of the account trie are addressed by a single path, the nodes of a storage
+            // trie by the hashed address of the account followed by their paths
+            let (hashed_address, node_paths) = match path_set.as_slice() {
+                [] => break,
+                [path] => (None, std::slice::from_ref(path)),
+                [account, node_paths @ ..] if account.len() == B256::len_bytes() => {
+                    (Some(B256::from_slice(account)), node_paths)
+                }
+                _ => break,
+            };
+            let Some(node_paths) =
+                node_paths.iter().map(|path| decode_compact_path(path)).collect::<Option<Vec<_>>>()
+            else {
+                break
+            };
+
+            let mut trie_nodes = match hashed_address {
+                None => proof
+                    .account_trie_nodes(&node_paths)
+                    .map_err(Into::<reth_db::DatabaseError>::into)?,
+                Some(hashed_address) => proof
+                    .storage_trie_nodes(hashed_address, &node_paths)
+                    .map_err(|StorageRootError::DB(err)| err)?,
+            };
+            for path in &node_paths {
+                let Some(node) = trie_nodes.remove(path) else { return Ok(Some(nodes)) };
+                size += node.len();
+                nodes.push(node);
+                if size >= max_bytes {
+                    return Ok(Some(nodes))
+                }
+            }
+        }
+
+        Ok(Some(nodes))
+    }
+
+    fn snap_bytecodes(&self, code_hashes: &[B256], max_bytes: usize) -> ProviderResult<Vec<Bytes>> {
+        let mut codes = Vec::new();
+        let mut size = 0;
+        for code_hash in code_hashes {
+            if let Some(code) = self.tx.get::<tables::Bytecodes>(*code_hash)? {
+                let code = code.original_bytes();
+                size += code.len();
+                codes.push(code);
+                if size >= max_bytes {
+                    break
+                }
+            }
+        }
+        Ok(codes)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> PreimageWriter for DatabaseProvider<TX> {
+    fn insert_preimages(
+        &self,
+        preimages: impl IntoIterator<Item = Bytes>,
+    ) -> ProviderResult<usize> {
+        let mut cursor = self.tx.cursor_write::<tables::Preimages>()?;
+        let mut inserted = 0;
+        for preimage in preimages {
+            cursor.upsert(keccak256(&preimage), preimage)?;
+            inserted += 1;
+        }
+        Ok(inserted)
+    }
+
+    fn insert_changed_preimages(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let accounts = self.changed_accounts_with_range(range.clone())?;
+        let slots = self
+            .changed_storages_with_range(range)?
+            .into_values()
+            .flatten()
+            .collect::<BTreeSet<_>>();
+        self.insert_preimages(
+            accounts
+                .iter()
+                .map(|address| Bytes::copy_from_slice(address.as_slice()))
+                .chain(slots.iter().map(|slot| Bytes::copy_from_slice(slot.as_slice()))),
+        )
+    }
+}
+
+impl<TX: DbTx> StateDumpReader for DatabaseProvider<TX> {
+    fn dump_state(
+        &self,
+        block_number: BlockNumber,
+        start: Address,
+        include_storage: bool,
+        f: &mut dyn FnMut(DumpedAccount) -> bool,
+    ) -> ProviderResult<()> {
+        let tip = self.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+        if block_number > tip {
+            return Err(ProviderError::HeaderNotFound(block_number.into()))
+        }
+
+        // The state of an older block is looked up key by key through the history indices of the
+        // later blocks, so the indices have to be complete and the history must not be pruned.
+        let historical = if block_number < tip {
+            for stage_id in [StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+                if self.get_stage_checkpoint(stage_id)?.unwrap_or_default().block_number < tip {
+                    return Err(ProviderError::StateAtBlockPruned(block_number))
+                }
+            }
+            for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+                if self
+                    .get_prune_checkpoint(segment)?
+                    .and_then(|checkpoint| checkpoint.block_number)
+                    .is_some_and(|pruned| pruned > block_number)
+                {
+                    return Err(ProviderError::StateAtBlockPruned(block_number))
+                }
+            }
+            Some(HistoricalStateProviderRef::new(
+                &self.tx,
+                block_number + 1,
+                self.static_file_provider.clone(),
+            ))
+        } else {
+            None
+        };
+
+        let mut plain_accounts = self.tx.cursor_read::<tables::PlainAccountState>()?;
+        let mut plain_storages = self.tx.cursor_dup_read::<tables::PlainStorageState>()?;
+        let mut account_history = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        let mut storage_history = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut bytecodes = self.tx.cursor_read::<tables::Bytecodes>()?;
+
+        // Accounts that don't exist anymore are only found in the history index, so the plain
+        // state and the index are walked side by side.
+        let mut next_plain = plain_accounts.seek(start)?;
+        let mut next_changed = match historical {
+            Some(_) => account_history.seek(ShardedKey::new(start, 0))?.map(|(key, _)| key.key),
+            None => None,
+        };
+        loop {
+            let address = match (next_plain.map(|(address, _)| address), next_changed) {
+                (Some(plain), Some(changed)) => plain.min(changed),
+                (Some(address), None) | (None, Some(address)) => address,
+                (None, None) => break,
+            };
+            let mut account = None;
+            if let Some((_, plain)) = next_plain.filter(|(plain, _)| *plain == address) {
+                account = Some(plain);
+                next_plain = plain_accounts.next()?;
+            }
+            if next_changed == Some(address) {
+                // the last shard of every address is keyed with `u64::MAX`
+                account_history.seek_exact(ShardedKey::last(address))?;
+                next_changed = account_history.next()?.map(|(key, _)| key.key);
+            }
+
+            // The slots with history may have changed after the block, so their values are
+            // looked up at the block. All other slots still have their value in the plain state.
+            let mut changed = BTreeMap::new();
+            if let Some(state) = &historical {
+                // the account may not have existed at the block
+                let Some(historical) = state.basic_account(address)? else { continue };
+                account = Some(historical);
+
+                let mut entry =
+                    storage_history.seek(StorageShardedKey::new(address, B256::ZERO, 0))?;
+                while let Some((key, _)) = entry.filter(|(key, _)| key.address == address) {
+                    let slot = key.sharded_key.key;
+                    changed.insert(slot, state.storage(address, slot)?.unwrap_or_default());
+                    storage_history.seek_exact(StorageShardedKey::last(address, slot))?;
+                    entry = storage_history.next()?;
+                }
+            }
+            let Some(account) = account else { continue };
+
+            let mut storage = BTreeMap::new();
+            if include_storage {
+                for entry in plain_storages.walk_dup(Some(address), None)? {
+                    let (_, entry) = entry?;
+                    if !changed.contains_key(&entry.key) {
+                        storage.insert(entry.key, entry.value);
+                    }
+                }
+                storage.extend(changed.iter().filter(|(_, value)| !value.is_zero()));
+            }
+
+            // The storage root is read from the storage trie of the latest state, with the slots
+            // changed after the block reverted to their values at it.
+            let hashed_address = keccak256(address);
+            let storage_root = if changed.is_empty() {
+                self.hashed_storage_root(hashed_address)?
+            } else {
+                let reverted = HashedPostState::default().with_storages([(
+                    hashed_address,
+                    HashedStorage::from_iter(
+                        false,
+                        changed.into_iter().map(|(slot, value)| (keccak256(slot), value)),
+                    ),
+                )]);
+                let sorted = reverted.clone().into_sorted();
+                let cursor_factory = HashedPostStateCursorFactory::new(&self.tx, &sorted);
+                Proof::from_tx(&self.tx)
+                    .with_hashed_cursor_factory(cursor_factory)
+                    .with_prefix_sets_mut(reverted.construct_prefix_sets())
+                    .storage_root(hashed_address)
+                    .map_err(|StorageRootError::DB(err)| err)?
+            };
+
+            let code = match account.bytecode_hash {
+                Some(code_hash) => bytecodes.seek_exact(code_hash)?.map(|(_, code)| code),
+                None => None,
+            };
+
+            let dumped = DumpedAccount {
+                address,
+                account,
+                storage_root,
+                storage: storage
+                    .into_iter()
+                    .map(|(key, value)| StorageEntry { key, value })
+                    .collect(),
+                code,
+            };
+            if !f(dumped) {
+                break
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns the range of blocks whose post-state is available, see
+    /// [`ChainDbReader::available_state_range`](crate::ChainDbReader::available_state_range).
+    pub fn available_state_range(&self) -> ProviderResult<RangeInclusive<BlockNumber>> {
+        let best_block_number = self.best_block_number()?;
+
+        // historical state is looked up through the history indices of the later blocks
+        for stage_id in [StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+            let checkpoint = self.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+            if checkpoint.block_number < best_block_number {
+                return Ok(best_block_number..=best_block_number)
+            }
+        }
+
+        // the state of a block is reverted with the changesets of the later blocks, so it is
+        // available if the block itself is the last pruned one
+        let mut lowest_block_number = 0;
+        for (segment, mode) in [
+            (PruneSegment::AccountHistory, self.prune_modes.account_history),
+            (PruneSegment::StorageHistory, self.prune_modes.storage_history),
+        ] {
+            if let Some(block_number) =
+                self.get_prune_checkpoint(segment)?.and_then(|checkpoint| checkpoint.block_number)
+            {
+                lowest_block_number = lowest_block_number.max(block_number);
+            }
+            // history that is due to be pruned is not served either, as it may go away any time
+            if let Some((block_number, _)) = mode.and_then(|mode| {
+                mode.prune_target_block(best_block_number, segment, PrunePurpose::User)
+                    .ok()
+                    .flatten()
+            }) {
+                lowest_block_number = lowest_block_number.max(block_number);
+            }
+        }
+
+        Ok(lowest_block_number.min(best_block_number)..=best_block_number)
+    }
+
+    /// Returns the raw value stored for the raw key in the given table, see
+    /// [`ChainDbReader::raw_table_value`](crate::ChainDbReader::raw_table_value).
+    pub fn raw_table_value(&self, table: Tables, key: &[u8]) -> ProviderResult<Option<Vec<u8>>> {
+        Ok(table.view(&RawValueViewer { tx: &self.tx, key })?)
+    }
+}
+
+/// Looks up the raw value of a raw key in a table.
+struct RawValueViewer<'a, TX> {
+    tx: &'a TX,
+    key: &'a [u8],
+}
+
+impl<TX: DbTx> TableViewer<Option<Vec<u8>>> for RawValueViewer<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self
+            .tx
+            .get::<RawTable<T>>(RawKey::from_vec(self.key.to_vec()))?
+            .map(RawValue::into_value))
+    }
+}
+
+impl<TX: DbTxMut + DbTx> BadBlockWriter for DatabaseProvider<TX> {
+    fn insert_bad_block(&self, block: SealedBlock, error: String) -> ProviderResult<()> {
+        // the same block can be rejected more than once
+        if self.bad_block(block.hash())?.is_some() {
+            return Ok(())
+        }
+
+        let rejected_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let mut cursor = self.tx.cursor_write::<tables::BadBlocks>()?;
+        let key = cursor.last()?.map_or(0, |(key, _)| key + 1);
+        cursor.append(key, StoredBadBlock { block, error, rejected_at })?;
+
+        // evict the oldest entries if we are over the limit
+        let mut stored = self.tx.entries::<tables::BadBlocks>()?;
+        while stored > MAX_BAD_BLOCKS && cursor.first()?.is_some() {
+            cursor.delete_current()?;
+            stored -= 1;
+        }
+
+        Ok(())
+    }
+}
+
 /// Helper method to recover senders for any blocks in the db which do not have senders. This
 /// compares the length of the input senders [`Vec`], with the length of given transactions [`Vec`],
 /// and will add to the input senders vec if there are more transactions.
@@ -3554,6 +4594,23 @@ fn recover_block_senders(
     Ok(())
 }
 
+/// Decodes a hex-prefix encoded trie path, as used by the `snap` protocol to address trie nodes.
+fn decode_compact_path(path: &[u8]) -> Option<Nibbles> {
+    // an empty path addresses the root node
+    let Some((&first, rest)) = path.split_first() else { return Some(Nibbles::default()) };
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // the flag in the high nibble marks an odd number of nibbles in its low nibble
+    match first >> 4 {
+        0 | 2 => {}
+        1 | 3 => nibbles.push(first & 0x0f),
+        _ => return None,
+    }
+    for byte in rest {
+        nibbles.extend([byte >> 4, byte & 0x0f]);
+    }
+    Some(Nibbles::from_nibbles_unchecked(nibbles))
+}
+
 fn range_size_hint(range: &impl RangeBounds<TxNumber>) -> Option<usize> {
     let start = match range.start_bound().cloned() {
         Bound::Included(start) => start,
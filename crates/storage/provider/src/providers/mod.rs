@@ -1,11 +1,15 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, FullExecutionDataProvider, HeaderProvider, ParliaSnapshotReader, ProviderError,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BadBlockReader, BadBlockWriter, BlockHashReader,
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
+    CanonStateSubscriptions, ChainDbReader, ChainDbStats, ChainSpecProvider, ChangeSetReader,
+    DatabaseProviderFactory, DumpedAccount, EvmEnvProvider, FinalizedBlockNotifications,
+    ForkChoiceNotifications, ForkChoiceSubscriptions, FullExecutionDataProvider, HeaderProvider,
+    LogIndexReader, ParliaSnapshotReader, PreimageReader, ProviderError, PruneCheckpointReader,
+    ReceiptProvider, ReceiptProviderIdExt, RequestsProvider, SnapAccountRange, SnapStateReader,
+    SnapStorageRange, StageCheckpointReader, StateDumpReader, StateProviderBox,
+    StateProviderFactory, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    TreeViewer, WithdrawalsProvider,
 };
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -13,16 +17,19 @@ use reth_blockchain_tree_api::{
     InsertPayloadOk,
 };
 use reth_chainspec::{ChainInfo, ChainSpec};
+use reth_db::Tables;
 use reth_db_api::{
     database::Database,
-    models::{AccountBeforeTx, StoredBlockBodyIndices},
+    database_metrics::DatabaseMetrics,
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBadBlock, StoredBlockBodyIndices},
 };
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
     parlia::Snapshot, Account, Address, BlobSidecars, Block, BlockHash, BlockHashOrNumber, BlockId,
-    BlockNumHash, BlockNumber, BlockNumberOrTag, BlockWithSenders, Header, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    BlockNumHash, BlockNumber, BlockNumberOrTag, BlockWithSenders, Bytes, Header, Receipt,
+    SealedBlock, SealedBlockWithSenders, SealedHeader, StorageEntry, TransactionMeta,
+    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
+    U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -255,6 +262,10 @@ where
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.database.block_number(hash)
     }
+
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        self.database.earliest_block_number()
+    }
 }
 
 impl<DB> BlockIdReader for BlockchainProvider<DB>
@@ -920,6 +931,19 @@ where
     }
 }
 
+impl<DB> ForkChoiceSubscriptions for BlockchainProvider<DB>
+where
+    DB: Send + Sync,
+{
+    fn subscribe_to_fork_choice(&self) -> ForkChoiceNotifications {
+        ForkChoiceNotifications(self.chain_info.subscribe_fork_choice())
+    }
+
+    fn subscribe_to_finalized_block(&self) -> FinalizedBlockNotifications {
+        FinalizedBlockNotifications(self.chain_info.subscribe_finalized_header())
+    }
+}
+
 impl<DB> ChangeSetReader for BlockchainProvider<DB>
 where
     DB: Database,
@@ -930,6 +954,13 @@ where
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.database.provider()?.account_block_changeset(block_number)
     }
+
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.database.provider()?.storage_block_changeset(block_number)
+    }
 }
 
 impl<DB> AccountReader for BlockchainProvider<DB>
@@ -950,3 +981,193 @@ where
         self.database.provider()?.get_parlia_snapshot(block_hash)
     }
 }
+
+impl<DB> AddressTransactionsReader for BlockchainProvider<DB>
+where
+    DB: Database + Sync + Send,
+{
+    fn address_transactions_indexed_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.address_transactions_indexed_block()
+    }
+
+    fn address_calls_indexed_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.address_calls_indexed_range()
+    }
+
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        before: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.address_transactions_before(address, before, limit)
+    }
+
+    fn address_transactions_from(
+        &self,
+        address: Address,
+        from: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.address_transactions_from(address, from, limit)
+    }
+
+    fn address_transactions_in_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.address_transactions_in_range(address, range)
+    }
+}
+
+impl<DB> PreimageReader for BlockchainProvider<DB>
+where
+    DB: Database + Sync + Send,
+{
+    fn preimages_recorded_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.preimages_recorded_block()
+    }
+
+    fn preimage(&self, hash: B256) -> ProviderResult<Option<Bytes>> {
+        self.database.preimage(hash)
+    }
+
+    fn hashed_accounts_from(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        self.database.hashed_accounts_from(start, limit)
+    }
+
+    fn hashed_storage(&self, hashed_address: B256) -> ProviderResult<Vec<(B256, U256)>> {
+        self.database.hashed_storage(hashed_address)
+    }
+
+    fn hashed_storage_root(&self, hashed_address: B256) -> ProviderResult<B256> {
+        self.database.hashed_storage_root(hashed_address)
+    }
+}
+
+impl<DB> SnapStateReader for BlockchainProvider<DB>
+where
+    DB: Database + Sync + Send,
+{
+    fn snap_state_root(&self) -> ProviderResult<B256> {
+        self.database.snap_state_root()
+    }
+
+    fn snap_account_range(
+        &self,
+        state_root: B256,
+        origin: B256,
+        limit: B256,
+        max_accounts: usize,
+    ) -> ProviderResult<Option<SnapAccountRange>> {
+        self.database.snap_account_range(state_root, origin, limit, max_accounts)
+    }
+
+    fn snap_storage_range(
+        &self,
+        state_root: B256,
+        hashed_address: B256,
+        origin: B256,
+        limit: B256,
+        max_slots: usize,
+    ) -> ProviderResult<Option<SnapStorageRange>> {
+        self.database.snap_storage_range(state_root, hashed_address, origin, limit, max_slots)
+    }
+
+    fn snap_trie_nodes(
+        &self,
+        state_root: B256,
+        paths: &[Vec<Bytes>],
+        max_bytes: usize,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        self.database.snap_trie_nodes(state_root, paths, max_bytes)
+    }
+
+    fn snap_bytecodes(&self, code_hashes: &[B256], max_bytes: usize) -> ProviderResult<Vec<Bytes>> {
+        self.database.snap_bytecodes(code_hashes, max_bytes)
+    }
+}
+
+impl<DB> StateDumpReader for BlockchainProvider<DB>
+where
+    DB: Database + Sync + Send,
+{
+    fn dump_state(
+        &self,
+        block_number: BlockNumber,
+        start: Address,
+        include_storage: bool,
+        f: &mut dyn FnMut(DumpedAccount) -> bool,
+    ) -> ProviderResult<()> {
+        self.database.dump_state(block_number, start, include_storage, f)
+    }
+}
+
+impl<DB> ChainDbReader for BlockchainProvider<DB>
+where
+    DB: Database + DatabaseMetrics + Sync + Send,
+{
+    fn available_state_range(&self) -> ProviderResult<RangeInclusive<BlockNumber>> {
+        self.database.available_state_range()
+    }
+
+    fn raw_table_value(&self, table: Tables, key: &[u8]) -> ProviderResult<Option<Vec<u8>>> {
+        self.database.raw_table_value(table, key)
+    }
+
+    fn chain_db_stats(&self) -> ProviderResult<ChainDbStats> {
+        self.database.chain_db_stats()
+    }
+}
+
+impl<DB> LogIndexReader for BlockchainProvider<DB>
+where
+    DB: Database + Sync + Send,
+{
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.log_topic_blocks(topic, range)
+    }
+}
+
+impl<DB> BadBlockReader for BlockchainProvider<DB>
+where
+    DB: Database + Sync + Send,
+{
+    fn bad_blocks(&self) -> ProviderResult<Vec<StoredBadBlock>> {
+        self.database.bad_blocks()
+    }
+
+    fn bad_block(&self, hash: B256) -> ProviderResult<Option<StoredBadBlock>> {
+        self.database.bad_block(hash)
+    }
+}
+
+impl<DB> BadBlockWriter for BlockchainProvider<DB>
+where
+    DB: Database + Sync + Send,
+{
+    fn insert_bad_block(&self, block: SealedBlock, error: String) -> ProviderResult<()> {
+        self.database.insert_bad_block(block, error)
+    }
+}
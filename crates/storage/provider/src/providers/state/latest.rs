@@ -1,5 +1,8 @@
 use crate::{
-    providers::{state::macros::delegate_provider_impls, StaticFileProvider},
+    providers::{
+        state::{macros::delegate_provider_impls, SnapSynced},
+        StaticFileProvider,
+    },
     AccountReader, BlockHashReader, StateProvider, StateRootProvider,
 };
 use reth_db::tables;
@@ -15,25 +18,33 @@ use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState};
 
 /// State provider over latest state that takes tx reference.
+///
+/// If the state was downloaded by the snap sync stage, the accounts and storage slots missing
+/// from the plain state are read from the hashed state.
 #[derive(Debug)]
 pub struct LatestStateProviderRef<'b, TX: DbTx> {
     /// database transaction
     tx: &'b TX,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Whether the state was snap synced
+    snap_synced: SnapSynced,
 }
 
 impl<'b, TX: DbTx> LatestStateProviderRef<'b, TX> {
     /// Create new state provider
     pub const fn new(tx: &'b TX, static_file_provider: StaticFileProvider) -> Self {
-        Self { tx, static_file_provider }
+        Self { tx, static_file_provider, snap_synced: SnapSynced::new() }
     }
 }
 
 impl<'b, TX: DbTx> AccountReader for LatestStateProviderRef<'b, TX> {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
-        self.tx.get::<tables::PlainAccountState>(address).map_err(Into::into)
+        if let Some(account) = self.tx.get::<tables::PlainAccountState>(address)? {
+            return Ok(Some(account))
+        }
+        self.snap_synced.account(self.tx, address)
     }
 }
 
@@ -114,7 +125,7 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
                 return Ok(Some(entry.value))
             }
         }
-        Ok(None)
+        self.snap_synced.storage(self.tx, account, storage_key)
     }
 
     /// Get account code by its hash
@@ -2,3 +2,76 @@
 pub(crate) mod historical;
 pub(crate) mod latest;
 pub(crate) mod macros;
+
+use reth_codecs::Compact;
+use reth_db::tables;
+use reth_db_api::{cursor::DbDupCursorRO, transaction::DbTx};
+use reth_primitives::{keccak256, Account, Address, StorageKey, StorageValue};
+use reth_stages_types::{SnapSyncCheckpoint, StageId};
+use reth_storage_errors::provider::ProviderResult;
+use std::sync::OnceLock;
+
+/// Whether the state of the database was downloaded by the snap sync stage, read on first use.
+///
+/// The plain state of a snap synced database only contains the accounts and storage slots written
+/// after the pivot block of the download. The rest of the state is only available in the hashed
+/// state tables, which the state providers fall back to on a miss in the plain state.
+#[derive(Debug, Default)]
+pub(crate) struct SnapSynced(OnceLock<bool>);
+
+impl SnapSynced {
+    /// Creates the flag, to be read on first use.
+    pub(crate) const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    /// Returns whether the state of the database was downloaded by the snap sync stage.
+    pub(crate) fn get<TX: DbTx>(&self, tx: &TX) -> ProviderResult<bool> {
+        if let Some(synced) = self.0.get() {
+            return Ok(*synced)
+        }
+        let synced = is_snap_synced(tx)?;
+        Ok(*self.0.get_or_init(|| synced))
+    }
+
+    /// Reads the account from the hashed state if the database was snap synced.
+    pub(crate) fn account<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+    ) -> ProviderResult<Option<Account>> {
+        if !self.get(tx)? {
+            return Ok(None)
+        }
+        Ok(tx.get::<tables::HashedAccounts>(keccak256(address))?)
+    }
+
+    /// Reads the storage slot from the hashed state if the database was snap synced.
+    pub(crate) fn storage<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if !self.get(tx)? {
+            return Ok(None)
+        }
+        let hashed_key = keccak256(storage_key);
+        Ok(tx
+            .cursor_dup_read::<tables::HashedStorages>()?
+            .seek_by_key_subkey(keccak256(address), hashed_key)?
+            .filter(|entry| entry.key == hashed_key)
+            .map(|entry| entry.value))
+    }
+}
+
+/// Returns whether the state of the database was downloaded by the snap sync stage.
+///
+/// See [`SnapSynced`].
+pub(crate) fn is_snap_synced<TX: DbTx>(tx: &TX) -> ProviderResult<bool> {
+    let buf = tx.get::<tables::StageCheckpointProgresses>(StageId::SnapSync.to_string())?;
+    Ok(buf.filter(|buf| !buf.is_empty()).is_some_and(|buf| {
+        let (checkpoint, _) = SnapSyncCheckpoint::from_compact(&buf, buf.len());
+        checkpoint.complete
+    }))
+}
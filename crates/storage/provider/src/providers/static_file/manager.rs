@@ -4,8 +4,9 @@ use super::{
 };
 use crate::{
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, DatabaseProvider,
-    HeaderProvider, ReceiptProvider, RequestsProvider, StageCheckpointReader, StatsReader,
-    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    HeaderProvider, ReceiptProvider, RequestsProvider, StageCheckpointReader,
+    StaticFileSegmentStats, StaticFileStats, StatsReader, TransactionVariant, TransactionsProvider,
+    TransactionsProviderExt, WithdrawalsProvider,
 };
 use dashmap::{mapref::entry::Entry as DashMapEntry, DashMap};
 use parking_lot::RwLock;
@@ -176,40 +177,59 @@ impl StaticFileProvider {
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
 
+        for stats in self.segment_stats()? {
+            metrics.record_segment(stats.segment, stats.size, stats.files.len(), stats.rows);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the statistics of the static files of each segment, ordered by segment.
+    pub fn segment_stats(&self) -> ProviderResult<Vec<StaticFileSegmentStats>> {
         let static_files =
             iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+        let mut stats = Vec::with_capacity(static_files.len());
         for (segment, ranges) in static_files {
-            let mut entries = 0;
-            let mut size = 0;
+            let (Some(first), Some(last)) = (ranges.first(), ranges.last()) else { continue };
 
-            for (block_range, _) in &ranges {
+            let mut files = Vec::with_capacity(ranges.len());
+            for (block_range, tx_range) in &ranges {
                 let fixed_block_range = find_fixed_range(block_range.start());
                 let jar_provider = self
                     .get_segment_provider(segment, || Some(fixed_block_range), None)?
                     .ok_or(ProviderError::MissingStaticFileBlock(segment, block_range.start()))?;
 
-                entries += jar_provider.rows();
-
-                let data_size = reth_fs_util::metadata(jar_provider.data_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let index_size = reth_fs_util::metadata(jar_provider.index_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let offsets_size = reth_fs_util::metadata(jar_provider.offsets_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let config_size = reth_fs_util::metadata(jar_provider.config_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-
-                size += data_size + index_size + offsets_size + config_size;
+                let file_size = |path: &Path| {
+                    reth_fs_util::metadata(path).map(|metadata| metadata.len()).unwrap_or_default()
+                };
+                files.push(StaticFileStats {
+                    block_range: *block_range,
+                    tx_range: *tx_range,
+                    columns: jar_provider.columns(),
+                    rows: jar_provider.rows(),
+                    data_size: file_size(jar_provider.data_path()),
+                    index_size: file_size(&jar_provider.index_path()),
+                    offsets_size: file_size(&jar_provider.offsets_path()),
+                    config_size: file_size(&jar_provider.config_path()),
+                });
             }
 
-            metrics.record_segment(segment, size, ranges.len(), entries);
+            stats.push(StaticFileSegmentStats {
+                segment,
+                block_range: SegmentRangeInclusive::new(first.0.start(), last.0.end()),
+                tx_range: first
+                    .1
+                    .zip(last.1)
+                    .map(|(first, last)| SegmentRangeInclusive::new(first.start(), last.end())),
+                rows: files.iter().map(|file| file.rows).sum(),
+                size: files.iter().map(StaticFileStats::size).sum(),
+                files,
+            });
         }
+        stats.sort_unstable_by_key(|stats| stats.segment);
 
-        Ok(())
+        Ok(stats)
     }
 
     /// Gets the [`StaticFileJarProvider`] of the requested segment and block.
@@ -1,7 +1,13 @@
 use std::sync::{Arc, Mutex};
-use tokio::sync::broadcast::{self, Sender};
+use tokio::sync::{
+    broadcast::{self, Sender},
+    watch,
+};
 
-use crate::{CanonStateNotification, CanonStateNotifications, CanonStateSubscriptions, Chain};
+use crate::{
+    CanonStateNotification, CanonStateNotifications, CanonStateSubscriptions, Chain,
+    FinalizedBlockNotifications, ForkChoiceNotifications, ForkChoiceSubscriptions,
+};
 
 /// A test `ChainEventSubscriptions`
 #[derive(Clone, Debug, Default)]
@@ -33,3 +39,13 @@ impl CanonStateSubscriptions for TestCanonStateSubscriptions {
         canon_notif_rx
     }
 }
+
+impl ForkChoiceSubscriptions for TestCanonStateSubscriptions {
+    fn subscribe_to_fork_choice(&self) -> ForkChoiceNotifications {
+        ForkChoiceNotifications(broadcast::channel(1).1)
+    }
+
+    fn subscribe_to_finalized_block(&self) -> FinalizedBlockNotifications {
+        FinalizedBlockNotifications(watch::channel(None).1)
+    }
+}
@@ -4,13 +4,17 @@ use std::{
 };
 
 use reth_chainspec::{ChainInfo, ChainSpec, MAINNET};
-use reth_db_api::models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db::Tables;
+use reth_db_api::models::{
+    AccountBeforeTx, BlockNumberAddress, StoredBadBlock, StoredBlockBodyIndices,
+};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    parlia::Snapshot, Account, Address, BlobSidecars, Block, BlockHash, BlockHashOrNumber, BlockId,
-    BlockNumber, BlockWithSenders, Bytecode, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StorageKey, StorageValue, TransactionMeta, TransactionSigned,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    constants::EMPTY_ROOT_HASH, parlia::Snapshot, Account, Address, BlobSidecars, Block, BlockHash,
+    BlockHashOrNumber, BlockId, BlockNumber, BlockWithSenders, Bytecode, Bytes, Header, Receipt,
+    SealedBlock, SealedBlockWithSenders, SealedHeader, StorageEntry, StorageKey, StorageValue,
+    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -18,17 +22,20 @@ use reth_storage_api::{SidecarsProvider, StateProofProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState};
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
 
 use crate::{
     providers::StaticFileProvider,
-    traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, HeaderProvider, ParliaSnapshotReader, PruneCheckpointReader,
-    ReceiptProviderIdExt, RequestsProvider, StageCheckpointReader, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, StaticFileProviderFactory, TransactionVariant,
-    TransactionsProvider, WithdrawalsProvider,
+    traits::{BlockSource, DumpedAccount, ReceiptProvider},
+    AccountReader, AddressTransactionsReader, BadBlockReader, BadBlockWriter, BlockHashReader,
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, CanonStateNotifications,
+    CanonStateSubscriptions, ChainDbReader, ChainDbStats, ChainSpecProvider, ChangeSetReader,
+    EvmEnvProvider, FinalizedBlockNotifications, ForkChoiceNotifications, ForkChoiceSubscriptions,
+    HeaderProvider, LogIndexReader, ParliaSnapshotReader, PreimageReader, PruneCheckpointReader,
+    ReceiptProviderIdExt, RequestsProvider, SnapAccountRange, SnapStateReader, SnapStorageRange,
+    StageCheckpointReader, StateDumpReader, StateProvider, StateProviderBox, StateProviderFactory,
+    StateRootProvider, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 
 /// Supports various api interfaces for testing purposes.
@@ -309,6 +316,13 @@ impl ChangeSetReader for NoopProvider {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         Ok(Vec::default())
     }
+
+    fn storage_block_changeset(
+        &self,
+        _block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(Vec::default())
+    }
 }
 
 impl StateRootProvider for NoopProvider {
@@ -502,8 +516,188 @@ impl CanonStateSubscriptions for NoopProvider {
     }
 }
 
+impl ForkChoiceSubscriptions for NoopProvider {
+    fn subscribe_to_fork_choice(&self) -> ForkChoiceNotifications {
+        ForkChoiceNotifications(broadcast::channel(1).1)
+    }
+
+    fn subscribe_to_finalized_block(&self) -> FinalizedBlockNotifications {
+        FinalizedBlockNotifications(watch::channel(None).1)
+    }
+}
+
 impl ParliaSnapshotReader for NoopProvider {
     fn get_parlia_snapshot(&self, _block_hash: B256) -> ProviderResult<Option<Snapshot>> {
         Ok(None)
     }
 }
+
+impl AddressTransactionsReader for NoopProvider {
+    fn address_transactions_indexed_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn address_calls_indexed_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn address_transactions_before(
+        &self,
+        _address: Address,
+        _before: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn address_transactions_from(
+        &self,
+        _address: Address,
+        _from: TxNumber,
+        _limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn address_transactions_in_range(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::new())
+    }
+}
+
+impl PreimageReader for NoopProvider {
+    fn preimages_recorded_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn preimage(&self, _hash: B256) -> ProviderResult<Option<Bytes>> {
+        Ok(None)
+    }
+
+    fn hashed_accounts_from(
+        &self,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>> {
+        Ok(Vec::new())
+    }
+
+    fn hashed_storage(&self, _hashed_address: B256) -> ProviderResult<Vec<(B256, U256)>> {
+        Ok(Vec::new())
+    }
+
+    fn hashed_storage_root(&self, _hashed_address: B256) -> ProviderResult<B256> {
+        Ok(EMPTY_ROOT_HASH)
+    }
+}
+
+impl SnapStateReader for NoopProvider {
+    fn snap_state_root(&self) -> ProviderResult<B256> {
+        Ok(EMPTY_ROOT_HASH)
+    }
+
+    fn snap_account_range(
+        &self,
+        _state_root: B256,
+        _origin: B256,
+        _limit: B256,
+        _max_accounts: usize,
+    ) -> ProviderResult<Option<SnapAccountRange>> {
+        Ok(None)
+    }
+
+    fn snap_storage_range(
+        &self,
+        _state_root: B256,
+        _hashed_address: B256,
+        _origin: B256,
+        _limit: B256,
+        _max_slots: usize,
+    ) -> ProviderResult<Option<SnapStorageRange>> {
+        Ok(None)
+    }
+
+    fn snap_trie_nodes(
+        &self,
+        _state_root: B256,
+        _paths: &[Vec<Bytes>],
+        _max_bytes: usize,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        Ok(None)
+    }
+
+    fn snap_bytecodes(
+        &self,
+        _code_hashes: &[B256],
+        _max_bytes: usize,
+    ) -> ProviderResult<Vec<Bytes>> {
+        Ok(Vec::new())
+    }
+}
+
+impl StateDumpReader for NoopProvider {
+    fn dump_state(
+        &self,
+        _block_number: BlockNumber,
+        _start: Address,
+        _include_storage: bool,
+        _f: &mut dyn FnMut(DumpedAccount) -> bool,
+    ) -> ProviderResult<()> {
+        Ok(())
+    }
+}
+
+impl ChainDbReader for NoopProvider {
+    fn available_state_range(&self) -> ProviderResult<RangeInclusive<BlockNumber>> {
+        Ok(0..=0)
+    }
+
+    fn raw_table_value(&self, _table: Tables, _key: &[u8]) -> ProviderResult<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn chain_db_stats(&self) -> ProviderResult<ChainDbStats> {
+        Ok(ChainDbStats::default())
+    }
+}
+
+impl LogIndexReader for NoopProvider {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::new())
+    }
+}
+
+impl BadBlockReader for NoopProvider {
+    fn bad_blocks(&self) -> ProviderResult<Vec<StoredBadBlock>> {
+        Ok(Vec::new())
+    }
+
+    fn bad_block(&self, _hash: B256) -> ProviderResult<Option<StoredBadBlock>> {
+        Ok(None)
+    }
+}
+
+impl BadBlockWriter for NoopProvider {
+    fn insert_bad_block(&self, _block: SealedBlock, _error: String) -> ProviderResult<()> {
+        Ok(())
+    }
+}
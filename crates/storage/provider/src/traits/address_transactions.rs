@@ -0,0 +1,47 @@
+use reth_primitives::{Address, BlockNumber, TxNumber};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Functionality to read the index of the transactions each address appears in.
+///
+/// The index is only populated if the `IndexAddressTransactions` stage is enabled.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait AddressTransactionsReader: Send + Sync {
+    /// Returns the highest block the index is populated up to, or `None` if the index is not
+    /// enabled.
+    fn address_transactions_indexed_block(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the range of blocks whose transactions are indexed together with the addresses
+    /// reached by their internal calls, i.e. the blocks the stage re-executed that haven't been
+    /// pruned, or `None` if no calls are indexed.
+    ///
+    /// The transactions of all other indexed blocks are only indexed under their sender,
+    /// recipient and log addresses.
+    fn address_calls_indexed_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns up to `limit` numbers of the transactions the address appears in that are lower
+    /// than `before`, highest first.
+    fn address_transactions_before(
+        &self,
+        address: Address,
+        before: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+
+    /// Returns up to `limit` numbers of the transactions the address appears in that are not lower
+    /// than `from`, lowest first.
+    fn address_transactions_from(
+        &self,
+        address: Address,
+        from: TxNumber,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+
+    /// Returns the numbers of the transactions in the given range the address appears in, lowest
+    /// first.
+    fn address_transactions_in_range(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>>;
+}
@@ -0,0 +1,26 @@
+use reth_db_api::models::StoredBadBlock;
+use reth_primitives::{SealedBlock, B256};
+use reth_storage_errors::provider::ProviderResult;
+
+/// The maximum number of bad blocks that are kept in the database.
+///
+/// Once the limit is reached, the oldest entries are evicted first.
+pub const MAX_BAD_BLOCKS: usize = 10;
+
+/// Functionality to read blocks that failed validation from the database.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BadBlockReader: Send + Sync {
+    /// Returns all stored bad blocks, most recently rejected first.
+    fn bad_blocks(&self) -> ProviderResult<Vec<StoredBadBlock>>;
+
+    /// Returns the stored bad block with the given hash, if any.
+    fn bad_block(&self, hash: B256) -> ProviderResult<Option<StoredBadBlock>>;
+}
+
+/// Functionality to record blocks that failed validation in the database.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BadBlockWriter: Send + Sync {
+    /// Records the given block as bad, evicting the oldest entries if more than
+    /// [`MAX_BAD_BLOCKS`] are stored.
+    fn insert_bad_block(&self, block: SealedBlock, error: String) -> ProviderResult<()>;
+}
@@ -9,8 +9,11 @@ use std::{
     sync::Arc,
     task::{ready, Context, Poll},
 };
-use tokio::sync::broadcast;
-use tokio_stream::{wrappers::BroadcastStream, Stream};
+use tokio::sync::{broadcast, watch};
+use tokio_stream::{
+    wrappers::{BroadcastStream, WatchStream},
+    Stream,
+};
 use tracing::debug;
 
 /// Type alias for a receiver that receives [`CanonStateNotification`]
@@ -143,18 +146,35 @@ impl CanonStateNotification {
 
 /// Wrapper around a broadcast receiver that receives fork choice notifications.
 #[derive(Debug, Deref, DerefMut)]
-pub struct ForkChoiceNotifications(broadcast::Receiver<SealedHeader>);
+pub struct ForkChoiceNotifications(pub broadcast::Receiver<SealedHeader>);
+
+/// Wrapper around a watch receiver that receives finalized block notifications.
+#[derive(Debug, Deref, DerefMut)]
+pub struct FinalizedBlockNotifications(pub watch::Receiver<Option<SealedHeader>>);
 
 /// A trait that allows to register to fork choice related events
 /// and get notified when a new fork choice is available.
+#[auto_impl(&, Arc)]
 pub trait ForkChoiceSubscriptions: Send + Sync {
     /// Get notified when a new head of the chain is selected.
     fn subscribe_to_fork_choice(&self) -> ForkChoiceNotifications;
 
+    /// Get notified when the finalized block of the chain changes.
+    fn subscribe_to_finalized_block(&self) -> FinalizedBlockNotifications;
+
     /// Convenience method to get a stream of the new head of the chain.
     fn fork_choice_stream(&self) -> ForkChoiceStream {
         ForkChoiceStream { st: BroadcastStream::new(self.subscribe_to_fork_choice().0) }
     }
+
+    /// Convenience method to get a stream of the finalized blocks of the chain.
+    ///
+    /// Only yields the changes after the stream was created.
+    fn finalized_block_stream(&self) -> FinalizedBlockStream {
+        FinalizedBlockStream {
+            st: WatchStream::from_changes(self.subscribe_to_finalized_block().0),
+        }
+    }
 }
 
 /// A stream of the fork choices in the form of [`SealedHeader`].
@@ -173,7 +193,7 @@ impl Stream for ForkChoiceStream {
             return match ready!(self.as_mut().project().st.poll_next(cx)) {
                 Some(Ok(notification)) => Poll::Ready(Some(notification)),
                 Some(Err(err)) => {
-                    debug!(%err, "finalized header notification stream lagging behind");
+                    debug!(%err, "fork choice notification stream lagging behind");
                     continue
                 }
                 None => Poll::Ready(None),
@@ -181,3 +201,25 @@ impl Stream for ForkChoiceStream {
         }
     }
 }
+
+/// A stream of the finalized blocks in the form of [`SealedHeader`].
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct FinalizedBlockStream {
+    #[pin]
+    st: WatchStream<Option<SealedHeader>>,
+}
+
+impl Stream for FinalizedBlockStream {
+    type Item = SealedHeader;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            return match ready!(self.as_mut().project().st.poll_next(cx)) {
+                Some(Some(header)) => Poll::Ready(Some(header)),
+                Some(None) => continue,
+                None => Poll::Ready(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use reth_db::Tables;
+use reth_primitives::{static_file::SegmentRangeInclusive, BlockNumber, StaticFileSegment};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Statistics of a database table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableStats {
+    /// The table.
+    pub table: Tables,
+    /// The number of entries in the table.
+    pub entries: usize,
+    /// The number of branch pages of the table.
+    pub branch_pages: usize,
+    /// The number of leaf pages of the table.
+    pub leaf_pages: usize,
+    /// The number of overflow pages of the table.
+    pub overflow_pages: usize,
+    /// The size of the table in bytes.
+    pub size: usize,
+}
+
+/// Statistics of a single static file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticFileStats {
+    /// The block range covered by the static file.
+    pub block_range: SegmentRangeInclusive,
+    /// The transaction range covered by the static file, if the segment is keyed by transaction.
+    pub tx_range: Option<SegmentRangeInclusive>,
+    /// The number of columns of the static file.
+    pub columns: usize,
+    /// The number of rows in the static file.
+    pub rows: usize,
+    /// The size of the data file in bytes.
+    pub data_size: u64,
+    /// The size of the index file in bytes.
+    pub index_size: u64,
+    /// The size of the offsets file in bytes.
+    pub offsets_size: u64,
+    /// The size of the config file in bytes.
+    pub config_size: u64,
+}
+
+impl StaticFileStats {
+    /// Returns the size of the static file in bytes, including its index, offsets and config.
+    pub const fn size(&self) -> u64 {
+        self.data_size + self.index_size + self.offsets_size + self.config_size
+    }
+}
+
+/// Statistics of the static files of a segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticFileSegmentStats {
+    /// The segment.
+    pub segment: StaticFileSegment,
+    /// The statistics of each static file of the segment, ordered by block range.
+    pub files: Vec<StaticFileStats>,
+    /// The block range covered by the static files.
+    pub block_range: SegmentRangeInclusive,
+    /// The transaction range covered by the static files, if the segment is keyed by
+    /// transaction.
+    pub tx_range: Option<SegmentRangeInclusive>,
+    /// The number of rows in the static files.
+    pub rows: usize,
+    /// The size of the static files in bytes, including their index, offsets and config.
+    pub size: u64,
+}
+
+/// Statistics of the database and the static files, as reported by `reth db stats`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainDbStats {
+    /// The statistics of the database tables, ordered by table name.
+    pub tables: Vec<TableStats>,
+    /// The number of pages in the database freelist, if available.
+    pub freelist: Option<usize>,
+    /// The size of the database freelist in bytes, if available.
+    pub freelist_size: Option<usize>,
+    /// The statistics of the static file segments.
+    pub static_files: Vec<StaticFileSegmentStats>,
+}
+
+/// Functionality to inspect the storage of the node.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ChainDbReader: Send + Sync {
+    /// Returns the range of blocks whose post-state is available.
+    ///
+    /// The state of the latest block is always available, the state of earlier blocks only if
+    /// the account and storage history indices are up to date and, depending on the prune modes,
+    /// the history of the later blocks is not pruned.
+    fn available_state_range(&self) -> ProviderResult<RangeInclusive<BlockNumber>>;
+
+    /// Returns the raw value stored for the raw key in the given table.
+    ///
+    /// For tables with duplicate keys, the first value of the key is returned.
+    fn raw_table_value(&self, table: Tables, key: &[u8]) -> ProviderResult<Option<Vec<u8>>>;
+
+    /// Returns the statistics of the database and the static files.
+    fn chain_db_stats(&self) -> ProviderResult<ChainDbStats>;
+}
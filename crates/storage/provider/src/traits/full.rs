@@ -1,9 +1,12 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, ParliaSnapshotReader,
-    StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory, TransactionsProvider,
+    AccountReader, AddressTransactionsReader, BadBlockReader, BlockReaderIdExt,
+    CanonStateSubscriptions, ChainDbReader, ChainSpecProvider, ChangeSetReader,
+    DatabaseProviderFactory, EvmEnvProvider, ForkChoiceSubscriptions, HeaderProvider,
+    LogIndexReader, ParliaSnapshotReader, PreimageReader, PruneCheckpointReader, SnapStateReader,
+    StageCheckpointReader, StateDumpReader, StateProviderFactory, StaticFileProviderFactory,
+    TransactionsProvider,
 };
 use reth_db_api::database::Database;
 
@@ -17,10 +20,19 @@ pub trait FullProvider<DB: Database>:
     + EvmEnvProvider
     + ChainSpecProvider
     + ChangeSetReader
+    + PruneCheckpointReader
     + CanonStateSubscriptions
+    + ForkChoiceSubscriptions
     + StageCheckpointReader
     + HeaderProvider
     + ParliaSnapshotReader
+    + BadBlockReader
+    + AddressTransactionsReader
+    + PreimageReader
+    + SnapStateReader
+    + StateDumpReader
+    + ChainDbReader
+    + LogIndexReader
     + Clone
     + Unpin
     + 'static
@@ -36,10 +48,19 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + PruneCheckpointReader
         + CanonStateSubscriptions
+        + ForkChoiceSubscriptions
         + StageCheckpointReader
         + HeaderProvider
         + ParliaSnapshotReader
+        + BadBlockReader
+        + AddressTransactionsReader
+        + PreimageReader
+        + SnapStateReader
+        + StateDumpReader
+        + ChainDbReader
+        + LogIndexReader
         + Clone
         + Unpin
         + 'static
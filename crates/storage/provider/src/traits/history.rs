@@ -1,6 +1,6 @@
 use auto_impl::auto_impl;
 use reth_db_api::models::BlockNumberAddress;
-use reth_primitives::{Address, BlockNumber, B256};
+use reth_primitives::{Address, BlockNumber, TxNumber, B256};
 use reth_storage_errors::provider::ProviderResult;
 use std::{
     collections::BTreeMap,
@@ -38,6 +38,34 @@ pub trait HistoryWriter: Send + Sync {
         storage_transitions: BTreeMap<(Address, B256), Vec<u64>>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear the address transaction indices of the given blocks.
+    ///
+    /// Returns number of addresses unwound.
+    fn unwind_address_transaction_indices(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert address transaction index to database. Used inside IndexAddressTransactions stage
+    fn insert_address_transaction_index(
+        &self,
+        address_transactions: BTreeMap<Address, Vec<TxNumber>>,
+    ) -> ProviderResult<()>;
+
+    /// Unwind and clear the log indices of the given blocks.
+    ///
+    /// Returns number of addresses and topics unwound.
+    fn unwind_log_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+
+    /// Insert log address and topic index to database. Used inside IndexLogs stage
+    fn insert_log_index(
+        &self,
+        addresses: BTreeMap<Address, Vec<BlockNumber>>,
+        topics: BTreeMap<B256, Vec<BlockNumber>>,
+    ) -> ProviderResult<()>;
+
     /// Read account/storage changesets and update account/storage history indices.
+    ///
+    /// Also updates the address transaction and log indices, if they are enabled.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
@@ -0,0 +1,32 @@
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Functionality to read the index of the blocks with logs of each address and topic.
+///
+/// The index is only populated if the `IndexLogs` stage is enabled.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait LogIndexReader: Send + Sync {
+    /// Returns the range of blocks the index is complete for, or `None` if the index is not
+    /// enabled.
+    ///
+    /// The range starts after the blocks whose index has been pruned and ends at the highest block
+    /// the index is populated up to.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the numbers of the blocks in the given range with logs emitted by the address,
+    /// lowest first.
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the numbers of the blocks in the given range with logs that contain the topic at
+    /// any position, lowest first.
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
@@ -21,8 +21,8 @@ pub use state::StateWriter;
 mod chain;
 pub use chain::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications, ForkChoiceStream,
-    ForkChoiceSubscriptions,
+    CanonStateNotifications, CanonStateSubscriptions, FinalizedBlockNotifications,
+    FinalizedBlockStream, ForkChoiceNotifications, ForkChoiceStream, ForkChoiceSubscriptions,
 };
 
 mod spec;
@@ -57,3 +57,23 @@ pub use parlia_provider::ParliaProvider;
 
 mod parlia_snapshot;
 pub use parlia_snapshot::ParliaSnapshotReader;
+
+mod bad_blocks;
+pub use bad_blocks::{BadBlockReader, BadBlockWriter, MAX_BAD_BLOCKS};
+
+mod address_transactions;
+pub use address_transactions::AddressTransactionsReader;
+
+mod preimage;
+pub use preimage::{PreimageReader, PreimageWriter};
+
+mod state_dump;
+pub use state_dump::{DumpedAccount, StateDumpReader};
+
+mod chain_db;
+pub use chain_db::{
+    ChainDbReader, ChainDbStats, StaticFileSegmentStats, StaticFileStats, TableStats,
+};
+
+mod log_index;
+pub use log_index::LogIndexReader;
@@ -0,0 +1,48 @@
+use reth_primitives::{Account, BlockNumber, Bytes, B256, U256};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Functionality to read the preimages of hashed state keys, i.e. the addresses and storage slots
+/// the keys of the hashed state tables are derived from.
+///
+/// The preimages are only recorded if the `Preimages` stage is enabled.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait PreimageReader: Send + Sync {
+    /// Returns the highest block the preimages are recorded up to, or `None` if the preimages are
+    /// not recorded.
+    fn preimages_recorded_block(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the preimage of the given `keccak256` hash, if known.
+    fn preimage(&self, hash: B256) -> ProviderResult<Option<Bytes>>;
+
+    /// Returns up to `limit` accounts of the latest state whose hashed address is not lower than
+    /// `start`, ordered by hashed address.
+    fn hashed_accounts_from(
+        &self,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<Vec<(B256, Account)>>;
+
+    /// Returns the non-zero storage of the account with the given hashed address in the latest
+    /// state, ordered by hashed slot.
+    fn hashed_storage(&self, hashed_address: B256) -> ProviderResult<Vec<(B256, U256)>>;
+
+    /// Returns the storage root of the account with the given hashed address in the latest state.
+    fn hashed_storage_root(&self, hashed_address: B256) -> ProviderResult<B256>;
+}
+
+/// Functionality to record the preimages of hashed state keys.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait PreimageWriter: Send + Sync {
+    /// Records the given preimages under their `keccak256` hash.
+    ///
+    /// Returns the number of recorded preimages.
+    fn insert_preimages(&self, preimages: impl IntoIterator<Item = Bytes>)
+        -> ProviderResult<usize>;
+
+    /// Records the preimages of the addresses and storage slots changed in the given block range.
+    ///
+    /// Returns the number of recorded preimages.
+    fn insert_changed_preimages(&self, range: RangeInclusive<BlockNumber>)
+        -> ProviderResult<usize>;
+}
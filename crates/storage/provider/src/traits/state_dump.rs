@@ -0,0 +1,36 @@
+use reth_primitives::{Account, Address, BlockNumber, Bytecode, StorageEntry, B256};
+use reth_storage_errors::provider::ProviderResult;
+
+/// An account of the state at a block, as walked by [`StateDumpReader::dump_state`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpedAccount {
+    /// The address of the account.
+    pub address: Address,
+    /// The account info.
+    pub account: Account,
+    /// The storage root of the account.
+    pub storage_root: B256,
+    /// The non-zero storage of the account, ordered by slot. Empty if the storage is excluded.
+    pub storage: Vec<StorageEntry>,
+    /// The code of the account, if any.
+    pub code: Option<Bytecode>,
+}
+
+/// Functionality to walk the full state at a block.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait StateDumpReader: Send + Sync {
+    /// Walks the accounts of the state at the end of the given block, ordered by address and
+    /// starting at `start`, and calls `f` with each account until it returns `false`.
+    ///
+    /// The state of historical blocks is looked up key by key through the history indices and
+    /// changesets of the later blocks, so it is only available if the history of those blocks is
+    /// indexed and not pruned. Only the storage of one account is held in memory at a time, and
+    /// none if `include_storage` is `false`.
+    fn dump_state(
+        &self,
+        block_number: BlockNumber,
+        start: Address,
+        include_storage: bool,
+        f: &mut dyn FnMut(DumpedAccount) -> bool,
+    ) -> ProviderResult<()>;
+}
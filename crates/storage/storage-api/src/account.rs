@@ -1,6 +1,6 @@
 use auto_impl::auto_impl;
-use reth_db_api::models::AccountBeforeTx;
-use reth_primitives::{Account, Address, BlockNumber};
+use reth_db_api::models::{AccountBeforeTx, BlockNumberAddress};
+use reth_primitives::{Account, Address, BlockNumber, StorageEntry};
 use reth_storage_errors::provider::ProviderResult;
 use std::{
     collections::{BTreeMap, BTreeSet},
@@ -52,4 +52,10 @@ pub trait ChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Iterate over storage changesets and return the storage slots from before this block.
+    fn storage_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>>;
 }
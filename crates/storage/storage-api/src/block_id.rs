@@ -21,6 +21,12 @@ pub trait BlockNumReader: BlockHashReader + Send + Sync {
     /// Gets the `BlockNumber` for the given hash. Returns `None` if no block with this hash exists.
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>>;
 
+    /// Returns the earliest block whose body and receipts are still available, the start of the
+    /// block range that can be served to peers.
+    fn earliest_block_number(&self) -> ProviderResult<BlockNumber> {
+        Ok(0)
+    }
+
     /// Gets the block number for the given `BlockHashOrNumber`. Returns `None` if no block with
     /// this hash exists. If the `BlockHashOrNumber` is a `Number`, it is returned as is.
     fn convert_hash_or_number(&self, id: BlockHashOrNumber) -> ProviderResult<Option<BlockNumber>> {
@@ -55,4 +55,7 @@ pub use withdrawals::*;
 mod sidecars;
 pub use sidecars::*;
 
+mod snap;
+pub use snap::*;
+
 pub mod noop;
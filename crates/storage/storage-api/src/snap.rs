@@ -0,0 +1,88 @@
+use reth_primitives::{Account, Bytes, B256, U256};
+use reth_storage_errors::provider::ProviderResult;
+
+/// An account of a [`SnapAccountRange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapAccount {
+    /// The hash of the account address
+    pub hashed_address: B256,
+    /// The account
+    pub account: Account,
+    /// The storage root of the account
+    pub storage_root: B256,
+}
+
+/// Consecutive accounts of the hashed state, with the trie nodes proving the boundaries of the
+/// range.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapAccountRange {
+    /// The accounts, ordered by their hashed address
+    pub accounts: Vec<SnapAccount>,
+    /// The account trie nodes proving the origin and the last account of the range
+    pub proof: Vec<Bytes>,
+}
+
+/// Consecutive storage slots of an account, with the trie nodes proving the boundaries of the
+/// range.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapStorageRange {
+    /// The hashed slots and their values, ordered by the hashed slot
+    pub slots: Vec<(B256, U256)>,
+    /// The storage trie nodes proving the origin and the last slot of the range.
+    ///
+    /// Empty if the range contains the whole storage of the account.
+    pub proof: Vec<Bytes>,
+}
+
+/// Functionality to serve ranges of the hashed state, as used by the `snap` protocol.
+///
+/// The states of the recent canonical blocks are available, as long as their changesets are not
+/// pruned. Requests for any other state root return `None`.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait SnapStateReader: Send + Sync {
+    /// Returns the state root of the latest state, the most recent state that can be served.
+    fn snap_state_root(&self) -> ProviderResult<B256>;
+
+    /// Returns up to `max_accounts` consecutive accounts, starting at `origin` and stopping after
+    /// the first account not lower than `limit`.
+    ///
+    /// Returns `None` if the state with the given root is not served.
+    fn snap_account_range(
+        &self,
+        state_root: B256,
+        origin: B256,
+        limit: B256,
+        max_accounts: usize,
+    ) -> ProviderResult<Option<SnapAccountRange>>;
+
+    /// Returns up to `max_slots` consecutive storage slots of the account with the given hashed
+    /// address, starting at `origin` and stopping after the first slot not lower than `limit`.
+    ///
+    /// Returns `None` if the state with the given root is not served.
+    fn snap_storage_range(
+        &self,
+        state_root: B256,
+        hashed_address: B256,
+        origin: B256,
+        limit: B256,
+        max_slots: usize,
+    ) -> ProviderResult<Option<SnapStorageRange>>;
+
+    /// Returns the RLP encoded trie nodes at the given hex-prefix encoded paths, in request order.
+    ///
+    /// A path set with a single path addresses a node of the account trie. Otherwise the first
+    /// element is the hashed address of an account and the remaining paths address nodes of its
+    /// storage trie. Stops at the first path without a node or once `max_bytes` are exceeded.
+    ///
+    /// Returns `None` if the state with the given root is not served.
+    fn snap_trie_nodes(
+        &self,
+        state_root: B256,
+        paths: &[Vec<Bytes>],
+        max_bytes: usize,
+    ) -> ProviderResult<Option<Vec<Bytes>>>;
+
+    /// Returns the bytecodes with the given hashes in request order, skipping the unknown ones
+    /// and stopping once `max_bytes` are exceeded.
+    fn snap_bytecodes(&self, code_hashes: &[B256], max_bytes: usize) -> ProviderResult<Vec<Bytes>>;
+}
@@ -0,0 +1,354 @@
+//! A pool of transaction bundles that are offered to the payload builder.
+//!
+//! Bundles are submitted privately, e.g. via `eth_sendBundle`, their transactions are never
+//! propagated and are only included as a whole: either all transactions of a bundle end up in a
+//! block, in order, or none of them.
+
+use crate::traits::PoolTransaction;
+use parking_lot::RwLock;
+use reth_primitives::{keccak256, TxHash, B256};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+    sync::Arc,
+};
+
+/// Default maximum number of bundles kept in the [`BundlePool`].
+pub const DEFAULT_MAX_BUNDLES: usize = 1024;
+
+/// Maximum number of blocks ahead of the current block a bundle or private transaction can
+/// target.
+pub const MAX_BUNDLE_BLOCK_RANGE: u64 = 100;
+
+/// Errors returned when inserting a [`Bundle`] into the [`BundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// The bundle does not contain any transactions.
+    #[error("bundle has no transactions")]
+    EmptyBundle,
+    /// A bundle with the same hash is already in the pool.
+    #[error("bundle {0} already exists")]
+    AlreadyExists(B256),
+    /// The pool reached its maximum number of bundles.
+    #[error("bundle pool is full")]
+    PoolFull,
+}
+
+/// A bundle of transactions that must be included in a block in order and atomically.
+#[derive(Debug)]
+pub struct Bundle<T> {
+    /// The hash of the bundle, the hash of its concatenated transaction hashes.
+    hash: B256,
+    /// The transactions of the bundle, in execution order.
+    transactions: Vec<T>,
+    /// The blocks the bundle may be included in.
+    block_range: RangeInclusive<u64>,
+    /// Earliest timestamp of a block the bundle may be included in.
+    min_timestamp: Option<u64>,
+    /// Latest timestamp of a block the bundle may be included in.
+    max_timestamp: Option<u64>,
+    /// Hashes of the transactions that are allowed to revert.
+    reverting_tx_hashes: HashSet<TxHash>,
+    /// UUID that can be used to replace or cancel the bundle.
+    replacement_uuid: Option<String>,
+}
+
+impl<T: PoolTransaction> Bundle<T> {
+    /// Creates a new bundle that can be included in blocks of the given range.
+    pub fn new(transactions: Vec<T>, block_range: RangeInclusive<u64>) -> Self {
+        let mut hashes = Vec::with_capacity(32 * transactions.len());
+        for tx in &transactions {
+            hashes.extend_from_slice(tx.hash().as_slice());
+        }
+
+        Self {
+            hash: keccak256(hashes),
+            transactions,
+            block_range,
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: HashSet::new(),
+            replacement_uuid: None,
+        }
+    }
+
+    /// Restricts the bundle to blocks with a timestamp in the given bounds.
+    pub const fn with_timestamps(
+        mut self,
+        min_timestamp: Option<u64>,
+        max_timestamp: Option<u64>,
+    ) -> Self {
+        self.min_timestamp = min_timestamp;
+        self.max_timestamp = max_timestamp;
+        self
+    }
+
+    /// Allows the transactions with the given hashes to revert.
+    pub fn with_reverting_tx_hashes(mut self, hashes: impl IntoIterator<Item = TxHash>) -> Self {
+        self.reverting_tx_hashes = hashes.into_iter().collect();
+        self
+    }
+
+    /// Sets the UUID that can be used to replace or cancel the bundle.
+    pub fn with_replacement_uuid(mut self, replacement_uuid: Option<String>) -> Self {
+        self.replacement_uuid = replacement_uuid;
+        self
+    }
+}
+
+impl<T> Bundle<T> {
+    /// Returns the hash of the bundle.
+    pub const fn hash(&self) -> B256 {
+        self.hash
+    }
+
+    /// Returns the transactions of the bundle, in execution order.
+    pub fn transactions(&self) -> &[T] {
+        &self.transactions
+    }
+
+    /// Returns the blocks the bundle may be included in.
+    pub const fn block_range(&self) -> &RangeInclusive<u64> {
+        &self.block_range
+    }
+
+    /// Returns the UUID that can be used to replace or cancel the bundle.
+    pub fn replacement_uuid(&self) -> Option<&str> {
+        self.replacement_uuid.as_deref()
+    }
+
+    /// Returns `true` if the transaction with the given hash is allowed to revert.
+    pub fn can_revert(&self, hash: &TxHash) -> bool {
+        self.reverting_tx_hashes.contains(hash)
+    }
+
+    /// Returns `true` if the bundle may be included in the block with the given number and
+    /// timestamp.
+    pub fn is_includable(&self, block_number: u64, timestamp: u64) -> bool {
+        self.block_range.contains(&block_number) &&
+            self.min_timestamp.map_or(true, |min| timestamp >= min) &&
+            self.max_timestamp.map_or(true, |max| timestamp <= max)
+    }
+}
+
+/// A pool of [`Bundle`]s, and the inclusion deadlines of private transactions.
+///
+/// Private transactions themselves live in the transaction pool with a
+/// [`TransactionOrigin::Private`](crate::TransactionOrigin::Private) origin, which keeps them
+/// from being propagated.
+#[derive(Debug)]
+pub struct BundlePool<T> {
+    inner: Arc<BundlePoolInner<T>>,
+}
+
+#[derive(Debug)]
+struct BundlePoolInner<T> {
+    /// Maximum number of bundles in the pool.
+    max_bundles: usize,
+    /// The bundles, in insertion order.
+    bundles: RwLock<Vec<Arc<Bundle<T>>>>,
+    /// The highest block number private transactions may be included in, by hash.
+    private_transactions: RwLock<HashMap<TxHash, u64>>,
+}
+
+impl<T> BundlePool<T> {
+    /// Creates a new pool that holds at most `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self {
+            inner: Arc::new(BundlePoolInner {
+                max_bundles,
+                bundles: Default::default(),
+                private_transactions: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.bundles.read().len()
+    }
+
+    /// Returns `true` if the pool has no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.inner.bundles.read().is_empty()
+    }
+
+    /// Returns the bundle with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<Arc<Bundle<T>>> {
+        self.inner.bundles.read().iter().find(|bundle| bundle.hash == *hash).cloned()
+    }
+
+    /// Inserts the bundle and returns its hash.
+    ///
+    /// A bundle with the same replacement UUID is replaced.
+    pub fn insert(&self, bundle: Bundle<T>) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+
+        let mut bundles = self.inner.bundles.write();
+        if bundles.iter().any(|existing| existing.hash == bundle.hash) {
+            return Err(BundlePoolError::AlreadyExists(bundle.hash))
+        }
+        if let Some(uuid) = bundle.replacement_uuid() {
+            bundles.retain(|existing| existing.replacement_uuid() != Some(uuid));
+        }
+        if bundles.len() >= self.inner.max_bundles {
+            return Err(BundlePoolError::PoolFull)
+        }
+
+        let hash = bundle.hash;
+        bundles.push(Arc::new(bundle));
+        Ok(hash)
+    }
+
+    /// Removes the bundle with the given replacement UUID.
+    ///
+    /// Returns `false` if there is no such bundle.
+    pub fn cancel(&self, replacement_uuid: &str) -> bool {
+        let mut bundles = self.inner.bundles.write();
+        let len = bundles.len();
+        bundles.retain(|bundle| bundle.replacement_uuid() != Some(replacement_uuid));
+        bundles.len() != len
+    }
+
+    /// Removes the bundle with the given hash.
+    ///
+    /// Returns `false` if there is no such bundle.
+    pub fn remove(&self, hash: &B256) -> bool {
+        let mut bundles = self.inner.bundles.write();
+        let len = bundles.len();
+        bundles.retain(|bundle| bundle.hash != *hash);
+        bundles.len() != len
+    }
+
+    /// Returns the bundles that may be included in the block with the given number and
+    /// timestamp, in insertion order.
+    pub fn bundles_for_block(&self, block_number: u64, timestamp: u64) -> Vec<Arc<Bundle<T>>> {
+        self.inner
+            .bundles
+            .read()
+            .iter()
+            .filter(|bundle| bundle.is_includable(block_number, timestamp))
+            .cloned()
+            .collect()
+    }
+
+    /// Records the highest block number the private transaction may be included in.
+    pub fn track_private_transaction(&self, hash: TxHash, max_block_number: u64) {
+        self.inner.private_transactions.write().insert(hash, max_block_number);
+    }
+
+    /// Stops tracking the private transaction.
+    ///
+    /// Returns `false` if the transaction was not tracked.
+    pub fn untrack_private_transaction(&self, hash: &TxHash) -> bool {
+        self.inner.private_transactions.write().remove(hash).is_some()
+    }
+
+    /// Updates the pool after a new canonical block.
+    ///
+    /// Removes all bundles that can no longer be included or that contain a mined transaction,
+    /// and returns the hashes of the private transactions that were not included in time and
+    /// should be removed from the transaction pool.
+    pub fn on_canonical_block(
+        &self,
+        block_number: u64,
+        mined_transactions: &[TxHash],
+    ) -> Vec<TxHash>
+    where
+        T: PoolTransaction,
+    {
+        let mined = mined_transactions.iter().collect::<HashSet<_>>();
+
+        self.inner.bundles.write().retain(|bundle| {
+            *bundle.block_range.end() > block_number &&
+                !bundle.transactions.iter().any(|tx| mined.contains(tx.hash()))
+        });
+
+        let mut expired = Vec::new();
+        self.inner.private_transactions.write().retain(|hash, max_block_number| {
+            if mined.contains(hash) {
+                return false
+            }
+            if *max_block_number <= block_number {
+                expired.push(*hash);
+                return false
+            }
+            true
+        });
+        expired
+    }
+}
+
+impl<T> Default for BundlePool<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUNDLES)
+    }
+}
+
+impl<T> Clone for BundlePool<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn replaces_and_cancels_bundles() {
+        let pool = BundlePool::default();
+        let first = Bundle::new(vec![MockTransaction::eip1559()], 1..=10)
+            .with_replacement_uuid(Some("uuid".to_string()));
+        let first = pool.insert(first).unwrap();
+
+        let second = Bundle::new(vec![MockTransaction::eip1559()], 1..=10)
+            .with_replacement_uuid(Some("uuid".to_string()));
+        let second = pool.insert(second).unwrap();
+        assert!(pool.get(&first).is_none());
+        assert!(pool.get(&second).is_some());
+
+        assert_eq!(pool.insert(Bundle::new(vec![], 1..=10)), Err(BundlePoolError::EmptyBundle));
+        assert!(pool.cancel("uuid"));
+        assert!(!pool.cancel("uuid"));
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn selects_includable_bundles() {
+        let pool = BundlePool::default();
+        pool.insert(Bundle::new(vec![MockTransaction::eip1559()], 5..=6)).unwrap();
+        pool.insert(
+            Bundle::new(vec![MockTransaction::eip1559()], 5..=10).with_timestamps(Some(100), None),
+        )
+        .unwrap();
+
+        assert!(pool.bundles_for_block(4, 100).is_empty());
+        assert_eq!(pool.bundles_for_block(5, 99).len(), 1);
+        assert_eq!(pool.bundles_for_block(6, 100).len(), 2);
+        assert_eq!(pool.bundles_for_block(7, 100).len(), 1);
+    }
+
+    #[test]
+    fn prunes_on_canonical_block() {
+        let pool = BundlePool::default();
+        let tx = MockTransaction::eip1559();
+        let mined = *tx.hash();
+        pool.insert(Bundle::new(vec![tx], 1..=10)).unwrap();
+        pool.insert(Bundle::new(vec![MockTransaction::eip1559()], 1..=2)).unwrap();
+        pool.insert(Bundle::new(vec![MockTransaction::eip1559()], 1..=3)).unwrap();
+
+        let (included, expired, pending) = (B256::random(), B256::random(), B256::random());
+        pool.track_private_transaction(included, 2);
+        pool.track_private_transaction(expired, 2);
+        pool.track_private_transaction(pending, 3);
+
+        assert_eq!(pool.on_canonical_block(2, &[mined, included]), vec![expired]);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.untrack_private_transaction(&pending));
+        assert!(!pool.untrack_private_transaction(&included));
+    }
+}
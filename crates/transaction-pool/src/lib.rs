@@ -160,6 +160,7 @@ use tracing::{instrument, trace};
 
 pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
+    bundle::{Bundle, BundlePool, BundlePoolError},
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
@@ -186,6 +187,7 @@ pub mod pool;
 pub mod validate;
 
 pub mod blobstore;
+pub mod bundle;
 mod config;
 pub mod identifier;
 mod ordering;
@@ -207,6 +209,8 @@ pub type EthTransactionPool<Client, S> = Pool<
 pub struct Pool<V, T: TransactionOrdering, S> {
     /// Arc'ed instance of the pool internals
     pool: Arc<PoolInner<V, T, S>>,
+    /// Privately submitted bundles
+    bundles: BundlePool<T::Transaction>,
 }
 
 // === impl Pool ===
@@ -219,7 +223,10 @@ where
 {
     /// Create a new transaction pool instance.
     pub fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
-        Self { pool: Arc::new(PoolInner::new(validator, ordering, blob_store, config)) }
+        Self {
+            pool: Arc::new(PoolInner::new(validator, ordering, blob_store, config)),
+            bundles: Default::default(),
+        }
     }
 
     /// Returns the wrapped pool.
@@ -521,6 +528,10 @@ where
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
         self.pool.blob_store().get_exact(tx_hashes)
     }
+
+    fn bundle_pool(&self) -> Option<&BundlePool<Self::Transaction>> {
+        Some(&self.bundles)
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
@@ -536,7 +547,12 @@ where
     }
 
     fn on_canonical_state_change(&self, update: CanonicalStateUpdate<'_>) {
+        let expired = self.bundles.on_canonical_block(update.number(), &update.mined_transactions);
         self.pool.on_canonical_state_change(update);
+        if !expired.is_empty() {
+            trace!(target: "txpool", ?expired, "removing expired private transactions");
+            self.pool.remove_transactions(expired);
+        }
     }
 
     fn update_accounts(&self, accounts: Vec<ChangedAccount>) {
@@ -558,6 +574,6 @@ where
 
 impl<V, T: TransactionOrdering, S> Clone for Pool<V, T, S> {
     fn clone(&self) -> Self {
-        Self { pool: Arc::clone(&self.pool) }
+        Self { pool: Arc::clone(&self.pool), bundles: self.bundles.clone() }
     }
 }
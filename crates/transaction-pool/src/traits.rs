@@ -2,6 +2,7 @@
 
 use crate::{
     blobstore::BlobStoreError,
+    bundle::BundlePool,
     error::PoolResult,
     pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
     validate::ValidPoolTransaction,
@@ -385,6 +386,14 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
+
+    /// Returns the [BundlePool] that holds privately submitted bundles, if the pool supports
+    /// bundles.
+    ///
+    /// Consumer: RPC and block production
+    fn bundle_pool(&self) -> Option<&BundlePool<Self::Transaction>> {
+        None
+    }
 }
 
 /// Extension for [TransactionPool] trait that allows to set the current block info.
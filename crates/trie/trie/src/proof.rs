@@ -10,8 +10,9 @@ use alloy_rlp::{BufMut, Encodable};
 use reth_db::tables;
 use reth_db_api::transaction::DbTx;
 use reth_execution_errors::{StateRootError, StorageRootError};
-use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Address, B256};
+use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Address, Bytes, B256, U256};
 use reth_trie_common::{proof::ProofRetainer, AccountProof, StorageProof, TrieAccount};
+use std::collections::BTreeMap;
 
 /// A struct for generating merkle proofs.
 ///
@@ -127,17 +128,143 @@ where
         hashed_address: B256,
         slots: &[B256],
     ) -> Result<(B256, Vec<StorageProof>), StorageRootError> {
+        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
+        let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
+
+        let (root, all_proof_nodes) = self.storage_root_with_proof_nodes(
+            hashed_address,
+            target_nibbles,
+            |nibbles, value| {
+                if let Some(proof) = proofs.iter_mut().find(|proof| &proof.nibbles == nibbles) {
+                    proof.set_value(value);
+                }
+            },
+        )?;
+
+        for proof in &mut proofs {
+            // Iterate over all proof nodes and find the matching ones.
+            // The filtered results are guaranteed to be in order.
+            let matching_proof_nodes = all_proof_nodes
+                .iter()
+                .filter(|(path, _)| proof.nibbles.starts_with(path))
+                .map(|(_, node)| node.clone());
+            proof.set_proof(matching_proof_nodes.collect());
+        }
+
+        Ok((root, proofs))
+    }
+
+    /// Generate the proof nodes of the account trie for the given hashed addresses, ordered by
+    /// their path.
+    ///
+    /// The targets don't have to exist in the trie, in which case the nodes prove their absence.
+    /// This is used to prove the boundaries of a range of accounts.
+    pub fn account_range_proof(&self, targets: &[B256]) -> Result<Vec<Bytes>, StateRootError> {
+        let target_nibbles = targets.iter().map(Nibbles::unpack).collect();
+        Ok(self.account_proof_nodes(target_nibbles)?.into_values().collect())
+    }
+
+    /// Generate the proof nodes of the storage trie of the given account for the given hashed
+    /// slots, ordered by their path.
+    ///
+    /// See [`Self::account_range_proof`].
+    pub fn storage_range_proof(
+        &self,
+        hashed_address: B256,
+        targets: &[B256],
+    ) -> Result<Vec<Bytes>, StorageRootError> {
+        let target_nibbles = targets.iter().map(Nibbles::unpack).collect::<Vec<_>>();
+        let (_, proof_nodes) =
+            self.storage_root_with_proof_nodes(hashed_address, target_nibbles, |_, _| {})?;
+        Ok(proof_nodes.into_values().collect())
+    }
+
+    /// Returns the RLP encoded nodes of the account trie at the given paths.
+    ///
+    /// Paths without a node, e.g. because they point into the middle of an extension or leaf
+    /// node, are missing from the result.
+    pub fn account_trie_nodes(
+        &self,
+        paths: &[Nibbles],
+    ) -> Result<BTreeMap<Nibbles, Bytes>, StateRootError> {
+        let mut nodes = self.account_proof_nodes(paths.iter().map(path_target).collect())?;
+        nodes.retain(|path, _| paths.contains(path));
+        Ok(nodes)
+    }
+
+    /// Returns the RLP encoded nodes of the storage trie of the given account at the given paths.
+    ///
+    /// See [`Self::account_trie_nodes`].
+    pub fn storage_trie_nodes(
+        &self,
+        hashed_address: B256,
+        paths: &[Nibbles],
+    ) -> Result<BTreeMap<Nibbles, Bytes>, StorageRootError> {
+        let (_, mut nodes) = self.storage_root_with_proof_nodes(
+            hashed_address,
+            paths.iter().map(path_target).collect(),
+            |_, _| {},
+        )?;
+        nodes.retain(|path, _| paths.contains(path));
+        Ok(nodes)
+    }
+
+    /// Compute the state root and retain the proof nodes of the account trie for the given
+    /// paths.
+    fn account_proof_nodes(
+        &self,
+        target_nibbles: Vec<Nibbles>,
+    ) -> Result<BTreeMap<Nibbles, Bytes>, StateRootError> {
+        let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
+        let trie_cursor =
+            DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
+
+        let mut prefix_set = self.prefix_sets.account_prefix_set.clone();
+        prefix_set.extend(target_nibbles.clone());
+        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
+
+        let retainer = ProofRetainer::from_iter(target_nibbles);
+        let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
+
+        let mut account_rlp = Vec::with_capacity(128);
+        let mut account_node_iter = TrieNodeIter::new(walker, hashed_account_cursor);
+        while let Some(account_node) = account_node_iter.try_next()? {
+            match account_node {
+                TrieElement::Branch(node) => {
+                    hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
+                }
+                TrieElement::Leaf(hashed_address, account) => {
+                    let storage_root = self.storage_root(hashed_address)?;
+                    account_rlp.clear();
+                    let account = TrieAccount::from((account, storage_root));
+                    account.encode(&mut account_rlp as &mut dyn BufMut);
+                    hash_builder.add_leaf(Nibbles::unpack(hashed_address), &account_rlp);
+                }
+            }
+        }
+
+        let _ = hash_builder.root();
+
+        Ok(hash_builder.take_proofs())
+    }
+
+    /// Compute the storage root and retain the proof nodes of the given paths.
+    ///
+    /// The callback is invoked with every visited leaf.
+    fn storage_root_with_proof_nodes(
+        &self,
+        hashed_address: B256,
+        target_nibbles: Vec<Nibbles>,
+        mut on_leaf: impl FnMut(&Nibbles, U256),
+    ) -> Result<(B256, BTreeMap<Nibbles, Bytes>), StorageRootError> {
         let mut hashed_storage_cursor =
             self.hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
 
-        let mut proofs = slots.iter().copied().map(StorageProof::new).collect::<Vec<_>>();
-
         // short circuit on empty storage
         if hashed_storage_cursor.is_storage_empty()? {
-            return Ok((EMPTY_ROOT_HASH, proofs))
+            return Ok((EMPTY_ROOT_HASH, BTreeMap::new()))
         }
 
-        let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
         let mut prefix_set =
             self.prefix_sets.storage_prefix_sets.get(&hashed_address).cloned().unwrap_or_default();
         prefix_set.extend(target_nibbles.clone());
@@ -157,9 +284,7 @@ where
                 }
                 TrieElement::Leaf(hashed_slot, value) => {
                     let nibbles = Nibbles::unpack(hashed_slot);
-                    if let Some(proof) = proofs.iter_mut().find(|proof| proof.nibbles == nibbles) {
-                        proof.set_value(value);
-                    }
+                    on_leaf(&nibbles, value);
                     hash_builder.add_leaf(nibbles, alloy_rlp::encode_fixed_size(&value).as_ref());
                 }
             }
@@ -167,21 +292,18 @@ where
 
         let root = hash_builder.root();
 
-        let all_proof_nodes = hash_builder.take_proofs();
-        for proof in &mut proofs {
-            // Iterate over all proof nodes and find the matching ones.
-            // The filtered results are guaranteed to be in order.
-            let matching_proof_nodes = all_proof_nodes
-                .iter()
-                .filter(|(path, _)| proof.nibbles.starts_with(path))
-                .map(|(_, node)| node.clone());
-            proof.set_proof(matching_proof_nodes.collect());
-        }
-
-        Ok((root, proofs))
+        Ok((root, hash_builder.take_proofs()))
     }
 }
 
+/// Returns the key of a leaf below the given path, used as a proof target to retain the nodes on
+/// the path.
+fn path_target(path: &Nibbles) -> Nibbles {
+    let mut target = path.to_vec();
+    target.resize(target.len().max(64), 0);
+    Nibbles::from_nibbles_unchecked(target)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -338,6 +460,61 @@ mod tests {
         assert_eq!(account_proof.verify(root), Ok(()));
     }
 
+    #[test]
+    fn testspec_account_range_proof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let first = Address::from_str("0x2031f89b3ea8014eb51a78c316e42af3e0d7695f").unwrap();
+        let last = Address::from_str("0x62b0dd4aab2b1a0a04e279e2b828791a10755528").unwrap();
+
+        let provider = factory.provider().unwrap();
+        let proof = Proof::from_tx(provider.tx_ref());
+        let range_proof = proof.account_range_proof(&[keccak256(first), keccak256(last)]).unwrap();
+
+        // the range proof is the union of the proofs of the boundaries
+        let mut expected = proof.account_proof(first, &[]).unwrap().proof;
+        for node in proof.account_proof(last, &[]).unwrap().proof {
+            if !expected.contains(&node) {
+                expected.push(node);
+            }
+        }
+        assert_eq!(range_proof.len(), expected.len());
+        assert!(expected.iter().all(|node| range_proof.contains(node)));
+
+        // the storage trie of an account without storage has no nodes
+        let storage_proof =
+            proof.storage_range_proof(keccak256(first), &[B256::ZERO, B256::repeat_byte(0xff)]);
+        assert_eq!(storage_proof.unwrap(), Vec::<Bytes>::new());
+    }
+
+    #[test]
+    fn testspec_account_trie_nodes() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        let root = insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let first = Address::from_str("0x2031f89b3ea8014eb51a78c316e42af3e0d7695f").unwrap();
+
+        let provider = factory.provider().unwrap();
+        let proof = Proof::from_tx(provider.tx_ref());
+
+        // all accounts share the prefix `0xa7`, so the root is an extension node followed by a
+        // branch node, and there is no node in the middle of the extension
+        let root_path = Nibbles::default();
+        let branch_path = Nibbles::from_nibbles_unchecked([0xa, 0x7]);
+        let extension_path = Nibbles::from_nibbles_unchecked([0xa]);
+        let nodes = proof
+            .account_trie_nodes(&[root_path.clone(), extension_path.clone(), branch_path.clone()])
+            .unwrap();
+        assert_eq!(keccak256(&nodes[&root_path]), root);
+        assert!(!nodes.contains_key(&extension_path));
+
+        let account_proof = proof.account_proof(first, &[]).unwrap().proof;
+        assert_eq!(nodes[&branch_path], account_proof[1]);
+    }
+
     #[test]
     fn mainnet_genesis_account_proof() {
         // Create test database and insert genesis accounts.
@@ -290,6 +290,7 @@ where
             initialized_cfg,
             parent_block,
             extra_data,
+            min_priority_fee,
             attributes,
             chain_spec,
         } = config;
@@ -305,6 +306,7 @@ where
                 initialized_cfg,
                 parent_block,
                 extra_data,
+                min_priority_fee,
                 attributes: attributes.0,
                 chain_spec,
             },
@@ -323,11 +325,12 @@ where
             initialized_cfg,
             parent_block,
             extra_data,
+            min_priority_fee,
             attributes,
             chain_spec,
         } = config;
         <reth_ethereum_payload_builder::EthereumPayloadBuilder as PayloadBuilder<Pool, Client>>::build_empty_payload(&reth_ethereum_payload_builder::EthereumPayloadBuilder::default(),client,
-                                                                                                                     PayloadConfig { initialized_block_env, initialized_cfg, parent_block, extra_data, attributes: attributes.0, chain_spec })
+                                                                                                                     PayloadConfig { initialized_block_env, initialized_cfg, parent_block, extra_data, min_priority_fee, attributes: attributes.0, chain_spec })
     }
 }
 